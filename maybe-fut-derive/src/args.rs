@@ -1,9 +1,21 @@
-use syn::{Ident, LitStr, Token};
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{ToTokens, quote};
+use syn::punctuated::Punctuated;
+use syn::{Ident, LitBool, LitStr, Token};
 
 pub struct MaybeFutArgs {
-    pub sync: Ident,
-    pub tokio: Ident,
-    pub tokio_feature: LitStr,
+    /// The name of the sync wrapper, if it must be generated.
+    pub sync: Option<Ident>,
+    /// The name of the tokio wrapper, if it must be generated.
+    pub tokio: Option<Ident>,
+    /// The cfg predicate gating the tokio wrapper; always set if `tokio` is set.
+    pub tokio_feature: Option<TokioGate>,
+    /// Whether to translate the maybe-fut types in the signatures of the generated methods.
+    pub unwrap: bool,
+    /// Whether the async methods of the tokio wrapper return a boxed `Send` future instead of being `async fn`.
+    pub boxed: bool,
+    /// Whether the futures returned by the async methods of the tokio wrapper must be `Send`.
+    pub send: bool,
 }
 
 impl syn::parse::Parse for MaybeFutArgs {
@@ -11,19 +23,28 @@ impl syn::parse::Parse for MaybeFutArgs {
         let mut sync = None;
         let mut tokio = None;
         let mut tokio_feature = None;
+        let mut unwrap = None;
+        let mut boxed = None;
+        let mut send = None;
 
         while !input.is_empty() {
             let key: Ident = input.parse()?;
             input.parse::<Token![=]>()?;
 
             match key.to_string().as_str() {
-                "sync" => sync = Some(input.parse()?),
-                "tokio" => tokio = Some(input.parse()?),
-                "tokio_feature" => tokio_feature = Some(input.parse()?),
+                "sync" => set_once(&mut sync, &key, input.parse()?)?,
+                "tokio" => set_once(&mut tokio, &key, input.parse()?)?,
+                "tokio_feature" => set_once(&mut tokio_feature, &key, input.parse()?)?,
+                "unwrap" => set_once(&mut unwrap, &key, input.parse::<LitBool>()?.value)?,
+                "boxed" => set_once(&mut boxed, &key, input.parse::<LitBool>()?.value)?,
+                "send" => set_once(&mut send, &key, input.parse::<LitBool>()?.value)?,
                 other => {
                     return Err(syn::Error::new_spanned(
                         key,
-                        format!("Unexpected key `{}`", other),
+                        format!(
+                            "Unexpected key `{}`, expected one of `sync`, `tokio`, `tokio_feature`, `unwrap`, `boxed`, `send`",
+                            other
+                        ),
                     ));
                 }
             }
@@ -35,32 +56,145 @@ impl syn::parse::Parse for MaybeFutArgs {
             }
         }
 
-        let sync = match sync {
-            Some(ident) => ident,
-            None => {
-                return Err(syn::Error::new_spanned(sync, "Missing sync attribute"));
-            }
-        };
-        let tokio = match tokio {
-            Some(ident) => ident,
-            None => {
-                return Err(syn::Error::new_spanned(tokio, "Missing tokio attribute"));
-            }
-        };
-        let tokio_feature = match tokio_feature {
-            Some(lit) => lit,
-            None => {
-                return Err(syn::Error::new_spanned(
-                    tokio_feature,
-                    "Missing tokio_feature attribute",
-                ));
-            }
-        };
+        if sync.is_none() && tokio.is_none() {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "At least one of `sync` and `tokio` arguments must be set",
+            ));
+        }
+        if tokio.is_some() {
+            required(tokio_feature.as_ref(), "tokio_feature")?;
+        }
 
         Ok(MaybeFutArgs {
             sync,
             tokio,
             tokio_feature,
+            unwrap: unwrap.unwrap_or_default(),
+            boxed: boxed.unwrap_or_default(),
+            send: send.unwrap_or_default(),
         })
     }
 }
+
+pub struct ModuleArgs {
+    pub sync: Ident,
+    pub tokio: Ident,
+    pub tokio_feature: Option<TokioGate>,
+}
+
+impl syn::parse::Parse for ModuleArgs {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut sync = None;
+        let mut tokio = None;
+        let mut tokio_feature = None;
+
+        while !input.is_empty() {
+            let key: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+
+            match key.to_string().as_str() {
+                "sync" => set_once(&mut sync, &key, input.parse()?)?,
+                "tokio" => set_once(&mut tokio, &key, input.parse()?)?,
+                "tokio_feature" => set_once(&mut tokio_feature, &key, input.parse()?)?,
+                other => {
+                    return Err(syn::Error::new_spanned(
+                        key,
+                        format!(
+                            "Unexpected key `{}`, expected one of `sync`, `tokio`, `tokio_feature`",
+                            other
+                        ),
+                    ));
+                }
+            }
+
+            if input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+            } else {
+                break;
+            }
+        }
+
+        Ok(ModuleArgs {
+            sync: required(sync, "sync")?,
+            tokio: required(tokio, "tokio")?,
+            tokio_feature,
+        })
+    }
+}
+
+/// The cfg predicate of the `tokio_feature` argument.
+///
+/// A string literal is the name of a feature, also inside of `any`, `all` and `not`, while the other predicates are
+/// raw cfg predicates, so `any("tokio", "tokio-net")` is the same as `any(feature = "tokio", feature = "tokio-net")`.
+#[derive(Clone)]
+pub struct TokioGate(TokenStream2);
+
+impl syn::parse::Parse for TokioGate {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.peek(LitStr) {
+            let feature: LitStr = input.parse()?;
+            return Ok(TokioGate(quote! { feature = #feature }));
+        }
+
+        let name: Ident = input.parse()?;
+        if input.peek(syn::token::Paren) {
+            let content;
+            syn::parenthesized!(content in input);
+            let predicates = Punctuated::<TokioGate, Token![,]>::parse_terminated(&content)?;
+            match name.to_string().as_str() {
+                "any" | "all" => {}
+                "not" if predicates.len() == 1 => {}
+                "not" => {
+                    return Err(syn::Error::new_spanned(
+                        name,
+                        "`not` expects exactly one predicate",
+                    ));
+                }
+                other => {
+                    return Err(syn::Error::new_spanned(
+                        &name,
+                        format!(
+                            "Unexpected predicate `{}`, expected one of `any`, `all`, `not`",
+                            other
+                        ),
+                    ));
+                }
+            }
+            let predicates = predicates.into_iter();
+            return Ok(TokioGate(quote! { #name(#(#predicates),*) }));
+        }
+
+        if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            let value: LitStr = input.parse()?;
+            return Ok(TokioGate(quote! { #name = #value }));
+        }
+
+        Ok(TokioGate(quote! { #name }))
+    }
+}
+
+impl ToTokens for TokioGate {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        self.0.to_tokens(tokens);
+    }
+}
+
+/// Sets the value of an argument, failing at `key` if the argument has already been set.
+fn set_once<T>(slot: &mut Option<T>, key: &Ident, value: T) -> syn::Result<()> {
+    if slot.is_some() {
+        return Err(syn::Error::new_spanned(
+            key,
+            format!("Duplicate key `{}`", key),
+        ));
+    }
+    *slot = Some(value);
+
+    Ok(())
+}
+
+/// Returns the value of a required argument, failing at the attribute if it is missing.
+fn required<T>(value: Option<T>, key: &str) -> syn::Result<T> {
+    value.ok_or_else(|| syn::Error::new(Span::call_site(), format!("Missing `{}` argument", key)))
+}
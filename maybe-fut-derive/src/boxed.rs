@@ -0,0 +1,214 @@
+//! Generation of boxed futures for the async methods, so that the generated wrappers can be used behind trait objects,
+//! and of `Send` futures, to check that the futures of the generated wrappers can be sent between threads.
+
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::visit_mut::VisitMut;
+use syn::{
+    FnArg, GenericParam, Generics, Lifetime, ReturnType, Type, TypeParamBound, WherePredicate,
+};
+
+/// The lifetime of the boxed future, which all the captured arguments must outlive.
+const FUTURE_LIFETIME: &str = "'__maybe_fut_future";
+
+/// Turns the signature of an async method into a method returning a pinned and boxed `Send` future.
+///
+/// The elided lifetimes of the arguments are named, and every lifetime and type parameter of the method is bound
+/// to outlive the returned future.
+///
+/// Returns the new return type and body of the method.
+pub fn boxed_method(
+    generics: &mut Generics,
+    args: &mut Punctuated<FnArg, syn::token::Comma>,
+    ret_type: &ReturnType,
+    body: TokenStream2,
+) -> (ReturnType, TokenStream2) {
+    let future_lifetime = bind_to_future(generics, args);
+
+    let output = future_output(ret_type);
+    let ret_type = syn::parse_quote! {
+        -> ::core::pin::Pin<
+            ::std::boxed::Box<
+                dyn ::core::future::Future<Output = #output> + ::core::marker::Send + #future_lifetime
+            >
+        >
+    };
+    let body = quote! {
+        ::std::boxed::Box::pin(async move { #body })
+    };
+
+    (ret_type, body)
+}
+
+/// Turns the signature of an async method into a method returning an `impl Future` which must be `Send`,
+/// so that a non-`Send` future is reported at the method definition.
+///
+/// The arguments are bound to the lifetime of the future as in [`boxed_method`].
+///
+/// Returns the new return type and body of the method.
+pub fn send_method(
+    generics: &mut Generics,
+    args: &mut Punctuated<FnArg, syn::token::Comma>,
+    ret_type: &ReturnType,
+    body: TokenStream2,
+) -> (ReturnType, TokenStream2) {
+    let future_lifetime = bind_to_future(generics, args);
+
+    let output = future_output(ret_type);
+    let ret_type = syn::parse_quote! {
+        -> impl ::core::future::Future<Output = #output> + ::core::marker::Send + #future_lifetime
+    };
+    let body = quote! {
+        async move { #body }
+    };
+
+    (ret_type, body)
+}
+
+/// Names the elided lifetimes of the arguments and binds every lifetime and type parameter of the method
+/// to outlive the lifetime of the returned future, which is returned.
+fn bind_to_future(
+    generics: &mut Generics,
+    args: &mut Punctuated<FnArg, syn::token::Comma>,
+) -> Lifetime {
+    let future_lifetime = Lifetime::new(FUTURE_LIFETIME, Span::call_site());
+
+    // name the elided lifetimes of the arguments
+    let mut named = ElidedLifetimes {
+        lifetimes: Vec::new(),
+        future_lifetime: &future_lifetime,
+    };
+    for arg in args.iter_mut() {
+        match arg {
+            FnArg::Receiver(receiver) => {
+                if let Some((_, lifetime @ None)) = &mut receiver.reference {
+                    *lifetime = Some(named.next_lifetime());
+                }
+            }
+            FnArg::Typed(arg) => named.visit_type_mut(&mut arg.ty),
+        }
+    }
+
+    // declare the lifetimes; lifetimes must be declared before any other generic parameter
+    let mut params: Vec<GenericParam> = vec![GenericParam::Lifetime(syn::LifetimeParam::new(
+        future_lifetime.clone(),
+    ))];
+    params.extend(
+        named
+            .lifetimes
+            .into_iter()
+            .map(|lifetime| GenericParam::Lifetime(syn::LifetimeParam::new(lifetime))),
+    );
+    params.extend(generics.params.iter().cloned());
+    params.sort_by_key(|param| !matches!(param, GenericParam::Lifetime(_)));
+    generics.params = params.into_iter().collect();
+
+    // everything captured by the future must outlive it; the bound is added where the parameter is already bounded,
+    // so that its bounds are not split between the declaration and the where clause
+    let mut where_clause = generics
+        .where_clause
+        .take()
+        .unwrap_or_else(|| syn::parse_quote! { where });
+    for param in generics.params.iter_mut() {
+        match param {
+            GenericParam::Lifetime(param) if param.lifetime != future_lifetime => {
+                let predicate =
+                    where_clause
+                        .predicates
+                        .iter_mut()
+                        .find_map(|predicate| match predicate {
+                            WherePredicate::Lifetime(predicate)
+                                if predicate.lifetime == param.lifetime =>
+                            {
+                                Some(&mut predicate.bounds)
+                            }
+                            _ => None,
+                        });
+                predicate
+                    .unwrap_or(&mut param.bounds)
+                    .push(future_lifetime.clone());
+            }
+            GenericParam::Type(param) => {
+                let predicate = where_clause
+                    .predicates
+                    .iter_mut()
+                    .find_map(|predicate| match predicate {
+                        WherePredicate::Type(predicate)
+                            if matches!(&predicate.bounded_ty, Type::Path(ty) if ty.path.is_ident(&param.ident)) =>
+                        {
+                            Some(&mut predicate.bounds)
+                        }
+                        _ => None,
+                    });
+                predicate
+                    .unwrap_or(&mut param.bounds)
+                    .push(TypeParamBound::Lifetime(future_lifetime.clone()));
+            }
+            _ => {}
+        }
+    }
+    where_clause
+        .predicates
+        .push(syn::parse_quote! { Self: #future_lifetime });
+    generics.where_clause = Some(where_clause);
+
+    future_lifetime
+}
+
+/// Returns the output type of the future of an async method.
+fn future_output(ret_type: &ReturnType) -> TokenStream2 {
+    match ret_type {
+        ReturnType::Default => quote! { () },
+        ReturnType::Type(_, ty) => quote! { #ty },
+    }
+}
+
+/// Names the elided lifetimes of a type and binds its `impl Trait` types to the lifetime of the future.
+struct ElidedLifetimes<'l> {
+    lifetimes: Vec<Lifetime>,
+    future_lifetime: &'l Lifetime,
+}
+
+impl ElidedLifetimes<'_> {
+    fn next_lifetime(&mut self) -> Lifetime {
+        let lifetime = Lifetime::new(
+            &format!("'__maybe_fut_arg_{}", self.lifetimes.len()),
+            Span::call_site(),
+        );
+        self.lifetimes.push(lifetime.clone());
+
+        lifetime
+    }
+}
+
+impl VisitMut for ElidedLifetimes<'_> {
+    fn visit_type_reference_mut(&mut self, reference: &mut syn::TypeReference) {
+        if reference.lifetime.is_none() {
+            reference.lifetime = Some(self.next_lifetime());
+        }
+        syn::visit_mut::visit_type_reference_mut(self, reference);
+    }
+
+    fn visit_lifetime_mut(&mut self, lifetime: &mut Lifetime) {
+        if lifetime.ident == "_" {
+            *lifetime = self.next_lifetime();
+        }
+    }
+
+    fn visit_type_impl_trait_mut(&mut self, impl_trait: &mut syn::TypeImplTrait) {
+        syn::visit_mut::visit_type_impl_trait_mut(self, impl_trait);
+        impl_trait
+            .bounds
+            .push(TypeParamBound::Lifetime(self.future_lifetime.clone()));
+    }
+
+    // the elided lifetimes of function pointers and `Fn` traits are higher-ranked, so they must be left as they are
+    fn visit_type_bare_fn_mut(&mut self, _: &mut syn::TypeBareFn) {}
+
+    fn visit_parenthesized_generic_arguments_mut(
+        &mut self,
+        _: &mut syn::ParenthesizedGenericArguments,
+    ) {
+    }
+}
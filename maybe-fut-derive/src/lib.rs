@@ -14,7 +14,10 @@
 )]
 
 mod args;
+mod boxed;
+mod module_derive;
 mod struct_derive;
+mod unwrap_types;
 
 use proc_macro::TokenStream;
 
@@ -27,16 +30,44 @@ pub fn maybe_fut(attr: TokenStream, item: TokenStream) -> TokenStream {
         }
     };
 
+    let item = match syn::parse::<syn::Item>(item) {
+        Ok(item) => item,
+        Err(err) => {
+            return err.to_compile_error().into();
+        }
+    };
+
     // check if the item is an impl block for a struct
-    if let Ok(struct_item) = syn::parse::<syn::ItemImpl>(item) {
-        return struct_derive::maybe_fut_struct(args, struct_item);
+    match item {
+        syn::Item::Impl(struct_item) => struct_derive::maybe_fut_struct(args, struct_item),
+        other => syn::Error::new_spanned(other, "maybe_fut can only be used on impl blocks")
+            .into_compile_error()
+            .into(),
     }
+}
+
+/// Generates the sync and async facades for a module.
+///
+/// The facades are two submodules of the annotated module, named after the `sync` and `tokio` arguments,
+/// which re-export respectively the sync and the tokio wrapper of every impl block annotated with `maybe_fut` in the module.
+///
+/// Optionally the `tokio_feature` argument, a feature name or a cfg predicate, gates the async facade.
+#[proc_macro_attribute]
+pub fn module(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = match syn::parse(attr) {
+        Ok(args) => args,
+        Err(err) => {
+            return err.to_compile_error().into();
+        }
+    };
 
-    // error
-    syn::Error::new(
-        proc_macro2::Span::call_site(),
-        "maybe_fut can only be used on impl blocks",
-    )
-    .into_compile_error()
-    .into()
+    match syn::parse::<syn::Item>(item) {
+        Ok(syn::Item::Mod(mod_item)) => module_derive::maybe_fut_module(args, mod_item),
+        Ok(other) => {
+            syn::Error::new_spanned(other, "maybe_fut::module can only be used on modules")
+                .into_compile_error()
+                .into()
+        }
+        Err(err) => err.to_compile_error().into(),
+    }
 }
@@ -0,0 +1,99 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{Attribute, Ident, Item, ItemMod};
+
+use super::args::{MaybeFutArgs, ModuleArgs, TokioGate};
+
+pub fn maybe_fut_module(
+    ModuleArgs {
+        sync: sync_mod_name,
+        tokio: tokio_mod_name,
+        tokio_feature,
+    }: ModuleArgs,
+    mut ast: ItemMod,
+) -> TokenStream {
+    let Some((_, items)) = ast.content.as_mut() else {
+        return syn::Error::new_spanned(
+            &ast,
+            "maybe_fut::module can only be used on inline modules",
+        )
+        .to_compile_error()
+        .into();
+    };
+
+    // collect the wrappers generated by the `maybe_fut` impl blocks in the module
+    let mut sync_wrappers: Vec<Ident> = Vec::new();
+    let mut tokio_wrappers: Vec<(Ident, TokioGate)> = Vec::new();
+    for item in items.iter() {
+        let Item::Impl(impl_item) = item else {
+            continue;
+        };
+        // trait impls don't generate any new type
+        if impl_item.trait_.is_some() {
+            continue;
+        }
+
+        let Some(attr) = maybe_fut_attr(&impl_item.attrs) else {
+            continue;
+        };
+        let args: MaybeFutArgs = match attr.parse_args() {
+            Ok(args) => args,
+            Err(err) => return err.to_compile_error().into(),
+        };
+
+        // the same type may be annotated in several impl blocks
+        if let Some(sync) = args.sync {
+            if !sync_wrappers.contains(&sync) {
+                sync_wrappers.push(sync);
+            }
+        }
+        if let Some((tokio, tokio_feature)) = args.tokio.zip(args.tokio_feature) {
+            if tokio_wrappers.iter().all(|(wrapper, _)| *wrapper != tokio) {
+                tokio_wrappers.push((tokio, tokio_feature));
+            }
+        }
+    }
+
+    let sync_exports = sync_wrappers.iter().map(|sync| {
+        quote! {
+            pub use super::#sync;
+        }
+    });
+    let tokio_exports = tokio_wrappers.iter().map(|(tokio, tokio_feature)| {
+        quote! {
+            #[cfg(#tokio_feature)]
+            pub use super::#tokio;
+        }
+    });
+    let tokio_mod_cfg = tokio_feature.map(|feature| {
+        quote! {
+            #[cfg(#feature)]
+        }
+    });
+
+    items.push(syn::parse_quote! {
+        /// Sync facade of the module.
+        pub mod #sync_mod_name {
+            #(#sync_exports)*
+        }
+    });
+    items.push(syn::parse_quote! {
+        /// Async facade of the module.
+        #tokio_mod_cfg
+        pub mod #tokio_mod_name {
+            #(#tokio_exports)*
+        }
+    });
+
+    quote! { #ast }.into()
+}
+
+/// Returns the `maybe_fut` attribute, if any.
+fn maybe_fut_attr(attrs: &[Attribute]) -> Option<&Attribute> {
+    attrs.iter().find(|attr| {
+        attr.path()
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "maybe_fut")
+    })
+}
@@ -2,59 +2,124 @@ use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{ToTokens, quote};
 use syn::punctuated::Punctuated;
-use syn::{Generics, Ident, ImplItemFn, ItemImpl, Type};
+use syn::visit_mut::VisitMut;
+use syn::{AttrStyle, Attribute, Generics, Ident, ImplItemFn, ItemImpl, Lifetime, Type};
 
 use super::args::MaybeFutArgs;
+use super::boxed::{boxed_method, send_method};
+use super::unwrap_types::{UnwrapImpl, is_maybe_fut_type};
 
 pub fn maybe_fut_struct(
     MaybeFutArgs {
         sync: sync_struct_name,
         tokio: tokio_struct_name,
         tokio_feature,
+        unwrap,
+        boxed,
+        send,
     }: MaybeFutArgs,
     ast: ItemImpl,
 ) -> TokenStream {
-    // get struct name of impl
-    let implementing_for = match implementing_for(&ast) {
-        Ok(ident) => ident,
-        Err(err) => return err,
-    };
+    // check the impl is for a type path
+    if let Err(err) = implementing_for(&ast.self_ty) {
+        return err.to_compile_error().into();
+    }
 
     // get all the methods in the impl block
-    let mut methods = Vec::new();
-    for impl_item in &ast.items {
-        if let syn::ImplItem::Fn(method) = impl_item {
-            methods.push(method.clone());
-        }
-    }
+    let methods = match impl_methods(&ast) {
+        Ok(methods) => methods,
+        Err(err) => return err.to_compile_error().into(),
+    };
 
-    // get generics impl parameters
-    let generics = &ast.generics;
-    // get generics parameters
-    let where_clause = &ast.generics.where_clause;
+    // get generics impl parameters, with elided lifetimes of the implementing type turned into named ones
+    let mut generics = ast.generics.clone();
+    let inner_ty = name_elided_lifetimes(&ast.self_ty, &mut generics);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     // get trait impl
     let trait_impl = &ast.trait_;
 
+    // the signatures of trait methods can't be translated, nor boxed
+    let unwrap = unwrap && trait_impl.is_none();
+    let boxed = boxed && trait_impl.is_none();
+    let send = send && trait_impl.is_none();
+
     // make sync structure block
-    let sync_quoted_methods =
-        gen_methods(&implementing_for, &ast.self_ty, generics, &methods, false);
+    let sync_quoted_methods = gen_methods(
+        &ast.self_ty,
+        &inner_ty,
+        &methods,
+        false,
+        unwrap,
+        false,
+        false,
+    );
 
     // make async structure block
     let async_quoted_methods =
-        gen_methods(&implementing_for, &ast.self_ty, generics, &methods, true);
+        gen_methods(&ast.self_ty, &inner_ty, &methods, true, unwrap, boxed, send);
 
     // check if we have a trait impl; in case it's a trait, we always return the `async_quoted_methods`, because if
     // a function is async, we cannot get rid of that in the sync impl
     if let Some((_, trait_name, for_token)) = trait_impl {
-        return quote! {
-            impl #generics #trait_name #for_token #sync_struct_name #generics #where_clause {
-                #(#async_quoted_methods)*
-            }
+        // the wrappers have the same generic arguments as the implementing type
+        let type_args = match implementing_for(&inner_ty) {
+            Ok(segment) => segment.arguments,
+            Err(err) => return err.to_compile_error().into(),
+        };
+        let unsafety = &ast.unsafety;
+
+        // the async methods of the trait get a blocking companion on the sync wrapper, which shadows the trait method
+        let blocking_methods: Vec<ImplItemFn> = methods
+            .iter()
+            .filter(|method| method.sig.asyncness.is_some())
+            .cloned()
+            .map(|mut method| {
+                method.vis = syn::parse_quote! { pub };
+                method
+            })
+            .collect();
+
+        let sync_impl = sync_struct_name.map(|sync_struct_name| {
+            let blocking_impl = if blocking_methods.is_empty() {
+                quote! {}
+            } else {
+                let blocking_quoted_methods = gen_methods(
+                    &ast.self_ty,
+                    &inner_ty,
+                    &blocking_methods,
+                    false,
+                    false,
+                    false,
+                    false,
+                );
+                quote! {
+                    impl #impl_generics #sync_struct_name #type_args #where_clause {
+                        #(#blocking_quoted_methods)*
+                    }
+                }
+            };
+
+            quote! {
+                #unsafety impl #impl_generics #trait_name #for_token #sync_struct_name #type_args #where_clause {
+                    #(#async_quoted_methods)*
+                }
 
-            #[cfg(feature = #tokio_feature)]
-            impl #generics #trait_name #for_token #tokio_struct_name #generics #where_clause {
-                #(#async_quoted_methods)*
+                #blocking_impl
+            }
+        });
+        let tokio_impl = tokio_struct_name.zip(tokio_feature).map(|(tokio_struct_name, tokio_feature)| {
+            quote! {
+                #[cfg(#tokio_feature)]
+                #unsafety impl #impl_generics #trait_name #for_token #tokio_struct_name #type_args #where_clause {
+                    #(#async_quoted_methods)*
+                }
             }
+        });
+
+        return quote! {
+            #sync_impl
+
+            #tokio_impl
 
             #ast
         }
@@ -62,61 +127,163 @@ pub fn maybe_fut_struct(
     }
 
     // Normal impl block
-    quote! {
-        pub struct #sync_struct_name #generics (#implementing_for #generics) #where_clause;
+    let sync_impl = sync_struct_name.map(|sync_struct_name| {
+        quote! {
+            pub struct #sync_struct_name #impl_generics (#inner_ty) #where_clause;
 
-        impl #generics #sync_struct_name #generics
-        #where_clause
-        {
-            #(#sync_quoted_methods)*
+            impl #impl_generics #sync_struct_name #ty_generics
+            #where_clause
+            {
+                #(#sync_quoted_methods)*
+            }
         }
+    });
+    let tokio_impl =
+        tokio_struct_name
+            .zip(tokio_feature)
+            .map(|(tokio_struct_name, tokio_feature)| {
+                quote! {
+                    #[cfg(#tokio_feature)]
+                    pub struct #tokio_struct_name #impl_generics (#inner_ty) #where_clause;
+
+                    #[cfg(#tokio_feature)]
+                    impl #impl_generics #tokio_struct_name #ty_generics
+                    #where_clause
+                    {
+                        #(#async_quoted_methods)*
+                    }
+                }
+            });
 
-        #[cfg(feature = #tokio_feature)]
-        pub struct #tokio_struct_name #generics (#implementing_for #generics) #where_clause;
+    quote! {
+        #sync_impl
 
-        #[cfg(feature = #tokio_feature)]
-        impl #generics #tokio_struct_name #generics
-        #where_clause
-        {
-            #(#async_quoted_methods)*
-        }
+        #tokio_impl
 
         #ast
     }
     .into()
 }
 
-/// Extracts the implementing type from the `ItemImpl` AST node.
-fn implementing_for(ast: &syn::ItemImpl) -> Result<syn::Ident, TokenStream> {
-    match ast.self_ty.as_ref() {
-        syn::Type::Path(type_path) => {
-            if let Some(segment) = type_path.path.segments.last() {
-                Ok(segment.ident.clone())
-            } else {
-                Err(syn::Error::new_spanned(
-                    ast.self_ty.clone(),
-                    "Expected a type path with at least one segment",
-                )
-                .to_compile_error()
-                .into())
-            }
+/// Extracts the last path segment of the implementing type of an impl block.
+fn implementing_for(self_ty: &Type) -> syn::Result<syn::PathSegment> {
+    match self_ty {
+        syn::Type::Path(type_path) if type_path.qself.is_none() => {
+            type_path.path.segments.last().cloned().ok_or_else(|| {
+                syn::Error::new_spanned(self_ty, "Expected a type path with at least one segment")
+            })
         }
         _ => Err(syn::Error::new_spanned(
-            ast.self_ty.clone(),
-            "Expected a type path for the implementing type",
-        )
-        .to_compile_error()
-        .into()),
+            self_ty,
+            "maybe_fut can only be used on impl blocks for a type path (e.g. `impl MyType`)",
+        )),
+    }
+}
+
+/// Returns the methods of the impl block.
+///
+/// Fails at every item which can't be carried to the generated wrappers.
+fn impl_methods(ast: &ItemImpl) -> syn::Result<Vec<ImplItemFn>> {
+    let mut methods = Vec::new();
+    let mut errors: Option<syn::Error> = None;
+    let mut push_error = |err: syn::Error| match errors.as_mut() {
+        Some(errors) => errors.combine(err),
+        None => errors = Some(err),
+    };
+
+    if let Some((Some(bang), _, _)) = &ast.trait_ {
+        push_error(syn::Error::new_spanned(
+            bang,
+            "maybe_fut can't be used on negative trait impls",
+        ));
+    }
+
+    for impl_item in &ast.items {
+        match impl_item {
+            syn::ImplItem::Fn(method) => {
+                if let Err(err) = check_self_ref_output(&ast.self_ty, method) {
+                    push_error(err);
+                }
+                methods.push(method.clone());
+            }
+            // associated items of inherent impls are left on the inner type
+            syn::ImplItem::Const(_) | syn::ImplItem::Type(_) if ast.trait_.is_none() => {}
+            syn::ImplItem::Const(item) => push_error(syn::Error::new_spanned(
+                item,
+                "associated consts are not supported in trait impls annotated with maybe_fut",
+            )),
+            syn::ImplItem::Type(item) => push_error(syn::Error::new_spanned(
+                item,
+                "associated types are not supported in trait impls annotated with maybe_fut",
+            )),
+            syn::ImplItem::Macro(item) => push_error(syn::Error::new_spanned(
+                item,
+                "macro invocations are not supported in impl blocks annotated with maybe_fut",
+            )),
+            other => push_error(syn::Error::new_spanned(
+                other,
+                "unsupported item in impl block annotated with maybe_fut",
+            )),
+        }
+    }
+
+    match errors {
+        Some(err) => Err(err),
+        None => Ok(methods),
     }
 }
 
+/// Replaces the elided lifetimes (`'_`) of the implementing type with named lifetimes,
+/// which are added to `generics`.
+///
+/// This is required, since the generated wrapper struct must declare all of its lifetimes.
+fn name_elided_lifetimes(self_ty: &Type, generics: &mut Generics) -> Type {
+    struct ElidedLifetimes<'g> {
+        generics: &'g mut Generics,
+        count: usize,
+    }
+
+    impl VisitMut for ElidedLifetimes<'_> {
+        fn visit_lifetime_mut(&mut self, lifetime: &mut Lifetime) {
+            if lifetime.ident != "_" {
+                return;
+            }
+
+            let named = Lifetime::new(&format!("'__maybe_fut_{}", self.count), lifetime.apostrophe);
+            // lifetimes must be declared before any other generic parameter
+            self.generics.params.insert(
+                self.count,
+                syn::GenericParam::Lifetime(syn::LifetimeParam::new(named.clone())),
+            );
+            self.count += 1;
+            *lifetime = named;
+        }
+    }
+
+    let mut self_ty = self_ty.clone();
+    ElidedLifetimes { generics, count: 0 }.visit_type_mut(&mut self_ty);
+
+    self_ty
+}
+
 /// Generates sync or async (based on value of `async_methods`) methods for the given methods in the impl block.
+///
+/// `self_ty` is the implementing type as written in the impl block, while `inner_ty` is the type wrapped by
+/// the generated struct (i.e. `self_ty` with named lifetimes).
+///
+/// If `unwrap` is set, the maybe-fut types in the signatures are translated into their std or tokio implementation.
+///
+/// If `boxed` is set, the generated async methods return a pinned and boxed `Send` future instead of being `async fn`.
+///
+/// If `send` is set, the generated async methods return an `impl Future + Send` instead of being `async fn`.
 fn gen_methods(
-    implementing_for: &Ident,
     self_ty: &Type,
-    generics: &Generics,
+    inner_ty: &Type,
     methods: &[ImplItemFn],
     async_methods: bool,
+    unwrap: bool,
+    boxed: bool,
+    send: bool,
 ) -> Vec<TokenStream2> {
     methods
         .iter()
@@ -127,12 +294,25 @@ fn gen_methods(
             let ret_type = &method.sig.output;
             let asyncness = method.sig.asyncness;
             let is_async = asyncness.is_some();
-            let attrs = &method.attrs;
+            // outer attributes are emitted in order on the generated method, while inner attributes go in its body
+            let (inner_attrs, attrs): (Vec<&Attribute>, Vec<&Attribute>) = method
+                .attrs
+                .iter()
+                .partition(|attr| matches!(attr.style, AttrStyle::Inner(_)));
             let mut first_is_self = false;
             let constness = method.sig.constness;
+            let unsafety = method.sig.unsafety;
+            let mut method_generics = method.sig.generics.clone();
 
-            let call_args = call_args(args, &mut first_is_self);
             let constructor_args = is_constructor(self_ty, method);
+            // constructors return the wrapper itself, so they are never translated
+            let unwrap_impl = match (unwrap && constructor_args.is_none(), async_methods) {
+                (false, _) => None,
+                (true, false) => Some(UnwrapImpl::Std),
+                (true, true) => Some(UnwrapImpl::Tokio),
+            };
+            let call_args = call_args(args, &mut first_is_self, unwrap_impl.is_some());
+            let turbofish = method_turbofish(method);
 
             let await_block = if is_async && async_methods {
                 quote! {
@@ -142,57 +322,148 @@ fn gen_methods(
                 quote! {}
             };
 
-            let generics_block = if generics.params.is_empty() {
-                quote! {}
-            } else {
-                quote! { ::#generics }
-            };
-
             let fn_body = if let Some(constructor_args) = constructor_args {
                 if constructor_args.is_result {
                     quote! {
-                        Ok(Self(#implementing_for #generics_block::#method_name(#call_args)#await_block?))
+                        Ok(Self(<#inner_ty>::#method_name #turbofish(#call_args)#await_block?))
                     }
                 } else if constructor_args.is_option {
                     quote! {
-                        Some(Self(#implementing_for #generics_block::#method_name(#call_args)#await_block?))
+                        Some(Self(<#inner_ty>::#method_name #turbofish(#call_args)#await_block?))
                     }
                 } else {
                     quote! {
-                        Self(#implementing_for #generics_block::#method_name(#call_args)#await_block)
+                        Self(<#inner_ty>::#method_name #turbofish(#call_args)#await_block)
                     }
                 }
             } else if !first_is_self {
                 quote! {
-                     #implementing_for #generics_block::#method_name(#call_args)#await_block
+                     <#inner_ty>::#method_name #turbofish(#call_args)#await_block
                 }
             } else {
                 quote! {
-                    self.0.#method_name(#call_args)#await_block
+                    self.0.#method_name #turbofish(#call_args)#await_block
                 }
             };
 
-            if is_async && !async_methods {
+            // the inner method can only be called in an unsafe block, even inside of an `unsafe fn`
+            let fn_body = if unsafety.is_some() {
                 quote! {
-                    #(#attrs)*
-                    #visibility #constness fn #method_name(#args) #ret_type {
+                    unsafe { #fn_body }
+                }
+            } else {
+                fn_body
+            };
+
+            let (fn_body, asyncness) = if is_async && !async_methods {
+                (
+                    quote! {
                         ::maybe_fut::SyncRuntime::block_on(
                             #fn_body
                         )
-                    }
-                }
+                    },
+                    None,
+                )
             } else {
+                (fn_body, asyncness)
+            };
+
+            // builder methods return a reference to the wrapper instead of the inner type, so that calls can be chained;
+            // they're checked to return their receiver by `check_self_ref_output`
+            let self_ref_output = self_ref_output(self_ty, method).filter(|_| first_is_self);
+            let fn_body = if self_ref_output.is_some() {
                 quote! {
-                    #(#attrs)*
-                    #visibility #constness #asyncness fn #method_name(#args) #ret_type {
-                        #fn_body
+                    #fn_body;
+                    self
+                }
+            } else {
+                fn_body
+            };
+
+            // translate the maybe-fut types in the signature
+            let (mut args, ret_type, fn_body) = match unwrap_impl {
+                Some(unwrap_impl) => {
+                    let args = unwrap_impl.args(args);
+                    match unwrap_impl.output(ret_type) {
+                        Some((ret_type, output)) => {
+                            (args, ret_type, unwrap_impl.unwrap(fn_body, output))
+                        }
+                        None => (args, ret_type.clone(), fn_body),
                     }
                 }
+                None => (args.clone(), ret_type.clone(), fn_body),
+            };
+            let ret_type = self_ref_output.unwrap_or(ret_type);
+
+            // box the returned future, or check that it is `Send`
+            let (ret_type, fn_body, asyncness) = if boxed && is_async && async_methods {
+                let (ret_type, fn_body) =
+                    boxed_method(&mut method_generics, &mut args, &ret_type, fn_body);
+                (ret_type, fn_body, None)
+            } else if send && is_async && async_methods {
+                let (ret_type, fn_body) =
+                    send_method(&mut method_generics, &mut args, &ret_type, fn_body);
+                (ret_type, fn_body, None)
+            } else {
+                (ret_type, fn_body, asyncness)
+            };
+            let method_where_clause = &method_generics.where_clause;
+
+            quote! {
+                #(#attrs)*
+                #visibility #constness #asyncness #unsafety fn #method_name #method_generics (#args) #ret_type
+                #method_where_clause
+                {
+                    #(#inner_attrs)*
+                    #fn_body
+                }
             }
         })
         .collect()
 }
 
+/// Returns the turbofish (e.g. `::<T, N>`) to forward the method generics to the inner call.
+///
+/// Lifetimes are omitted, since they may be late-bound and can't be specified explicitly.
+/// If any argument is an `impl Trait`, no turbofish is generated, since explicit generic arguments
+/// are not allowed in that case; the generics will be inferred instead.
+fn method_turbofish(method: &ImplItemFn) -> TokenStream2 {
+    let has_impl_trait_arg = method.sig.inputs.iter().any(|arg| match arg {
+        syn::FnArg::Typed(arg) => contains_impl_trait(arg.ty.to_token_stream()),
+        syn::FnArg::Receiver(_) => false,
+    });
+    if has_impl_trait_arg {
+        return quote! {};
+    }
+
+    let params: Vec<&Ident> = method
+        .sig
+        .generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            syn::GenericParam::Type(ty) => Some(&ty.ident),
+            syn::GenericParam::Const(cnst) => Some(&cnst.ident),
+            syn::GenericParam::Lifetime(_) => None,
+        })
+        .collect();
+
+    if params.is_empty() {
+        quote! {}
+    } else {
+        quote! { ::<#(#params),*> }
+    }
+}
+
+/// Returns whether the given tokens contain an `impl` keyword (e.g. `&impl AsRef<Path>`).
+fn contains_impl_trait(tokens: TokenStream2) -> bool {
+    tokens.into_iter().any(|token| match token {
+        proc_macro2::TokenTree::Ident(ident) => ident == "impl",
+        proc_macro2::TokenTree::Group(group) => contains_impl_trait(group.stream()),
+        _ => false,
+    })
+}
+
 struct ConstructorParams {
     pub is_result: bool,
     pub is_option: bool,
@@ -273,15 +544,101 @@ fn is_constructor(self_ty: &Type, method: &ImplItemFn) -> Option<ConstructorPara
     None
 }
 
+/// Returns the return type of the method with `Self` as referenced type, if it returns a reference to the
+/// implementing type (e.g. `&mut Self`), as builder methods do.
+fn self_ref_output(self_ty: &Type, method: &ImplItemFn) -> Option<syn::ReturnType> {
+    let syn::ReturnType::Type(arrow, ty) = &method.sig.output else {
+        return None;
+    };
+    let syn::Type::Reference(reference) = ty.as_ref() else {
+        return None;
+    };
+
+    let is_self = match reference.elem.as_ref() {
+        syn::Type::Path(type_path) if type_path.path.is_ident("Self") => true,
+        elem => elem.to_token_stream().to_string() == self_ty.to_token_stream().to_string(),
+    };
+    if !is_self {
+        return None;
+    }
+
+    let mut reference = reference.clone();
+    reference.elem = Box::new(syn::parse_quote! { Self });
+    Some(syn::ReturnType::Type(
+        *arrow,
+        Box::new(syn::Type::Reference(reference)),
+    ))
+}
+
+/// Checks that a method taking `self` and returning a reference to the implementing type returns its receiver.
+///
+/// The generated wrapper returns a reference to itself, so that builder methods can be chained, which would silently
+/// change the behaviour of a method returning any other instance, such as a field or a lookup result.
+fn check_self_ref_output(self_ty: &Type, method: &ImplItemFn) -> syn::Result<()> {
+    let takes_self = matches!(method.sig.inputs.first(), Some(syn::FnArg::Receiver(_)));
+    if !takes_self || self_ref_output(self_ty, method).is_none() || returns_receiver(&method.block)
+    {
+        return Ok(());
+    }
+
+    Err(syn::Error::new_spanned(
+        &method.sig.output,
+        "methods returning `&Self` or `&mut Self` must return their receiver (`self`), like builder methods, \
+since the generated wrapper returns a reference to itself rather than the one returned by the inner method",
+    ))
+}
+
+/// Returns whether the body of a method returns `self` from every `return` and from its tail expression.
+fn returns_receiver(block: &syn::Block) -> bool {
+    struct Returns {
+        all_self: bool,
+    }
+
+    impl VisitMut for Returns {
+        fn visit_expr_return_mut(&mut self, expr: &mut syn::ExprReturn) {
+            self.all_self &= expr.expr.as_deref().is_some_and(is_self);
+        }
+
+        // closures, async blocks and nested items return from themselves
+        fn visit_expr_closure_mut(&mut self, _: &mut syn::ExprClosure) {}
+
+        fn visit_expr_async_mut(&mut self, _: &mut syn::ExprAsync) {}
+
+        fn visit_item_mut(&mut self, _: &mut syn::Item) {}
+    }
+
+    fn is_self(expr: &syn::Expr) -> bool {
+        match expr {
+            syn::Expr::Path(path) => path.qself.is_none() && path.path.is_ident("self"),
+            syn::Expr::Paren(paren) => is_self(&paren.expr),
+            _ => false,
+        }
+    }
+
+    let tail_is_self = match block.stmts.last() {
+        Some(syn::Stmt::Expr(expr, None)) => is_self(expr),
+        // the returned expression is checked along with the other returns
+        Some(syn::Stmt::Expr(syn::Expr::Return(_), Some(_))) => true,
+        _ => false,
+    };
+    let mut returns = Returns { all_self: true };
+    returns.visit_block_mut(&mut block.clone());
+
+    tail_is_self && returns.all_self
+}
+
 /// Returns the call arguments for the method with self removed.
 ///
 /// Also returns whether the first argument is self.
+///
+/// If `unwrap` is set, the arguments of maybe-fut types are converted from their underlying implementation.
 fn call_args(
     args: &Punctuated<syn::FnArg, syn::token::Comma>,
     first_is_self: &mut bool,
-) -> Punctuated<Box<syn::Pat>, syn::token::Comma> {
+    unwrap: bool,
+) -> Punctuated<TokenStream2, syn::token::Comma> {
     // arguments to pass must have first argument removed if it is self and we must remove the types
-    let mut call_args: Punctuated<Box<syn::Pat>, syn::token::Comma> = Punctuated::new();
+    let mut call_args: Punctuated<TokenStream2, syn::token::Comma> = Punctuated::new();
     for arg in args.iter() {
         // check if first is self
         if !*first_is_self {
@@ -292,7 +649,12 @@ fn call_args(
         }
 
         if let syn::FnArg::Typed(arg) = arg {
-            call_args.push(arg.pat.clone());
+            let pat = &arg.pat;
+            if unwrap && is_maybe_fut_type(&arg.ty) {
+                call_args.push(quote! { ::core::convert::From::from(#pat) });
+            } else {
+                call_args.push(quote! { #pat });
+            }
         }
     }
 
@@ -0,0 +1,125 @@
+//! Translation of the maybe-fut types in the signatures of the generated methods into their underlying implementations.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::{FnArg, GenericArgument, PathArguments, ReturnType, Type};
+
+/// The underlying implementation of the maybe-fut types to translate to.
+#[derive(Debug, Clone, Copy)]
+pub enum UnwrapImpl {
+    Std,
+    Tokio,
+}
+
+/// How the output of a method must be unwrapped.
+#[derive(Debug, Clone, Copy)]
+pub enum UnwrapOutput {
+    /// The output is the maybe-fut type itself.
+    Value,
+    /// The output is a `Result` or an `Option` of the maybe-fut type.
+    Map,
+}
+
+impl UnwrapImpl {
+    /// Returns the underlying implementation type of `ty`.
+    fn impl_type(self, ty: &Type) -> Type {
+        match self {
+            Self::Std => syn::parse_quote! { <#ty as ::maybe_fut::Unwrap>::StdImpl },
+            Self::Tokio => syn::parse_quote! { <#ty as ::maybe_fut::Unwrap>::TokioImpl },
+        }
+    }
+
+    /// Returns the closure to unwrap a maybe-fut type.
+    ///
+    /// The maybe-fut types are backed by tokio within a tokio runtime, and by std outside of it, so the closure
+    /// panics with a message pointing at the misuse, rather than with the generic one of `unwrap_std`.
+    fn unwrap_fn(self) -> TokenStream2 {
+        match self {
+            Self::Std => quote! {
+                (|value| ::maybe_fut::Unwrap::get_std(value).expect(
+                    "the maybe-fut type is not backed by std: the sync struct must not be used within a tokio runtime",
+                ))
+            },
+            Self::Tokio => quote! {
+                (|value| ::maybe_fut::Unwrap::get_tokio(value).expect(
+                    "the maybe-fut type is not backed by tokio: the async struct must be used within a tokio runtime",
+                ))
+            },
+        }
+    }
+
+    /// Translates the maybe-fut types of the arguments into their underlying implementation.
+    pub fn args(
+        self,
+        args: &Punctuated<FnArg, syn::token::Comma>,
+    ) -> Punctuated<FnArg, syn::token::Comma> {
+        let mut args = args.clone();
+        for arg in args.iter_mut() {
+            if let FnArg::Typed(arg) = arg {
+                if is_maybe_fut_type(&arg.ty) {
+                    *arg.ty = self.impl_type(&arg.ty);
+                }
+            }
+        }
+
+        args
+    }
+
+    /// Translates the output, if it is a maybe-fut type or a `Result` or `Option` of it.
+    pub fn output(self, output: &ReturnType) -> Option<(ReturnType, UnwrapOutput)> {
+        let ReturnType::Type(arrow, ty) = output else {
+            return None;
+        };
+
+        if is_maybe_fut_type(ty) {
+            return Some((
+                ReturnType::Type(*arrow, Box::new(self.impl_type(ty))),
+                UnwrapOutput::Value,
+            ));
+        }
+
+        // check for `Result<T, _>` or `Option<T>`
+        let mut ty = ty.clone();
+        let Type::Path(type_path) = ty.as_mut() else {
+            return None;
+        };
+        let segment = type_path.path.segments.last_mut()?;
+        if segment.ident != "Result" && segment.ident != "Option" {
+            return None;
+        }
+        let PathArguments::AngleBracketed(args) = &mut segment.arguments else {
+            return None;
+        };
+        let Some(GenericArgument::Type(inner)) = args.args.first_mut() else {
+            return None;
+        };
+        if !is_maybe_fut_type(inner) {
+            return None;
+        }
+        *inner = self.impl_type(inner);
+
+        Some((ReturnType::Type(*arrow, ty), UnwrapOutput::Map))
+    }
+
+    /// Unwraps the value returned by `body`.
+    pub fn unwrap(self, body: TokenStream2, output: UnwrapOutput) -> TokenStream2 {
+        let unwrap_fn = self.unwrap_fn();
+        match output {
+            UnwrapOutput::Value => quote! { #unwrap_fn(#body) },
+            UnwrapOutput::Map => quote! { (#body).map(#unwrap_fn) },
+        }
+    }
+}
+
+/// Returns whether `ty` is a maybe-fut type, spelled with its full path (e.g. `maybe_fut::fs::File`).
+pub fn is_maybe_fut_type(ty: &Type) -> bool {
+    match ty {
+        Type::Path(type_path) if type_path.qself.is_none() => type_path
+            .path
+            .segments
+            .first()
+            .is_some_and(|segment| segment.ident == "maybe_fut"),
+        _ => false,
+    }
+}
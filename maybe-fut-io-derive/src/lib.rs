@@ -17,6 +17,33 @@
 //!    Tokio(tokio::fs::File),
 //! }
 //! ```
+//!
+//! The traits can also be derived directly on the inner enum, for crates exposing the enum itself:
+//!
+//! ```rust,ignore
+//! #[derive(Read, Write, Seek)]
+//! #[io(feature("tokio-fs"))]
+//! enum File {
+//!    Std(std::fs::File),
+//!    Tokio(tokio::fs::File),
+//! }
+//! ```
+//!
+//! If the struct has more than one field, the field holding the inner enum must be selected by name or index,
+//! e.g. `#[io(field = "inner")]` or `#[io(field = "1")]`.
+//!
+//! The variants of the inner enum are named `Std` and `Tokio` by default; different names can be set with
+//! `#[io(std_variant = "Sync", tokio_variant = "Async")]`.
+//!
+//! If the inner enum has an async-std variant, the feature enabling it must be set with `#[io(async_std("async-std"))]`;
+//! the variant is named `AsyncStd` by default, and a different name can be set with `async_std_variant`.
+//!
+//! Likewise, a tokio-uring variant is enabled with `#[io(uring("uring-fs"))]` and compiled only on Linux;
+//! it is named `Uring` by default (see `uring_variant`) and must provide async inherent `read`, `write`,
+//! `write_vectored`, `flush` and `seek` methods.
+//!
+//! The generated code refers to the traits through `::maybe_fut`; if the crate has been renamed,
+//! the path can be set with `#[io(crate_path(my_maybe_fut))]`.
 
 #![doc(html_playground_url = "https://play.rust-lang.org")]
 #![doc(
@@ -27,54 +54,58 @@
 )]
 
 use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
-use syn::{Data, DeriveInput, Fields, parenthesized, parse_macro_input};
+use syn::{Data, DeriveInput, Member, parenthesized, parse_macro_input};
 
 #[proc_macro_derive(Read, attributes(io))]
 pub fn read(item: TokenStream) -> TokenStream {
     let input = parse_macro_input!(item as DeriveInput);
     let struct_name = &input.ident;
-    // struct must be a tuple struct
-    let fields = match input.data {
-        Data::Struct(ref data) => match data.fields {
-            Fields::Unnamed(ref fields) => &fields.unnamed,
-            Fields::Named(_) => panic!("Unwrap can only be derived for tuple structs"),
-            Fields::Unit => panic!("Unwrap can only be derived for tuple structs"),
-        },
-        _ => panic!("Unwrap can only be derived for structs"),
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let Attributes {
+        feature,
+        krate,
+        field,
+        std_variant,
+        tokio_variant,
+        async_std,
+        uring,
+    } = match attrs(&input) {
+        Ok(attrs) => attrs,
+        Err(err) => return err.into_compile_error().into(),
     };
 
-    // should be a single field
-    let parent_struct_field = match fields.len() {
-        1 => &fields[0],
-        _ => panic!("Unwrap can only be derived for structs with a single field"),
+    let (scrutinee, enum_ident) = match inner_enum(&input, field.as_ref(), "Read") {
+        Ok(inner) => inner,
+        Err(err) => return err.into_compile_error().into(),
     };
 
-    // this field must be an Enum
-    let field_type = match &parent_struct_field.ty {
-        syn::Type::Path(path) => path,
-        _ => panic!("Unwrap can only be derived for structs with a single field"),
-    };
-
-    let field_type_ident = &field_type.path.segments.last().unwrap().ident;
-
-    let Attributes { feature } = attrs(&input);
+    let read_arm = async_std_arm(
+        async_std.as_ref(),
+        &enum_ident,
+        quote! { ReadExt },
+        quote! { read(buf) },
+    );
+    let uring_read_arm = uring_arm(uring.as_ref(), &enum_ident, quote! { read(buf) });
 
     let output = quote! {
         const _: () = {
-            use crate::io::Read;
+            use #krate::io::Read;
 
-            impl Read for #struct_name {
+            impl #impl_generics Read for #struct_name #ty_generics #where_clause {
                 async fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
                     use std::io::Read as _;
 
-                    match &mut self.0 {
-                        #field_type_ident::Std(inner) => inner.read(buf),
+                    match &mut #scrutinee {
+                        #enum_ident::#std_variant(inner) => inner.read(buf),
                         #[cfg(feature = #feature)]
-                        #field_type_ident::Tokio(inner) => {
+                        #enum_ident::#tokio_variant(inner) => {
                             use tokio::io::AsyncReadExt as _;
                             inner.read(buf).await
                         }
+                        #read_arm
+                        #uring_read_arm
                     }
                 }
             }
@@ -88,60 +119,126 @@ pub fn read(item: TokenStream) -> TokenStream {
 pub fn write(item: TokenStream) -> TokenStream {
     let input = parse_macro_input!(item as DeriveInput);
     let struct_name = &input.ident;
-    // struct must be a tuple struct
-    let fields = match input.data {
-        Data::Struct(ref data) => match data.fields {
-            Fields::Unnamed(ref fields) => &fields.unnamed,
-            Fields::Named(_) => panic!("Write can only be derived for tuple structs"),
-            Fields::Unit => panic!("Write can only be derived for tuple structs"),
-        },
-        _ => panic!("Write can only be derived for structs"),
-    };
-
-    // should be a single field
-    let parent_struct_field = match fields.len() {
-        1 => &fields[0],
-        _ => panic!("Write can only be derived for structs with a single field"),
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let Attributes {
+        feature,
+        krate,
+        field,
+        std_variant,
+        tokio_variant,
+        async_std,
+        uring,
+    } = match attrs(&input) {
+        Ok(attrs) => attrs,
+        Err(err) => return err.into_compile_error().into(),
     };
 
-    // this field must be an Enum
-    let field_type = match &parent_struct_field.ty {
-        syn::Type::Path(path) => path,
-        _ => panic!("Write can only be derived for structs with a single field"),
+    let (scrutinee, enum_ident) = match inner_enum(&input, field.as_ref(), "Write") {
+        Ok(inner) => inner,
+        Err(err) => return err.into_compile_error().into(),
     };
 
-    let field_type_ident = &field_type.path.segments.last().unwrap().ident;
-
-    let Attributes { feature } = attrs(&input);
+    let write_arm = async_std_arm(
+        async_std.as_ref(),
+        &enum_ident,
+        quote! { WriteExt },
+        quote! { write(buf) },
+    );
+    let flush_arm = async_std_arm(
+        async_std.as_ref(),
+        &enum_ident,
+        quote! { WriteExt },
+        quote! { flush() },
+    );
+    let write_vectored_arm = async_std_arm(
+        async_std.as_ref(),
+        &enum_ident,
+        quote! { WriteExt },
+        quote! { write_vectored(bufs) },
+    );
+    let uring_write_arm = uring_arm(uring.as_ref(), &enum_ident, quote! { write(buf) });
+    let uring_flush_arm = uring_arm(uring.as_ref(), &enum_ident, quote! { flush() });
+    let uring_write_vectored_arm =
+        uring_arm(uring.as_ref(), &enum_ident, quote! { write_vectored(bufs) });
+    // async-std doesn't tell whether a writer is vectored
+    let is_write_vectored_arm = async_std.as_ref().map(|(feature, variant)| {
+        quote! {
+            #[cfg(feature = #feature)]
+            #enum_ident::#variant(_) => false,
+        }
+    });
+    // the tokio-uring variant writes all the buffers at once
+    let uring_is_write_vectored_arm = uring.as_ref().map(|(feature, variant)| {
+        quote! {
+            #[cfg(all(feature = #feature, target_os = "linux"))]
+            #enum_ident::#variant(_) => true,
+        }
+    });
 
     let output = quote! {
         const _: () = {
-            use crate::io::Write;
+            use #krate::io::Write;
 
-            impl Write for #struct_name {
+            impl #impl_generics Write for #struct_name #ty_generics #where_clause {
                 async fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
                     use std::io::Write as _;
 
-                    match &mut self.0 {
-                        #field_type_ident::Std(inner) => inner.write(buf),
+                    match &mut #scrutinee {
+                        #enum_ident::#std_variant(inner) => inner.write(buf),
                         #[cfg(feature = #feature)]
-                        #field_type_ident::Tokio(inner) => {
+                        #enum_ident::#tokio_variant(inner) => {
                             use tokio::io::AsyncWriteExt as _;
                             inner.write(buf).await
                         }
+                        #write_arm
+                        #uring_write_arm
                     }
                 }
 
                 async fn flush(&mut self) -> std::io::Result<()> {
                     use std::io::Write as _;
 
-                    match &mut self.0 {
-                        #field_type_ident::Std(inner) => inner.flush(),
+                    match &mut #scrutinee {
+                        #enum_ident::#std_variant(inner) => inner.flush(),
                         #[cfg(feature = #feature)]
-                        #field_type_ident::Tokio(inner) => {
+                        #enum_ident::#tokio_variant(inner) => {
                             use tokio::io::AsyncWriteExt as _;
                             inner.flush().await
                         }
+                        #flush_arm
+                        #uring_flush_arm
+                    }
+                }
+
+                async fn write_vectored(
+                    &mut self,
+                    bufs: &[std::io::IoSlice<'_>],
+                ) -> std::io::Result<usize> {
+                    use std::io::Write as _;
+
+                    match &mut #scrutinee {
+                        #enum_ident::#std_variant(inner) => inner.write_vectored(bufs),
+                        #[cfg(feature = #feature)]
+                        #enum_ident::#tokio_variant(inner) => {
+                            use tokio::io::AsyncWriteExt as _;
+                            inner.write_vectored(bufs).await
+                        }
+                        #write_vectored_arm
+                        #uring_write_vectored_arm
+                    }
+                }
+
+                fn is_write_vectored(&self) -> bool {
+                    match &#scrutinee {
+                        // `std::io::Write::is_write_vectored` is unstable; the std writers wrapped by maybe-fut
+                        // all implement vectored writes
+                        #enum_ident::#std_variant(_) => true,
+                        #[cfg(feature = #feature)]
+                        #enum_ident::#tokio_variant(inner) => {
+                            tokio::io::AsyncWrite::is_write_vectored(inner)
+                        }
+                        #is_write_vectored_arm
+                        #uring_is_write_vectored_arm
                     }
                 }
             }
@@ -155,47 +252,50 @@ pub fn write(item: TokenStream) -> TokenStream {
 pub fn seek(item: TokenStream) -> TokenStream {
     let input = parse_macro_input!(item as DeriveInput);
     let struct_name = &input.ident;
-    // struct must be a tuple struct
-    let fields = match input.data {
-        Data::Struct(ref data) => match data.fields {
-            Fields::Unnamed(ref fields) => &fields.unnamed,
-            Fields::Named(_) => panic!("Seek can only be derived for tuple structs"),
-            Fields::Unit => panic!("Seek can only be derived for tuple structs"),
-        },
-        _ => panic!("Seek can only be derived for structs"),
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let Attributes {
+        feature,
+        krate,
+        field,
+        std_variant,
+        tokio_variant,
+        async_std,
+        uring,
+    } = match attrs(&input) {
+        Ok(attrs) => attrs,
+        Err(err) => return err.into_compile_error().into(),
     };
 
-    // should be a single field
-    let parent_struct_field = match fields.len() {
-        1 => &fields[0],
-        _ => panic!("Seek can only be derived for structs with a single field"),
+    let (scrutinee, enum_ident) = match inner_enum(&input, field.as_ref(), "Seek") {
+        Ok(inner) => inner,
+        Err(err) => return err.into_compile_error().into(),
     };
 
-    // this field must be an Enum
-    let field_type = match &parent_struct_field.ty {
-        syn::Type::Path(path) => path,
-        _ => panic!("Seek can only be derived for structs with a single field"),
-    };
-
-    let field_type_ident = &field_type.path.segments.last().unwrap().ident;
-
-    let Attributes { feature } = attrs(&input);
+    let seek_arm = async_std_arm(
+        async_std.as_ref(),
+        &enum_ident,
+        quote! { SeekExt },
+        quote! { seek(pos) },
+    );
+    let uring_seek_arm = uring_arm(uring.as_ref(), &enum_ident, quote! { seek(pos) });
 
     let output = quote! {
         const _: () = {
-            use crate::io::Seek;
+            use #krate::io::Seek;
 
-            impl Seek for #struct_name {
+            impl #impl_generics Seek for #struct_name #ty_generics #where_clause {
                 async fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
                     use std::io::Seek as _;
 
-                    match &mut self.0 {
-                        #field_type_ident::Std(inner) => inner.seek(pos),
+                    match &mut #scrutinee {
+                        #enum_ident::#std_variant(inner) => inner.seek(pos),
                         #[cfg(feature = #feature)]
-                        #field_type_ident::Tokio(inner) => {
+                        #enum_ident::#tokio_variant(inner) => {
                             use tokio::io::AsyncSeekExt as _;
                             inner.seek(pos).await
                         }
+                        #seek_arm
+                        #uring_seek_arm
                     }
                 }
             }
@@ -205,12 +305,66 @@ pub fn seek(item: TokenStream) -> TokenStream {
     output.into()
 }
 
+/// Returns the match arm for the async-std variant of the inner enum, if it has one.
+fn async_std_arm(
+    async_std: Option<&(syn::LitStr, syn::Ident)>,
+    enum_ident: &syn::Ident,
+    ext_trait: TokenStream2,
+    call: TokenStream2,
+) -> Option<TokenStream2> {
+    async_std.map(|(feature, variant)| {
+        quote! {
+            #[cfg(feature = #feature)]
+            #enum_ident::#variant(inner) => {
+                use async_std::io::#ext_trait as _;
+                inner.#call.await
+            }
+        }
+    })
+}
+
+/// Returns the match arm for the tokio-uring variant of the inner enum, if it has one.
+///
+/// The tokio-uring file doesn't implement the io traits, so the variant must provide async inherent methods instead.
+fn uring_arm(
+    uring: Option<&(syn::LitStr, syn::Ident)>,
+    enum_ident: &syn::Ident,
+    call: TokenStream2,
+) -> Option<TokenStream2> {
+    uring.map(|(feature, variant)| {
+        quote! {
+            #[cfg(all(feature = #feature, target_os = "linux"))]
+            #enum_ident::#variant(inner) => inner.#call.await,
+        }
+    })
+}
+
 struct Attributes {
     feature: syn::LitStr,
+    /// The field holding the inner enum, by name or index; required if the struct has more than one field.
+    field: Option<syn::LitStr>,
+    /// Path to the maybe-fut crate; `::maybe_fut` by default.
+    krate: syn::Path,
+    /// Name of the std variant of the inner enum; `Std` by default.
+    std_variant: syn::Ident,
+    /// Name of the tokio variant of the inner enum; `Tokio` by default.
+    tokio_variant: syn::Ident,
+    /// Feature enabling the async-std variant of the inner enum and the name of the variant, if the enum has one.
+    async_std: Option<(syn::LitStr, syn::Ident)>,
+    /// Feature enabling the tokio-uring variant of the inner enum and the name of the variant, if the enum has one.
+    uring: Option<(syn::LitStr, syn::Ident)>,
 }
 
-fn attrs(input: &DeriveInput) -> Attributes {
+fn attrs(input: &DeriveInput) -> syn::Result<Attributes> {
     let mut feature: Option<syn::LitStr> = None;
+    let mut krate: Option<syn::Path> = None;
+    let mut field: Option<syn::LitStr> = None;
+    let mut std_variant: Option<syn::Ident> = None;
+    let mut tokio_variant: Option<syn::Ident> = None;
+    let mut async_std: Option<syn::LitStr> = None;
+    let mut async_std_variant: Option<syn::Ident> = None;
+    let mut uring: Option<syn::LitStr> = None;
+    let mut uring_variant: Option<syn::Ident> = None;
 
     for attr in &input.attrs {
         if attr.path().is_ident("io") {
@@ -218,24 +372,152 @@ fn attrs(input: &DeriveInput) -> Attributes {
                 if meta.path.is_ident("feature") {
                     let content;
                     parenthesized!(content in meta.input);
-                    feature = Some(
-                        content
-                            .parse::<syn::LitStr>()
-                            .expect("feature ident not a value"),
-                    );
+                    feature = Some(content.parse::<syn::LitStr>()?);
+                    Ok(())
+                } else if meta.path.is_ident("crate_path") {
+                    let content;
+                    parenthesized!(content in meta.input);
+                    krate = Some(content.parse::<syn::Path>()?);
+                    Ok(())
+                } else if meta.path.is_ident("field") {
+                    field = Some(meta.value()?.parse::<syn::LitStr>()?);
+                    Ok(())
+                } else if meta.path.is_ident("std_variant") {
+                    std_variant = Some(meta.value()?.parse::<syn::LitStr>()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("tokio_variant") {
+                    tokio_variant = Some(meta.value()?.parse::<syn::LitStr>()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("async_std") {
+                    let content;
+                    parenthesized!(content in meta.input);
+                    async_std = Some(content.parse::<syn::LitStr>()?);
+                    Ok(())
+                } else if meta.path.is_ident("async_std_variant") {
+                    async_std_variant = Some(meta.value()?.parse::<syn::LitStr>()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("uring") {
+                    let content;
+                    parenthesized!(content in meta.input);
+                    uring = Some(content.parse::<syn::LitStr>()?);
+                    Ok(())
+                } else if meta.path.is_ident("uring_variant") {
+                    uring_variant = Some(meta.value()?.parse::<syn::LitStr>()?.parse()?);
                     Ok(())
                 } else if meta.path.is_ident("io") {
                     // This is the main attribute, we can ignore it
                     Ok(())
                 } else {
-                    Err(meta.error("Expected #[io]"))
+                    Err(meta.error(
+                        "Unexpected key in #[io], expected one of `feature`, `field`, `std_variant`, `tokio_variant`, `async_std`, `async_std_variant`, `uring`, `uring_variant`, `crate_path`",
+                    ))
                 }
-            })
-            .expect("Invalid syntax in #[io]");
+            })?;
         }
     }
 
-    Attributes {
-        feature: feature.expect("Missing `feature` in #[io]"),
-    }
+    let Some(feature) = feature else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "Missing `feature` in #[io], e.g. #[io(feature(\"tokio\"))]",
+        ));
+    };
+
+    Ok(Attributes {
+        feature,
+        krate: krate.unwrap_or_else(|| syn::parse_quote! { ::maybe_fut }),
+        field,
+        std_variant: std_variant.unwrap_or_else(|| syn::parse_quote! { Std }),
+        tokio_variant: tokio_variant.unwrap_or_else(|| syn::parse_quote! { Tokio }),
+        async_std: async_std.map(|feature| {
+            let variant = async_std_variant.unwrap_or_else(|| syn::parse_quote! { AsyncStd });
+            (feature, variant)
+        }),
+        uring: uring.map(|feature| {
+            let variant = uring_variant.unwrap_or_else(|| syn::parse_quote! { Uring });
+            (feature, variant)
+        }),
+    })
+}
+
+/// Returns the expression to access the inner enum from `self`, along with the name of the enum.
+///
+/// If the derive is applied to the enum itself, the expression is `*self`.
+/// If the struct has more than one field, the field must be selected with `#[io(field = "...")]`.
+fn inner_enum(
+    input: &DeriveInput,
+    field: Option<&syn::LitStr>,
+    derive: &str,
+) -> syn::Result<(TokenStream2, syn::Ident)> {
+    let fields = match input.data {
+        Data::Struct(ref data) => &data.fields,
+        Data::Enum(_) => {
+            if let Some(field) = field {
+                return Err(syn::Error::new_spanned(
+                    field,
+                    "`field` can't be set when deriving for an enum",
+                ));
+            }
+            return Ok((quote! { *self }, input.ident.clone()));
+        }
+        Data::Union(ref data) => {
+            return Err(syn::Error::new_spanned(
+                data.union_token,
+                format!("{derive} can only be derived for structs and enums"),
+            ));
+        }
+    };
+
+    let (member, parent_struct_field) = match (field, fields.len()) {
+        (Some(field), _) => {
+            // the field is selected by name or by index
+            let selected = match field.value().parse::<usize>() {
+                Ok(index) => Member::from(index),
+                Err(_) => Member::Named(field.parse()?),
+            };
+            fields
+                .members()
+                .zip(fields.iter())
+                .find(|(member, _)| *member == selected)
+                .ok_or_else(|| {
+                    syn::Error::new_spanned(
+                        field,
+                        format!("The struct has no field `{}`", field.value()),
+                    )
+                })?
+        }
+        // should be a single field
+        (None, 1) => fields
+            .members()
+            .zip(fields.iter())
+            .next()
+            .expect("fields is not empty"),
+        (None, _) => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                format!(
+                    "{derive} can only be derived for structs with a single field or with the field selected with #[io(field = \"...\")]"
+                ),
+            ));
+        }
+    };
+
+    // this field must be an Enum
+    let field_type = match &parent_struct_field.ty {
+        syn::Type::Path(path) => path,
+        other => {
+            return Err(syn::Error::new_spanned(
+                other,
+                "The field holding the inner enum must be a path to the enum type",
+            ));
+        }
+    };
+    let enum_ident = field_type
+        .path
+        .segments
+        .last()
+        .map(|segment| segment.ident.clone())
+        .ok_or_else(|| syn::Error::new_spanned(field_type, "Expected a path to the enum type"))?;
+
+    Ok((quote! { self.#member }, enum_ident))
 }
@@ -17,6 +17,59 @@
 //!    Tokio(tokio::fs::File),
 //! }
 //! ```
+//!
+//! The derive can also be applied directly to the inner enum:
+//!
+//! ```rust,ignore
+//! #[derive(Unwrap)]
+//! #[unwrap_types(std(std::fs::File), tokio(tokio::fs::File))]
+//! enum InnerWrapper {
+//!    Std(std::fs::File),
+//!    Tokio(tokio::fs::File),
+//! }
+//! ```
+//!
+//! The generated code refers to the trait through `::maybe_fut`; if the crate has been renamed,
+//! the path can be set with `#[unwrap_types(crate_path(my_maybe_fut))]`.
+//! The tokio accessors are implemented whenever maybe-fut is built with its `tokio` feature, whatever the features
+//! of the deriving crate; the tokio type is used only if the `tokio_gated` feature of the deriving crate is enabled,
+//! and the std type otherwise.
+//!
+//! The `tokio` and `tokio_gated` keys can be omitted together for wrappers of types without a tokio counterpart:
+//! only the std variant is matched, and with the `tokio` feature the tokio accessors return the std type.
+//!
+//! ```rust,ignore
+//! #[derive(Unwrap)]
+//! #[unwrap_types(std(std::fs::Metadata))]
+//! struct Metadata(MetadataInner);
+//!
+//! enum MetadataInner {
+//!    Std(std::fs::Metadata),
+//! }
+//! ```
+//!
+//! Generic wrappers are supported, with their bounds and where-clauses; the types in `#[unwrap_types]` are taken as
+//! written, so they spell the generic arguments they need:
+//!
+//! ```rust,ignore
+//! #[derive(Unwrap)]
+//! #[unwrap_types(std(std::io::Cursor<B>), tokio(tokio::io::DuplexStream), tokio_gated("tokio"))]
+//! struct MyBuffer<B = Vec<u8>>(BufferInner<B>)
+//! where
+//!     B: AsRef<[u8]>;
+//! ```
+//!
+//! Structs with more than one field must mark the field holding the inner enum with `#[unwrap_inner]`:
+//!
+//! ```rust,ignore
+//! #[derive(Unwrap)]
+//! #[unwrap_types(std(std::fs::File), tokio(tokio::fs::File))]
+//! struct MyWrapper {
+//!     #[unwrap_inner]
+//!     inner: InnerWrapper,
+//!     path: PathBuf,
+//! }
+//! ```
 
 #![doc(html_playground_url = "https://play.rust-lang.org")]
 #![doc(
@@ -28,236 +81,309 @@
 
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{Data, DeriveInput, Fields, parenthesized, parse_macro_input};
+use syn::{Data, DeriveInput, Field, Fields, Ident, Member, parenthesized, parse_macro_input};
 
-#[proc_macro_derive(Unwrap, attributes(unwrap_types))]
+#[proc_macro_derive(Unwrap, attributes(unwrap_types, unwrap_inner))]
 pub fn unwrap(item: TokenStream) -> TokenStream {
     let input = parse_macro_input!(item as DeriveInput);
     let struct_name = &input.ident;
-    let generics = &input.generics;
-    // struct must be a tuple struct
-    let fields = match input.data {
-        Data::Struct(ref data) => match data.fields {
-            Fields::Unnamed(ref fields) => &fields.unnamed,
-            Fields::Named(_) => panic!("Unwrap can only be derived for tuple structs"),
-            Fields::Unit => panic!("Unwrap can only be derived for tuple structs"),
-        },
-        _ => panic!("Unwrap can only be derived for structs"),
-    };
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    // the patterns matching the std and tokio variants of the inner enum
+    let (std_variant, tokio_variant) = match input.data {
+        Data::Struct(ref data) => {
+            // the field holding the inner enum
+            let (member, parent_struct_field) = match inner_field(struct_name, &data.fields) {
+                Ok(inner) => inner,
+                Err(err) => return err.into_compile_error().into(),
+            };
+
+            // this field must be an Enum
+            let field_type = match &parent_struct_field.ty {
+                syn::Type::Path(path) => path,
+                other => {
+                    return syn::Error::new_spanned(
+                        other,
+                        "The field holding the inner enum must be a path to the enum type",
+                    )
+                    .into_compile_error()
+                    .into();
+                }
+            };
 
-    // should be a single field
-    let parent_struct_field = match fields.len() {
-        1 => &fields[0],
-        _ => panic!("Unwrap can only be derived for structs with a single field"),
-    };
+            // the path of the enum may be qualified (e.g. `some::module::Inner`); generic arguments are inferred
+            let mut enum_path = field_type.path.clone();
+            for segment in enum_path.segments.iter_mut() {
+                segment.arguments = syn::PathArguments::None;
+            }
 
-    // this field must be an Enum
-    let field_type = match &parent_struct_field.ty {
-        syn::Type::Path(path) => path,
-        _ => panic!("Unwrap can only be derived for structs with a single field"),
-    };
+            (
+                quote! { #struct_name { #member: #enum_path::Std(inner), .. } },
+                quote! { #struct_name { #member: #enum_path::Tokio(inner), .. } },
+            )
+        }
+        Data::Enum(ref data) => {
+            // the enum itself is the inner enum
+            if !data
+                .variants
+                .iter()
+                .any(|variant| variant.ident == "Std" && variant.fields.len() == 1)
+            {
+                return syn::Error::new_spanned(
+                    struct_name,
+                    "Unwrap can only be derived for enums with a `Std` variant with a single field",
+                )
+                .into_compile_error()
+                .into();
+            }
 
-    let field_type_ident = &field_type.path.segments.last().unwrap().ident;
+            (
+                quote! { #struct_name::Std(inner) },
+                quote! { #struct_name::Tokio(inner) },
+            )
+        }
+        Data::Union(ref data) => {
+            return syn::Error::new_spanned(
+                data.union_token,
+                "Unwrap can only be derived for structs and enums",
+            )
+            .into_compile_error()
+            .into();
+        }
+    };
 
     let mut std_mod: Option<syn::Type> = None;
     let mut tokio_mod: Option<syn::Type> = None;
     let mut tokio_gated: Option<syn::LitStr> = None;
+    let mut krate: Option<syn::Path> = None;
 
     for attr in &input.attrs {
         if attr.path().is_ident("unwrap_types") {
-            attr.parse_nested_meta(|meta| {
+            let result = attr.parse_nested_meta(|meta| {
                 if meta.path.is_ident("std") {
                     let content;
                     parenthesized!(content in meta.input);
-                    std_mod = Some(content.parse::<syn::Type>().expect("std ident not a value"));
+                    std_mod = Some(content.parse::<syn::Type>()?);
                     Ok(())
                 } else if meta.path.is_ident("tokio") {
                     let content;
                     parenthesized!(content in meta.input);
-                    tokio_mod = Some(
-                        content
-                            .parse::<syn::Type>()
-                            .expect("tokio ident not a value"),
-                    );
+                    tokio_mod = Some(content.parse::<syn::Type>()?);
                     Ok(())
                 } else if meta.path.is_ident("tokio_gated") {
                     let content;
                     parenthesized!(content in meta.input);
-                    tokio_gated = Some(
-                        content
-                            .parse::<syn::LitStr>()
-                            .expect("tokio_gated ident not a value"),
-                    );
+                    tokio_gated = Some(content.parse::<syn::LitStr>()?);
+                    Ok(())
+                } else if meta.path.is_ident("crate_path") {
+                    let content;
+                    parenthesized!(content in meta.input);
+                    krate = Some(content.parse::<syn::Path>()?);
                     Ok(())
                 } else if meta.path.is_ident("unwrap_types") {
                     // This is the main attribute, we can ignore it
                     Ok(())
                 } else {
-                    Err(meta.error("Expected #[unwrap_types]"))
+                    Err(meta.error(
+                        "Unexpected key in #[unwrap_types], expected one of `std`, `tokio`, `tokio_gated`, `crate_path`",
+                    ))
                 }
-            })
-            .expect("Invalid syntax in #[unwrap_types]");
+            });
+            if let Err(err) = result {
+                return err.into_compile_error().into();
+            }
         }
     }
 
-    let std_inner_type = std_mod.expect("Missing `std` in #[unwrap_types]");
-    let tokio_inner_type = tokio_mod.expect("Missing `tokio` in #[unwrap_types]");
-    let tokio_gated = tokio_gated
-        .as_ref()
-        .expect("Missing `tokio_gated` in #[unwrap_types]");
+    let missing = |key: &str| {
+        syn::Error::new_spanned(struct_name, format!("Missing `{key}` in #[unwrap_types]"))
+            .into_compile_error()
+            .into()
+    };
+    let Some(std_inner_type) = std_mod else {
+        return missing("std");
+    };
+    let krate = krate.unwrap_or_else(|| syn::parse_quote! { ::maybe_fut });
+
+    // without a tokio type, the tokio accessors required by the `tokio` feature return the std type
+    let tokio_accessors = match (tokio_mod, tokio_gated) {
+        (Some(tokio_inner_type), Some(tokio_gated)) => {
+            let gated_accessors = tokio_accessors(
+                quote! { feature = #tokio_gated },
+                quote! { #tokio_inner_type },
+                &tokio_variant,
+                "Expected Tokio variant",
+            );
+            let fallback_accessors = tokio_accessors(
+                quote! { not(feature = #tokio_gated) },
+                quote! { #std_inner_type },
+                &std_variant,
+                "Expected Std variant",
+            );
+            quote! {
+                #gated_accessors
+                #fallback_accessors
+            }
+        }
+        (None, None) => tokio_accessors(
+            quote! { all() },
+            quote! { #std_inner_type },
+            &std_variant,
+            "Expected Std variant",
+        ),
+        (Some(_), None) => return missing("tokio_gated"),
+        (None, Some(_)) => return missing("tokio"),
+    };
+    // the tokio items of `Unwrap` exist only if maybe-fut has the `tokio` feature, which is checked by maybe-fut itself
+    let tokio_impl = quote! {
+        #krate::__maybe_fut_if_tokio! {
+            #tokio_accessors
+        }
+    };
 
     let output = quote! {
         const _: () = {
-            use crate::Unwrap;
-
-            impl #generics Unwrap for #struct_name #generics {
-                type StdImpl = #std_inner_type #generics;
-                #[cfg(feature = #tokio_gated)]
-                type TokioImpl = #tokio_inner_type #generics;
-                #[cfg(all(not(feature = #tokio_gated), feature = "tokio"))]
-                type TokioImpl = #std_inner_type #generics;
+            use #krate::Unwrap;
 
+            impl #impl_generics Unwrap for #struct_name #ty_generics #where_clause {
+                type StdImpl = #std_inner_type;
 
                 fn unwrap_std(self) -> Self::StdImpl {
                     match self {
-                        #struct_name(#field_type_ident::Std(inner)) => inner,
-                        _ => panic!("Expected Std variant"),
-                    }
-                }
-
-                #[cfg(feature = #tokio_gated)]
-                fn unwrap_tokio(self) -> Self::TokioImpl {
-                    match self {
-                        #struct_name(#field_type_ident::Tokio(inner)) => inner,
-                        _ => panic!("Expected Tokio variant"),
-                    }
-                }
-
-                #[cfg(all(not(feature = #tokio_gated), feature = "tokio"))]
-                fn unwrap_tokio(self) -> Self::TokioImpl {
-                    match self {
-                        #struct_name(#field_type_ident::Std(inner)) => inner,
+                        #std_variant => inner,
                         _ => panic!("Expected Std variant"),
                     }
                 }
 
                 fn unwrap_std_ref(&self) -> &Self::StdImpl {
                     match self {
-                        #struct_name(#field_type_ident::Std(inner)) => inner,
-                        _ => panic!("Expected Std variant"),
-                    }
-                }
-
-                #[cfg(feature = #tokio_gated)]
-                fn unwrap_tokio_ref(&self) -> &Self::TokioImpl {
-                    match self {
-                        #struct_name(#field_type_ident::Tokio(inner)) => inner,
-                        _ => panic!("Expected Tokio variant"),
-                    }
-                }
-
-                #[cfg(all(not(feature = #tokio_gated), feature = "tokio"))]
-                fn unwrap_tokio_ref(&self) -> &Self::TokioImpl {
-                    match self {
-                        #struct_name(#field_type_ident::Std(inner)) => inner,
+                        #std_variant => inner,
                         _ => panic!("Expected Std variant"),
                     }
                 }
 
                 fn unwrap_std_mut(&mut self) -> &mut Self::StdImpl {
                     match self {
-                        #struct_name(#field_type_ident::Std(inner)) => inner,
-                        _ => panic!("Expected Std variant"),
-                    }
-                }
-
-                #[cfg(feature = #tokio_gated)]
-                fn unwrap_tokio_mut(&mut self) -> &mut Self::TokioImpl {
-                    match self {
-                        #struct_name(#field_type_ident::Tokio(inner)) => inner,
-                        _ => panic!("Expected Tokio variant"),
-                    }
-                }
-
-                #[cfg(all(not(feature = #tokio_gated), feature = "tokio"))]
-                fn unwrap_tokio_mut(&mut self) -> &mut Self::TokioImpl {
-                    match self {
-                        #struct_name(#field_type_ident::Std(inner)) => inner,
+                        #std_variant => inner,
                         _ => panic!("Expected Std variant"),
                     }
                 }
 
                 fn get_std(self) -> Option<Self::StdImpl> {
                     match self {
-                        #struct_name(#field_type_ident::Std(inner)) => Some(inner),
-                        _ => None,
-                    }
-                }
-
-                #[cfg(feature = #tokio_gated)]
-                fn get_tokio(self) -> Option<Self::TokioImpl> {
-                    match self {
-                        #struct_name(#field_type_ident::Tokio(inner)) => Some(inner),
-                        _ => None,
-                    }
-                }
-
-                #[cfg(all(not(feature = #tokio_gated), feature = "tokio"))]
-                fn get_tokio(self) -> Option<Self::TokioImpl> {
-                    match self {
-                        #struct_name(#field_type_ident::Std(inner)) => Some(inner),
+                        #std_variant => Some(inner),
                         _ => None,
                     }
                 }
 
                 fn get_std_ref(&self) -> Option<&Self::StdImpl > {
                     match self {
-                        #struct_name(#field_type_ident::Std(inner)) => Some(inner),
-                        _ => None,
-                    }
-                }
-
-                #[cfg(feature = #tokio_gated)]
-                fn get_tokio_ref(&self) -> Option<&Self::TokioImpl> {
-                    match self {
-                        #struct_name(#field_type_ident::Tokio(inner)) => Some(inner),
-                        _ => None,
-                    }
-                }
-
-                #[cfg(all(not(feature = #tokio_gated), feature = "tokio"))]
-                fn get_tokio_ref(&self) -> Option<&Self::TokioImpl> {
-                    match self {
-                        #struct_name(#field_type_ident::Std(inner)) => Some(inner),
+                        #std_variant => Some(inner),
                         _ => None,
                     }
                 }
 
                 fn get_std_mut(&mut self) -> Option<&mut Self::StdImpl > {
                     match self {
-                        #struct_name(#field_type_ident::Std(inner)) => Some(inner),
-                        _ => None,
-                    }
-                }
-
-                #[cfg(feature = #tokio_gated)]
-                fn get_tokio_mut(&mut self) -> Option<&mut Self::TokioImpl> {
-                    match self {
-                        #struct_name(#field_type_ident::Tokio(inner)) => Some(inner),
+                        #std_variant => Some(inner),
                         _ => None,
                     }
                 }
 
-                #[cfg(all(not(feature = #tokio_gated), feature = "tokio"))]
-                fn get_tokio_mut(&mut self) -> Option<&mut Self::TokioImpl> {
-                    match self {
-                        #struct_name(#field_type_ident::Std(inner)) => Some(inner),
-                        _ => None,
-                    }
-                }
+                #tokio_impl
             }
         };
     };
 
     output.into()
 }
+
+/// Returns the `TokioImpl` type and the tokio accessors of [`Unwrap`], compiled only if `cfg` holds.
+///
+/// The accessors return `impl_type`, matching the variant of the inner enum with `variant`.
+fn tokio_accessors(
+    cfg: proc_macro2::TokenStream,
+    impl_type: proc_macro2::TokenStream,
+    variant: &proc_macro2::TokenStream,
+    expected: &str,
+) -> proc_macro2::TokenStream {
+    quote! {
+        #[cfg(#cfg)]
+        type TokioImpl = #impl_type;
+
+        #[cfg(#cfg)]
+        fn unwrap_tokio(self) -> Self::TokioImpl {
+            match self {
+                #variant => inner,
+                _ => panic!(#expected),
+            }
+        }
+
+        #[cfg(#cfg)]
+        fn unwrap_tokio_ref(&self) -> &Self::TokioImpl {
+            match self {
+                #variant => inner,
+                _ => panic!(#expected),
+            }
+        }
+
+        #[cfg(#cfg)]
+        fn unwrap_tokio_mut(&mut self) -> &mut Self::TokioImpl {
+            match self {
+                #variant => inner,
+                _ => panic!(#expected),
+            }
+        }
+
+        #[cfg(#cfg)]
+        fn get_tokio(self) -> Option<Self::TokioImpl> {
+            match self {
+                #variant => Some(inner),
+                _ => None,
+            }
+        }
+
+        #[cfg(#cfg)]
+        fn get_tokio_ref(&self) -> Option<&Self::TokioImpl> {
+            match self {
+                #variant => Some(inner),
+                _ => None,
+            }
+        }
+
+        #[cfg(#cfg)]
+        fn get_tokio_mut(&mut self) -> Option<&mut Self::TokioImpl> {
+            match self {
+                #variant => Some(inner),
+                _ => None,
+            }
+        }
+    }
+}
+
+/// Returns the field holding the inner enum, with the member to access it.
+///
+/// If the struct has more than one field, the field must be marked with `#[unwrap_inner]`.
+fn inner_field<'a>(struct_name: &Ident, fields: &'a Fields) -> syn::Result<(Member, &'a Field)> {
+    let mut members = fields.members().zip(fields.iter());
+    if fields.len() == 1 {
+        return Ok(members.next().expect("fields is not empty"));
+    }
+
+    let mut marked = members.filter(|(_, field)| {
+        field
+            .attrs
+            .iter()
+            .any(|attr| attr.path().is_ident("unwrap_inner"))
+    });
+    match (marked.next(), marked.next()) {
+        (Some(inner), None) => Ok(inner),
+        (None, _) => Err(syn::Error::new_spanned(
+            struct_name,
+            "Unwrap can only be derived for structs with a single field or with a field marked with #[unwrap_inner]",
+        )),
+        (Some(_), Some((_, field))) => Err(syn::Error::new_spanned(
+            field,
+            "Only one field can be marked with #[unwrap_inner]",
+        )),
+    }
+}
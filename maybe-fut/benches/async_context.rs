@@ -2,8 +2,6 @@ use std::hint::black_box;
 use std::path::Path;
 
 use criterion::{Criterion, criterion_group, criterion_main};
-use maybe_fut::io::Write as _;
-use tokio::io::AsyncWriteExt as _;
 use tokio::runtime::Runtime;
 
 async fn is_async_context() {
@@ -11,12 +9,16 @@ async fn is_async_context() {
 }
 
 async fn tokio_create_file(path: &Path) {
+    use tokio::io::AsyncWriteExt as _;
+
     let mut f = tokio::fs::File::create(path).await.unwrap();
     f.write_all(b"Hello, world!").await.unwrap();
     f.flush().await.unwrap();
 }
 
 async fn maybe_fut_create_file(path: &Path) {
+    use maybe_fut::io::Write as _;
+
     let mut f = maybe_fut::fs::File::create(path).await.unwrap();
     f.write_all(b"Hello, world!").await.unwrap();
     f.flush().await.unwrap();
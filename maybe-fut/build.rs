@@ -8,7 +8,15 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         tokio_fs: { feature = "tokio-fs" },
         tokio_net: { feature = "tokio-net" },
         tokio_sync: { feature = "tokio-sync" },
-        tokio_time: { feature = "tokio-time" }
+        tokio_time: { feature = "tokio-time" },
+        async_std: { feature = "async-std" },
+        uring_fs: { all(feature = "uring-fs", target_os = "linux") },
+        uring_net: { all(feature = "uring-net", target_os = "linux") },
+        uring: { any(uring_fs, uring_net) },
+        stream: { feature = "stream" },
+        crc32fast: { feature = "crc32fast" },
+        sha2: { feature = "sha2" },
+        socket2: { feature = "socket2" }
     }
 
     Ok(())
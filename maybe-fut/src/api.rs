@@ -1,5 +1,10 @@
+pub mod config;
 pub mod fs;
+pub mod future;
 pub mod io;
 pub mod net;
+pub mod pool;
+pub mod stream;
 pub mod sync;
+pub mod task;
 pub mod time;
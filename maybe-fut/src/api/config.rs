@@ -0,0 +1,65 @@
+//! Crate-level defaults, set once for the whole process.
+//!
+//! The defaults apply to the values created after they are set:
+//!
+//! - [`buf_capacity`]: the capacity of the buffer of [`crate::io::BufReader::new`] and [`crate::io::BufWriter::new`].
+//! - [`copy_buf_size`]: the size of the chunks copied by [`crate::io::copy`].
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The default value of [`buf_capacity`] and [`copy_buf_size`].
+pub const DEFAULT_BUF_SIZE: usize = 8 * 1024;
+
+static BUF_CAPACITY: AtomicUsize = AtomicUsize::new(DEFAULT_BUF_SIZE);
+static COPY_BUF_SIZE: AtomicUsize = AtomicUsize::new(DEFAULT_BUF_SIZE);
+
+/// Returns the default capacity of the buffered readers and writers; [`DEFAULT_BUF_SIZE`] unless set.
+pub fn buf_capacity() -> usize {
+    BUF_CAPACITY.load(Ordering::Relaxed)
+}
+
+/// Sets the default capacity of the buffered readers and writers.
+///
+/// # Panics
+///
+/// Panics if `capacity` is zero.
+pub fn set_buf_capacity(capacity: usize) {
+    assert!(
+        capacity > 0,
+        "the buffer capacity must be greater than zero"
+    );
+    BUF_CAPACITY.store(capacity, Ordering::Relaxed);
+}
+
+/// Returns the size of the chunks copied by [`crate::io::copy`]; [`DEFAULT_BUF_SIZE`] unless set.
+pub fn copy_buf_size() -> usize {
+    COPY_BUF_SIZE.load(Ordering::Relaxed)
+}
+
+/// Sets the size of the chunks copied by [`crate::io::copy`].
+///
+/// # Panics
+///
+/// Panics if `size` is zero, since a read into an empty buffer can't be told apart from the end of the reader.
+pub fn set_copy_buf_size(size: usize) {
+    assert!(size > 0, "the copy buffer size must be greater than zero");
+    COPY_BUF_SIZE.store(size, Ordering::Relaxed);
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "greater than zero")]
+    fn test_should_reject_empty_buf_capacity() {
+        set_buf_capacity(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "greater than zero")]
+    fn test_should_reject_empty_copy_buf_size() {
+        set_copy_buf_size(0);
+    }
+}
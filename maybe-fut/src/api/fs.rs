@@ -3,17 +3,27 @@
 //! This module contains utilty methods for working with the file system.
 //! This includes reading/writingt to files, and working with directories.
 
+mod advice;
+#[cfg(async_std)]
+mod async_std_fs;
 mod dir_builder;
 mod dir_entry;
 mod file;
 mod open_options;
 mod read_dir;
+mod read_dir_options;
+mod sys;
+#[cfg(uring_fs)]
+#[cfg_attr(docsrs, doc(cfg(feature = "uring-fs")))]
+pub mod uring;
 
+pub use self::advice::Advice;
 pub use self::dir_builder::DirBuilder;
 pub use self::dir_entry::DirEntry;
 pub use self::file::File;
 pub use self::open_options::OpenOptions;
-pub use self::read_dir::ReadDir;
+pub use self::read_dir::{ReadDir, ReadDirWithMetadata};
+pub use self::read_dir_options::ReadDirOptions;
 use crate::maybe_fut_function;
 
 maybe_fut_function!(
@@ -21,7 +31,9 @@ maybe_fut_function!(
     canonicalize(path: impl AsRef<std::path::Path>) -> std::io::Result<std::path::PathBuf>,
     std::fs::canonicalize,
     tokio::fs::canonicalize,
-    tokio_fs
+    tokio_fs,
+    self::async_std_fs::canonicalize,
+    async_std
 );
 
 maybe_fut_function!(
@@ -31,7 +43,9 @@ maybe_fut_function!(
     copy(from: impl AsRef<std::path::Path>, to: impl AsRef<std::path::Path>) -> std::io::Result<u64>,
     std::fs::copy,
     tokio::fs::copy,
-    tokio_fs
+    tokio_fs,
+    self::async_std_fs::copy,
+    async_std
 );
 
 maybe_fut_function!(
@@ -39,7 +53,9 @@ maybe_fut_function!(
     create_dir(path: impl AsRef<std::path::Path>) -> std::io::Result<()>,
     std::fs::create_dir,
     tokio::fs::create_dir,
-    tokio_fs
+    tokio_fs,
+    self::async_std_fs::create_dir,
+    async_std
 );
 
 maybe_fut_function!(
@@ -47,7 +63,9 @@ maybe_fut_function!(
     create_dir_all(path: impl AsRef<std::path::Path>) -> std::io::Result<()>,
     std::fs::create_dir_all,
     tokio::fs::create_dir_all,
-    tokio_fs
+    tokio_fs,
+    self::async_std_fs::create_dir_all,
+    async_std
 );
 
 maybe_fut_function!(
@@ -58,7 +76,9 @@ maybe_fut_function!(
     hard_link(original: impl AsRef<std::path::Path>, link: impl AsRef<std::path::Path>) -> std::io::Result<()>,
     std::fs::hard_link,
     tokio::fs::hard_link,
-    tokio_fs
+    tokio_fs,
+    self::async_std_fs::hard_link,
+    async_std
 );
 
 maybe_fut_function!(
@@ -68,7 +88,9 @@ maybe_fut_function!(
     metadata(path: impl AsRef<std::path::Path>) -> std::io::Result<std::fs::Metadata>,
     std::fs::metadata,
     tokio::fs::metadata,
-    tokio_fs
+    tokio_fs,
+    self::async_std_fs::metadata,
+    async_std
 );
 
 maybe_fut_function!(
@@ -81,7 +103,9 @@ maybe_fut_function!(
     read(path: impl AsRef<std::path::Path>) -> std::io::Result<Vec<u8>>,
     std::fs::read,
     tokio::fs::read,
-    tokio_fs
+    tokio_fs,
+    self::async_std_fs::read,
+    async_std
 );
 
 /// Returns a stream over the entries within a directory
@@ -89,7 +113,7 @@ pub async fn read_dir(path: impl AsRef<std::path::Path>) -> std::io::Result<Read
     #[cfg(tokio_fs)]
     #[cfg_attr(docsrs, doc(cfg(feature = "tokio-fs")))]
     {
-        if crate::context::is_async_context() {
+        if crate::context::is_tokio_context() {
             tokio::fs::read_dir(path).await.map(ReadDir::from)
         } else {
             std::fs::read_dir(path).map(ReadDir::from)
@@ -101,12 +125,36 @@ pub async fn read_dir(path: impl AsRef<std::path::Path>) -> std::io::Result<Read
     }
 }
 
+/// Returns the entries within a directory, filtered and sorted according to the given [`ReadDirOptions`].
+///
+/// Unlike [`read_dir`], the entries are collected before being returned, since they may have to be sorted.
+pub async fn read_dir_with(
+    path: impl AsRef<std::path::Path>,
+    options: ReadDirOptions,
+) -> std::io::Result<Vec<DirEntry>> {
+    let mut read_dir = read_dir(path).await?;
+    let mut entries = Vec::new();
+    while let Some(entry) = read_dir.next_entry().await? {
+        if options.matches(&entry.file_name()) {
+            entries.push(entry);
+        }
+    }
+
+    if options.sorted() {
+        entries.sort_by_key(DirEntry::file_name);
+    }
+
+    Ok(entries)
+}
+
 maybe_fut_function!(
     /// Reads a symbolic link, returning the file that the link points to.
     read_link(path: impl AsRef<std::path::Path>) -> std::io::Result<std::path::PathBuf>,
     std::fs::read_link,
     tokio::fs::read_link,
-    tokio_fs
+    tokio_fs,
+    self::async_std_fs::read_link,
+    async_std
 );
 
 maybe_fut_function!(
@@ -114,7 +162,9 @@ maybe_fut_function!(
     read_to_string(path: impl AsRef<std::path::Path>) -> std::io::Result<String>,
     std::fs::read_to_string,
     tokio::fs::read_to_string,
-    tokio_fs
+    tokio_fs,
+    self::async_std_fs::read_to_string,
+    async_std
 );
 
 maybe_fut_function!(
@@ -124,7 +174,9 @@ maybe_fut_function!(
     remove_dir(path: impl AsRef<std::path::Path>) -> std::io::Result<()>,
     std::fs::remove_dir,
     tokio::fs::remove_dir,
-    tokio_fs
+    tokio_fs,
+    self::async_std_fs::remove_dir,
+    async_std
 );
 
 maybe_fut_function!(
@@ -134,7 +186,9 @@ maybe_fut_function!(
     remove_dir_all(path: impl AsRef<std::path::Path>) -> std::io::Result<()>,
     std::fs::remove_dir_all,
     tokio::fs::remove_dir_all,
-    tokio_fs
+    tokio_fs,
+    self::async_std_fs::remove_dir_all,
+    async_std
 );
 
 maybe_fut_function!(
@@ -145,7 +199,9 @@ maybe_fut_function!(
     remove_file(path: impl AsRef<std::path::Path>) -> std::io::Result<()>,
     std::fs::remove_file,
     tokio::fs::remove_file,
-    tokio_fs
+    tokio_fs,
+    self::async_std_fs::remove_file,
+    async_std
 );
 
 maybe_fut_function!(
@@ -158,7 +214,50 @@ maybe_fut_function!(
     ) -> std::io::Result<()>,
     std::fs::rename,
     tokio::fs::rename,
-    tokio_fs
+    tokio_fs,
+    self::async_std_fs::rename,
+    async_std
+);
+
+maybe_fut_function!(
+    /// Renames a file or directory to a new name, failing with [`std::io::ErrorKind::AlreadyExists`] if `to` already exists.
+    ///
+    /// Unlike [`rename`], the check and the rename are a single atomic operation, so it can be used to claim
+    /// lock files or jobs: only one of the concurrent renames to the same destination succeeds.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function uses `renameat2` with `RENAME_NOREPLACE` on Linux and Android, and `MoveFileExW`
+    /// without `MOVEFILE_REPLACE_EXISTING` on Windows.
+    /// On the other platforms, and on Linux file systems not supporting `RENAME_NOREPLACE`, it checks that `to`
+    /// doesn't exist before renaming, so a file created by someone else in the meantime is replaced.
+    rename_noreplace(
+        from: impl AsRef<std::path::Path>,
+        to: impl AsRef<std::path::Path>,
+    ) -> std::io::Result<()>,
+    self::sys::rename_noreplace,
+    self::sys::tokio_rename_noreplace,
+    tokio_fs,
+    self::sys::async_std_rename_noreplace,
+    async_std
+);
+
+maybe_fut_function!(
+    /// Returns whether `a` and `b` point to the same file, e.g. through hard links, symbolic links or different
+    /// relative paths, which is useful to avoid copying a file onto itself.
+    ///
+    /// It fails if either path doesn't exist.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function compares the device and inode numbers of the metadata of the files on Unix platforms,
+    /// and their volume serial numbers and file indexes on Windows.
+    same_file(a: impl AsRef<std::path::Path>, b: impl AsRef<std::path::Path>) -> std::io::Result<bool>,
+    self::sys::same_file,
+    self::sys::tokio_same_file,
+    tokio_fs,
+    self::sys::async_std_same_file,
+    async_std
 );
 
 maybe_fut_function!(
@@ -166,7 +265,27 @@ maybe_fut_function!(
     set_permissions(path: impl AsRef<std::path::Path>, perm: std::fs::Permissions) -> std::io::Result<()>,
     std::fs::set_permissions,
     tokio::fs::set_permissions,
-    tokio_fs
+    tokio_fs,
+    self::async_std_fs::set_permissions,
+    async_std
+);
+
+maybe_fut_function!(
+    /// Flushes the entries of a directory to the disk.
+    ///
+    /// Syncing a file only makes its contents durable: after creating, renaming or removing a file,
+    /// the directory containing it must be synced as well for the change to survive a crash.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function opens the directory and calls `fsync` on it on Unix platforms.
+    /// Directories can't be synced on Windows, so there it only checks that the path is a directory.
+    sync_dir(path: impl AsRef<std::path::Path>) -> std::io::Result<()>,
+    self::sys::sync_dir,
+    self::sys::tokio_sync_dir,
+    tokio_fs,
+    self::sys::async_std_sync_dir,
+    async_std
 );
 
 maybe_fut_function!(
@@ -174,7 +293,9 @@ maybe_fut_function!(
     symlink_metadata(path: impl AsRef<std::path::Path>) -> std::io::Result<std::fs::Metadata>,
     std::fs::symlink_metadata,
     tokio::fs::symlink_metadata,
-    tokio_fs
+    tokio_fs,
+    self::async_std_fs::symlink_metadata,
+    async_std
 );
 
 maybe_fut_function!(
@@ -188,7 +309,9 @@ maybe_fut_function!(
     write(path: impl AsRef<std::path::Path>, contents: impl AsRef<[u8]>) -> std::io::Result<()>,
     std::fs::write,
     tokio::fs::write,
-    tokio_fs
+    tokio_fs,
+    self::async_std_fs::write,
+    async_std
 );
 
 #[cfg(test)]
@@ -368,6 +491,123 @@ mod test {
         read_dir(tempdir.path()).await.expect("read_dir failed");
     }
 
+    #[test]
+    fn test_should_read_dir_with_metadata_sync() {
+        let tempdir = tempfile::tempdir().unwrap();
+        for i in 0..100 {
+            std::fs::write(tempdir.path().join(format!("{i}.txt")), vec![0; i]).unwrap();
+        }
+
+        let mut read_dir = SyncRuntime::block_on(read_dir(tempdir.path()))
+            .expect("read_dir failed")
+            .with_metadata();
+        let mut count = 0;
+        while let Some((entry, metadata)) =
+            SyncRuntime::block_on(read_dir.next_entry()).expect("next_entry failed")
+        {
+            let name = entry.file_name().into_string().unwrap();
+            let size: u64 = name.trim_end_matches(".txt").parse().unwrap();
+            assert!(metadata.is_file());
+            assert_eq!(metadata.len(), size);
+            count += 1;
+        }
+        assert_eq!(count, 100);
+    }
+
+    #[tokio::test]
+    async fn test_should_read_dir_with_metadata_async() {
+        let tempdir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(tempdir.path().join("dir")).unwrap();
+        for i in 0..100 {
+            std::fs::write(tempdir.path().join(format!("{i}.txt")), vec![0; i]).unwrap();
+        }
+
+        let mut read_dir = read_dir(tempdir.path())
+            .await
+            .expect("read_dir failed")
+            .with_metadata();
+        let (mut files, mut dirs) = (0, 0);
+        while let Some((entry, metadata)) = read_dir.next_entry().await.expect("next_entry failed")
+        {
+            if metadata.is_dir() {
+                assert_eq!(entry.file_name(), "dir");
+                dirs += 1;
+            } else {
+                files += 1;
+            }
+        }
+        assert_eq!((files, dirs), (100, 1));
+    }
+
+    #[test]
+    fn test_should_read_dir_with_options_sync() {
+        let tempdir = tempfile::tempdir().unwrap();
+        for name in ["c.txt", "a.txt", ".b.txt", "d.md"] {
+            std::fs::write(tempdir.path().join(name), name).unwrap();
+        }
+
+        let options = ReadDirOptions::new()
+            .extension("txt")
+            .skip_hidden(true)
+            .sort_by_name(true);
+        let entries = SyncRuntime::block_on(read_dir_with(tempdir.path(), options))
+            .expect("read_dir_with failed");
+        let names: Vec<_> = entries.iter().map(DirEntry::file_name).collect();
+        assert_eq!(names, ["a.txt", "c.txt"]);
+    }
+
+    #[tokio::test]
+    async fn test_should_read_dir_with_options_async() {
+        let tempdir = tempfile::tempdir().unwrap();
+        for name in ["c.txt", "a.txt", ".b.txt", "d.md"] {
+            std::fs::write(tempdir.path().join(name), name).unwrap();
+        }
+
+        let options = ReadDirOptions::new().glob("*.*").sort_by_name(true);
+        let entries = read_dir_with(tempdir.path(), options)
+            .await
+            .expect("read_dir_with failed");
+        let names: Vec<_> = entries.iter().map(DirEntry::file_name).collect();
+        assert_eq!(names, [".b.txt", "a.txt", "c.txt", "d.md"]);
+    }
+
+    #[cfg(stream)]
+    #[test]
+    fn test_should_stream_read_dir_sync() {
+        use futures_util::StreamExt as _;
+
+        let tempdir = tempfile::tempdir().unwrap();
+        std::fs::write(tempdir.path().join("a.txt"), "a").unwrap();
+        std::fs::write(tempdir.path().join("b.txt"), "b").unwrap();
+
+        let entries = SyncRuntime::block_on(read_dir(tempdir.path())).expect("read_dir failed");
+        let mut names: Vec<_> = SyncRuntime::block_on(
+            entries
+                .map(|entry| entry.expect("entry failed").file_name())
+                .collect::<Vec<_>>(),
+        );
+        names.sort();
+        assert_eq!(names, vec!["a.txt", "b.txt"]);
+    }
+
+    #[cfg(stream)]
+    #[tokio::test]
+    async fn test_should_stream_read_dir_async() {
+        use futures_util::StreamExt as _;
+
+        let tempdir = tempfile::tempdir().unwrap();
+        std::fs::write(tempdir.path().join("a.txt"), "a").unwrap();
+        std::fs::write(tempdir.path().join("b.txt"), "b").unwrap();
+
+        let entries = read_dir(tempdir.path()).await.expect("read_dir failed");
+        let mut names: Vec<_> = entries
+            .map(|entry| entry.expect("entry failed").file_name())
+            .collect()
+            .await;
+        names.sort();
+        assert_eq!(names, vec!["a.txt", "b.txt"]);
+    }
+
     #[test]
     fn test_should_read_to_string_sync() {
         let tempdir = tempfile::tempdir().unwrap();
@@ -466,6 +706,88 @@ mod test {
         rename(&src, &dst).await.expect("rename failed");
     }
 
+    #[test]
+    fn test_should_rename_noreplace_sync() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let src = tempdir.path().join("src.txt");
+        let dst = tempdir.path().join("dst.txt");
+
+        std::fs::write(&src, "Hello, world!").unwrap();
+
+        SyncRuntime::block_on(rename_noreplace(&src, &dst)).expect("rename_noreplace failed");
+        assert!(!src.exists());
+        assert_eq!(std::fs::read_to_string(&dst).unwrap(), "Hello, world!");
+    }
+
+    #[test]
+    fn test_should_tell_same_file_sync() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let a = tempdir.path().join("a.txt");
+        let b = tempdir.path().join("b.txt");
+        let link = tempdir.path().join("link.txt");
+
+        std::fs::write(&a, "a").unwrap();
+        std::fs::write(&b, "b").unwrap();
+        std::fs::hard_link(&a, &link).unwrap();
+
+        assert!(SyncRuntime::block_on(same_file(&a, &a)).unwrap());
+        assert!(SyncRuntime::block_on(same_file(&a, &link)).unwrap());
+        assert!(
+            SyncRuntime::block_on(same_file(&a, tempdir.path().join(".").join("a.txt"))).unwrap()
+        );
+        assert!(!SyncRuntime::block_on(same_file(&a, &b)).unwrap());
+        assert!(SyncRuntime::block_on(same_file(&a, tempdir.path().join("missing"))).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_should_tell_same_file_async() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let a = tempdir.path().join("a.txt");
+        let b = tempdir.path().join("b.txt");
+
+        std::fs::write(&a, "a").unwrap();
+        std::fs::write(&b, "b").unwrap();
+
+        assert!(same_file(&a, &a).await.unwrap());
+        assert!(!same_file(&a, &b).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_should_not_replace_on_rename_noreplace_async() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let src = tempdir.path().join("src.txt");
+        let dst = tempdir.path().join("dst.txt");
+
+        std::fs::write(&src, "new").unwrap();
+        std::fs::write(&dst, "old").unwrap();
+
+        let err = rename_noreplace(&src, &dst)
+            .await
+            .expect_err("rename_noreplace replaced the destination");
+        assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+        assert_eq!(std::fs::read_to_string(&src).unwrap(), "new");
+        assert_eq!(std::fs::read_to_string(&dst).unwrap(), "old");
+    }
+
+    #[test]
+    fn test_should_sync_dir_sync() {
+        let tempdir = tempfile::tempdir().unwrap();
+        std::fs::write(tempdir.path().join("file.txt"), "Hello, world!").unwrap();
+
+        SyncRuntime::block_on(sync_dir(tempdir.path())).expect("sync_dir failed");
+    }
+
+    #[tokio::test]
+    async fn test_should_sync_dir_async() {
+        let tempdir = tempfile::tempdir().unwrap();
+        std::fs::write(tempdir.path().join("file.txt"), "Hello, world!").unwrap();
+
+        sync_dir(tempdir.path()).await.expect("sync_dir failed");
+        sync_dir(tempdir.path().join("missing"))
+            .await
+            .expect_err("sync_dir succeeded on a missing directory");
+    }
+
     #[test]
     #[cfg(unix)]
     fn test_should_set_permissions_sync() {
@@ -533,4 +855,23 @@ mod test {
 
         write(&file, b"Hello, world!").await.expect("write failed");
     }
+
+    #[cfg(async_std)]
+    #[async_std::test]
+    async fn test_should_write_and_read_async_std() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let file = tempdir.path().join("file.txt");
+
+        write(&file, "Hello, world!").await.expect("write failed");
+        assert_eq!(
+            read_to_string(&file).await.expect("read_to_string failed"),
+            "Hello, world!"
+        );
+        assert_eq!(
+            canonicalize(&file).await.expect("canonicalize failed"),
+            std::fs::canonicalize(&file).unwrap()
+        );
+        remove_file(&file).await.expect("remove_file failed");
+        assert!(!file.exists());
+    }
 }
@@ -0,0 +1,32 @@
+/// The expected access pattern of a range of a file, given to [`super::File::advise`].
+///
+/// The kernel may use it to tune the read-ahead and the caching of the file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Advice {
+    /// No particular access pattern is expected; this is the default.
+    Normal,
+    /// The data is expected to be accessed sequentially, from lower offsets to higher ones.
+    Sequential,
+    /// The data is expected to be accessed in random order.
+    Random,
+    /// The data is expected to be accessed in the near future.
+    WillNeed,
+    /// The data is not expected to be accessed in the near future.
+    DontNeed,
+    /// The data is expected to be accessed only once.
+    NoReuse,
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+impl From<Advice> for libc::c_int {
+    fn from(advice: Advice) -> Self {
+        match advice {
+            Advice::Normal => libc::POSIX_FADV_NORMAL,
+            Advice::Sequential => libc::POSIX_FADV_SEQUENTIAL,
+            Advice::Random => libc::POSIX_FADV_RANDOM,
+            Advice::WillNeed => libc::POSIX_FADV_WILLNEED,
+            Advice::DontNeed => libc::POSIX_FADV_DONTNEED,
+            Advice::NoReuse => libc::POSIX_FADV_NOREUSE,
+        }
+    }
+}
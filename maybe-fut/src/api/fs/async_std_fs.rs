@@ -0,0 +1,91 @@
+//! Adapters of the async-std fs api to the signatures of the std fs api.
+//!
+//! The async-std functions take and return async-std paths, so the std paths must be converted.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub async fn open(path: impl AsRef<Path>) -> io::Result<async_std::fs::File> {
+    async_std::fs::File::open(path.as_ref()).await
+}
+
+pub async fn create(path: impl AsRef<Path>) -> io::Result<async_std::fs::File> {
+    async_std::fs::File::create(path.as_ref()).await
+}
+
+pub async fn create_new(path: impl AsRef<Path>) -> io::Result<async_std::fs::File> {
+    async_std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create_new(true)
+        .open(path.as_ref())
+        .await
+}
+
+pub async fn canonicalize(path: impl AsRef<Path>) -> io::Result<PathBuf> {
+    async_std::fs::canonicalize(path.as_ref())
+        .await
+        .map(Into::into)
+}
+
+pub async fn copy(from: impl AsRef<Path>, to: impl AsRef<Path>) -> io::Result<u64> {
+    async_std::fs::copy(from.as_ref(), to.as_ref()).await
+}
+
+pub async fn create_dir(path: impl AsRef<Path>) -> io::Result<()> {
+    async_std::fs::create_dir(path.as_ref()).await
+}
+
+pub async fn create_dir_all(path: impl AsRef<Path>) -> io::Result<()> {
+    async_std::fs::create_dir_all(path.as_ref()).await
+}
+
+pub async fn hard_link(original: impl AsRef<Path>, link: impl AsRef<Path>) -> io::Result<()> {
+    async_std::fs::hard_link(original.as_ref(), link.as_ref()).await
+}
+
+pub async fn metadata(path: impl AsRef<Path>) -> io::Result<std::fs::Metadata> {
+    async_std::fs::metadata(path.as_ref()).await
+}
+
+pub async fn read(path: impl AsRef<Path>) -> io::Result<Vec<u8>> {
+    async_std::fs::read(path.as_ref()).await
+}
+
+pub async fn read_link(path: impl AsRef<Path>) -> io::Result<PathBuf> {
+    async_std::fs::read_link(path.as_ref())
+        .await
+        .map(Into::into)
+}
+
+pub async fn read_to_string(path: impl AsRef<Path>) -> io::Result<String> {
+    async_std::fs::read_to_string(path.as_ref()).await
+}
+
+pub async fn remove_dir(path: impl AsRef<Path>) -> io::Result<()> {
+    async_std::fs::remove_dir(path.as_ref()).await
+}
+
+pub async fn remove_dir_all(path: impl AsRef<Path>) -> io::Result<()> {
+    async_std::fs::remove_dir_all(path.as_ref()).await
+}
+
+pub async fn remove_file(path: impl AsRef<Path>) -> io::Result<()> {
+    async_std::fs::remove_file(path.as_ref()).await
+}
+
+pub async fn rename(from: impl AsRef<Path>, to: impl AsRef<Path>) -> io::Result<()> {
+    async_std::fs::rename(from.as_ref(), to.as_ref()).await
+}
+
+pub async fn set_permissions(path: impl AsRef<Path>, perm: std::fs::Permissions) -> io::Result<()> {
+    async_std::fs::set_permissions(path.as_ref(), perm).await
+}
+
+pub async fn symlink_metadata(path: impl AsRef<Path>) -> io::Result<std::fs::Metadata> {
+    async_std::fs::symlink_metadata(path.as_ref()).await
+}
+
+pub async fn write(path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    async_std::fs::write(path.as_ref(), contents).await
+}
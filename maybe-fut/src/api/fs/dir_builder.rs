@@ -44,7 +44,7 @@ impl DirBuilder {
     pub fn new() -> Self {
         #[cfg(tokio_fs)]
         {
-            if crate::context::is_async_context() {
+            if crate::context::is_tokio_context() {
                 tokio::fs::DirBuilder::new().into()
             } else {
                 std::fs::DirBuilder::new().into()
@@ -7,7 +7,7 @@ use super::OpenOptions;
 use crate::{maybe_fut_constructor_result, maybe_fut_method};
 
 #[derive(Debug, Read, Seek, Write, Unwrap)]
-#[io(feature("tokio-fs"))]
+#[io(feature("tokio-fs"), async_std("async-std"))]
 #[unwrap_types(std(std::fs::File), tokio(tokio::fs::File), tokio_gated("tokio-fs"))]
 /// A reference to an open file on the filesystem.
 pub struct File(FileInner);
@@ -21,6 +21,10 @@ enum FileInner {
     #[cfg_attr(docsrs, doc(cfg(feature = "tokio-fs")))]
     /// Tokio variant of file <https://docs.rs/tokio/latest/tokio/fs/struct.File.html>
     Tokio(tokio::fs::File),
+    #[cfg(async_std)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "async-std")))]
+    /// async-std variant of file <https://docs.rs/async-std/latest/async_std/fs/struct.File.html>
+    AsyncStd(async_std::fs::File),
 }
 
 impl From<std::fs::File> for File {
@@ -37,6 +41,14 @@ impl From<tokio::fs::File> for File {
     }
 }
 
+#[cfg(async_std)]
+#[cfg_attr(docsrs, doc(cfg(feature = "async-std")))]
+impl From<async_std::fs::File> for File {
+    fn from(file: async_std::fs::File) -> Self {
+        Self(FileInner::AsyncStd(file))
+    }
+}
+
 impl File {
     maybe_fut_constructor_result!(
         /// Attempts to open a file in read-only mode.
@@ -51,7 +63,9 @@ impl File {
         open(path: impl AsRef<Path>) -> std::io::Result<Self>,
         std::fs::File::open,
         tokio::fs::File::open,
-        tokio_fs
+        tokio_fs,
+        super::async_std_fs::open,
+        async_std
     );
 
     maybe_fut_constructor_result!(
@@ -67,7 +81,9 @@ impl File {
         create(path: impl AsRef<Path>) -> std::io::Result<Self>,
         std::fs::File::create,
         tokio::fs::File::create,
-        tokio_fs
+        tokio_fs,
+        super::async_std_fs::create,
+        async_std
     );
 
     maybe_fut_constructor_result!(
@@ -88,15 +104,51 @@ impl File {
         create_new(path: impl AsRef<Path>) -> std::io::Result<Self>,
         std::fs::File::create_new,
         tokio::fs::File::create_new,
-        tokio_fs
+        tokio_fs,
+        super::async_std_fs::create_new,
+        async_std
     );
 
+    /// Attempts to open a file in read-only mode with the std implementation, regardless of the context.
+    ///
+    /// Unlike [`Self::open`], it never checks the context, which is useful to create a file in an async context to use it on another thread.
+    pub fn open_std(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        std::fs::File::open(path).map(Self::from)
+    }
+
+    /// Attempts to open a file in read-only mode with the tokio implementation, regardless of the context.
+    ///
+    /// Unlike [`Self::open`], it never checks the context. It must be called from within a tokio runtime.
+    #[cfg(tokio_fs)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio-fs")))]
+    pub async fn open_tokio(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        tokio::fs::File::open(path).await.map(Self::from)
+    }
+
+    /// Opens a file in write-only mode with the std implementation, regardless of the context.
+    ///
+    /// Unlike [`Self::create`], it never checks the context, which is useful to create a file in an async context to use it on another thread.
+    pub fn create_std(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        std::fs::File::create(path).map(Self::from)
+    }
+
+    /// Opens a file in write-only mode with the tokio implementation, regardless of the context.
+    ///
+    /// Unlike [`Self::create`], it never checks the context. It must be called from within a tokio runtime.
+    #[cfg(tokio_fs)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio-fs")))]
+    pub async fn create_tokio(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        tokio::fs::File::create(path).await.map(Self::from)
+    }
+
     maybe_fut_method!(
         /// Queries metadata about the underlying file.
         metadata() -> std::io::Result<std::fs::Metadata>,
         FileInner::Std,
         FileInner::Tokio,
-        tokio_fs
+        tokio_fs,
+        FileInner::AsyncStd,
+        async_std
     );
 
     /// Returns a new [`OpenOptions`] object.
@@ -124,7 +176,9 @@ impl File {
         set_len(size: u64) -> std::io::Result<()>,
         FileInner::Std,
         FileInner::Tokio,
-        tokio_fs
+        tokio_fs,
+        FileInner::AsyncStd,
+        async_std
     );
 
     maybe_fut_method!(
@@ -139,7 +193,9 @@ impl File {
         set_permissions(perm: std::fs::Permissions) -> std::io::Result<()>,
         FileInner::Std,
         FileInner::Tokio,
-        tokio_fs
+        tokio_fs,
+        FileInner::AsyncStd,
+        async_std
     );
 
     maybe_fut_method!(
@@ -149,7 +205,9 @@ impl File {
         sync_all() -> std::io::Result<()>,
         FileInner::Std,
         FileInner::Tokio,
-        tokio_fs
+        tokio_fs,
+        FileInner::AsyncStd,
+        async_std
     );
 
     maybe_fut_method!(
@@ -162,9 +220,34 @@ impl File {
         sync_data() -> std::io::Result<()>,
         FileInner::Std,
         FileInner::Tokio,
-        tokio_fs
+        tokio_fs,
+        FileInner::AsyncStd,
+        async_std
     );
 
+    /// Advises the kernel about the access pattern of the range of `len` bytes starting at `offset`,
+    /// so that it can tune the read-ahead and the caching of the file.
+    ///
+    /// A `len` of 0 extends the range to the end of the file.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function corresponds to `posix_fadvise` on Linux and Android; it does nothing on the other platforms.
+    #[cfg_attr(
+        not(any(target_os = "linux", target_os = "android")),
+        allow(unused_variables)
+    )]
+    pub fn advise(&self, offset: u64, len: u64, advice: super::Advice) -> std::io::Result<()> {
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        {
+            super::sys::advise(std::os::fd::AsFd::as_fd(self), offset, len, advice)
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "android")))]
+        {
+            Ok(())
+        }
+    }
+
     /// Creates a new [`File`] instance that shares the same underlying file handle as the existing [`File`] instance.
     /// Reads, writes, and seeks will affect both [`File`] instances simultaneously.
     pub async fn try_clone(&self) -> std::io::Result<Self> {
@@ -172,8 +255,12 @@ impl File {
             FileInner::Std(file) => file.try_clone().map(Self::from),
             #[cfg(tokio_fs)]
             FileInner::Tokio(file) => file.try_clone().await.map(Self::from),
+            // async-std files can't be cloned, but their handle can
+            #[cfg(async_std)]
+            FileInner::AsyncStd(file) => async_std_try_clone(file).map(Self::from),
         }
     }
+
     /// Converts the [`File`] inner instance to a [`std::fs::File`] instance if it is currently a [`tokio::fs::File`].
     ///
     /// This can be useful when you need for instance to pass an `impl std::io::Write` to a function.
@@ -182,6 +269,8 @@ impl File {
             FileInner::Std(file) => file,
             #[cfg(tokio_fs)]
             FileInner::Tokio(file) => file.into_std().await,
+            #[cfg(async_std)]
+            FileInner::AsyncStd(file) => async_std_into_std(file),
         }
     }
 
@@ -194,10 +283,46 @@ impl File {
         match self.0 {
             FileInner::Std(file) => tokio::fs::File::from_std(file),
             FileInner::Tokio(file) => file,
+            #[cfg(async_std)]
+            FileInner::AsyncStd(file) => tokio::fs::File::from_std(async_std_into_std(file)),
         }
     }
 }
 
+/// Converts an async-std file into a std file, through its owned handle.
+#[cfg(async_std)]
+fn async_std_into_std(file: async_std::fs::File) -> std::fs::File {
+    #[cfg(unix)]
+    {
+        std::fs::File::from(std::os::fd::OwnedFd::from(file))
+    }
+    #[cfg(windows)]
+    {
+        std::fs::File::from(std::os::windows::io::OwnedHandle::from(file))
+    }
+}
+
+/// Clones an async-std file by duplicating its handle.
+#[cfg(async_std)]
+fn async_std_try_clone(file: &async_std::fs::File) -> std::io::Result<async_std::fs::File> {
+    #[cfg(unix)]
+    {
+        use std::os::fd::AsFd as _;
+
+        file.as_fd()
+            .try_clone_to_owned()
+            .map(async_std::fs::File::from)
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::io::AsHandle as _;
+
+        file.as_handle()
+            .try_clone_to_owned()
+            .map(async_std::fs::File::from)
+    }
+}
+
 #[cfg(unix)]
 impl std::os::fd::AsFd for File {
     fn as_fd(&self) -> std::os::fd::BorrowedFd<'_> {
@@ -205,6 +330,8 @@ impl std::os::fd::AsFd for File {
             FileInner::Std(file) => file.as_fd(),
             #[cfg(tokio_fs)]
             FileInner::Tokio(file) => file.as_fd(),
+            #[cfg(async_std)]
+            FileInner::AsyncStd(file) => file.as_fd(),
         }
     }
 }
@@ -216,6 +343,8 @@ impl std::os::windows::io::AsHandle for File {
             FileInner::Std(file) => file.as_handle(),
             #[cfg(tokio_fs)]
             FileInner::Tokio(file) => file.as_handle(),
+            #[cfg(async_std)]
+            FileInner::AsyncStd(file) => file.as_handle(),
         }
     }
 }
@@ -227,6 +356,8 @@ impl std::os::fd::AsRawFd for File {
             FileInner::Std(file) => file.as_raw_fd(),
             #[cfg(tokio_fs)]
             FileInner::Tokio(file) => file.as_raw_fd(),
+            #[cfg(async_std)]
+            FileInner::AsyncStd(file) => file.as_raw_fd(),
         }
     }
 }
@@ -238,6 +369,8 @@ impl std::os::windows::io::AsRawHandle for File {
             FileInner::Std(file) => file.as_raw_handle(),
             #[cfg(tokio_fs)]
             FileInner::Tokio(file) => file.as_raw_handle(),
+            #[cfg(async_std)]
+            FileInner::AsyncStd(file) => file.as_raw_handle(),
         }
     }
 }
@@ -245,9 +378,15 @@ impl std::os::windows::io::AsRawHandle for File {
 #[cfg(unix)]
 impl std::os::fd::FromRawFd for File {
     unsafe fn from_raw_fd(fd: std::os::fd::RawFd) -> Self {
+        #[cfg(async_std)]
+        if crate::context::is_async_std_context() {
+            return Self(FileInner::AsyncStd(unsafe {
+                async_std::fs::File::from_raw_fd(fd)
+            }));
+        }
         #[cfg(tokio_fs)]
         {
-            if crate::context::is_async_context() {
+            if crate::context::is_tokio_context() {
                 Self(FileInner::Tokio(unsafe {
                     tokio::fs::File::from_raw_fd(fd)
                 }))
@@ -265,9 +404,15 @@ impl std::os::fd::FromRawFd for File {
 #[cfg(windows)]
 impl std::os::windows::io::FromRawHandle for File {
     unsafe fn from_raw_handle(handle: std::os::windows::io::RawHandle) -> Self {
+        #[cfg(async_std)]
+        if crate::context::is_async_std_context() {
+            return Self(FileInner::AsyncStd(unsafe {
+                async_std::fs::File::from_raw_handle(handle)
+            }));
+        }
         #[cfg(tokio_fs)]
         {
-            if crate::context::is_async_context() {
+            if crate::context::is_tokio_context() {
                 Self(FileInner::Tokio(unsafe {
                     tokio::fs::File::from_raw_handle(handle)
                 }))
@@ -286,14 +431,244 @@ impl std::os::windows::io::FromRawHandle for File {
     }
 }
 
+#[cfg(unix)]
+impl From<std::os::fd::OwnedFd> for File {
+    /// Takes ownership of the file descriptor, wrapping it into the variant of the current context.
+    fn from(fd: std::os::fd::OwnedFd) -> Self {
+        use std::os::fd::{FromRawFd as _, IntoRawFd as _};
+
+        // SAFETY: the descriptor is owned, so it is open and not owned by anything else
+        unsafe { Self::from_raw_fd(fd.into_raw_fd()) }
+    }
+}
+
+#[cfg(windows)]
+impl From<std::os::windows::io::OwnedHandle> for File {
+    /// Takes ownership of the handle, wrapping it into the variant of the current context.
+    fn from(handle: std::os::windows::io::OwnedHandle) -> Self {
+        use std::os::windows::io::{FromRawHandle as _, IntoRawHandle as _};
+
+        // SAFETY: the handle is owned, so it is open and not owned by anything else
+        unsafe { Self::from_raw_handle(handle.into_raw_handle()) }
+    }
+}
+
+#[cfg(unix)]
+impl From<File> for std::os::fd::OwnedFd {
+    /// Releases the file descriptor of the file.
+    ///
+    /// # Panics
+    ///
+    /// The descriptor of a tokio file with an operation still in flight is duplicated rather than released;
+    /// this panics if it can't be duplicated.
+    fn from(file: File) -> Self {
+        match file.0 {
+            FileInner::Std(file) => file.into(),
+            #[cfg(tokio_fs)]
+            FileInner::Tokio(file) => file.try_into_std().map(Self::from).unwrap_or_else(|file| {
+                use std::os::fd::AsFd as _;
+
+                file.as_fd()
+                    .try_clone_to_owned()
+                    .expect("failed to duplicate the file descriptor")
+            }),
+            #[cfg(async_std)]
+            FileInner::AsyncStd(file) => file.into(),
+        }
+    }
+}
+
+#[cfg(windows)]
+impl From<File> for std::os::windows::io::OwnedHandle {
+    /// Releases the handle of the file.
+    ///
+    /// # Panics
+    ///
+    /// The handle of a tokio file with an operation still in flight is duplicated rather than released;
+    /// this panics if it can't be duplicated.
+    fn from(file: File) -> Self {
+        match file.0 {
+            FileInner::Std(file) => file.into(),
+            #[cfg(tokio_fs)]
+            FileInner::Tokio(file) => file.try_into_std().map(Self::from).unwrap_or_else(|file| {
+                use std::os::windows::io::AsHandle as _;
+
+                file.as_handle()
+                    .try_clone_to_owned()
+                    .expect("failed to duplicate the file handle")
+            }),
+            #[cfg(async_std)]
+            FileInner::AsyncStd(file) => file.into(),
+        }
+    }
+}
+
+#[cfg(unix)]
+impl std::os::fd::IntoRawFd for File {
+    fn into_raw_fd(self) -> std::os::fd::RawFd {
+        std::os::fd::OwnedFd::from(self).into_raw_fd()
+    }
+}
+
+#[cfg(windows)]
+impl std::os::windows::io::IntoRawHandle for File {
+    fn into_raw_handle(self) -> std::os::windows::io::RawHandle {
+        std::os::windows::io::OwnedHandle::from(self).into_raw_handle()
+    }
+}
+
+/// Returns the error of the I/O traits which are not supported by the current variant of the file.
+fn unsupported_io(io_trait: &str) -> std::io::Error {
+    std::io::Error::other(format!(
+        "{io_trait} is not supported by the current variant of File"
+    ))
+}
+
+/// Reads from the file if it is a [`std::fs::File`]; otherwise an error is returned.
+impl std::io::Read for File {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match &mut self.0 {
+            FileInner::Std(file) => std::io::Read::read(file, buf),
+            #[allow(unreachable_patterns)]
+            _ => Err(unsupported_io("std::io::Read")),
+        }
+    }
+}
+
+/// Writes to the file if it is a [`std::fs::File`]; otherwise an error is returned.
+impl std::io::Write for File {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match &mut self.0 {
+            FileInner::Std(file) => std::io::Write::write(file, buf),
+            #[allow(unreachable_patterns)]
+            _ => Err(unsupported_io("std::io::Write")),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match &mut self.0 {
+            FileInner::Std(file) => std::io::Write::flush(file),
+            #[allow(unreachable_patterns)]
+            _ => Err(unsupported_io("std::io::Write")),
+        }
+    }
+}
+
+/// Seeks the file if it is a [`std::fs::File`]; otherwise an error is returned.
+impl std::io::Seek for File {
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        match &mut self.0 {
+            FileInner::Std(file) => std::io::Seek::seek(file, pos),
+            #[allow(unreachable_patterns)]
+            _ => Err(unsupported_io("std::io::Seek")),
+        }
+    }
+}
+
+/// Reads from the file if it is a [`tokio::fs::File`], or with a blocking read if it is a [`std::fs::File`];
+/// otherwise an error is returned.
+#[cfg(tokio_fs)]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio-fs")))]
+impl tokio::io::AsyncRead for File {
+    fn poll_read(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        match &mut self.0 {
+            FileInner::Std(file) => {
+                let size = std::io::Read::read(file, buf.initialize_unfilled())?;
+                buf.advance(size);
+                std::task::Poll::Ready(Ok(()))
+            }
+            FileInner::Tokio(file) => std::pin::Pin::new(file).poll_read(cx, buf),
+            #[allow(unreachable_patterns)]
+            _ => std::task::Poll::Ready(Err(unsupported_io("tokio::io::AsyncRead"))),
+        }
+    }
+}
+
+/// Writes to the file if it is a [`tokio::fs::File`], or with a blocking write if it is a [`std::fs::File`];
+/// otherwise an error is returned.
+#[cfg(tokio_fs)]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio-fs")))]
+impl tokio::io::AsyncWrite for File {
+    fn poll_write(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &[u8],
+    ) -> std::task::Poll<std::io::Result<usize>> {
+        match &mut self.0 {
+            FileInner::Std(file) => std::task::Poll::Ready(std::io::Write::write(file, buf)),
+            FileInner::Tokio(file) => std::pin::Pin::new(file).poll_write(cx, buf),
+            #[allow(unreachable_patterns)]
+            _ => std::task::Poll::Ready(Err(unsupported_io("tokio::io::AsyncWrite"))),
+        }
+    }
+
+    fn poll_flush(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        match &mut self.0 {
+            FileInner::Std(file) => std::task::Poll::Ready(std::io::Write::flush(file)),
+            FileInner::Tokio(file) => std::pin::Pin::new(file).poll_flush(cx),
+            #[allow(unreachable_patterns)]
+            _ => std::task::Poll::Ready(Err(unsupported_io("tokio::io::AsyncWrite"))),
+        }
+    }
+
+    fn poll_shutdown(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        match &mut self.0 {
+            FileInner::Std(file) => std::task::Poll::Ready(std::io::Write::flush(file)),
+            FileInner::Tokio(file) => std::pin::Pin::new(file).poll_shutdown(cx),
+            #[allow(unreachable_patterns)]
+            _ => std::task::Poll::Ready(Err(unsupported_io("tokio::io::AsyncWrite"))),
+        }
+    }
+}
+
+/// Seeks the file if it is a [`tokio::fs::File`], or with a blocking seek if it is a [`std::fs::File`];
+/// otherwise an error is returned.
+#[cfg(tokio_fs)]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio-fs")))]
+impl tokio::io::AsyncSeek for File {
+    fn start_seek(
+        mut self: std::pin::Pin<&mut Self>,
+        position: std::io::SeekFrom,
+    ) -> std::io::Result<()> {
+        match &mut self.0 {
+            FileInner::Std(file) => std::io::Seek::seek(file, position).map(|_| ()),
+            FileInner::Tokio(file) => std::pin::Pin::new(file).start_seek(position),
+            #[allow(unreachable_patterns)]
+            _ => Err(unsupported_io("tokio::io::AsyncSeek")),
+        }
+    }
+
+    fn poll_complete(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<std::io::Result<u64>> {
+        match &mut self.0 {
+            FileInner::Std(file) => std::task::Poll::Ready(std::io::Seek::stream_position(file)),
+            FileInner::Tokio(file) => std::pin::Pin::new(file).poll_complete(cx),
+            #[allow(unreachable_patterns)]
+            _ => std::task::Poll::Ready(Err(unsupported_io("tokio::io::AsyncSeek"))),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
 
     use tempfile::NamedTempFile;
 
     use super::*;
-    use crate::SyncRuntime;
     use crate::io::{Read, Seek, Write};
+    use crate::{SyncRuntime, Unwrap as _};
 
     #[test]
     fn test_should_instantiate_file_sync() {
@@ -306,6 +681,7 @@ mod test {
         assert!(matches!(variant.0, FileInner::Std(_)));
     }
 
+    #[cfg(tokio_fs)]
     #[tokio::test]
     async fn test_should_instantiate_file_async() {
         let temp = NamedTempFile::new().expect("Failed to create temp file");
@@ -326,6 +702,7 @@ mod test {
         assert!(matches!(variant.0, FileInner::Std(_)));
     }
 
+    #[cfg(tokio_fs)]
     #[tokio::test]
     async fn test_should_create_file_async() {
         let temp = NamedTempFile::new().expect("Failed to create temp file");
@@ -373,6 +750,7 @@ mod test {
         let _std_file = SyncRuntime::block_on(file.to_std());
     }
 
+    #[cfg(tokio_fs)]
     #[tokio::test]
     async fn test_should_convert_to_tokio() {
         let temp = NamedTempFile::new().expect("Failed to create temp file");
@@ -384,6 +762,7 @@ mod test {
         let _tokio_file = file.to_tokio().await;
     }
 
+    #[cfg(tokio_fs)]
     #[test]
     fn test_should_convert_to_std_sync() {
         let temp = NamedTempFile::new().expect("Failed to create temp file");
@@ -395,6 +774,7 @@ mod test {
         let _std_file = SyncRuntime::block_on(file.to_tokio());
     }
 
+    #[cfg(tokio_fs)]
     #[tokio::test]
     async fn test_should_convert_to_tokio_async() {
         let temp = NamedTempFile::new().expect("Failed to create temp file");
@@ -492,4 +872,191 @@ mod test {
         file.read(&mut buf).await.expect("Failed to read file");
         assert_eq!(buf, b"world");
     }
+
+    #[cfg(async_std)]
+    #[async_std::test]
+    async fn test_should_instantiate_file_async_std() {
+        let temp = NamedTempFile::new().expect("Failed to create temp file");
+
+        // write file
+        std::fs::write(temp.path(), b"Hello world").expect("Failed to write file");
+
+        let variant = File::open(temp.path()).await.expect("Failed to open file");
+        assert!(matches!(variant.0, FileInner::AsyncStd(_)));
+    }
+
+    #[cfg(async_std)]
+    #[async_std::test]
+    async fn test_should_read_and_write_async_std() {
+        let temp = NamedTempFile::new().expect("Failed to create temp file");
+
+        let mut file = File::create(temp.path())
+            .await
+            .expect("Failed to open file");
+        assert!(matches!(file.0, FileInner::AsyncStd(_)));
+        file.write(b"Hello world")
+            .await
+            .expect("Failed to write file");
+        file.flush().await.expect("Failed to flush file");
+        file.sync_all().await.expect("Failed to sync file");
+
+        let mut file = File::open(temp.path()).await.expect("Failed to open file");
+        file.seek(std::io::SeekFrom::Start(6))
+            .await
+            .expect("Failed to seek file");
+        let mut buf = [0; 5];
+        file.read(&mut buf).await.expect("Failed to read file");
+        assert_eq!(&buf, b"world");
+    }
+
+    #[cfg(async_std)]
+    #[async_std::test]
+    async fn test_should_clone_and_convert_async_std_file() {
+        let temp = NamedTempFile::new().expect("Failed to create temp file");
+
+        // write file
+        std::fs::write(temp.path(), b"Hello world").expect("Failed to write file");
+
+        let file = File::open(temp.path()).await.expect("Failed to open file");
+        let clone = file.try_clone().await.expect("Failed to clone file");
+        assert!(matches!(clone.0, FileInner::AsyncStd(_)));
+
+        let std_file = file.to_std().await;
+        assert_eq!(std_file.metadata().unwrap().len(), 11);
+    }
+
+    #[test]
+    fn test_file_should_be_send_with_every_feature() {
+        fn assert_send<T: Send>() {}
+
+        assert_send::<File>();
+    }
+
+    #[test]
+    fn test_should_use_std_io_traits_on_std_file() {
+        let temp = NamedTempFile::new().expect("Failed to create temp file");
+
+        let mut file =
+            SyncRuntime::block_on(File::create(temp.path())).expect("Failed to open file");
+        std::io::Write::write_all(&mut file, b"Hello world").expect("Failed to write file");
+        std::io::Write::flush(&mut file).expect("Failed to flush file");
+
+        let mut file = SyncRuntime::block_on(File::open(temp.path())).expect("Failed to open file");
+        std::io::Seek::seek(&mut file, std::io::SeekFrom::Start(6)).expect("Failed to seek file");
+        let mut buf = String::new();
+        std::io::Read::read_to_string(&mut file, &mut buf).expect("Failed to read file");
+        assert_eq!(buf, "world");
+    }
+
+    #[cfg(tokio_fs)]
+    #[tokio::test]
+    async fn test_should_use_tokio_io_traits_on_tokio_file() {
+        use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+
+        let temp = NamedTempFile::new().expect("Failed to create temp file");
+
+        let mut file = File::create(temp.path())
+            .await
+            .expect("Failed to open file");
+        assert!(matches!(file.0, FileInner::Tokio(_)));
+        AsyncWriteExt::write_all(&mut file, b"Hello world")
+            .await
+            .expect("Failed to write file");
+        AsyncWriteExt::flush(&mut file)
+            .await
+            .expect("Failed to flush file");
+
+        let mut file = File::open(temp.path()).await.expect("Failed to open file");
+        AsyncSeekExt::seek(&mut file, std::io::SeekFrom::Start(6))
+            .await
+            .expect("Failed to seek file");
+        let mut buf = String::new();
+        AsyncReadExt::read_to_string(&mut file, &mut buf)
+            .await
+            .expect("Failed to read file");
+        assert_eq!(buf, "world");
+
+        // std traits are not supported by tokio files
+        assert!(std::io::Read::read(&mut file, &mut [0; 4]).is_err());
+    }
+
+    #[cfg(tokio_fs)]
+    #[tokio::test]
+    async fn test_should_use_tokio_io_traits_on_std_file() {
+        let temp = NamedTempFile::new().expect("Failed to create temp file");
+
+        // write file
+        std::fs::write(temp.path(), b"Hello world").expect("Failed to write file");
+
+        let mut file = File::from(std::fs::File::open(temp.path()).expect("Failed to open file"));
+        let mut buf = String::new();
+        tokio::io::AsyncReadExt::read_to_string(&mut file, &mut buf)
+            .await
+            .expect("Failed to read file");
+        assert_eq!(buf, "Hello world");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_should_convert_from_and_into_owned_fd_sync() {
+        let temp = NamedTempFile::new().expect("Failed to create temp file");
+        std::fs::write(temp.path(), b"Hello world").expect("Failed to write file");
+
+        let fd = std::os::fd::OwnedFd::from(std::fs::File::open(temp.path()).unwrap());
+        let file = File::from(fd);
+        assert!(file.get_std_ref().is_some());
+
+        let mut std_file = std::fs::File::from(std::os::fd::OwnedFd::from(file));
+        let mut buf = String::new();
+        std::io::Read::read_to_string(&mut std_file, &mut buf).expect("Failed to read file");
+        assert_eq!(buf, "Hello world");
+    }
+
+    #[cfg(all(unix, tokio_fs))]
+    #[tokio::test]
+    async fn test_should_convert_from_and_into_owned_fd_async() {
+        let temp = NamedTempFile::new().expect("Failed to create temp file");
+        std::fs::write(temp.path(), b"Hello world").expect("Failed to write file");
+
+        let fd = std::os::fd::OwnedFd::from(std::fs::File::open(temp.path()).unwrap());
+        let mut file = File::from(fd);
+        assert!(file.get_tokio_ref().is_some());
+        let mut buf = [0; 5];
+        file.read_exact(&mut buf)
+            .await
+            .expect("Failed to read file");
+        assert_eq!(&buf, b"Hello");
+
+        // the cursor is shared by the descriptor
+        let mut std_file = std::fs::File::from(std::os::fd::OwnedFd::from(file));
+        let mut buf = String::new();
+        std::io::Read::read_to_string(&mut std_file, &mut buf).expect("Failed to read file");
+        assert_eq!(buf, " world");
+    }
+
+    #[test]
+    fn test_should_advise_file() {
+        let temp = NamedTempFile::new().expect("Failed to create temp file");
+        std::fs::write(temp.path(), b"Hello world").expect("Failed to write file");
+
+        let file = SyncRuntime::block_on(File::open(temp.path())).expect("Failed to open file");
+        file.advise(0, 0, crate::fs::Advice::Sequential)
+            .expect("Failed to advise file");
+        file.advise(6, 5, crate::fs::Advice::WillNeed)
+            .expect("Failed to advise file");
+    }
+
+    #[cfg(tokio_fs)]
+    #[tokio::test]
+    async fn test_should_open_with_explicit_backend() {
+        let temp = NamedTempFile::new().expect("Failed to create temp file");
+
+        let file = File::open_std(temp.path()).expect("Failed to open file");
+        assert!(matches!(file.0, FileInner::Std(_)));
+
+        let file = File::create_tokio(temp.path())
+            .await
+            .expect("Failed to create file");
+        assert!(matches!(file.0, FileInner::Tokio(_)));
+    }
 }
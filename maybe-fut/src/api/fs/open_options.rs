@@ -47,7 +47,7 @@ impl OpenOptions {
     pub fn new() -> Self {
         #[cfg(tokio_fs)]
         {
-            if crate::context::is_async_context() {
+            if crate::context::is_tokio_context() {
                 tokio::fs::OpenOptions::new().into()
             } else {
                 std::fs::OpenOptions::new().into()
@@ -385,6 +385,7 @@ mod test {
         assert!(matches!(options.0, OpenOptionsInner::Std(_)));
     }
 
+    #[cfg(tokio_fs)]
     #[tokio::test]
     async fn test_open_options_async() {
         let options = OpenOptions::new();
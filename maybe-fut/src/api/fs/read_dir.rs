@@ -1,5 +1,12 @@
+use std::collections::VecDeque;
+use std::fs::Metadata;
+use std::path::PathBuf;
+
 use super::DirEntry;
 
+/// The number of entries whose metadata is fetched concurrently by [`ReadDirWithMetadata`].
+const METADATA_BATCH_SIZE: usize = 64;
+
 #[derive(Debug, Unwrap)]
 #[unwrap_types(
     std(std::fs::ReadDir),
@@ -10,6 +17,8 @@ use super::DirEntry;
 ///
 /// This struct is returned from the [`super::read_dir`] function of this module and will yield instances of [`DirEntry`].
 /// Through a [`DirEntry`] information like the entry’s path and possibly other metadata can be learned.
+///
+/// It implements [`crate::stream::Stream`] and, with the `stream` feature, `futures_core::Stream` as well.
 pub struct ReadDir(ReadDirInner);
 
 /// Inner pointer to sync or async read dir.
@@ -54,3 +63,137 @@ impl ReadDir {
         }
     }
 }
+
+impl ReadDir {
+    /// Turns the stream into a stream of the entries with their metadata.
+    ///
+    /// The metadata of the entries is fetched concurrently, in batches, on the blocking thread pool of tokio inside of
+    /// a tokio runtime and on a pool of threads otherwise, which is much faster than calling [`DirEntry::metadata`]
+    /// on each entry when the file system has a high latency, as network file systems do.
+    pub fn with_metadata(self) -> ReadDirWithMetadata {
+        ReadDirWithMetadata {
+            read_dir: self,
+            batch: VecDeque::new(),
+            done: false,
+        }
+    }
+}
+
+impl crate::stream::Stream for ReadDir {
+    type Item = std::io::Result<DirEntry>;
+
+    async fn next(&mut self) -> Option<Self::Item> {
+        self.next_entry().await.transpose()
+    }
+}
+
+#[cfg(stream)]
+#[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
+impl futures_core::Stream for ReadDir {
+    type Item = std::io::Result<DirEntry>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        #[cfg_attr(not(tokio_fs), allow(unused_variables))] cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        match &mut self.0 {
+            ReadDirInner::Std(inner) => {
+                std::task::Poll::Ready(inner.next().map(|entry| entry.map(DirEntry::from)))
+            }
+            #[cfg(tokio_fs)]
+            ReadDirInner::Tokio(inner) => inner
+                .poll_next_entry(cx)
+                .map(|res| res.map(|entry| entry.map(DirEntry::from)).transpose()),
+        }
+    }
+}
+
+/// Reads the entries in a directory with their metadata.
+///
+/// This struct is returned from [`ReadDir::with_metadata`] and yields the entries with their metadata, which, like
+/// [`DirEntry::metadata`], doesn't traverse symlinks.
+///
+/// It implements [`crate::stream::Stream`].
+#[derive(Debug)]
+pub struct ReadDirWithMetadata {
+    read_dir: ReadDir,
+    /// The entries read ahead, with their metadata.
+    batch: VecDeque<std::io::Result<(DirEntry, Metadata)>>,
+    /// Whether all the entries of the directory have been read.
+    done: bool,
+}
+
+impl ReadDirWithMetadata {
+    /// Returns the next entry in the directory stream, with its metadata.
+    pub async fn next_entry(&mut self) -> std::io::Result<Option<(DirEntry, Metadata)>> {
+        if self.batch.is_empty() && !self.done {
+            self.fill_batch().await;
+        }
+
+        self.batch.pop_front().transpose()
+    }
+
+    /// Reads the next batch of entries and fetches their metadata.
+    async fn fill_batch(&mut self) {
+        let mut entries = Vec::with_capacity(METADATA_BATCH_SIZE);
+        let mut error = None;
+        while entries.len() < METADATA_BATCH_SIZE {
+            match self.read_dir.next_entry().await {
+                Ok(Some(entry)) => entries.push(entry),
+                Ok(None) => {
+                    self.done = true;
+                    break;
+                }
+                Err(err) => {
+                    error = Some(err);
+                    break;
+                }
+            }
+        }
+
+        let metadata = metadata_all(entries.iter().map(DirEntry::path).collect()).await;
+        self.batch.extend(
+            entries
+                .into_iter()
+                .zip(metadata)
+                .map(|(entry, metadata)| metadata.map(|metadata| (entry, metadata))),
+        );
+        // the error is yielded after the entries read before it
+        self.batch.extend(error.map(Err));
+    }
+}
+
+impl crate::stream::Stream for ReadDirWithMetadata {
+    type Item = std::io::Result<(DirEntry, Metadata)>;
+
+    async fn next(&mut self) -> Option<Self::Item> {
+        self.next_entry().await.transpose()
+    }
+}
+
+/// Fetches the metadata of all the given paths concurrently, without traversing symlinks.
+async fn metadata_all(paths: Vec<PathBuf>) -> Vec<std::io::Result<Metadata>> {
+    #[cfg(tokio_fs)]
+    if crate::context::is_tokio_context() {
+        let handles: Vec<_> = paths
+            .into_iter()
+            .map(|path| tokio::task::spawn_blocking(move || std::fs::symlink_metadata(path)))
+            .collect();
+        let mut metadata = Vec::with_capacity(handles.len());
+        for handle in handles {
+            metadata.push(
+                handle
+                    .await
+                    .map_err(|_| std::io::Error::other("background task failed"))
+                    .and_then(|res| res),
+            );
+        }
+        return metadata;
+    }
+
+    crate::SyncRuntime::block_on_all(
+        paths
+            .into_iter()
+            .map(|path| async move { std::fs::symlink_metadata(path) }),
+    )
+}
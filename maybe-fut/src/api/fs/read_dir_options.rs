@@ -0,0 +1,152 @@
+use std::ffi::{OsStr, OsString};
+use std::path::Path;
+
+/// Options to filter and sort the entries returned by [`super::read_dir_with`].
+///
+/// By default no entry is filtered out and the entries are returned in the order yielded by the file system.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReadDirOptions {
+    extensions: Vec<OsString>,
+    glob: Option<String>,
+    skip_hidden: bool,
+    sort_by_name: bool,
+}
+
+impl ReadDirOptions {
+    /// Creates a new set of options, which doesn't filter nor sort the entries.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keeps only the entries with the given extension (without the leading dot).
+    ///
+    /// It can be called multiple times to keep the entries matching any of the given extensions.
+    pub fn extension(mut self, extension: impl AsRef<OsStr>) -> Self {
+        self.extensions.push(extension.as_ref().to_os_string());
+        self
+    }
+
+    /// Keeps only the entries whose file name matches the given glob pattern.
+    ///
+    /// `*` matches any sequence of characters and `?` matches any single character.
+    pub fn glob(mut self, pattern: impl Into<String>) -> Self {
+        self.glob = Some(pattern.into());
+        self
+    }
+
+    /// Skips the hidden entries, which are the ones whose file name starts with a dot.
+    pub fn skip_hidden(mut self, skip_hidden: bool) -> Self {
+        self.skip_hidden = skip_hidden;
+        self
+    }
+
+    /// Sorts the entries by file name.
+    pub fn sort_by_name(mut self, sort_by_name: bool) -> Self {
+        self.sort_by_name = sort_by_name;
+        self
+    }
+
+    /// Returns whether an entry with the given file name is kept.
+    pub(super) fn matches(&self, file_name: &OsStr) -> bool {
+        if self.skip_hidden && file_name.as_encoded_bytes().starts_with(b".") {
+            return false;
+        }
+
+        if !self.extensions.is_empty() {
+            let Some(extension) = Path::new(file_name).extension() else {
+                return false;
+            };
+            if !self.extensions.iter().any(|ext| ext == extension) {
+                return false;
+            }
+        }
+
+        match &self.glob {
+            Some(pattern) => glob_match(pattern, &file_name.to_string_lossy()),
+            None => true,
+        }
+    }
+
+    /// Returns whether the entries must be sorted by file name.
+    pub(super) fn sorted(&self) -> bool {
+        self.sort_by_name
+    }
+}
+
+/// Matches `text` against a glob `pattern` supporting the `*` and `?` wildcards.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    // position of the last `*` in the pattern and of the text it was matched against
+    let mut backtrack = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some('?') => {
+                p += 1;
+                t += 1;
+            }
+            Some(c) if *c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                // let the last `*` match one more character
+                Some((star, matched)) => {
+                    backtrack = Some((star, matched + 1));
+                    p = star + 1;
+                    t = matched + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_should_match_glob() {
+        assert!(glob_match("*", "file.txt"));
+        assert!(glob_match("*.txt", "file.txt"));
+        assert!(glob_match("file.???", "file.txt"));
+        assert!(glob_match("f*e*.t*", "file.txt"));
+        assert!(glob_match("file.txt", "file.txt"));
+        assert!(!glob_match("*.rs", "file.txt"));
+        assert!(!glob_match("file.??", "file.txt"));
+        assert!(!glob_match("file", "file.txt"));
+    }
+
+    #[test]
+    fn test_should_match_file_names() {
+        let options = ReadDirOptions::new();
+        assert!(options.matches(OsStr::new(".hidden")));
+        assert!(options.matches(OsStr::new("file.txt")));
+
+        let options = ReadDirOptions::new().skip_hidden(true);
+        assert!(!options.matches(OsStr::new(".hidden")));
+        assert!(options.matches(OsStr::new("file.txt")));
+
+        let options = ReadDirOptions::new().extension("txt").extension("md");
+        assert!(options.matches(OsStr::new("file.txt")));
+        assert!(options.matches(OsStr::new("README.md")));
+        assert!(!options.matches(OsStr::new("main.rs")));
+        assert!(!options.matches(OsStr::new("Makefile")));
+
+        let options = ReadDirOptions::new().glob("test_*").extension("rs");
+        assert!(options.matches(OsStr::new("test_fs.rs")));
+        assert!(!options.matches(OsStr::new("test_fs.txt")));
+        assert!(!options.matches(OsStr::new("fs.rs")));
+    }
+}
@@ -0,0 +1,245 @@
+//! File system operations which are not provided by std, implemented through the system calls of each platform.
+//!
+//! The operations are blocking, so the async runtimes run them on their blocking thread pools.
+
+use std::io;
+use std::path::Path;
+
+/// Renames `from` to `to`, failing with [`io::ErrorKind::AlreadyExists`] if `to` already exists.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn rename_noreplace(from: impl AsRef<Path>, to: impl AsRef<Path>) -> io::Result<()> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt as _;
+
+    let c_from = CString::new(from.as_ref().as_os_str().as_bytes())?;
+    let c_to = CString::new(to.as_ref().as_os_str().as_bytes())?;
+    // SAFETY: both paths are valid nul-terminated strings
+    let res = unsafe {
+        libc::renameat2(
+            libc::AT_FDCWD,
+            c_from.as_ptr(),
+            libc::AT_FDCWD,
+            c_to.as_ptr(),
+            libc::RENAME_NOREPLACE,
+        )
+    };
+    if res == 0 {
+        return Ok(());
+    }
+
+    let err = io::Error::last_os_error();
+    match err.raw_os_error() {
+        // the kernel or the file system doesn't support the flag
+        Some(libc::ENOSYS | libc::EINVAL) => rename_noreplace_racy(from.as_ref(), to.as_ref()),
+        _ => Err(err),
+    }
+}
+
+/// Renames `from` to `to`, failing with [`io::ErrorKind::AlreadyExists`] if `to` already exists.
+#[cfg(windows)]
+pub fn rename_noreplace(from: impl AsRef<Path>, to: impl AsRef<Path>) -> io::Result<()> {
+    use std::os::windows::ffi::OsStrExt as _;
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn MoveFileExW(existing: *const u16, new: *const u16, flags: u32) -> i32;
+    }
+
+    let wide = |path: &Path| {
+        path.as_os_str()
+            .encode_wide()
+            .chain(Some(0))
+            .collect::<Vec<u16>>()
+    };
+    let (w_from, w_to) = (wide(from.as_ref()), wide(to.as_ref()));
+    // without `MOVEFILE_REPLACE_EXISTING`, the move fails if the destination exists
+    // SAFETY: both paths are valid nul-terminated wide strings
+    if unsafe { MoveFileExW(w_from.as_ptr(), w_to.as_ptr(), 0) } == 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(())
+}
+
+/// Renames `from` to `to`, failing with [`io::ErrorKind::AlreadyExists`] if `to` already exists.
+#[cfg(not(any(target_os = "linux", target_os = "android", windows)))]
+pub fn rename_noreplace(from: impl AsRef<Path>, to: impl AsRef<Path>) -> io::Result<()> {
+    rename_noreplace_racy(from.as_ref(), to.as_ref())
+}
+
+/// Checks that `to` doesn't exist before renaming `from` to it.
+///
+/// `to` may still be created by someone else between the check and the rename, in which case it is replaced.
+#[cfg(not(windows))]
+fn rename_noreplace_racy(from: &Path, to: &Path) -> io::Result<()> {
+    match std::fs::symlink_metadata(to) {
+        Ok(_) => Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            "the destination of the rename already exists",
+        )),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => std::fs::rename(from, to),
+        Err(err) => Err(err),
+    }
+}
+
+/// Flushes the entries of the directory at `path` to the disk.
+#[cfg(unix)]
+pub fn sync_dir(path: impl AsRef<Path>) -> io::Result<()> {
+    std::fs::File::open(path)?.sync_all()
+}
+
+/// Checks that `path` is a directory, since directories can't be flushed on this platform.
+#[cfg(not(unix))]
+pub fn sync_dir(path: impl AsRef<Path>) -> io::Result<()> {
+    if std::fs::metadata(path)?.is_dir() {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::NotADirectory,
+            "the path to sync is not a directory",
+        ))
+    }
+}
+
+/// Returns whether `a` and `b` point to the same file, comparing the device and inode numbers of their metadata.
+#[cfg(unix)]
+pub fn same_file(a: impl AsRef<Path>, b: impl AsRef<Path>) -> io::Result<bool> {
+    use std::os::unix::fs::MetadataExt as _;
+
+    let (a, b) = (std::fs::metadata(a)?, std::fs::metadata(b)?);
+    Ok(a.dev() == b.dev() && a.ino() == b.ino())
+}
+
+/// Returns whether `a` and `b` point to the same file, comparing the volume serial numbers and file indexes of their
+/// handles.
+#[cfg(windows)]
+pub fn same_file(a: impl AsRef<Path>, b: impl AsRef<Path>) -> io::Result<bool> {
+    use std::os::windows::fs::OpenOptionsExt as _;
+    use std::os::windows::io::AsRawHandle as _;
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct ByHandleFileInformation {
+        file_attributes: u32,
+        creation_time: [u32; 2],
+        last_access_time: [u32; 2],
+        last_write_time: [u32; 2],
+        volume_serial_number: u32,
+        file_size_high: u32,
+        file_size_low: u32,
+        number_of_links: u32,
+        file_index_high: u32,
+        file_index_low: u32,
+    }
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn GetFileInformationByHandle(
+            file: std::os::windows::io::RawHandle,
+            info: *mut ByHandleFileInformation,
+        ) -> i32;
+    }
+
+    // opens the file without any access right, so that it works on directories and on files open elsewhere
+    const FILE_FLAG_BACKUP_SEMANTICS: u32 = 0x0200_0000;
+    let id = |path: &Path| -> io::Result<(u32, u32, u32)> {
+        let file = std::fs::OpenOptions::new()
+            .access_mode(0)
+            .custom_flags(FILE_FLAG_BACKUP_SEMANTICS)
+            .open(path)?;
+        let mut info = ByHandleFileInformation::default();
+        // SAFETY: the handle is open, and `info` is valid for writes
+        if unsafe { GetFileInformationByHandle(file.as_raw_handle(), &mut info) } == 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok((
+            info.volume_serial_number,
+            info.file_index_high,
+            info.file_index_low,
+        ))
+    };
+    Ok(id(a.as_ref())? == id(b.as_ref())?)
+}
+
+/// Gives the kernel an advice about the access pattern of a range of the file.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn advise(
+    fd: std::os::fd::BorrowedFd<'_>,
+    offset: u64,
+    len: u64,
+    advice: super::Advice,
+) -> io::Result<()> {
+    use std::os::fd::AsRawFd as _;
+
+    let to_off = |value: u64| {
+        libc::off_t::try_from(value).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the range to advise exceeds the maximum file offset",
+            )
+        })
+    };
+    // SAFETY: the descriptor is borrowed, so it is open
+    let res = unsafe {
+        libc::posix_fadvise(fd.as_raw_fd(), to_off(offset)?, to_off(len)?, advice.into())
+    };
+    // the error is returned rather than set in errno
+    match res {
+        0 => Ok(()),
+        err => Err(io::Error::from_raw_os_error(err)),
+    }
+}
+
+/// Runs a blocking operation on the blocking thread pool of tokio.
+#[cfg(tokio_fs)]
+async fn tokio_blocking<T, F>(f: F) -> io::Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> io::Result<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|_| io::Error::other("background task failed"))?
+}
+
+#[cfg(tokio_fs)]
+pub async fn tokio_rename_noreplace(
+    from: impl AsRef<Path>,
+    to: impl AsRef<Path>,
+) -> io::Result<()> {
+    let (from, to) = (from.as_ref().to_owned(), to.as_ref().to_owned());
+    tokio_blocking(move || rename_noreplace(from, to)).await
+}
+
+#[cfg(tokio_fs)]
+pub async fn tokio_sync_dir(path: impl AsRef<Path>) -> io::Result<()> {
+    let path = path.as_ref().to_owned();
+    tokio_blocking(move || sync_dir(path)).await
+}
+
+#[cfg(tokio_fs)]
+pub async fn tokio_same_file(a: impl AsRef<Path>, b: impl AsRef<Path>) -> io::Result<bool> {
+    let (a, b) = (a.as_ref().to_owned(), b.as_ref().to_owned());
+    tokio_blocking(move || same_file(a, b)).await
+}
+
+#[cfg(async_std)]
+pub async fn async_std_rename_noreplace(
+    from: impl AsRef<Path>,
+    to: impl AsRef<Path>,
+) -> io::Result<()> {
+    let (from, to) = (from.as_ref().to_owned(), to.as_ref().to_owned());
+    async_std::task::spawn_blocking(move || rename_noreplace(from, to)).await
+}
+
+#[cfg(async_std)]
+pub async fn async_std_sync_dir(path: impl AsRef<Path>) -> io::Result<()> {
+    let path = path.as_ref().to_owned();
+    async_std::task::spawn_blocking(move || sync_dir(path)).await
+}
+
+#[cfg(async_std)]
+pub async fn async_std_same_file(a: impl AsRef<Path>, b: impl AsRef<Path>) -> io::Result<bool> {
+    let (a, b) = (a.as_ref().to_owned(), b.as_ref().to_owned());
+    async_std::task::spawn_blocking(move || same_file(a, b)).await
+}
@@ -0,0 +1,286 @@
+//! io_uring implementation of the file system api, based on tokio-uring.
+//!
+//! tokio-uring files are bound to the thread of the runtime which opened them, so they're not [`Send`].
+//! For this reason, they're provided by the separate [`File`] type rather than by [`crate::fs::File`],
+//! which can still be moved across tasks and threads when the `uring-fs` feature is enabled.
+//!
+//! The types and functions of this module must be used inside of a tokio-uring runtime, such as the one started
+//! by [`crate::start_uring`].
+
+use std::io::{self, SeekFrom};
+use std::os::fd::{AsFd, AsRawFd, BorrowedFd, OwnedFd, RawFd};
+use std::path::Path;
+
+/// A reference to an open file on the filesystem, served by io_uring.
+///
+/// tokio-uring files are read and written at a given offset, so [`File`] keeps track of the cursor
+/// to provide the same api of [`crate::fs::File`]. The operations which are not supported by tokio-uring
+/// are performed on a duplicate of the file descriptor.
+///
+/// Reading, writing and seeking a [`File`] is usually done by using the [`crate::io::Read`], [`crate::io::Write`]
+/// and [`crate::io::Seek`] traits.
+#[derive(Debug)]
+pub struct File {
+    file: tokio_uring::fs::File,
+    pos: u64,
+}
+
+impl From<tokio_uring::fs::File> for File {
+    fn from(file: tokio_uring::fs::File) -> Self {
+        Self { file, pos: 0 }
+    }
+}
+
+impl From<std::fs::File> for File {
+    /// Takes ownership of the std file; the cursor starts from the beginning of the file.
+    fn from(file: std::fs::File) -> Self {
+        tokio_uring::fs::File::from_std(file).into()
+    }
+}
+
+impl File {
+    /// Attempts to open a file in read-only mode.
+    ///
+    /// See <https://docs.rs/tokio-uring/latest/tokio_uring/fs/struct.File.html#method.open>
+    pub async fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        tokio_uring::fs::File::open(path).await.map(Self::from)
+    }
+
+    /// Opens a file in write-only mode, creating it if it doesn't exist and truncating it if it does.
+    ///
+    /// See <https://docs.rs/tokio-uring/latest/tokio_uring/fs/struct.File.html#method.create>
+    pub async fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        tokio_uring::fs::File::create(path).await.map(Self::from)
+    }
+
+    /// Opens a file in read-write mode, failing if it already exists.
+    ///
+    /// See [`crate::fs::File::create_new`].
+    pub async fn create_new(path: impl AsRef<Path>) -> io::Result<Self> {
+        tokio_uring::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(path)
+            .await
+            .map(Self::from)
+    }
+
+    /// Returns a std file sharing the file descriptor of the file.
+    fn to_std(&self) -> io::Result<std::fs::File> {
+        self.as_fd().try_clone_to_owned().map(std::fs::File::from)
+    }
+
+    /// Closes the file, returning a std file sharing its file descriptor.
+    pub async fn into_std(self) -> io::Result<std::fs::File> {
+        let file = self.to_std()?;
+        self.file.close().await?;
+
+        Ok(file)
+    }
+
+    /// Creates a new file sharing the file descriptor and the cursor of this one.
+    pub fn try_clone(&self) -> io::Result<Self> {
+        Ok(Self {
+            file: tokio_uring::fs::File::from_std(self.to_std()?),
+            pos: self.pos,
+        })
+    }
+
+    /// Queries metadata about the underlying file.
+    pub async fn metadata(&self) -> io::Result<std::fs::Metadata> {
+        self.to_std()?.metadata()
+    }
+
+    /// Truncates or extends the underlying file, updating the size of this file to become size.
+    pub async fn set_len(&self, size: u64) -> io::Result<()> {
+        self.to_std()?.set_len(size)
+    }
+
+    /// Changes the permissions on the underlying file.
+    pub async fn set_permissions(&self, perm: std::fs::Permissions) -> io::Result<()> {
+        self.to_std()?.set_permissions(perm)
+    }
+
+    /// Attempts to sync all OS-internal metadata to disk.
+    pub async fn sync_all(&self) -> io::Result<()> {
+        self.file.sync_all().await
+    }
+
+    /// This function is similar to [`Self::sync_all`], except that it may not synchronize file metadata to the filesystem.
+    pub async fn sync_data(&self) -> io::Result<()> {
+        self.file.sync_data().await
+    }
+}
+
+impl crate::io::Read for File {
+    async fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let (res, data) = self
+            .file
+            .read_at(Vec::with_capacity(buf.len()), self.pos)
+            .await;
+        let size = res?;
+        buf[..size].copy_from_slice(&data[..size]);
+        self.pos += size as u64;
+
+        Ok(size)
+    }
+}
+
+impl crate::io::Write for File {
+    async fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let (res, _) = self.file.write_at(buf.to_vec(), self.pos).await;
+        let size = res?;
+        self.pos += size as u64;
+
+        Ok(size)
+    }
+
+    /// Writes are not buffered, so there is nothing to flush.
+    async fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl crate::io::Seek for File {
+    async fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(pos) => {
+                self.pos = pos;
+                return Ok(pos);
+            }
+            SeekFrom::Current(offset) => (self.pos, offset),
+            SeekFrom::End(offset) => (self.metadata().await?.len(), offset),
+        };
+        self.pos = base.checked_add_signed(offset).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+
+        Ok(self.pos)
+    }
+}
+
+impl AsFd for File {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        // SAFETY: the descriptor is owned by the file, which outlives the borrow
+        unsafe { BorrowedFd::borrow_raw(self.file.as_raw_fd()) }
+    }
+}
+
+impl AsRawFd for File {
+    fn as_raw_fd(&self) -> RawFd {
+        self.file.as_raw_fd()
+    }
+}
+
+impl TryFrom<File> for OwnedFd {
+    type Error = io::Error;
+
+    /// Returns a duplicate of the file descriptor; the file itself is closed when dropped.
+    fn try_from(file: File) -> io::Result<Self> {
+        file.as_fd().try_clone_to_owned()
+    }
+}
+
+/// Removes a file at this path.
+///
+/// See [`crate::fs::remove_file`].
+pub async fn remove_file(path: impl AsRef<Path>) -> io::Result<()> {
+    tokio_uring::fs::remove_file(path).await
+}
+
+/// Removes an empty directory.
+///
+/// See [`crate::fs::remove_dir`].
+pub async fn remove_dir(path: impl AsRef<Path>) -> io::Result<()> {
+    tokio_uring::fs::remove_dir(path).await
+}
+
+/// Renames a file or directory to a new name, replacing the original file if to already exists.
+///
+/// See [`crate::fs::rename`].
+pub async fn rename(from: impl AsRef<Path>, to: impl AsRef<Path>) -> io::Result<()> {
+    tokio_uring::fs::rename(from, to).await
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+    use tempfile::NamedTempFile;
+
+    use super::*;
+    use crate::io::{Read as _, Seek as _, Write as _};
+
+    #[test]
+    fn test_should_read_write_and_seek_uring() {
+        let temp = NamedTempFile::new().expect("Failed to create temp file");
+
+        crate::start_uring(async {
+            let mut file = File::create(temp.path())
+                .await
+                .expect("Failed to open file");
+            file.write(b"Hello world")
+                .await
+                .expect("Failed to write file");
+            file.flush().await.expect("Failed to flush file");
+            file.sync_all().await.expect("Failed to sync file");
+
+            let mut file = File::open(temp.path()).await.expect("Failed to open file");
+            assert_eq!(
+                file.metadata().await.expect("Failed to get metadata").len(),
+                11
+            );
+            file.seek(SeekFrom::End(-5))
+                .await
+                .expect("Failed to seek file");
+            let mut buf = [0; 5];
+            file.read(&mut buf).await.expect("Failed to read file");
+            assert_eq!(&buf, b"world");
+        });
+    }
+
+    #[test]
+    fn test_should_clone_and_convert_uring_file() {
+        let temp = NamedTempFile::new().expect("Failed to create temp file");
+        std::fs::write(temp.path(), b"Hello world").expect("Failed to write file");
+
+        crate::start_uring(async {
+            let mut file = File::open(temp.path()).await.expect("Failed to open file");
+            file.seek(SeekFrom::Start(6))
+                .await
+                .expect("Failed to seek file");
+            let mut clone = file.try_clone().expect("Failed to clone file");
+            assert_eq!(
+                clone.read_to_string_new().await.expect("Failed to read"),
+                "world"
+            );
+
+            let std_file = file.into_std().await.expect("Failed to convert file");
+            assert_eq!(std_file.metadata().unwrap().len(), 11);
+        });
+    }
+
+    #[test]
+    fn test_should_rename_and_remove_uring() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let file = tempdir.path().join("file.txt");
+        let renamed = tempdir.path().join("renamed.txt");
+        let dir = tempdir.path().join("dir");
+
+        crate::start_uring(async {
+            std::fs::write(&file, "Hello, world!").expect("write failed");
+            rename(&file, &renamed).await.expect("rename failed");
+            assert!(!file.exists());
+            remove_file(&renamed).await.expect("remove_file failed");
+            assert!(!renamed.exists());
+
+            std::fs::create_dir(&dir).expect("create_dir failed");
+            remove_dir(&dir).await.expect("remove_dir failed");
+            assert!(!dir.exists());
+        });
+    }
+}
@@ -0,0 +1,405 @@
+//! Combinators to wait for several futures at once.
+//!
+//! In async contexts, the futures are polled concurrently by the calling task.
+//! In sync contexts, where each future blocks until it completes, they're run concurrently on a small pool of threads
+//! (see [`crate::SyncRuntime::block_on_all`]), so that sync callers don't have to run them one after the other.
+//! That's why the futures and their outputs must be [`Send`].
+//! The futures raced by [`select`] and [`select_all`] are run on a thread each in sync contexts, and they must be
+//! `'static` as well, since the losers keep running after the winner is returned.
+//! The futures are polled by the runtime whenever there is one, even if the std implementation is forced with
+//! [`crate::set_default_backend`].
+//!
+//! Reference:
+//!
+//! - futures: <https://docs.rs/futures/latest/futures/future/index.html>
+
+use std::future::poll_fn;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use crate::SyncRuntime;
+
+/// Waits for both futures to complete, returning their outputs.
+pub async fn join<A, B>(a: A, b: B) -> (A::Output, B::Output)
+where
+    A: Future + Send,
+    B: Future + Send,
+    A::Output: Send,
+    B::Output: Send,
+{
+    if !crate::context::is_async_runtime() {
+        return block_on_both(a, b);
+    }
+
+    let (mut a, mut b) = (MaybeDone::new(a), MaybeDone::new(b));
+    poll_fn(|cx| {
+        let a_done = a.poll(cx);
+        let b_done = b.poll(cx);
+        if a_done && b_done {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    })
+    .await;
+
+    (a.take(), b.take())
+}
+
+/// Waits for both futures to complete successfully, returning their outputs, or returns the first error.
+///
+/// In async contexts, the other future is dropped as soon as one fails.
+/// In sync contexts, both futures always run to completion.
+pub async fn try_join<A, B, T1, T2, E>(a: A, b: B) -> Result<(T1, T2), E>
+where
+    A: Future<Output = Result<T1, E>> + Send,
+    B: Future<Output = Result<T2, E>> + Send,
+    T1: Send,
+    T2: Send,
+    E: Send,
+{
+    if !crate::context::is_async_runtime() {
+        let (a, b) = block_on_both(a, b);
+        return Ok((a?, b?));
+    }
+
+    let (mut a, mut b) = (MaybeDone::new(a), MaybeDone::new(b));
+    poll_fn(|cx| {
+        let a_done = a.poll(cx);
+        if let Some(err) = a.take_err() {
+            return Poll::Ready(Err(err));
+        }
+        let b_done = b.poll(cx);
+        if let Some(err) = b.take_err() {
+            return Poll::Ready(Err(err));
+        }
+        if a_done && b_done {
+            Poll::Ready(Ok(()))
+        } else {
+            Poll::Pending
+        }
+    })
+    .await?;
+
+    Ok((a.take()?, b.take()?))
+}
+
+/// Waits for all the futures to complete, returning their outputs in the same order.
+pub async fn join_all<I>(futures: I) -> Vec<<I::Item as Future>::Output>
+where
+    I: IntoIterator,
+    I::Item: Future + Send,
+    <I::Item as Future>::Output: Send,
+{
+    if !crate::context::is_async_runtime() {
+        return SyncRuntime::block_on_all(futures);
+    }
+
+    let mut futures: Vec<_> = futures.into_iter().map(MaybeDone::new).collect();
+    poll_fn(|cx| {
+        // every future must be polled, so that all of them register their waker
+        let mut done = true;
+        for future in futures.iter_mut() {
+            done &= future.poll(cx);
+        }
+        if done { Poll::Ready(()) } else { Poll::Pending }
+    })
+    .await;
+
+    futures.into_iter().map(MaybeDone::take).collect()
+}
+
+/// The output of [`select`], telling which future completed first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectOutput<A, B> {
+    /// The first future completed first.
+    Left(A),
+    /// The second future completed first.
+    Right(B),
+}
+
+/// Waits for the first of the two futures to complete, returning its output.
+///
+/// In async contexts, the other future is dropped, and the first future is polled first, so it wins if both are
+/// ready. In sync contexts, a blocking future can't be cancelled, so the other future keeps running to completion on
+/// a detached thread: its side effects still happen after [`select`] returns, and its output is dropped.
+pub async fn select<A, B>(a: A, b: B) -> SelectOutput<A::Output, B::Output>
+where
+    A: Future + Send + 'static,
+    B: Future + Send + 'static,
+    A::Output: Send + 'static,
+    B::Output: Send + 'static,
+{
+    if !crate::context::is_async_runtime() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        spawn_racer(async { SelectOutput::Left(a.await) }, tx.clone());
+        spawn_racer(async { SelectOutput::Right(b.await) }, tx);
+        return recv_winner(rx);
+    }
+
+    let (mut a, mut b) = (Box::pin(a), Box::pin(b));
+    poll_fn(|cx| {
+        if let Poll::Ready(output) = a.as_mut().poll(cx) {
+            return Poll::Ready(SelectOutput::Left(output));
+        }
+        b.as_mut().poll(cx).map(SelectOutput::Right)
+    })
+    .await
+}
+
+/// Waits for the first of the futures to complete, returning its output and its index.
+///
+/// In async contexts, the other futures are dropped, and the futures are polled in order, so the first ready one
+/// wins. In sync contexts, a blocking future can't be cancelled, so the other futures keep running to completion on
+/// detached threads: their side effects still happen after [`select_all`] returns, and their outputs are dropped.
+///
+/// # Panics
+///
+/// Panics if there are no futures.
+pub async fn select_all<I>(futures: I) -> (<I::Item as Future>::Output, usize)
+where
+    I: IntoIterator,
+    I::Item: Future + Send + 'static,
+    <I::Item as Future>::Output: Send + 'static,
+{
+    let mut futures: Vec<_> = futures.into_iter().map(Box::pin).collect();
+    assert!(!futures.is_empty(), "select_all called with no futures");
+
+    if !crate::context::is_async_runtime() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        for (index, future) in futures.into_iter().enumerate() {
+            spawn_racer(async move { (future.await, index) }, tx.clone());
+        }
+        return recv_winner(rx);
+    }
+
+    poll_fn(|cx| {
+        futures
+            .iter_mut()
+            .enumerate()
+            .find_map(|(index, future)| match future.as_mut().poll(cx) {
+                Poll::Ready(output) => Some((output, index)),
+                Poll::Pending => None,
+            })
+            .map_or(Poll::Pending, Poll::Ready)
+    })
+    .await
+}
+
+/// Blocks on a future raced by [`select`] or [`select_all`] on a new thread, sending its output to `tx`.
+fn spawn_racer<F>(future: F, tx: std::sync::mpsc::Sender<F::Output>)
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    std::thread::spawn(move || {
+        // the receiver is gone once the winner has been received
+        let _ = tx.send(SyncRuntime::block_on(future));
+    });
+}
+
+/// Receives the output of the first future to complete.
+///
+/// # Panics
+///
+/// Panics if all the futures panicked.
+fn recv_winner<T>(rx: std::sync::mpsc::Receiver<T>) -> T {
+    rx.recv().expect("all the raced futures panicked")
+}
+
+/// Blocks on both futures in a sync context, running `b` on another thread.
+fn block_on_both<A, B>(a: A, b: B) -> (A::Output, B::Output)
+where
+    A: Future + Send,
+    B: Future + Send,
+    A::Output: Send,
+    B::Output: Send,
+{
+    std::thread::scope(|scope| {
+        let b = scope.spawn(|| SyncRuntime::block_on(b));
+        let a = SyncRuntime::block_on(a);
+        let b = b
+            .join()
+            .unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+
+        (a, b)
+    })
+}
+
+/// A future which keeps its output once completed.
+enum MaybeDone<F: Future> {
+    Pending(Pin<Box<F>>),
+    Done(F::Output),
+    Taken,
+}
+
+impl<F: Future> MaybeDone<F> {
+    fn new(future: F) -> Self {
+        Self::Pending(Box::pin(future))
+    }
+
+    /// Polls the future if it is still pending, returning whether it is done.
+    fn poll(&mut self, cx: &mut Context<'_>) -> bool {
+        if let Self::Pending(future) = self {
+            match future.as_mut().poll(cx) {
+                Poll::Ready(output) => *self = Self::Done(output),
+                Poll::Pending => return false,
+            }
+        }
+
+        true
+    }
+
+    /// Takes the output of the completed future.
+    fn take(self) -> F::Output {
+        match self {
+            Self::Done(output) => output,
+            _ => unreachable!("the future has not completed"),
+        }
+    }
+}
+
+impl<F, T, E> MaybeDone<F>
+where
+    F: Future<Output = Result<T, E>>,
+{
+    /// Takes the error of the future, if it failed.
+    fn take_err(&mut self) -> Option<E> {
+        match std::mem::replace(self, Self::Taken) {
+            Self::Done(Err(err)) => Some(err),
+            other => {
+                *self = other;
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::sync::Barrier;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_should_join_sync() {
+        let barrier = Barrier::new(2);
+        // would deadlock if the futures were run sequentially
+        let (_, b) = SyncRuntime::block_on(join(async { barrier.wait() }, async {
+            barrier.wait();
+            42
+        }));
+        assert_eq!(b, 42);
+    }
+
+    #[test]
+    fn test_should_try_join_sync() {
+        let res: Result<_, &str> =
+            SyncRuntime::block_on(try_join(async { Ok(1) }, async { Ok("two") }));
+        assert_eq!(res, Ok((1, "two")));
+
+        let res: Result<(i32, i32), _> =
+            SyncRuntime::block_on(try_join(async { Ok(1) }, async { Err("failed") }));
+        assert_eq!(res, Err("failed"));
+    }
+
+    #[test]
+    fn test_should_join_all_sync() {
+        let res = SyncRuntime::block_on(join_all((0..8).map(|i| async move { i + 1 })));
+        assert_eq!(res, (1..9).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_should_select_sync() {
+        let started = std::time::Instant::now();
+        let res = SyncRuntime::block_on(select(
+            async {
+                std::thread::sleep(std::time::Duration::from_millis(500));
+                1
+            },
+            async { "two" },
+        ));
+        assert_eq!(res, SelectOutput::Right("two"));
+        assert!(started.elapsed() < std::time::Duration::from_millis(400));
+    }
+
+    #[test]
+    fn test_should_select_all_sync() {
+        let res = SyncRuntime::block_on(select_all((0..4).map(|i| async move {
+            if i != 2 {
+                std::thread::sleep(std::time::Duration::from_millis(500));
+            }
+            i * 10
+        })));
+        assert_eq!(res, (20, 2));
+    }
+
+    #[cfg(tokio)]
+    #[tokio::test]
+    async fn test_should_select_tokio() {
+        let res = select(std::future::pending::<()>(), async { 2 }).await;
+        assert_eq!(res, SelectOutput::Right(2));
+        let res = select(async { 1 }, async { 2 }).await;
+        assert_eq!(res, SelectOutput::Left(1));
+    }
+
+    #[cfg(tokio_time)]
+    #[tokio::test]
+    async fn test_should_select_all_tokio() {
+        let futures: Vec<std::pin::Pin<Box<dyn Future<Output = i32> + Send>>> = vec![
+            Box::pin(std::future::pending()),
+            Box::pin(async {
+                tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+                2
+            }),
+        ];
+        assert_eq!(select_all(futures).await, (2, 1));
+    }
+
+    #[cfg(tokio_time)]
+    #[tokio::test]
+    async fn test_should_join_tokio() {
+        let started = std::time::Instant::now();
+        let (a, b) = join(
+            async {
+                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                1
+            },
+            async {
+                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                2
+            },
+        )
+        .await;
+        assert_eq!((a, b), (1, 2));
+        assert!(started.elapsed() < std::time::Duration::from_millis(190));
+    }
+
+    #[cfg(tokio)]
+    #[tokio::test]
+    async fn test_should_try_join_tokio_short_circuit() {
+        let res: Result<((), ()), _> =
+            try_join(std::future::pending(), async { Err("failed") }).await;
+        assert_eq!(res, Err("failed"));
+    }
+
+    #[cfg(tokio_sync)]
+    #[tokio::test]
+    async fn test_should_join_all_tokio() {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let futures: Vec<std::pin::Pin<Box<dyn Future<Output = i32> + Send>>> = vec![
+            Box::pin(async {
+                rx.await.expect("sender dropped");
+                1
+            }),
+            Box::pin(async {
+                tx.send(()).expect("receiver dropped");
+                2
+            }),
+        ];
+        assert_eq!(join_all(futures).await, vec![1, 2]);
+    }
+}
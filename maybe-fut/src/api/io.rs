@@ -8,10 +8,15 @@
 //! - tokio: <https://docs.rs/tokio/latest/tokio/io/index.html>
 
 mod buf_reader;
+mod buf_stream;
 mod buf_writer;
+mod counting_writer;
 mod empty;
+mod hashing;
 mod lines;
+mod poll_compat;
 mod read;
+mod reader_state;
 mod repeat;
 mod seek;
 mod sink;
@@ -19,12 +24,20 @@ mod split;
 mod stderr;
 mod stdin;
 mod stdout;
+pub mod test;
 mod write;
 
+/// Derives the I/O traits for a wrapper around an enum with a `Std` and a `Tokio` variant.
+pub use maybe_fut_io_derive::{Read, Seek, Write};
+
 pub use self::buf_reader::{BufRead, BufReader};
+pub use self::buf_stream::BufStream;
 pub use self::buf_writer::BufWriter;
+pub use self::counting_writer::{CountingWriter, counting_sink};
 pub use self::empty::{Empty, empty};
+pub use self::hashing::{Digest, HashingReader, HashingWriter};
 pub use self::lines::Lines;
+pub use self::poll_compat::PollCompat;
 pub use self::read::Read;
 pub use self::repeat::{Repeat, repeat};
 pub use self::seek::Seek;
@@ -46,7 +59,7 @@ where
     W: Write + ?Sized,
 {
     let mut total = 0;
-    let mut buf = [0; 8192];
+    let mut buf = vec![0; crate::config::copy_buf_size()];
     loop {
         let n = reader.read(&mut buf).await?;
         if n == 0 {
@@ -58,9 +71,84 @@ where
     Ok(total)
 }
 
+/// Copies the contents of a reader into a writer, like [`copy`], until the reader returns EOF or `token` is cancelled.
+///
+/// In async contexts, the pending read or write is dropped as soon as the token is cancelled.
+/// In sync contexts, the token is checked before each read and write, so the copy stops at the end of the current chunk.
+///
+/// On success, the number of bytes written to the writer is returned, even if the copy has been cancelled;
+/// the bytes read but not written yet when the token is cancelled are discarded.
+pub async fn copy_cancellable<R, W>(
+    reader: &mut R,
+    writer: &mut W,
+    token: &crate::sync::CancellationToken,
+) -> std::io::Result<u64>
+where
+    R: Read + ?Sized,
+    W: Write + ?Sized,
+{
+    let mut total = 0;
+    let mut buf = vec![0; crate::config::copy_buf_size()];
+    loop {
+        let Some(n) = until_cancelled(reader.read(&mut buf), token).await else {
+            return Ok(total);
+        };
+        let n = n?;
+        if n == 0 {
+            return Ok(total);
+        }
+
+        let mut written = 0;
+        while written < n {
+            let Some(res) = until_cancelled(writer.write(&buf[written..n]), token).await else {
+                return Ok(total);
+            };
+            match res {
+                Ok(0) => {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::WriteZero,
+                        "failed to write whole buffer",
+                    ));
+                }
+                Ok(m) => {
+                    written += m;
+                    total += m as u64;
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+/// Runs `future` until it completes, returning its output, or until `token` is cancelled, returning [`None`].
+///
+/// In sync contexts, where `future` completes on its first poll, the token is only checked before running it.
+async fn until_cancelled<F>(future: F, token: &crate::sync::CancellationToken) -> Option<F::Output>
+where
+    F: Future,
+{
+    if token.is_cancelled() {
+        return None;
+    }
+    if !crate::context::is_async_runtime() {
+        return Some(future.await);
+    }
+
+    let mut future = std::pin::pin!(future);
+    let mut cancelled = std::pin::pin!(token.cancelled());
+    std::future::poll_fn(|cx| {
+        if let std::task::Poll::Ready(output) = future.as_mut().poll(cx) {
+            return std::task::Poll::Ready(Some(output));
+        }
+        cancelled.as_mut().poll(cx).map(|()| None)
+    })
+    .await
+}
+
 /// Reads all bytes from a reader into a new [`String`].
 ///
-/// This is a convenience function for [`Read::read_to_string`].
+/// This is a convenience function for [`Read::read_to_string_new`].
 ///
 /// Using this function avoids having to create a variable first and
 /// provides more type safety since you can only get the buffer out if there were no errors
@@ -68,11 +156,12 @@ pub async fn read_to_string<R>(reader: &mut R) -> std::io::Result<String>
 where
     R: Read + ?Sized,
 {
-    reader.read_to_string().await
+    reader.read_to_string_new().await
 }
 
+// named `tests`, since `test` is the public module of the mocks
 #[cfg(test)]
-mod test {
+mod tests {
     use super::*;
 
     #[tokio::test]
@@ -90,6 +179,24 @@ mod test {
         assert_eq!(result, "A".repeat(8192));
     }
 
+    #[tokio::test]
+    async fn test_read_to_string_appends() {
+        let mut reader = Buffer::new(b"world".to_vec());
+        let mut buf = String::from("hello ");
+        let n = reader.read_to_string(&mut buf).await.unwrap();
+        assert_eq!(n, 5);
+        assert_eq!(buf, "hello world");
+    }
+
+    #[tokio::test]
+    async fn test_read_to_string_should_keep_buffer_on_invalid_utf8() {
+        let mut reader = Buffer::new(vec![0xff, 0xfe]);
+        let mut buf = String::from("hello");
+        let err = reader.read_to_string(&mut buf).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(buf, "hello");
+    }
+
     struct Buffer {
         data: Vec<u8>,
         pos: usize,
@@ -112,4 +219,61 @@ mod test {
             Ok(n)
         }
     }
+
+    /// A reader which returns a single chunk, then never completes.
+    struct Stalled {
+        sent: bool,
+    }
+
+    impl Read for Stalled {
+        async fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.sent {
+                std::future::pending::<()>().await;
+            }
+            self.sent = true;
+            buf[..4].copy_from_slice(b"AAAA");
+            Ok(4)
+        }
+    }
+
+    #[test]
+    fn test_copy_cancellable_sync() {
+        let token = crate::sync::CancellationToken::new();
+        let mut reader = Buffer::new(vec![b'A'; 8192]);
+        let mut writer = counting_sink();
+        let total =
+            crate::SyncRuntime::block_on(copy_cancellable(&mut reader, &mut writer, &token))
+                .unwrap();
+        assert_eq!(total, 8192);
+        assert_eq!(writer.bytes_written(), 8192);
+
+        token.cancel();
+        let mut reader = Buffer::new(vec![b'A'; 8192]);
+        let total =
+            crate::SyncRuntime::block_on(copy_cancellable(&mut reader, &mut writer, &token))
+                .unwrap();
+        assert_eq!(total, 0);
+        assert_eq!(writer.bytes_written(), 8192);
+    }
+
+    #[tokio::test]
+    async fn test_copy_cancellable_tokio() {
+        let token = crate::sync::CancellationToken::new();
+        let mut reader = Stalled { sent: false };
+        let mut writer = sink();
+
+        let cancel = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            cancel.cancel();
+        });
+        let total = tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            copy_cancellable(&mut reader, &mut writer, &token),
+        )
+        .await
+        .expect("copy was not cancelled")
+        .unwrap();
+        assert_eq!(total, 4);
+    }
 }
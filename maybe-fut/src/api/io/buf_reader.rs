@@ -1,4 +1,4 @@
-use super::{Lines, Read, Split};
+use super::{Lines, Read, Split, Write};
 
 pub trait BufRead: Read {
     /// Returns the contents of the internal buffer, filling it with more data, via Read methods, if empty.
@@ -107,7 +107,7 @@ pub trait BufRead: Read {
     where
         Self: Sized,
     {
-        Split { buf: self, delim }
+        Split::new(self, delim)
     }
 
     /// Returns an iterator over the lines of this reader.
@@ -115,7 +115,7 @@ pub trait BufRead: Read {
     where
         Self: Sized,
     {
-        Lines { buf: self }
+        Lines::new(self)
     }
 }
 
@@ -130,12 +130,10 @@ pub struct BufReader<R: ?Sized> {
     inner: R,
 }
 
-const DEFAULT_BUF_SIZE: usize = 8192;
-
 impl<R: Read> BufReader<R> {
-    /// Creates a new BufReader with the default buffer size.
+    /// Creates a new BufReader with the default buffer size (see [`crate::config::buf_capacity`]).
     pub fn new(inner: R) -> Self {
-        Self::with_capacity(DEFAULT_BUF_SIZE, inner)
+        Self::with_capacity(crate::config::buf_capacity(), inner)
     }
 
     /// Creates a new BufReader with the specified buffer size.
@@ -165,13 +163,48 @@ impl<R: Read> BufReader<R> {
 
     /// Returns the number of bytes the internal buffer can hold.
     pub fn capacity(&self) -> usize {
-        self.buf.capacity()
+        self.buf.len()
+    }
+
+    /// Grows the internal buffer, if needed, so that it can hold at least `additional` more bytes than the ones
+    /// currently buffered.
+    pub fn reserve(&mut self, additional: usize) {
+        let required = (self.filled - self.pos).saturating_add(additional);
+        if required > self.capacity() {
+            self.resize_buf(required);
+        }
+    }
+
+    /// Shrinks the internal buffer to the default capacity (see [`crate::config::buf_capacity`]), if it's larger.
+    ///
+    /// The buffer is never shrunk below the data currently buffered.
+    pub fn shrink_to_fit(&mut self) {
+        let capacity = self.capacity().min(crate::config::buf_capacity());
+        self.resize_buf(capacity);
+    }
+
+    /// Sets the number of bytes the internal buffer can hold.
+    ///
+    /// The buffer is never shrunk below the data currently buffered.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.resize_buf(capacity);
     }
 
     /// Returns the underlying reader.
     pub fn into_inner(self) -> R {
         self.inner
     }
+
+    /// Moves the buffered data to the front of the internal buffer and resizes it to `capacity`, or to the buffered
+    /// data if larger, releasing the memory in excess.
+    fn resize_buf(&mut self, capacity: usize) {
+        let buffered = self.filled - self.pos;
+        self.buf.copy_within(self.pos..self.filled, 0);
+        self.pos = 0;
+        self.filled = buffered;
+        self.buf.resize(capacity.max(buffered), 0);
+        self.buf.shrink_to_fit();
+    }
 }
 
 impl<R: Read> Read for BufReader<R>
@@ -179,13 +212,13 @@ where
     R: ?Sized,
 {
     async fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
-        if self.buf.len() >= self.buf.capacity() {
-            self.buf.clear();
+        // reads larger than the buffer bypass it, if it's empty
+        if self.pos >= self.filled && buf.len() >= self.buf.len() {
             return self.inner.read(buf).await;
         }
         let rem = self.fill_buf().await?;
-        let nread = rem.len();
-        buf.copy_from_slice(rem);
+        let nread = rem.len().min(buf.len());
+        buf[..nread].copy_from_slice(&rem[..nread]);
         self.consume(nread).await;
         Ok(nread)
     }
@@ -209,6 +242,28 @@ where
     }
 }
 
+/// Writes bypass the buffer and go straight to the inner stream, so that a [`BufReader`] can wrap a duplex stream.
+impl<R> Write for BufReader<R>
+where
+    R: Read + Write,
+{
+    async fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.inner.write(buf).await
+    }
+
+    async fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush().await
+    }
+
+    async fn write_vectored(&mut self, bufs: &[std::io::IoSlice<'_>]) -> std::io::Result<usize> {
+        self.inner.write_vectored(bufs).await
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -357,6 +412,52 @@ mod test {
         assert_eq!(buf.capacity(), 8192);
     }
 
+    #[tokio::test]
+    async fn test_should_read_through_buffer() {
+        let data = b"line1\nline2\r\nline3\n";
+        let mut buf = BufReader::with_capacity(8, Buffer::new(data.to_vec()));
+
+        let mut out = [0; 4];
+        assert_eq!(buf.read(&mut out).await.unwrap(), 4);
+        assert_eq!(&out, b"line");
+        assert_eq!(buf.buffer(), b"1\nli");
+
+        // the buffered data is returned before reading again
+        let mut out = [0; 16];
+        assert_eq!(buf.read(&mut out).await.unwrap(), 4);
+        assert_eq!(&out[..4], b"1\nli");
+        // large reads bypass the empty buffer
+        assert_eq!(buf.read(&mut out).await.unwrap(), 11);
+        assert_eq!(&out[..11], b"ne2\r\nline3\n");
+    }
+
+    #[tokio::test]
+    async fn test_should_manage_capacity() {
+        let data = b"line1\nline2\r\nline3\n";
+        let mut buf = BufReader::with_capacity(8, Buffer::new(data.to_vec()));
+        buf.fill_buf().await.unwrap();
+        buf.consume(6).await;
+        assert_eq!(buf.buffer(), b"li");
+
+        buf.reserve(4);
+        assert_eq!(buf.capacity(), 8);
+        buf.reserve(32);
+        assert_eq!(buf.capacity(), 34);
+        assert_eq!(buf.buffer(), b"li");
+
+        buf.set_capacity(1);
+        assert_eq!(buf.capacity(), 2);
+        assert_eq!(buf.buffer(), b"li");
+
+        buf.set_capacity(64 * 1024);
+        buf.shrink_to_fit();
+        assert_eq!(buf.capacity(), crate::config::buf_capacity());
+
+        let mut line = String::new();
+        buf.read_line(&mut line).await.unwrap();
+        assert_eq!(line, "line2\r\n");
+    }
+
     struct Buffer {
         data: Vec<u8>,
         pos: usize,
@@ -0,0 +1,117 @@
+use super::{BufRead, BufReader, BufWriter, Read, Write};
+
+/// Wraps a stream, such as a [`crate::net::TcpStream`], and buffers both its input and its output.
+///
+/// It's a [`BufReader`] over a [`BufWriter`]: reads are served from the read buffer, while writes are buffered
+/// until the write buffer is full or the stream is flushed.
+/// Remember to call [`Write::flush`] before waiting for the answer of the peer.
+pub struct BufStream<S: Read + Write> {
+    inner: BufReader<BufWriter<S>>,
+}
+
+impl<S: Read + Write> BufStream<S> {
+    /// Creates a new [`BufStream`] with the default buffer sizes (see [`crate::config::buf_capacity`]).
+    pub fn new(stream: S) -> Self {
+        Self {
+            inner: BufReader::new(BufWriter::new(stream)),
+        }
+    }
+
+    /// Creates a new [`BufStream`] with the specified sizes of the read and write buffers.
+    pub fn with_capacity(reader_capacity: usize, writer_capacity: usize, stream: S) -> Self {
+        Self {
+            inner: BufReader::with_capacity(
+                reader_capacity,
+                BufWriter::with_capacity(writer_capacity, stream),
+            ),
+        }
+    }
+
+    /// Returns a reference to the underlying stream.
+    pub fn get_ref(&self) -> &S {
+        self.inner.get_ref().get_ref()
+    }
+
+    /// Returns a mutable reference to the underlying stream.
+    ///
+    /// Reading from or writing to the stream directly may corrupt the buffered data.
+    pub fn get_mut(&mut self) -> &mut S {
+        self.inner.get_mut().get_mut()
+    }
+
+    /// Returns the underlying stream.
+    ///
+    /// The data in the read buffer and the data not flushed yet are lost.
+    pub fn into_inner(self) -> S {
+        self.inner.into_inner().into_inner()
+    }
+}
+
+impl<S: Read + Write> Read for BufStream<S> {
+    async fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.inner.read(buf).await
+    }
+}
+
+impl<S: Read + Write> BufRead for BufStream<S> {
+    async fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.inner.fill_buf().await
+    }
+
+    async fn consume(&mut self, amount: usize) {
+        self.inner.consume(amount).await
+    }
+}
+
+impl<S: Read + Write> Write for BufStream<S> {
+    async fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.inner.write(buf).await
+    }
+
+    async fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush().await
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::SyncRuntime;
+    use crate::io::test::MockStream;
+
+    #[test]
+    fn test_should_buffer_reads_and_writes() {
+        let stream = MockStream::builder()
+            .write(b"PING\r\nPING\r\n")
+            .read(b"PONG\r\nPONG\r\n")
+            .build();
+        let mut stream = BufStream::new(stream);
+
+        // both writes reach the stream with a single write on flush
+        SyncRuntime::block_on(stream.write_all(b"PING\r\n")).expect("failed to write");
+        SyncRuntime::block_on(stream.write_all(b"PING\r\n")).expect("failed to write");
+        SyncRuntime::block_on(stream.flush()).expect("failed to flush");
+
+        let mut line = String::new();
+        SyncRuntime::block_on(stream.read_line(&mut line)).expect("failed to read");
+        assert_eq!(line, "PONG\r\n");
+        assert_eq!(
+            SyncRuntime::block_on(stream.fill_buf()).expect("failed to fill buffer"),
+            b"PONG\r\n"
+        );
+    }
+
+    #[test]
+    fn test_should_return_inner_stream() {
+        let stream = MockStream::builder().write(b"PING").build();
+        let mut stream = BufStream::with_capacity(16, 2, stream);
+
+        // writes larger than the write buffer bypass it
+        SyncRuntime::block_on(stream.write_all(b"PING")).expect("failed to write");
+        // the mock checks on drop that the write reached it
+        let _stream: MockStream = stream.into_inner();
+    }
+}
@@ -1,4 +1,6 @@
-use super::Write;
+use std::io::IoSlice;
+
+use super::{Read, Write};
 
 /// Wraps a writer and buffers its output.
 #[derive(Debug)]
@@ -9,15 +11,13 @@ pub struct BufWriter<W: ?Sized + Write> {
     inner: W,
 }
 
-const DEFAULT_BUF_SIZE: usize = 8 * 1024;
-
 impl<W> BufWriter<W>
 where
     W: Write,
 {
-    /// Creates a new [`BufWriter`] with the default buffer size.
+    /// Creates a new [`BufWriter`] with the default buffer size (see [`crate::config::buf_capacity`]).
     pub fn new(inner: W) -> Self {
-        Self::with_capacity(DEFAULT_BUF_SIZE, inner)
+        Self::with_capacity(crate::config::buf_capacity(), inner)
     }
 
     /// Creates a new [`BufWriter`] with the specified buffer size.
@@ -37,7 +37,31 @@ where
 
     /// Returns the number of bytes the internal buffer can hold.
     pub fn capacity(&self) -> usize {
-        self.buf.capacity()
+        self.buf.len()
+    }
+
+    /// Grows the internal buffer, if needed, so that it can hold at least `additional` more bytes than the ones
+    /// currently buffered.
+    pub fn reserve(&mut self, additional: usize) {
+        let required = (self.filled - self.pos).saturating_add(additional);
+        if required > self.capacity() {
+            self.resize_buf(required);
+        }
+    }
+
+    /// Shrinks the internal buffer to the default capacity (see [`crate::config::buf_capacity`]), if it's larger.
+    ///
+    /// The buffer is never shrunk below the data currently buffered, which is kept until flushed.
+    pub fn shrink_to_fit(&mut self) {
+        let capacity = self.capacity().min(crate::config::buf_capacity());
+        self.resize_buf(capacity);
+    }
+
+    /// Sets the number of bytes the internal buffer can hold.
+    ///
+    /// The buffer is never shrunk below the data currently buffered, which is kept until flushed.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.resize_buf(capacity);
     }
 
     /// Returns a reference to the underlying writer.
@@ -57,9 +81,45 @@ where
 
     /// Disassembles this BufWriter<W>, returning the underlying writer, and any buffered but unwritten data.
     pub fn into_parts(self) -> (W, Vec<u8>) {
-        let buf = self.buf;
-        let inner = self.inner;
-        (inner, buf)
+        let mut buf = self.buf;
+        buf.truncate(self.filled);
+        buf.drain(..self.pos);
+        (self.inner, buf)
+    }
+}
+
+impl<W> BufWriter<W>
+where
+    W: Write,
+{
+    /// Moves the buffered data to the front of the internal buffer and resizes it to `capacity`, or to the buffered
+    /// data if larger, releasing the memory in excess.
+    fn resize_buf(&mut self, capacity: usize) {
+        let buffered = self.filled - self.pos;
+        self.buf.copy_within(self.pos..self.filled, 0);
+        self.pos = 0;
+        self.filled = buffered;
+        self.buf.resize(capacity.max(buffered), 0);
+        self.buf.shrink_to_fit();
+    }
+
+    /// Writes the buffered data to the underlying writer.
+    async fn flush_buf(&mut self) -> std::io::Result<()> {
+        while self.pos < self.filled {
+            match self.inner.write(&self.buf[self.pos..self.filled]).await? {
+                0 => {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::WriteZero,
+                        "failed to write the buffered data",
+                    ));
+                }
+                n => self.pos += n,
+            }
+        }
+        self.pos = 0;
+        self.filled = 0;
+
+        Ok(())
     }
 }
 
@@ -68,26 +128,53 @@ where
     W: Write,
 {
     async fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        if buf.len() < self.capacity() {
+        if self.filled + buf.len() > self.buf.len() {
+            let buffered = self.filled - self.pos;
+            if buffered > 0 && buf.len() >= self.buf.len() && self.inner.is_write_vectored() {
+                // write both the buffered data and the large write with a single call
+                let n = self
+                    .inner
+                    .write_vectored(&[
+                        IoSlice::new(&self.buf[self.pos..self.filled]),
+                        IoSlice::new(buf),
+                    ])
+                    .await?;
+                if n > buffered {
+                    self.pos = 0;
+                    self.filled = 0;
+                    return Ok(n - buffered);
+                }
+                self.pos += n;
+            }
+            self.flush_buf().await?;
+        }
+
+        if buf.len() >= self.buf.len() {
+            // large writes bypass the buffer
+            self.inner.write(buf).await
+        } else {
             self.buf[self.filled..self.filled + buf.len()].copy_from_slice(buf);
             self.filled += buf.len();
             Ok(buf.len())
-        } else {
-            let n = self.inner.write(buf).await?;
-            self.filled += n;
-            Ok(n)
         }
     }
 
     async fn flush(&mut self) -> std::io::Result<()> {
-        if self.filled > 0 {
-            self.inner.write(&self.buf[..self.filled]).await?;
-            self.filled = 0;
-        }
+        self.flush_buf().await?;
         self.inner.flush().await
     }
 }
 
+/// Reads bypass the buffer and go straight to the inner stream, so that a [`BufWriter`] can wrap a duplex stream.
+impl<W> Read for BufWriter<W>
+where
+    W: Read + Write,
+{
+    async fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.inner.read(buf).await
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -129,11 +216,15 @@ mod test {
     #[tokio::test]
     async fn test_buf_writer_into_parts() {
         let data = vec![0; 1024];
-        let buf_writer = BufWriter::new(Buffer::new(data));
+        let mut buf_writer = BufWriter::new(Buffer::new(data));
 
+        let (_, buf) = BufWriter::new(Buffer::new(vec![0; 1024])).into_parts();
+        assert!(buf.is_empty());
+
+        buf_writer.write(b"Hello, world!").await.unwrap();
         let (inner, buf) = buf_writer.into_parts();
         assert_eq!(inner.pos, 0);
-        assert_eq!(buf.len(), DEFAULT_BUF_SIZE);
+        assert_eq!(buf, b"Hello, world!");
     }
 
     #[tokio::test]
@@ -151,7 +242,7 @@ mod test {
         let buf_writer = BufWriter::new(Buffer::new(data));
 
         let capacity = buf_writer.capacity();
-        assert_eq!(capacity, DEFAULT_BUF_SIZE);
+        assert_eq!(capacity, crate::config::DEFAULT_BUF_SIZE);
     }
 
     #[tokio::test]
@@ -172,6 +263,120 @@ mod test {
         assert_eq!(inner.pos, 0);
     }
 
+    #[tokio::test]
+    async fn test_buf_writer_should_flush_when_full() {
+        let mut buf_writer = BufWriter::with_capacity(16, Buffer::new(vec![0; 64]));
+
+        buf_writer.write_all(b"Hello, ").await.unwrap();
+        buf_writer.write_all(b"world! Hello, ").await.unwrap();
+        assert_eq!(buf_writer.get_ref().pos, 7);
+        assert_eq!(buf_writer.buffer(), b"world! Hello, ");
+
+        buf_writer.flush().await.unwrap();
+        assert_eq!(&buf_writer.get_ref().data[..21], b"Hello, world! Hello, ");
+    }
+
+    #[tokio::test]
+    async fn test_buf_writer_should_coalesce_large_write_with_vectored_writer() {
+        let mut buf_writer = BufWriter::with_capacity(16, VectoredBuffer::default());
+
+        buf_writer.write_all(b"Hello, ").await.unwrap();
+        let large = [b'x'; 32];
+        buf_writer.write_all(&large).await.unwrap();
+        buf_writer.flush().await.unwrap();
+
+        let inner = buf_writer.into_inner();
+        assert_eq!(inner.calls, 1);
+        assert_eq!(&inner.data[..7], b"Hello, ");
+        assert_eq!(&inner.data[7..], &large);
+    }
+
+    #[tokio::test]
+    async fn test_buf_writer_should_not_coalesce_with_non_vectored_writer() {
+        let mut buf_writer = BufWriter::with_capacity(
+            16,
+            VectoredBuffer {
+                vectored: false,
+                ..Default::default()
+            },
+        );
+
+        buf_writer.write_all(b"Hello, ").await.unwrap();
+        buf_writer.write_all(&[b'x'; 32]).await.unwrap();
+        buf_writer.flush().await.unwrap();
+
+        let inner = buf_writer.into_inner();
+        assert_eq!(inner.calls, 2);
+        assert_eq!(inner.data.len(), 39);
+    }
+
+    #[tokio::test]
+    async fn test_buf_writer_should_manage_capacity() {
+        let mut buf_writer = BufWriter::with_capacity(16, Buffer::new(vec![0; 64]));
+        buf_writer.write_all(b"Hello").await.unwrap();
+
+        buf_writer.reserve(8);
+        assert_eq!(buf_writer.capacity(), 16);
+        buf_writer.reserve(32);
+        assert_eq!(buf_writer.capacity(), 37);
+
+        // the capacity is never below the buffered data
+        buf_writer.set_capacity(2);
+        assert_eq!(buf_writer.capacity(), 5);
+        assert_eq!(buf_writer.buffer(), b"Hello");
+
+        buf_writer.set_capacity(64 * 1024);
+        buf_writer.shrink_to_fit();
+        assert_eq!(buf_writer.capacity(), crate::config::buf_capacity());
+
+        buf_writer.write_all(b", world!").await.unwrap();
+        buf_writer.flush().await.unwrap();
+        assert_eq!(&buf_writer.get_ref().data[..13], b"Hello, world!");
+    }
+
+    /// A writer counting the calls to write, which can be vectored.
+    struct VectoredBuffer {
+        data: Vec<u8>,
+        calls: usize,
+        vectored: bool,
+    }
+
+    impl Default for VectoredBuffer {
+        fn default() -> Self {
+            Self {
+                data: Vec::new(),
+                calls: 0,
+                vectored: true,
+            }
+        }
+    }
+
+    impl Write for VectoredBuffer {
+        async fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.calls += 1;
+            self.data.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        async fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+
+        async fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> std::io::Result<usize> {
+            self.calls += 1;
+            let mut total = 0;
+            for buf in bufs {
+                self.data.extend_from_slice(buf);
+                total += buf.len();
+            }
+            Ok(total)
+        }
+
+        fn is_write_vectored(&self) -> bool {
+            self.vectored
+        }
+    }
+
     struct Buffer {
         data: Vec<u8>,
         pos: usize,
@@ -189,7 +394,7 @@ mod test {
                 return Ok(0);
             }
             let n = std::cmp::min(buf.len(), self.data.len() - self.pos);
-            self.data[self.pos..self.pos + n].copy_from_slice(buf);
+            self.data[self.pos..self.pos + n].copy_from_slice(&buf[..n]);
             self.pos += n;
             Ok(n)
         }
@@ -0,0 +1,126 @@
+use std::io::IoSlice;
+
+use super::{Sink, Write, sink};
+
+/// Wraps a writer and counts the bytes written to it.
+///
+/// Only the bytes accepted by the underlying writer are counted, so after a failed or partial write the count is
+/// still exact.
+#[derive(Debug, Default)]
+pub struct CountingWriter<W> {
+    inner: W,
+    bytes_written: u64,
+}
+
+impl<W> CountingWriter<W>
+where
+    W: Write,
+{
+    /// Creates a new [`CountingWriter`] wrapping `inner`, with a count of zero.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            bytes_written: 0,
+        }
+    }
+
+    /// Returns the number of bytes written so far.
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes_written
+    }
+
+    /// Resets the count to zero.
+    pub fn reset(&mut self) {
+        self.bytes_written = 0;
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// The bytes written directly to the underlying writer are not counted.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W> Write for CountingWriter<W>
+where
+    W: Write,
+{
+    async fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = self.inner.write(buf).await?;
+        self.bytes_written += n as u64;
+        Ok(n)
+    }
+
+    async fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush().await
+    }
+
+    async fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> std::io::Result<usize> {
+        let n = self.inner.write_vectored(bufs).await?;
+        self.bytes_written += n as u64;
+        Ok(n)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+}
+
+/// Creates a [`Sink`] counting the bytes written to it, e.g. to compute the length of some content before sending it.
+pub fn counting_sink() -> CountingWriter<Sink> {
+    CountingWriter::new(sink())
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::SyncRuntime;
+    use crate::io::test::MockWriter;
+
+    #[test]
+    fn test_should_count_bytes_written() {
+        let mut writer = counting_sink();
+        SyncRuntime::block_on(writer.write_all(b"Hello, ")).expect("failed to write");
+        SyncRuntime::block_on(writer.write_vectored(&[IoSlice::new(b"world"), IoSlice::new(b"!")]))
+            .expect("failed to write");
+        assert_eq!(writer.bytes_written(), 13);
+
+        writer.reset();
+        assert_eq!(writer.bytes_written(), 0);
+    }
+
+    #[test]
+    fn test_should_not_count_failed_writes() {
+        let mut writer = CountingWriter::new(
+            MockWriter::builder()
+                .write(b"hello")
+                .write_error(std::io::Error::from(std::io::ErrorKind::BrokenPipe))
+                .build_writer(),
+        );
+        SyncRuntime::block_on(writer.write_all(b"hello")).expect("failed to write");
+        assert!(SyncRuntime::block_on(writer.write(b"world")).is_err());
+        assert_eq!(writer.bytes_written(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_should_count_bytes_written_async() {
+        let mut writer = counting_sink();
+        writer.write_all(&[0; 1024]).await.expect("failed to write");
+        writer.flush().await.expect("failed to flush");
+        assert_eq!(writer.bytes_written(), 1024);
+    }
+}
@@ -0,0 +1,305 @@
+use std::io::{IoSlice, IoSliceMut};
+
+use super::{Read, Write};
+
+/// A streaming digest, computed over the bytes fed to it with [`Digest::update`].
+///
+/// It is implemented for the `sha2` hashers with the `sha2` feature, and for `crc32fast::Hasher` with the
+/// `crc32fast` feature.
+pub trait Digest {
+    /// The digest of the bytes.
+    type Output;
+
+    /// Feeds `data` to the digest.
+    fn update(&mut self, data: &[u8]);
+
+    /// Consumes the digest, returning the digest of the bytes fed so far.
+    fn finalize(self) -> Self::Output;
+}
+
+#[cfg(crc32fast)]
+#[cfg_attr(docsrs, doc(cfg(feature = "crc32fast")))]
+impl Digest for crc32fast::Hasher {
+    type Output = u32;
+
+    fn update(&mut self, data: &[u8]) {
+        crc32fast::Hasher::update(self, data);
+    }
+
+    fn finalize(self) -> Self::Output {
+        crc32fast::Hasher::finalize(self)
+    }
+}
+
+/// Implements [`Digest`] for the given `sha2` hashers.
+#[cfg(sha2)]
+macro_rules! impl_sha2_digest {
+    ($($hasher:ty),*) => {
+        $(
+            #[cfg_attr(docsrs, doc(cfg(feature = "sha2")))]
+            impl Digest for $hasher {
+                type Output = sha2::digest::Output<$hasher>;
+
+                fn update(&mut self, data: &[u8]) {
+                    sha2::Digest::update(self, data);
+                }
+
+                fn finalize(self) -> Self::Output {
+                    sha2::Digest::finalize(self)
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(sha2)]
+impl_sha2_digest!(
+    sha2::Sha224,
+    sha2::Sha256,
+    sha2::Sha384,
+    sha2::Sha512,
+    sha2::Sha512_224,
+    sha2::Sha512_256
+);
+
+/// Wraps a reader and feeds all the bytes read from it to a [`Digest`].
+///
+/// Any [`Digest`] can be used, e.g. `sha2::Sha256` to compute the SHA-256 digest of the data while reading it,
+/// without a second pass over the data.
+#[derive(Debug, Default)]
+pub struct HashingReader<R, D> {
+    inner: R,
+    digest: D,
+}
+
+impl<R, D> HashingReader<R, D>
+where
+    R: Read,
+    D: Digest,
+{
+    /// Creates a new [`HashingReader`] feeding the bytes read from `inner` to `digest`.
+    pub fn new(inner: R, digest: D) -> Self {
+        Self { inner, digest }
+    }
+
+    /// Consumes the reader, returning the digest of the bytes read.
+    pub fn finalize(self) -> D::Output {
+        self.digest.finalize()
+    }
+
+    /// Returns a reference to the digest.
+    pub fn digest(&self) -> &D {
+        &self.digest
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// The bytes read directly from the underlying reader are not hashed.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns the underlying reader and the digest.
+    pub fn into_parts(self) -> (R, D) {
+        (self.inner, self.digest)
+    }
+}
+
+impl<R, D> Read for HashingReader<R, D>
+where
+    R: Read,
+    D: Digest,
+{
+    async fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf).await?;
+        self.digest.update(&buf[..n]);
+        Ok(n)
+    }
+
+    async fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> std::io::Result<usize> {
+        let mut remaining = self.inner.read_vectored(bufs).await?;
+        let n = remaining;
+        for buf in bufs.iter() {
+            if remaining == 0 {
+                break;
+            }
+            let len = buf.len().min(remaining);
+            self.digest.update(&buf[..len]);
+            remaining -= len;
+        }
+        Ok(n)
+    }
+
+    fn is_read_vectored(&self) -> bool {
+        self.inner.is_read_vectored()
+    }
+}
+
+/// Wraps a writer and feeds all the bytes written to it to a [`Digest`].
+///
+/// Only the bytes accepted by the underlying writer are hashed.
+#[derive(Debug, Default)]
+pub struct HashingWriter<W, D> {
+    inner: W,
+    digest: D,
+}
+
+impl<W, D> HashingWriter<W, D>
+where
+    W: Write,
+    D: Digest,
+{
+    /// Creates a new [`HashingWriter`] feeding the bytes written to `inner` to `digest`.
+    pub fn new(inner: W, digest: D) -> Self {
+        Self { inner, digest }
+    }
+
+    /// Consumes the writer, returning the digest of the bytes written.
+    pub fn finalize(self) -> D::Output {
+        self.digest.finalize()
+    }
+
+    /// Returns a reference to the digest.
+    pub fn digest(&self) -> &D {
+        &self.digest
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// The bytes written directly to the underlying writer are not hashed.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns the underlying writer and the digest.
+    pub fn into_parts(self) -> (W, D) {
+        (self.inner, self.digest)
+    }
+}
+
+impl<W, D> Write for HashingWriter<W, D>
+where
+    W: Write,
+    D: Digest,
+{
+    async fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = self.inner.write(buf).await?;
+        self.digest.update(&buf[..n]);
+        Ok(n)
+    }
+
+    async fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush().await
+    }
+
+    async fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> std::io::Result<usize> {
+        let mut remaining = self.inner.write_vectored(bufs).await?;
+        let n = remaining;
+        for buf in bufs {
+            if remaining == 0 {
+                break;
+            }
+            let len = buf.len().min(remaining);
+            self.digest.update(&buf[..len]);
+            remaining -= len;
+        }
+        Ok(n)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::SyncRuntime;
+    use crate::io::test::{MockReader, MockWriter};
+
+    /// A digest collecting the bytes fed to it.
+    #[derive(Debug, Default)]
+    struct Collect(Vec<u8>);
+
+    impl Digest for Collect {
+        type Output = Vec<u8>;
+
+        fn update(&mut self, data: &[u8]) {
+            self.0.extend_from_slice(data);
+        }
+
+        fn finalize(self) -> Self::Output {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_should_hash_bytes_read() {
+        let reader = MockReader::builder()
+            .read(b"Hello, ")
+            .read(b"world!")
+            .build_reader();
+        let mut reader = HashingReader::new(reader, Collect::default());
+
+        let mut data = Vec::new();
+        SyncRuntime::block_on(reader.read_to_end(&mut data)).expect("failed to read");
+        assert_eq!(data, b"Hello, world!");
+        assert_eq!(reader.finalize(), b"Hello, world!");
+    }
+
+    #[test]
+    fn test_should_hash_bytes_written() {
+        let writer = MockWriter::builder().write(b"Hello, world!").build_writer();
+        let mut writer = HashingWriter::new(writer, Collect::default());
+
+        SyncRuntime::block_on(writer.write_all(b"Hello, ")).expect("failed to write");
+        SyncRuntime::block_on(writer.write_vectored(&[IoSlice::new(b"world"), IoSlice::new(b"!")]))
+            .expect("failed to write");
+        assert_eq!(writer.finalize(), b"Hello, world!");
+    }
+
+    #[cfg(crc32fast)]
+    #[tokio::test]
+    async fn test_should_crc32_bytes_copied() {
+        let mut reader = HashingReader::new(
+            MockReader::builder().read(&[7; 1024]).build_reader(),
+            crc32fast::Hasher::new(),
+        );
+        let mut writer = HashingWriter::new(crate::io::sink(), crc32fast::Hasher::new());
+
+        crate::io::copy(&mut reader, &mut writer)
+            .await
+            .expect("failed to copy");
+        assert_eq!(reader.finalize(), crc32fast::hash(&[7; 1024]));
+        assert_eq!(writer.finalize(), crc32fast::hash(&[7; 1024]));
+    }
+
+    #[cfg(sha2)]
+    #[test]
+    fn test_should_sha256_bytes_read() {
+        use sha2::Digest as _;
+
+        let reader = MockReader::builder()
+            .read(b"Hello, ")
+            .read(b"world!")
+            .build_reader();
+        let mut reader = HashingReader::new(reader, sha2::Sha256::new());
+
+        let mut data = Vec::new();
+        SyncRuntime::block_on(reader.read_to_end(&mut data)).expect("failed to read");
+        assert_eq!(reader.finalize(), sha2::Sha256::digest(b"Hello, world!"));
+    }
+}
@@ -1,27 +1,73 @@
 use super::BufRead;
+use super::reader_state::ReaderState;
 
+/// The lines of a reader, returned by [`BufRead::lines`].
+///
+/// It implements [`crate::stream::Stream`] and, with the `stream` feature, `futures_core::Stream` as well.
 #[derive(Debug)]
 pub struct Lines<B> {
-    pub(crate) buf: B,
+    state: ReaderState<B, std::io::Result<String>>,
+}
+
+impl<B> Lines<B> {
+    pub(crate) fn new(buf: B) -> Self {
+        Self {
+            state: ReaderState::new(buf),
+        }
+    }
 }
 
 impl<B: BufRead> Lines<B> {
     /// Returns next line from the buffer.
     pub async fn next(&mut self) -> Option<std::io::Result<String>> {
-        let mut buf = String::new();
-        match self.buf.read_line(&mut buf).await {
-            Ok(0) => None,
-            Ok(_n) => {
-                if buf.ends_with('\n') {
-                    buf.pop();
-                    if buf.ends_with('\r') {
-                        buf.pop();
-                    }
+        if let Some(line) = self.state.take_pending().await {
+            return line;
+        }
+        next_line(self.state.reader_mut()).await
+    }
+}
+
+impl<B: BufRead> crate::stream::Stream for Lines<B> {
+    type Item = std::io::Result<String>;
+
+    async fn next(&mut self) -> Option<Self::Item> {
+        Lines::next(self).await
+    }
+}
+
+#[cfg(stream)]
+#[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
+impl<B: BufRead + 'static> futures_core::Stream for Lines<B> {
+    type Item = std::io::Result<String>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        self.state.poll_next(cx, |mut buf| {
+            Box::pin(async move {
+                let line = next_line(&mut buf).await;
+                (buf, line)
+            })
+        })
+    }
+}
+
+/// Reads the next line from `buf`, without the line terminator.
+async fn next_line<B: BufRead>(buf: &mut B) -> Option<std::io::Result<String>> {
+    let mut line = String::new();
+    match buf.read_line(&mut line).await {
+        Ok(0) => None,
+        Ok(_n) => {
+            if line.ends_with('\n') {
+                line.pop();
+                if line.ends_with('\r') {
+                    line.pop();
                 }
-                Some(Ok(buf))
             }
-            Err(e) => Some(Err(e)),
+            Some(Ok(line))
         }
+        Err(e) => Some(Err(e)),
     }
 }
 
@@ -35,7 +81,7 @@ mod test {
     async fn test_should_return_lines() {
         let data = b"line1\nline2\r\nline3\n";
         let buf = BufReader::new(Buffer::new(data.to_vec()));
-        let mut lines = Lines { buf };
+        let mut lines = Lines::new(buf);
 
         assert_eq!(lines.next().await.unwrap().unwrap(), "line1");
         assert_eq!(lines.next().await.unwrap().unwrap(), "line2");
@@ -43,6 +89,19 @@ mod test {
         assert!(lines.next().await.is_none());
     }
 
+    #[cfg(stream)]
+    #[tokio::test]
+    async fn test_should_stream_lines() {
+        use futures_util::StreamExt as _;
+
+        let data = b"line1\nline2\r\nline3\n";
+        let mut lines = Lines::new(BufReader::new(Buffer::new(data.to_vec())));
+
+        assert_eq!(lines.next().await.unwrap().unwrap(), "line1");
+        let rest: Vec<String> = lines.map(|line| line.unwrap()).collect().await;
+        assert_eq!(rest, vec!["line2", "line3"]);
+    }
+
     struct Buffer {
         data: Vec<u8>,
         pos: usize,
@@ -0,0 +1,248 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::{fmt, io};
+
+use super::{Read, Write};
+
+/// A future running an I/O operation, which owns the I/O object and gives it back along with the outcome.
+type PendingOp<T> = Pin<Box<dyn Future<Output = (T, Outcome)>>>;
+
+/// The outcome of an I/O operation run by [`PollCompat`].
+enum Outcome {
+    Read(io::Result<usize>, Vec<u8>),
+    Write(io::Result<usize>),
+    Flush(io::Result<()>),
+}
+
+/// Adapts a maybe-fut I/O object to the `poll_*` methods used by manual [`Future`] implementations.
+///
+/// The methods of [`Read`] and [`Write`] are async, so their futures borrow the I/O object and can't be stored
+/// next to it. [`PollCompat`] moves the object into a boxed future while an operation is in flight, and gets it back
+/// once the operation completes; the data are copied from and into owned buffers.
+///
+/// As with the poll methods of tokio, an operation which returned [`Poll::Pending`] must be polled again until it
+/// completes, before starting another one.
+///
+/// With the `tokio` feature, it implements `tokio::io::AsyncRead` and `tokio::io::AsyncWrite`.
+pub struct PollCompat<T> {
+    inner: Option<T>,
+    pending: Option<PendingOp<T>>,
+}
+
+// the inner object is never pinned in place, since it's moved into the boxed future
+impl<T> Unpin for PollCompat<T> {}
+
+impl<T: fmt::Debug> fmt::Debug for PollCompat<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PollCompat")
+            .field("inner", &self.inner)
+            .field("pending", &self.pending.is_some())
+            .finish()
+    }
+}
+
+impl<T> PollCompat<T> {
+    /// Creates a new [`PollCompat`] wrapping the given I/O object.
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner: Some(inner),
+            pending: None,
+        }
+    }
+
+    /// Returns a reference to the I/O object, or `None` while an operation is in flight.
+    pub fn get_ref(&self) -> Option<&T> {
+        self.inner.as_ref()
+    }
+
+    /// Returns a mutable reference to the I/O object, or `None` while an operation is in flight.
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        self.inner.as_mut()
+    }
+
+    /// Returns the I/O object, or `None` if an operation is in flight.
+    pub fn into_inner(self) -> Option<T> {
+        self.inner
+    }
+
+    /// Polls the operation in flight, starting it with `start` if there is none.
+    fn poll_op(
+        &mut self,
+        cx: &mut Context<'_>,
+        start: impl FnOnce(T) -> PendingOp<T>,
+    ) -> Poll<io::Result<Outcome>> {
+        if self.pending.is_none() {
+            let Some(inner) = self.inner.take() else {
+                return Poll::Ready(Err(io::Error::other(
+                    "the I/O object of PollCompat has been lost by a panicking operation",
+                )));
+            };
+            self.pending = Some(start(inner));
+        }
+
+        let pending = self.pending.as_mut().expect("an operation is in flight");
+        let (inner, outcome) = std::task::ready!(pending.as_mut().poll(cx));
+        self.pending = None;
+        self.inner = Some(inner);
+
+        Poll::Ready(Ok(outcome))
+    }
+}
+
+/// Returns the error of an operation polled while an operation of another kind is in flight.
+fn interleaved_op() -> io::Error {
+    io::Error::other("another operation of PollCompat is in flight")
+}
+
+impl<T> PollCompat<T>
+where
+    T: Read + 'static,
+{
+    /// Attempts to read data into `buf`, returning the number of bytes read.
+    pub fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        let len = buf.len();
+        let outcome = std::task::ready!(self.poll_op(cx, |mut inner| {
+            Box::pin(async move {
+                let mut data = vec![0; len];
+                let res = inner.read(&mut data).await;
+                (inner, Outcome::Read(res, data))
+            })
+        }))?;
+
+        match outcome {
+            Outcome::Read(res, data) => Poll::Ready(res.map(|size| {
+                // the buffer may differ from the one of the first poll
+                let size = size.min(buf.len());
+                buf[..size].copy_from_slice(&data[..size]);
+                size
+            })),
+            _ => Poll::Ready(Err(interleaved_op())),
+        }
+    }
+}
+
+impl<T> PollCompat<T>
+where
+    T: Write + 'static,
+{
+    /// Attempts to write `buf`, returning the number of bytes written.
+    pub fn poll_write(&mut self, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        let outcome = std::task::ready!(self.poll_op(cx, |mut inner| {
+            let data = buf.to_vec();
+            Box::pin(async move {
+                let res = inner.write(&data).await;
+                (inner, Outcome::Write(res))
+            })
+        }))?;
+
+        match outcome {
+            Outcome::Write(res) => Poll::Ready(res),
+            _ => Poll::Ready(Err(interleaved_op())),
+        }
+    }
+
+    /// Attempts to flush the I/O object.
+    pub fn poll_flush(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let outcome = std::task::ready!(self.poll_op(cx, |mut inner| {
+            Box::pin(async move {
+                let res = inner.flush().await;
+                (inner, Outcome::Flush(res))
+            })
+        }))?;
+
+        match outcome {
+            Outcome::Flush(res) => Poll::Ready(res),
+            _ => Poll::Ready(Err(interleaved_op())),
+        }
+    }
+}
+
+#[cfg(tokio)]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+impl<T> tokio::io::AsyncRead for PollCompat<T>
+where
+    T: Read + 'static,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let size = std::task::ready!(self.get_mut().poll_read(cx, buf.initialize_unfilled()))?;
+        buf.advance(size);
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(tokio)]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+impl<T> tokio::io::AsyncWrite for PollCompat<T>
+where
+    T: Write + 'static,
+{
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.get_mut().poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.get_mut().poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.get_mut().poll_flush(cx)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::SyncRuntime;
+    use crate::io::repeat;
+
+    #[test]
+    fn test_should_poll_read_sync() {
+        let mut compat = PollCompat::new(repeat(b'x'));
+        let mut buf = [0; 4];
+        let size = SyncRuntime::block_on(std::future::poll_fn(|cx| compat.poll_read(cx, &mut buf)))
+            .expect("failed to read");
+        assert_eq!(size, 4);
+        assert_eq!(&buf, b"xxxx");
+        assert!(compat.get_ref().is_some());
+    }
+
+    #[cfg(tokio_fs)]
+    #[tokio::test]
+    async fn test_should_use_tokio_io_traits() {
+        use tokio::io::{AsyncReadExt as _, AsyncWriteExt as _};
+
+        let temp = tempfile::NamedTempFile::new().expect("Failed to create temp file");
+
+        let file = crate::fs::File::create(temp.path())
+            .await
+            .expect("Failed to create file");
+        let mut compat = PollCompat::new(file);
+        compat
+            .write_all(b"Hello, world!")
+            .await
+            .expect("Failed to write file");
+        compat.shutdown().await.expect("Failed to flush file");
+
+        let file = crate::fs::File::open(temp.path())
+            .await
+            .expect("Failed to open file");
+        let mut compat = PollCompat::new(file);
+        let mut buf = String::new();
+        compat
+            .read_to_string(&mut buf)
+            .await
+            .expect("Failed to read file");
+        assert_eq!(buf, "Hello, world!");
+    }
+}
@@ -42,12 +42,27 @@ pub trait Read {
         }
     }
 
-    fn read_to_string(&mut self) -> impl Future<Output = std::io::Result<String>> {
-        let mut buf = Vec::new();
+    /// Reads all bytes until EOF, appending them to `buf`, and returns the number of bytes read.
+    ///
+    /// If the data is not valid UTF-8, an error of kind [`std::io::ErrorKind::InvalidData`] is returned and `buf` is
+    /// left unchanged.
+    fn read_to_string(&mut self, buf: &mut String) -> impl Future<Output = std::io::Result<usize>> {
         async move {
-            self.read_to_end(&mut buf).await?;
-            String::from_utf8(buf)
-                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+            let mut bytes = Vec::new();
+            let n = self.read_to_end(&mut bytes).await?;
+            let data = String::from_utf8(bytes)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+            buf.push_str(&data);
+            Ok(n)
+        }
+    }
+
+    /// Reads all bytes until EOF into a new [`String`].
+    fn read_to_string_new(&mut self) -> impl Future<Output = std::io::Result<String>> {
+        async move {
+            let mut buf = String::new();
+            self.read_to_string(&mut buf).await?;
+            Ok(buf)
         }
     }
 
@@ -0,0 +1,78 @@
+//! State of the readers yielding items through an async `next` method, such as [`super::Lines`] and [`super::Split`].
+//!
+//! The items are read through the async methods of [`super::BufRead`], which can't be polled directly;
+//! so, to implement `Stream`, the reader is moved into a boxed future while the next item is being read
+//! and it is given back along with the item.
+
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+#[cfg(stream)]
+use std::task::{Context, Poll};
+
+/// A future reading the next item, which owns the reader and gives it back along with the item.
+type PendingNext<B, Item> = Pin<Box<dyn Future<Output = (B, Option<Item>)>>>;
+
+/// A reader, which is moved into [`PendingNext`] while an item is being read by `Stream::poll_next`.
+pub(crate) struct ReaderState<B, Item> {
+    reader: Option<B>,
+    pending: Option<PendingNext<B, Item>>,
+}
+
+// the reader is never pinned in place, since it's moved into the boxed future
+impl<B, Item> Unpin for ReaderState<B, Item> {}
+
+impl<B: fmt::Debug, Item> fmt::Debug for ReaderState<B, Item> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReaderState")
+            .field("reader", &self.reader)
+            .field("pending", &self.pending.is_some())
+            .finish()
+    }
+}
+
+impl<B, Item> ReaderState<B, Item> {
+    pub(crate) fn new(reader: B) -> Self {
+        Self {
+            reader: Some(reader),
+            pending: None,
+        }
+    }
+
+    /// Completes the read started by a stream poll, if any, returning its item.
+    pub(crate) async fn take_pending(&mut self) -> Option<Option<Item>> {
+        let pending = self.pending.take()?;
+        let (reader, item) = pending.await;
+        self.reader = Some(reader);
+
+        Some(item)
+    }
+
+    /// Returns the reader; [`Self::take_pending`] must be awaited first.
+    pub(crate) fn reader_mut(&mut self) -> &mut B {
+        self.reader
+            .as_mut()
+            .expect("the reader is taken only while a read is pending")
+    }
+
+    /// Polls the read of the next item, starting it with `next` if no read is pending.
+    #[cfg(stream)]
+    pub(crate) fn poll_next(
+        &mut self,
+        cx: &mut Context<'_>,
+        next: fn(B) -> PendingNext<B, Item>,
+    ) -> Poll<Option<Item>> {
+        let pending = self.pending.get_or_insert_with(|| {
+            next(
+                self.reader
+                    .take()
+                    .expect("the reader is taken only while a read is pending"),
+            )
+        });
+        let (reader, item) = std::task::ready!(pending.as_mut().poll(cx));
+        self.pending = None;
+        self.reader = Some(reader);
+
+        Poll::Ready(item)
+    }
+}
@@ -1,25 +1,71 @@
 use super::BufRead;
+use super::reader_state::ReaderState;
 
+/// The tokens of a reader separated by a delimiter, returned by [`BufRead::split`].
+///
+/// It implements [`crate::stream::Stream`] and, with the `stream` feature, `futures_core::Stream` as well.
 #[derive(Debug)]
 pub struct Split<B> {
-    pub(crate) buf: B,
-    pub(crate) delim: u8,
+    state: ReaderState<(B, u8), std::io::Result<Vec<u8>>>,
+}
+
+impl<B> Split<B> {
+    pub(crate) fn new(buf: B, delim: u8) -> Self {
+        Self {
+            state: ReaderState::new((buf, delim)),
+        }
+    }
 }
 
 impl<B: BufRead> Split<B> {
     /// Returns next token from the buffer.
     pub async fn next(&mut self) -> Option<std::io::Result<Vec<u8>>> {
-        let mut buf = Vec::new();
-        match self.buf.read_until(self.delim, &mut buf).await {
-            Ok(0) => None,
-            Ok(_n) => {
-                if buf[buf.len() - 1] == self.delim {
-                    buf.pop();
-                }
-                Some(Ok(buf))
+        if let Some(token) = self.state.take_pending().await {
+            return token;
+        }
+        let (buf, delim) = self.state.reader_mut();
+        next_token(buf, *delim).await
+    }
+}
+
+impl<B: BufRead> crate::stream::Stream for Split<B> {
+    type Item = std::io::Result<Vec<u8>>;
+
+    async fn next(&mut self) -> Option<Self::Item> {
+        Split::next(self).await
+    }
+}
+
+#[cfg(stream)]
+#[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
+impl<B: BufRead + 'static> futures_core::Stream for Split<B> {
+    type Item = std::io::Result<Vec<u8>>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        self.state.poll_next(cx, |(mut buf, delim)| {
+            Box::pin(async move {
+                let token = next_token(&mut buf, delim).await;
+                ((buf, delim), token)
+            })
+        })
+    }
+}
+
+/// Reads the next token from `buf`, without the delimiter.
+async fn next_token<B: BufRead>(buf: &mut B, delim: u8) -> Option<std::io::Result<Vec<u8>>> {
+    let mut token = Vec::new();
+    match buf.read_until(delim, &mut token).await {
+        Ok(0) => None,
+        Ok(_n) => {
+            if token[token.len() - 1] == delim {
+                token.pop();
             }
-            Err(e) => Some(Err(e)),
+            Some(Ok(token))
         }
+        Err(e) => Some(Err(e)),
     }
 }
 
@@ -33,7 +79,7 @@ mod test {
     async fn test_should_return_tokens() {
         let data = b"line1|line2|line3";
         let buf = BufReader::new(Buffer::new(data.to_vec()));
-        let mut tokens = Split { buf, delim: b'|' };
+        let mut tokens = Split::new(buf, b'|');
 
         assert_eq!(tokens.next().await.unwrap().unwrap(), b"line1");
         assert_eq!(tokens.next().await.unwrap().unwrap(), b"line2");
@@ -41,6 +87,21 @@ mod test {
         assert!(tokens.next().await.is_none());
     }
 
+    #[cfg(stream)]
+    #[tokio::test]
+    async fn test_should_stream_tokens() {
+        use futures_util::StreamExt as _;
+
+        let data = b"line1|line2|line3";
+        let tokens = Split::new(BufReader::new(Buffer::new(data.to_vec())), b'|');
+
+        let tokens: Vec<Vec<u8>> = tokens.map(|token| token.unwrap()).collect().await;
+        assert_eq!(
+            tokens,
+            vec![b"line1".to_vec(), b"line2".to_vec(), b"line3".to_vec()]
+        );
+    }
+
     struct Buffer {
         data: Vec<u8>,
         pos: usize,
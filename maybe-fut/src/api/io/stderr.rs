@@ -29,7 +29,7 @@ impl From<tokio::io::Stderr> for Stderr {
 pub fn stderr() -> Stderr {
     #[cfg(tokio)]
     {
-        if crate::is_async_context() {
+        if crate::is_tokio_context() {
             tokio::io::stderr().into()
         } else {
             std::io::stderr().into()
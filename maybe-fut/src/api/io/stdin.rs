@@ -29,7 +29,7 @@ impl From<tokio::io::Stdin> for Stdin {
 pub fn stdin() -> Stdin {
     #[cfg(tokio)]
     {
-        if crate::is_async_context() {
+        if crate::is_tokio_context() {
             tokio::io::stdin().into()
         } else {
             std::io::stdin().into()
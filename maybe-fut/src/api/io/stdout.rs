@@ -29,7 +29,7 @@ impl From<tokio::io::Stdout> for Stdout {
 pub fn stdout() -> Stdout {
     #[cfg(tokio)]
     {
-        if crate::is_async_context() {
+        if crate::is_tokio_context() {
             tokio::io::stdout().into()
         } else {
             std::io::stdout().into()
@@ -0,0 +1,338 @@
+//! Scripted I/O objects to unit test code using the maybe-fut io traits, without sockets or files.
+//!
+//! A [`Builder`] scripts the reads, the writes and the errors expected from the code under test, in order.
+//! The built mock replays the scripted reads and checks the written data against the scripted writes,
+//! panicking on any unexpected operation. When dropped, it panics if some actions of the script were not performed.
+//!
+//! ```rust
+//! use maybe_fut::io::test::MockStream;
+//! use maybe_fut::io::{Read, Write};
+//!
+//! let mut stream = MockStream::builder()
+//!     .write(b"PING\r\n")
+//!     .read(b"PONG\r\n")
+//!     .build();
+//!
+//! maybe_fut::block_on(async {
+//!     stream.write_all(b"PING\r\n").await.unwrap();
+//!     let mut buf = [0; 6];
+//!     stream.read_exact(&mut buf).await.unwrap();
+//!     assert_eq!(&buf, b"PONG\r\n");
+//! });
+//! ```
+//!
+//! Reference:
+//!
+//! - tokio-test: <https://docs.rs/tokio-test/latest/tokio_test/io/index.html>
+
+use std::collections::VecDeque;
+use std::io;
+
+use super::{Read, Write};
+
+/// An action of the script of a mock.
+#[derive(Debug)]
+enum Action {
+    Read(Vec<u8>),
+    Write(Vec<u8>),
+    ReadError(io::Error),
+    WriteError(io::Error),
+}
+
+/// Builds a [`MockStream`], a [`MockReader`] or a [`MockWriter`] from a script of actions.
+#[derive(Debug, Default)]
+pub struct Builder {
+    actions: VecDeque<Action>,
+}
+
+impl Builder {
+    /// Creates a new [`Builder`] with an empty script.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Scripts a read returning `data`.
+    ///
+    /// The data can be returned over several reads, if the buffers of the reads are smaller.
+    pub fn read(mut self, data: &[u8]) -> Self {
+        self.actions.push_back(Action::Read(data.to_vec()));
+        self
+    }
+
+    /// Scripts a read failing with `error`.
+    pub fn read_error(mut self, error: io::Error) -> Self {
+        self.actions.push_back(Action::ReadError(error));
+        self
+    }
+
+    /// Scripts a write of `data`.
+    ///
+    /// The data can be written over several writes, which must write exactly `data` overall.
+    pub fn write(mut self, data: &[u8]) -> Self {
+        self.actions.push_back(Action::Write(data.to_vec()));
+        self
+    }
+
+    /// Scripts a write failing with `error`.
+    pub fn write_error(mut self, error: io::Error) -> Self {
+        self.actions.push_back(Action::WriteError(error));
+        self
+    }
+
+    /// Builds a [`MockStream`] replaying the script.
+    pub fn build(self) -> MockStream {
+        MockStream {
+            script: Script(self.actions),
+        }
+    }
+
+    /// Builds a [`MockReader`] replaying the script.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the script contains writes.
+    pub fn build_reader(self) -> MockReader {
+        assert!(
+            self.actions
+                .iter()
+                .all(|action| matches!(action, Action::Read(_) | Action::ReadError(_))),
+            "the script of a MockReader can only contain reads"
+        );
+        MockReader {
+            script: Script(self.actions),
+        }
+    }
+
+    /// Builds a [`MockWriter`] replaying the script.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the script contains reads.
+    pub fn build_writer(self) -> MockWriter {
+        assert!(
+            self.actions
+                .iter()
+                .all(|action| matches!(action, Action::Write(_) | Action::WriteError(_))),
+            "the script of a MockWriter can only contain writes"
+        );
+        MockWriter {
+            script: Script(self.actions),
+        }
+    }
+}
+
+/// The remaining actions of the script of a mock.
+#[derive(Debug)]
+struct Script(VecDeque<Action>);
+
+impl Script {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self.0.front_mut() {
+            // the end of the script is the end of the stream
+            None => Ok(0),
+            Some(Action::Read(data)) => {
+                let n = data.len().min(buf.len());
+                buf[..n].copy_from_slice(&data[..n]);
+                data.drain(..n);
+                if data.is_empty() {
+                    self.0.pop_front();
+                }
+                Ok(n)
+            }
+            Some(Action::ReadError(_)) => match self.0.pop_front() {
+                Some(Action::ReadError(err)) => Err(err),
+                _ => unreachable!("the front action is a read error"),
+            },
+            Some(action) => panic!("unexpected read: the next scripted action is {action:?}"),
+        }
+    }
+
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self.0.front_mut() {
+            None => panic!("unexpected write of {buf:?}: the script is over"),
+            Some(Action::Write(expected)) => {
+                let n = expected.len().min(buf.len());
+                assert_eq!(
+                    &buf[..n],
+                    &expected[..n],
+                    "the written data doesn't match the script"
+                );
+                expected.drain(..n);
+                if expected.is_empty() {
+                    self.0.pop_front();
+                }
+                Ok(n)
+            }
+            Some(Action::WriteError(_)) => match self.0.pop_front() {
+                Some(Action::WriteError(err)) => Err(err),
+                _ => unreachable!("the front action is a write error"),
+            },
+            Some(action) => {
+                panic!("unexpected write of {buf:?}: the next scripted action is {action:?}")
+            }
+        }
+    }
+}
+
+impl Drop for Script {
+    fn drop(&mut self) {
+        // don't panic while panicking, e.g. because of a failed assertion of the test
+        if !std::thread::panicking() && !self.0.is_empty() {
+            panic!(
+                "the mock was dropped before performing the remaining actions of the script: {:?}",
+                self.0
+            );
+        }
+    }
+}
+
+/// A scripted stream implementing [`Read`] and [`Write`].
+///
+/// Reads and writes must be performed in the order of the script.
+#[derive(Debug)]
+pub struct MockStream {
+    script: Script,
+}
+
+impl MockStream {
+    /// Returns a new [`Builder`] to script the stream.
+    pub fn builder() -> Builder {
+        Builder::new()
+    }
+}
+
+impl Read for MockStream {
+    async fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.script.read(buf)
+    }
+}
+
+impl Write for MockStream {
+    async fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.script.write(buf)
+    }
+
+    async fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A scripted reader implementing [`Read`].
+#[derive(Debug)]
+pub struct MockReader {
+    script: Script,
+}
+
+impl MockReader {
+    /// Returns a new [`Builder`] to script the reader.
+    pub fn builder() -> Builder {
+        Builder::new()
+    }
+}
+
+impl Read for MockReader {
+    async fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.script.read(buf)
+    }
+}
+
+/// A scripted writer implementing [`Write`].
+#[derive(Debug)]
+pub struct MockWriter {
+    script: Script,
+}
+
+impl MockWriter {
+    /// Returns a new [`Builder`] to script the writer.
+    pub fn builder() -> Builder {
+        Builder::new()
+    }
+}
+
+impl Write for MockWriter {
+    async fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.script.write(buf)
+    }
+
+    async fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::SyncRuntime;
+
+    #[test]
+    fn test_should_replay_script() {
+        let mut stream = MockStream::builder().write(b"hello").read(b"world").build();
+
+        SyncRuntime::block_on(stream.write_all(b"hello")).expect("failed to write");
+        let data = SyncRuntime::block_on(stream.read_to_string_new()).expect("failed to read");
+        assert_eq!(data, "world");
+    }
+
+    #[test]
+    fn test_should_split_scripted_data() {
+        let mut reader = MockReader::builder().read(b"hello").build_reader();
+
+        let mut buf = [0; 3];
+        assert_eq!(SyncRuntime::block_on(reader.read(&mut buf)).unwrap(), 3);
+        assert_eq!(&buf, b"hel");
+        assert_eq!(SyncRuntime::block_on(reader.read(&mut buf)).unwrap(), 2);
+        assert_eq!(&buf[..2], b"lo");
+        assert_eq!(SyncRuntime::block_on(reader.read(&mut buf)).unwrap(), 0);
+
+        let mut writer = MockWriter::builder().write(b"hello").build_writer();
+        SyncRuntime::block_on(writer.write_all(b"he")).unwrap();
+        SyncRuntime::block_on(writer.write_all(b"llo")).unwrap();
+    }
+
+    #[test]
+    fn test_should_replay_errors() {
+        let mut stream = MockStream::builder()
+            .read_error(io::Error::from(io::ErrorKind::ConnectionReset))
+            .write_error(io::Error::from(io::ErrorKind::BrokenPipe))
+            .build();
+
+        let mut buf = [0; 4];
+        let err = SyncRuntime::block_on(stream.read(&mut buf)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::ConnectionReset);
+        let err = SyncRuntime::block_on(stream.write(b"data")).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+    }
+
+    #[test]
+    #[should_panic(expected = "the written data doesn't match the script")]
+    fn test_should_panic_on_unexpected_data() {
+        let mut writer = MockWriter::builder().write(b"hello").build_writer();
+        let _ = SyncRuntime::block_on(writer.write(b"world"));
+    }
+
+    #[test]
+    #[should_panic(expected = "unexpected write")]
+    fn test_should_panic_on_unexpected_write() {
+        let mut stream = MockStream::builder().read(b"hello").build();
+        let _ = SyncRuntime::block_on(stream.write(b"hello"));
+    }
+
+    #[test]
+    #[should_panic(expected = "remaining actions of the script")]
+    fn test_should_panic_on_drop_with_remaining_actions() {
+        let _ = MockStream::builder().write(b"hello").build();
+    }
+
+    #[tokio::test]
+    async fn test_should_replay_script_in_async_context() {
+        let mut stream = MockStream::builder().read(b"ping").write(b"pong").build();
+
+        let mut buf = [0; 4];
+        stream.read_exact(&mut buf).await.expect("failed to read");
+        assert_eq!(&buf, b"ping");
+        stream.write_all(b"pong").await.expect("failed to write");
+    }
+}
@@ -30,6 +30,13 @@ pub trait Write {
         }
     }
 
+    /// Returns whether this writer has an efficient [`Write::write_vectored`] implementation.
+    ///
+    /// If it doesn't, the default implementation of [`Write::write_vectored`] writes the buffers one by one.
+    fn is_write_vectored(&self) -> bool {
+        false
+    }
+
     /// Attempts to write an entire buffer into this writer.
     fn write_all(&mut self, mut buf: &[u8]) -> impl Future<Output = std::io::Result<()>> {
         async move {
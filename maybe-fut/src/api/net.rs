@@ -7,10 +7,43 @@
 //! - [Standard Library Networking](https://doc.rust-lang.org/std/net/index.html)
 //! - [Tokio Networking](https://docs.rs/tokio/latest/tokio/net/index.html)
 
+mod lookup;
+pub mod proxy;
+mod ready;
+mod tcp_info;
 mod tcp_listener;
+mod tcp_socket;
+mod tcp_split;
 mod tcp_stream;
+mod tos;
 mod udp_socket;
+#[cfg(unix)]
+mod unix_listener;
+#[cfg(unix)]
+mod unix_stream;
+#[cfg(uring_net)]
+#[cfg_attr(docsrs, doc(cfg(feature = "uring-net")))]
+pub mod uring;
+mod vectored;
+#[cfg(windows)]
+#[cfg_attr(docsrs, doc(cfg(windows)))]
+pub mod windows;
 
-pub use self::tcp_listener::TcpListener;
+/// The TCP keepalive parameters, set with [`TcpStream::set_tcp_keepalive`].
+#[cfg(socket2)]
+#[cfg_attr(docsrs, doc(cfg(feature = "socket2")))]
+pub use socket2::TcpKeepalive;
+
+pub use self::lookup::{LookupHost, ToSocketAddrs, lookup_host};
+pub use self::tcp_info::TcpInfo;
+pub use self::tcp_listener::{Incoming, TcpListener};
+pub use self::tcp_socket::TcpSocket;
+pub use self::tcp_split::{OwnedReadHalf, OwnedWriteHalf, ReadHalf, WriteHalf};
 pub use self::tcp_stream::TcpStream;
 pub use self::udp_socket::UdpSocket;
+#[cfg(unix)]
+#[cfg_attr(docsrs, doc(cfg(unix)))]
+pub use self::unix_listener::UnixListener;
+#[cfg(unix)]
+#[cfg_attr(docsrs, doc(cfg(unix)))]
+pub use self::unix_stream::UnixStream;
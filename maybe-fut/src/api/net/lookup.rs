@@ -0,0 +1,280 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+/// The addresses resolved by [`lookup_host`].
+///
+/// It's an [`Iterator`], which can be turned into a [`crate::stream::Stream`] with [`crate::stream::iter`].
+#[derive(Debug, Clone)]
+pub struct LookupHost(std::vec::IntoIter<SocketAddr>);
+
+impl Iterator for LookupHost {
+    type Item = SocketAddr;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl ExactSizeIterator for LookupHost {}
+
+impl From<Vec<SocketAddr>> for LookupHost {
+    fn from(addrs: Vec<SocketAddr>) -> Self {
+        Self(addrs.into_iter())
+    }
+}
+
+/// Resolves `host` to the socket addresses it points to.
+///
+/// `host` must contain a port as well, such as `example.com:80` or `127.0.0.1:8080`.
+///
+/// The resolution blocks the thread with [`std::net::ToSocketAddrs`] in sync contexts, while it runs on the blocking
+/// pool of the runtime with tokio and async-std.
+pub async fn lookup_host(host: &str) -> std::io::Result<LookupHost> {
+    #[cfg(async_std)]
+    if crate::context::is_async_std_context() {
+        return async_std::net::ToSocketAddrs::to_socket_addrs(host)
+            .await
+            .map(LookupHost);
+    }
+    #[cfg(tokio_net)]
+    if crate::is_tokio_context() {
+        return tokio::net::lookup_host(host)
+            .await
+            .map(|addrs| LookupHost::from(addrs.collect::<Vec<_>>()));
+    }
+
+    std::net::ToSocketAddrs::to_socket_addrs(host).map(LookupHost)
+}
+
+/// A value which can be resolved to one or more socket addresses, such as a [`SocketAddr`], an `(ip, port)` pair
+/// or a `"host:port"` string.
+///
+/// It is the maybe-fut counterpart of [`std::net::ToSocketAddrs`]: the host names are resolved with [`lookup_host`],
+/// so that the resolution doesn't block the thread in async contexts.
+pub trait ToSocketAddrs {
+    /// Resolves the value to the socket addresses it points to.
+    fn to_socket_addrs(&self) -> impl Future<Output = std::io::Result<LookupHost>>;
+}
+
+impl<T> ToSocketAddrs for &T
+where
+    T: ToSocketAddrs + ?Sized,
+{
+    fn to_socket_addrs(&self) -> impl Future<Output = std::io::Result<LookupHost>> {
+        (**self).to_socket_addrs()
+    }
+}
+
+impl ToSocketAddrs for SocketAddr {
+    async fn to_socket_addrs(&self) -> std::io::Result<LookupHost> {
+        Ok(LookupHost::from(vec![*self]))
+    }
+}
+
+impl ToSocketAddrs for SocketAddrV4 {
+    async fn to_socket_addrs(&self) -> std::io::Result<LookupHost> {
+        SocketAddr::V4(*self).to_socket_addrs().await
+    }
+}
+
+impl ToSocketAddrs for SocketAddrV6 {
+    async fn to_socket_addrs(&self) -> std::io::Result<LookupHost> {
+        SocketAddr::V6(*self).to_socket_addrs().await
+    }
+}
+
+impl ToSocketAddrs for (IpAddr, u16) {
+    async fn to_socket_addrs(&self) -> std::io::Result<LookupHost> {
+        SocketAddr::from(*self).to_socket_addrs().await
+    }
+}
+
+impl ToSocketAddrs for (Ipv4Addr, u16) {
+    async fn to_socket_addrs(&self) -> std::io::Result<LookupHost> {
+        SocketAddr::from(*self).to_socket_addrs().await
+    }
+}
+
+impl ToSocketAddrs for (Ipv6Addr, u16) {
+    async fn to_socket_addrs(&self) -> std::io::Result<LookupHost> {
+        SocketAddr::from(*self).to_socket_addrs().await
+    }
+}
+
+impl ToSocketAddrs for (&str, u16) {
+    async fn to_socket_addrs(&self) -> std::io::Result<LookupHost> {
+        let (host, port) = *self;
+        // IP addresses don't need to be resolved
+        if let Ok(ip) = host.parse::<IpAddr>() {
+            return (ip, port).to_socket_addrs().await;
+        }
+
+        lookup_host(&format!("{host}:{port}")).await
+    }
+}
+
+impl ToSocketAddrs for (String, u16) {
+    async fn to_socket_addrs(&self) -> std::io::Result<LookupHost> {
+        (self.0.as_str(), self.1).to_socket_addrs().await
+    }
+}
+
+impl ToSocketAddrs for str {
+    async fn to_socket_addrs(&self) -> std::io::Result<LookupHost> {
+        // socket addresses don't need to be resolved
+        if let Ok(addr) = self.parse::<SocketAddr>() {
+            return addr.to_socket_addrs().await;
+        }
+
+        lookup_host(self).await
+    }
+}
+
+impl ToSocketAddrs for String {
+    async fn to_socket_addrs(&self) -> std::io::Result<LookupHost> {
+        self.as_str().to_socket_addrs().await
+    }
+}
+
+impl ToSocketAddrs for [SocketAddr] {
+    async fn to_socket_addrs(&self) -> std::io::Result<LookupHost> {
+        Ok(LookupHost::from(self.to_vec()))
+    }
+}
+
+/// Resolves `addr` and calls `f` with each of its addresses, until one succeeds.
+///
+/// Returns the error of the last address if all of them fail.
+pub(crate) async fn each_addr<A, F, Fut, T>(addr: A, mut f: F) -> std::io::Result<T>
+where
+    A: ToSocketAddrs,
+    F: FnMut(SocketAddr) -> Fut,
+    Fut: Future<Output = std::io::Result<T>>,
+{
+    let mut last_err = None;
+    for addr in addr.to_socket_addrs().await? {
+        match f(addr).await {
+            Ok(value) => return Ok(value),
+            Err(err) => last_err = Some(err),
+        }
+    }
+
+    Err(last_err.unwrap_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "could not resolve to any addresses",
+        )
+    }))
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::SyncRuntime;
+
+    #[test]
+    fn test_should_lookup_host_sync() {
+        let addrs: Vec<_> = SyncRuntime::block_on(lookup_host("127.0.0.1:8080"))
+            .expect("failed to lookup host")
+            .collect();
+        assert_eq!(addrs, vec!["127.0.0.1:8080".parse().unwrap()]);
+
+        let mut addrs =
+            SyncRuntime::block_on(lookup_host("localhost:80")).expect("failed to lookup host");
+        assert_ne!(addrs.len(), 0);
+        assert!(addrs.all(|addr| addr.ip().is_loopback() && addr.port() == 80));
+    }
+
+    #[test]
+    fn test_should_fail_lookup_without_port() {
+        assert!(SyncRuntime::block_on(lookup_host("localhost")).is_err());
+    }
+
+    #[cfg(tokio_net)]
+    #[tokio::test]
+    async fn test_should_lookup_host_tokio() {
+        use crate::stream::Stream as _;
+
+        let addrs: Vec<_> = crate::stream::iter(
+            lookup_host("localhost:443")
+                .await
+                .expect("failed to lookup host"),
+        )
+        .collect()
+        .await;
+        assert!(!addrs.is_empty());
+        assert!(
+            addrs
+                .iter()
+                .all(|addr| addr.ip().is_loopback() && addr.port() == 443)
+        );
+    }
+
+    #[test]
+    fn test_should_resolve_socket_addrs() {
+        let addr: SocketAddr = "127.0.0.1:8080".parse().unwrap();
+        let resolve = |addrs: std::io::Result<LookupHost>| -> Vec<SocketAddr> {
+            addrs.expect("failed to resolve").collect()
+        };
+
+        assert_eq!(
+            resolve(SyncRuntime::block_on(addr.to_socket_addrs())),
+            vec![addr]
+        );
+        assert_eq!(
+            resolve(SyncRuntime::block_on("127.0.0.1:8080".to_socket_addrs())),
+            vec![addr]
+        );
+        assert_eq!(
+            resolve(SyncRuntime::block_on(("127.0.0.1", 8080).to_socket_addrs())),
+            vec![addr]
+        );
+        assert_eq!(
+            resolve(SyncRuntime::block_on(
+                (Ipv4Addr::LOCALHOST, 8080).to_socket_addrs()
+            )),
+            vec![addr]
+        );
+        assert_eq!(
+            resolve(SyncRuntime::block_on([addr, addr][..].to_socket_addrs())),
+            vec![addr, addr]
+        );
+        assert!(
+            resolve(SyncRuntime::block_on(
+                (String::from("localhost"), 8080).to_socket_addrs()
+            ))
+            .iter()
+            .all(|addr| addr.ip().is_loopback())
+        );
+    }
+
+    #[test]
+    fn test_should_try_each_addr() {
+        let addrs = [
+            "127.0.0.1:1".parse().unwrap(),
+            "127.0.0.1:2".parse().unwrap(),
+        ];
+        let port = SyncRuntime::block_on(each_addr(&addrs[..], |addr| async move {
+            if addr.port() == 1 {
+                Err(std::io::Error::other("refused"))
+            } else {
+                Ok(addr.port())
+            }
+        }))
+        .expect("no address succeeded");
+        assert_eq!(port, 2);
+
+        let err = SyncRuntime::block_on(each_addr(
+            &[][..],
+            |addr: SocketAddr| async move { Ok(addr) },
+        ))
+        .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    }
+}
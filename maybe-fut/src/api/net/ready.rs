@@ -0,0 +1,86 @@
+use std::mem::MaybeUninit;
+
+use socket2::SockRef;
+
+/// Blocks the current thread until `socket` is readable, with `poll`.
+#[cfg(unix)]
+pub fn readable(socket: &impl std::os::fd::AsFd) -> std::io::Result<()> {
+    wait(socket.as_fd(), libc::POLLIN)
+}
+
+/// Blocks the current thread until `socket` is writable, with `poll`.
+#[cfg(unix)]
+pub fn writable(socket: &impl std::os::fd::AsFd) -> std::io::Result<()> {
+    wait(socket.as_fd(), libc::POLLOUT)
+}
+
+#[cfg(unix)]
+fn wait(socket: std::os::fd::BorrowedFd<'_>, events: libc::c_short) -> std::io::Result<()> {
+    use std::os::fd::AsRawFd as _;
+
+    let mut fd = libc::pollfd {
+        fd: socket.as_raw_fd(),
+        events,
+        revents: 0,
+    };
+    loop {
+        // SAFETY: `fd` is a single valid `pollfd`, and the descriptor is borrowed for the whole call
+        if unsafe { libc::poll(&mut fd, 1, -1) } >= 0 {
+            // errors and hang-ups are reported by the next operation on the socket
+            return Ok(());
+        }
+        let err = std::io::Error::last_os_error();
+        if err.kind() != std::io::ErrorKind::Interrupted {
+            return Err(err);
+        }
+    }
+}
+
+/// Waiting for readiness is only supported on Unix; fails with [`std::io::ErrorKind::Unsupported`] on the other
+/// platforms.
+#[cfg(not(unix))]
+pub fn readable<T>(_socket: &T) -> std::io::Result<()> {
+    Err(unsupported())
+}
+
+/// Waiting for readiness is only supported on Unix; fails with [`std::io::ErrorKind::Unsupported`] on the other
+/// platforms.
+#[cfg(not(unix))]
+pub fn writable<T>(_socket: &T) -> std::io::Result<()> {
+    Err(unsupported())
+}
+
+#[cfg(not(unix))]
+fn unsupported() -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "waiting for the readiness of std sockets is only supported on Unix",
+    )
+}
+
+/// Receives data from `socket` without blocking, failing with [`std::io::ErrorKind::WouldBlock`] if there is none.
+///
+/// On the platforms other than Unix, it only doesn't block if the socket is in non-blocking mode.
+pub fn try_recv(socket: SockRef<'_>, buf: &mut [u8]) -> std::io::Result<usize> {
+    // SAFETY: socket2 never writes uninitialised bytes into the buffer passed to `recv`
+    let buf = unsafe { &mut *(buf as *mut [u8] as *mut [MaybeUninit<u8>]) };
+    socket.recv_with_flags(buf, DONT_WAIT)
+}
+
+/// Sends data on `socket` without blocking, failing with [`std::io::ErrorKind::WouldBlock`] if it isn't writable.
+///
+/// On the platforms other than Unix, it only doesn't block if the socket is in non-blocking mode.
+pub fn try_send(socket: SockRef<'_>, buf: &[u8]) -> std::io::Result<usize> {
+    socket.send_with_flags(buf, DONT_WAIT | NO_SIGNAL)
+}
+
+#[cfg(unix)]
+const DONT_WAIT: std::ffi::c_int = libc::MSG_DONTWAIT;
+#[cfg(not(unix))]
+const DONT_WAIT: std::ffi::c_int = 0;
+
+/// Like std, report a closed connection with `EPIPE` rather than raising `SIGPIPE`.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+const NO_SIGNAL: std::ffi::c_int = libc::MSG_NOSIGNAL;
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+const NO_SIGNAL: std::ffi::c_int = 0;
@@ -0,0 +1,58 @@
+use std::time::Duration;
+
+/// Statistics of a TCP connection, as reported by the `TCP_INFO` socket option.
+///
+/// Returned by [`super::TcpStream::info`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TcpInfo {
+    /// The smoothed round-trip time estimated by the kernel.
+    pub rtt: Duration,
+    /// The variance of the round-trip time.
+    pub rtt_var: Duration,
+    /// The size of the congestion window, in segments.
+    pub congestion_window: u32,
+    /// The number of retransmissions of the segment currently awaiting acknowledgement.
+    pub retransmits: u32,
+    /// The total number of segments retransmitted over the lifetime of the connection.
+    pub total_retransmits: u32,
+}
+
+/// Reads the `TCP_INFO` socket option of `stream`.
+#[cfg(target_os = "linux")]
+pub fn tcp_info(stream: &super::TcpStream) -> std::io::Result<TcpInfo> {
+    use std::os::fd::AsRawFd as _;
+
+    // SAFETY: `tcp_info` is a plain C struct, for which all zeroes is a valid value
+    let mut info: libc::tcp_info = unsafe { std::mem::zeroed() };
+    let mut len = std::mem::size_of::<libc::tcp_info>() as libc::socklen_t;
+    // SAFETY: `info` is valid for writes of `len` bytes, and the kernel writes at most `len` bytes
+    let res = unsafe {
+        libc::getsockopt(
+            stream.as_raw_fd(),
+            libc::IPPROTO_TCP,
+            libc::TCP_INFO,
+            (&mut info as *mut libc::tcp_info).cast(),
+            &mut len,
+        )
+    };
+    if res != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    Ok(TcpInfo {
+        rtt: Duration::from_micros(info.tcpi_rtt.into()),
+        rtt_var: Duration::from_micros(info.tcpi_rttvar.into()),
+        congestion_window: info.tcpi_snd_cwnd,
+        retransmits: info.tcpi_retransmits.into(),
+        total_retransmits: info.tcpi_total_retrans,
+    })
+}
+
+/// `TCP_INFO` is only read on Linux; fails with [`std::io::ErrorKind::Unsupported`] on the other platforms.
+#[cfg(not(target_os = "linux"))]
+pub fn tcp_info(_stream: &super::TcpStream) -> std::io::Result<TcpInfo> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "TCP_INFO is only supported on Linux",
+    ))
+}
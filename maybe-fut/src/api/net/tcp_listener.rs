@@ -1,4 +1,6 @@
+use std::future::Future;
 use std::net::SocketAddr;
+use std::pin::Pin;
 
 use crate::{maybe_fut_constructor_result, maybe_fut_method_sync};
 
@@ -21,6 +23,9 @@ enum TcpListenerInner {
     #[cfg(tokio_net)]
     #[cfg_attr(docsrs, doc(cfg(feature = "tokio-net")))]
     Tokio(tokio::net::TcpListener),
+    #[cfg(async_std)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "async-std")))]
+    AsyncStd(async_std::net::TcpListener),
 }
 
 impl From<std::net::TcpListener> for TcpListener {
@@ -37,16 +42,93 @@ impl From<tokio::net::TcpListener> for TcpListener {
     }
 }
 
+#[cfg(async_std)]
+#[cfg_attr(docsrs, doc(cfg(feature = "async-std")))]
+impl From<async_std::net::TcpListener> for TcpListener {
+    fn from(listener: async_std::net::TcpListener) -> Self {
+        Self(TcpListenerInner::AsyncStd(listener))
+    }
+}
+
 impl TcpListener {
-    maybe_fut_constructor_result!(
-        /// Creates a new [`TcpListener`] bound to the specified address.
-        ///
-        /// The returned listener is ready for accepting connections.
-        bind(addr: SocketAddr) -> std::io::Result<Self>,
-        std::net::TcpListener::bind,
-        tokio::net::TcpListener::bind,
-        tokio_net
-    );
+    /// Creates a new [`TcpListener`] bound to the specified address.
+    ///
+    /// The returned listener is ready for accepting connections.
+    ///
+    /// If `addr` resolves to several addresses, each of them is tried in order until one succeeds; otherwise,
+    /// the error of the last attempt is returned.
+    pub async fn bind(addr: impl super::ToSocketAddrs) -> std::io::Result<Self> {
+        super::lookup::each_addr(addr, Self::bind_addr).await
+    }
+
+    /// Creates a new [`TcpListener`] bound to a single address.
+    async fn bind_addr(addr: SocketAddr) -> std::io::Result<Self> {
+        maybe_fut_constructor_result!(
+            @body
+            (addr),
+            std::net::TcpListener::bind,
+            tokio::net::TcpListener::bind,
+            tokio_net,
+            async_std::net::TcpListener::bind,
+            async_std
+        )
+    }
+
+    /// Creates a new TCP listener bound to the specified address with the std implementation, regardless of the context.
+    ///
+    /// Unlike [`Self::bind`], it never checks the context, which is useful to create a listener in an async context to use it on another thread.
+    pub fn bind_std(addr: SocketAddr) -> std::io::Result<Self> {
+        std::net::TcpListener::bind(addr).map(Self::from)
+    }
+
+    /// Creates a new TCP listener bound to the specified address with the tokio implementation, regardless of the context.
+    ///
+    /// Unlike [`Self::bind`], it never checks the context. It must be called from within a tokio runtime.
+    #[cfg(tokio_net)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio-net")))]
+    pub async fn bind_tokio(addr: SocketAddr) -> std::io::Result<Self> {
+        tokio::net::TcpListener::bind(addr).await.map(Self::from)
+    }
+
+    /// Adopts a listener created elsewhere, wrapping it into the variant of the current context.
+    ///
+    /// The listener is switched to the mode its backend expects: non-blocking for the async runtimes, and blocking
+    /// for std, so a listener released by an async runtime can be used in a sync context.
+    /// It must be called from within the runtime of the current context, to register the listener with its reactor.
+    pub fn from_std(listener: std::net::TcpListener) -> std::io::Result<Self> {
+        #[cfg(async_std)]
+        if crate::context::is_async_std_context() {
+            return Ok(async_std::net::TcpListener::from(listener).into());
+        }
+        #[cfg(tokio_net)]
+        if crate::context::is_tokio_context() {
+            listener.set_nonblocking(true)?;
+            return tokio::net::TcpListener::from_std(listener).map(Self::from);
+        }
+
+        listener.set_nonblocking(false)?;
+        Ok(listener.into())
+    }
+
+    /// Converts the [`TcpListener`] inner instance to a [`std::net::TcpListener`], to hand it to a library requiring
+    /// a std listener.
+    ///
+    /// The listeners of the async runtimes are switched back to blocking mode; a std listener is returned as it is.
+    pub fn into_std(self) -> std::io::Result<std::net::TcpListener> {
+        match self.0 {
+            TcpListenerInner::Std(listener) => Ok(listener),
+            #[cfg(tokio_net)]
+            TcpListenerInner::Tokio(listener) => listener.into_std().and_then(into_blocking),
+            #[cfg(all(async_std, unix))]
+            TcpListenerInner::AsyncStd(listener) => into_blocking(std::net::TcpListener::from(
+                std::os::fd::OwnedFd::from(listener),
+            )),
+            #[cfg(all(async_std, windows))]
+            TcpListenerInner::AsyncStd(listener) => into_blocking(std::net::TcpListener::from(
+                std::os::windows::io::OwnedSocket::from(listener),
+            )),
+        }
+    }
 
     /// Accepts a new incoming connection.
     ///
@@ -62,32 +144,95 @@ impl TcpListener {
                 let (stream, addr) = listener.accept().await?;
                 Ok((crate::net::TcpStream::from(stream), addr))
             }
+            #[cfg(async_std)]
+            TcpListenerInner::AsyncStd(listener) => {
+                let (stream, addr) = listener.accept().await?;
+                Ok((crate::net::TcpStream::from(stream), addr))
+            }
         }
     }
 
+    /// Returns an iterator over the connections being received on this listener.
+    ///
+    /// The returned [`Incoming`] never returns `None`; it is equivalent to calling [`TcpListener::accept`] in a loop.
+    pub fn incoming(&self) -> Incoming<'_> {
+        Incoming {
+            listener: self,
+            pending: None,
+        }
+    }
+
+    /// Converts the listener into a [`socket2::Socket`], to set the options not exposed by maybe-fut.
+    ///
+    /// The [`socket2::Socket`] can be converted back into a listener of the current context with [`From`].
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases as the conversion into the owned descriptor of the listener.
+    #[cfg(socket2)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "socket2")))]
+    pub fn into_socket2(self) -> socket2::Socket {
+        self.into()
+    }
+
     maybe_fut_method_sync!(
         /// Returns the local address of this listener.
         local_addr() -> std::io::Result<SocketAddr>,
         TcpListenerInner::Std,
         TcpListenerInner::Tokio,
-        tokio_net
+        tokio_net,
+        TcpListenerInner::AsyncStd,
+        async_std
     );
 
-    maybe_fut_method_sync!(
-        /// Sets the value for the `IP_TTL` option on this socket.
-        set_ttl(ttl: u32) -> std::io::Result<()>,
-        TcpListenerInner::Std,
-        TcpListenerInner::Tokio,
-        tokio_net
-    );
+    /// Moves the listener into or out of non-blocking mode.
+    ///
+    /// In non-blocking mode, [`Self::accept`] returns a [`std::io::ErrorKind::WouldBlock`] error instead of waiting
+    /// for a connection. It only works with the std `TcpListener`, since the async runtimes require the listener to
+    /// be non-blocking.
+    pub fn set_nonblocking(&self, nonblocking: bool) -> std::io::Result<()> {
+        match &self.0 {
+            TcpListenerInner::Std(listener) => listener.set_nonblocking(nonblocking),
+            #[cfg(tokio_net)]
+            TcpListenerInner::Tokio(_) => Err(std::io::Error::other(
+                "tokio TcpListener does not support set_nonblocking",
+            )),
+            #[cfg(async_std)]
+            TcpListenerInner::AsyncStd(_) => Err(std::io::Error::other(
+                "async-std TcpListener does not support set_nonblocking",
+            )),
+        }
+    }
 
-    maybe_fut_method_sync!(
-        /// Gets the value for the `IP_TTL` option on this socket.
-        ttl() -> std::io::Result<u32>,
-        TcpListenerInner::Std,
-        TcpListenerInner::Tokio,
-        tokio_net
-    );
+    /// Sets the value for the `IP_TTL` option on this socket.
+    ///
+    /// It doesn't work with async-std's `TcpListener` because it doesn't support setting the TTL.
+    pub fn set_ttl(&self, ttl: u32) -> std::io::Result<()> {
+        match &self.0 {
+            TcpListenerInner::Std(listener) => listener.set_ttl(ttl),
+            #[cfg(tokio_net)]
+            TcpListenerInner::Tokio(listener) => listener.set_ttl(ttl),
+            #[cfg(async_std)]
+            TcpListenerInner::AsyncStd(_) => Err(std::io::Error::other(
+                "async-std TcpListener does not support set_ttl",
+            )),
+        }
+    }
+
+    /// Gets the value for the `IP_TTL` option on this socket.
+    ///
+    /// It doesn't work with async-std's `TcpListener` because it doesn't support getting the TTL.
+    pub fn ttl(&self) -> std::io::Result<u32> {
+        match &self.0 {
+            TcpListenerInner::Std(listener) => listener.ttl(),
+            #[cfg(tokio_net)]
+            TcpListenerInner::Tokio(listener) => listener.ttl(),
+            #[cfg(async_std)]
+            TcpListenerInner::AsyncStd(_) => Err(std::io::Error::other(
+                "async-std TcpListener does not support ttl",
+            )),
+        }
+    }
 }
 
 #[cfg(unix)]
@@ -97,6 +242,8 @@ impl std::os::fd::AsFd for TcpListener {
             TcpListenerInner::Std(file) => file.as_fd(),
             #[cfg(tokio_net)]
             TcpListenerInner::Tokio(file) => file.as_fd(),
+            #[cfg(async_std)]
+            TcpListenerInner::AsyncStd(file) => file.as_fd(),
         }
     }
 }
@@ -108,6 +255,8 @@ impl std::os::fd::AsRawFd for TcpListener {
             TcpListenerInner::Std(file) => file.as_raw_fd(),
             #[cfg(tokio_net)]
             TcpListenerInner::Tokio(file) => file.as_raw_fd(),
+            #[cfg(async_std)]
+            TcpListenerInner::AsyncStd(file) => file.as_raw_fd(),
         }
     }
 }
@@ -119,6 +268,8 @@ impl std::os::windows::io::AsSocket for TcpListener {
             TcpListenerInner::Std(file) => file.as_socket(),
             #[cfg(tokio_net)]
             TcpListenerInner::Tokio(file) => file.as_socket(),
+            #[cfg(async_std)]
+            TcpListenerInner::AsyncStd(file) => file.as_socket(),
         }
     }
 }
@@ -130,10 +281,217 @@ impl std::os::windows::io::AsRawSocket for TcpListener {
             TcpListenerInner::Std(file) => file.as_raw_socket(),
             #[cfg(tokio_net)]
             TcpListenerInner::Tokio(file) => file.as_raw_socket(),
+            #[cfg(async_std)]
+            TcpListenerInner::AsyncStd(file) => file.as_raw_socket(),
+        }
+    }
+}
+
+/// Wraps a std listener into the variant of the current context.
+///
+/// # Panics
+///
+/// Panics if the listener can't be set up for the backend of the current context, e.g. switched to the right
+/// blocking mode or registered with the tokio reactor.
+fn from_std_in_context(listener: std::net::TcpListener) -> TcpListener {
+    TcpListener::from_std(listener)
+        .expect("failed to set up the listener for the backend of the current context")
+}
+
+/// Switches a listener released by an async runtime back to blocking mode.
+#[cfg(any(tokio_net, async_std))]
+fn into_blocking(listener: std::net::TcpListener) -> std::io::Result<std::net::TcpListener> {
+    listener.set_nonblocking(false)?;
+
+    Ok(listener)
+}
+
+#[cfg(unix)]
+impl From<std::os::fd::OwnedFd> for TcpListener {
+    /// Takes ownership of the file descriptor, wrapping it into the variant of the current context.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the listener can't be set up for the backend of the current context.
+    fn from(fd: std::os::fd::OwnedFd) -> Self {
+        from_std_in_context(std::net::TcpListener::from(fd))
+    }
+}
+
+#[cfg(windows)]
+impl From<std::os::windows::io::OwnedSocket> for TcpListener {
+    /// Takes ownership of the socket, wrapping it into the variant of the current context.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the listener can't be set up for the backend of the current context.
+    fn from(socket: std::os::windows::io::OwnedSocket) -> Self {
+        from_std_in_context(std::net::TcpListener::from(socket))
+    }
+}
+
+#[cfg(unix)]
+impl From<TcpListener> for std::os::fd::OwnedFd {
+    /// Releases the file descriptor of the listener.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the listener can't be deregistered from the tokio reactor.
+    fn from(listener: TcpListener) -> Self {
+        match listener.0 {
+            TcpListenerInner::Std(listener) => listener.into(),
+            #[cfg(tokio_net)]
+            TcpListenerInner::Tokio(listener) => listener
+                .into_std()
+                .expect("failed to deregister the listener from the tokio reactor")
+                .into(),
+            #[cfg(async_std)]
+            TcpListenerInner::AsyncStd(listener) => listener.into(),
+        }
+    }
+}
+
+#[cfg(windows)]
+impl From<TcpListener> for std::os::windows::io::OwnedSocket {
+    /// Releases the socket.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the listener can't be deregistered from the tokio reactor.
+    fn from(listener: TcpListener) -> Self {
+        match listener.0 {
+            TcpListenerInner::Std(listener) => listener.into(),
+            #[cfg(tokio_net)]
+            TcpListenerInner::Tokio(listener) => listener
+                .into_std()
+                .expect("failed to deregister the listener from the tokio reactor")
+                .into(),
+            #[cfg(async_std)]
+            TcpListenerInner::AsyncStd(listener) => listener.into(),
         }
     }
 }
 
+#[cfg(unix)]
+impl std::os::fd::IntoRawFd for TcpListener {
+    fn into_raw_fd(self) -> std::os::fd::RawFd {
+        std::os::fd::OwnedFd::from(self).into_raw_fd()
+    }
+}
+
+#[cfg(windows)]
+impl std::os::windows::io::IntoRawSocket for TcpListener {
+    fn into_raw_socket(self) -> std::os::windows::io::RawSocket {
+        std::os::windows::io::OwnedSocket::from(self).into_raw_socket()
+    }
+}
+
+#[cfg(socket2)]
+#[cfg_attr(docsrs, doc(cfg(feature = "socket2")))]
+impl From<socket2::Socket> for TcpListener {
+    /// Takes ownership of a socket configured with socket2, wrapping it into the variant of the current context.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the listener can't be set up for the backend of the current context.
+    fn from(socket: socket2::Socket) -> Self {
+        from_std_in_context(std::net::TcpListener::from(socket))
+    }
+}
+
+#[cfg(socket2)]
+#[cfg_attr(docsrs, doc(cfg(feature = "socket2")))]
+impl From<TcpListener> for socket2::Socket {
+    /// Releases the socket, to configure it with socket2.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases as the conversion into the owned descriptor of the listener.
+    fn from(listener: TcpListener) -> Self {
+        #[cfg(unix)]
+        let socket = std::os::fd::OwnedFd::from(listener);
+        #[cfg(windows)]
+        let socket = std::os::windows::io::OwnedSocket::from(listener);
+
+        socket2::Socket::from(socket)
+    }
+}
+
+/// A future accepting the next connection of a [`TcpListener`].
+type PendingAccept<'a> =
+    Pin<Box<dyn Future<Output = std::io::Result<(crate::net::TcpStream, SocketAddr)>> + Send + 'a>>;
+
+/// The connections received on a [`TcpListener`], returned by [`TcpListener::incoming`].
+///
+/// It implements [`crate::stream::Stream`] and, with the `stream` feature, `futures_core::Stream` as well.
+/// In sync contexts, it can be iterated with a `for` loop, through its [`IntoIterator`] implementation.
+pub struct Incoming<'a> {
+    listener: &'a TcpListener,
+    /// The connection being accepted by `Stream::poll_next`.
+    pending: Option<PendingAccept<'a>>,
+}
+
+impl std::fmt::Debug for Incoming<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Incoming")
+            .field("listener", &self.listener)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Incoming<'_> {
+    /// Accepts the next connection.
+    ///
+    /// This method never returns `None`.
+    pub async fn next(&mut self) -> Option<std::io::Result<crate::net::TcpStream>> {
+        let accepted = match self.pending.take() {
+            Some(pending) => pending.await,
+            None => self.listener.accept().await,
+        };
+        Some(accepted.map(|(stream, _addr)| stream))
+    }
+}
+
+impl crate::stream::Stream for Incoming<'_> {
+    type Item = std::io::Result<crate::net::TcpStream>;
+
+    async fn next(&mut self) -> Option<Self::Item> {
+        Incoming::next(self).await
+    }
+}
+
+impl<'a> IntoIterator for Incoming<'a> {
+    type Item = std::io::Result<crate::net::TcpStream>;
+    type IntoIter = crate::stream::BlockingIter<Incoming<'a>>;
+
+    /// Returns a blocking iterator over the connections, to be used in sync contexts.
+    ///
+    /// See [`crate::stream::BlockingIter`] for more details.
+    fn into_iter(self) -> Self::IntoIter {
+        crate::stream::Stream::into_blocking_iter(self)
+    }
+}
+
+#[cfg(stream)]
+#[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
+impl futures_core::Stream for Incoming<'_> {
+    type Item = std::io::Result<crate::net::TcpStream>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        let listener = self.listener;
+        let pending = self
+            .pending
+            .get_or_insert_with(|| Box::pin(listener.accept()));
+        let accepted = std::task::ready!(pending.as_mut().poll(cx));
+        self.pending = None;
+
+        std::task::Poll::Ready(Some(accepted.map(|(stream, _addr)| stream)))
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -205,6 +563,50 @@ mod test {
         assert!(accepted_stream.get_tokio_ref().is_some());
     }
 
+    #[test]
+    #[serial_test::serial]
+    fn test_should_convert_listener_from_and_into_std() {
+        let std_listener = std::net::TcpListener::bind("127.0.0.1:0").expect("failed to bind");
+        std_listener
+            .set_nonblocking(true)
+            .expect("failed to set nonblocking");
+        let listener = TcpListener::from_std(std_listener).expect("failed to adopt listener");
+        assert!(listener.get_std_ref().is_some());
+
+        // adopted in blocking mode, so accept waits for the connection
+        let addr = listener.local_addr().expect("failed to get local addr");
+        let _stream = std::net::TcpStream::connect(addr).expect("failed to connect");
+        assert!(block_on(listener.accept()).is_ok());
+
+        listener
+            .set_nonblocking(true)
+            .expect("failed to set nonblocking");
+        let err = block_on(listener.accept()).expect_err("accept should not block");
+        assert_eq!(err.kind(), std::io::ErrorKind::WouldBlock);
+
+        let std_listener = listener.into_std().expect("failed to convert to std");
+        assert_eq!(
+            std_listener.local_addr().expect("failed to get local addr"),
+            addr
+        );
+    }
+
+    #[cfg(tokio_net)]
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_should_convert_listener_from_and_into_std_tokio() {
+        let std_listener = std::net::TcpListener::bind("127.0.0.1:0").expect("failed to bind");
+        let listener = TcpListener::from_std(std_listener).expect("failed to adopt listener");
+        assert!(listener.get_tokio_ref().is_some());
+        assert!(listener.set_nonblocking(false).is_err());
+        let addr = listener.local_addr().expect("failed to get local addr");
+
+        let std_listener = listener.into_std().expect("failed to convert to std");
+        let _stream = std::net::TcpStream::connect(addr).expect("failed to connect");
+        // switched back to blocking mode, so accept waits for the connection
+        assert!(std_listener.accept().is_ok());
+    }
+
     #[test]
     #[serial_test::serial]
     fn test_should_set_and_get_ttl_from_std() {
@@ -274,4 +676,196 @@ mod test {
         assert_eq!(local_addr.ip(), addr.ip());
         assert!(local_addr.port() > 0);
     }
+
+    #[cfg(async_std)]
+    #[async_std::test]
+    #[serial_test::serial]
+    async fn test_should_accept_from_async_std() {
+        let addr = "127.0.0.1:0"
+            .parse::<SocketAddr>()
+            .expect("Failed to parse address");
+
+        let listener = TcpListener::bind(addr)
+            .await
+            .expect("Failed to bind listener");
+        assert!(matches!(listener.0, TcpListenerInner::AsyncStd(_)));
+        assert!(listener.ttl().is_err());
+
+        let peer_address = listener.local_addr().expect("Failed to get local address");
+
+        // Create a stream to connect to the listener
+        let _stream = async_std::net::TcpStream::connect(peer_address)
+            .await
+            .expect("Failed to connect to listener");
+        let (accepted_stream, _accepted_addr) = listener
+            .accept()
+            .await
+            .expect("Failed to accept connection");
+        assert!(accepted_stream.get_std_ref().is_none());
+    }
+
+    #[cfg(all(socket2, tokio_net))]
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_should_convert_tcp_listener_from_and_into_socket2() {
+        let socket = socket2::Socket::new(socket2::Domain::IPV4, socket2::Type::STREAM, None)
+            .expect("Failed to create socket");
+        socket
+            .set_reuse_address(true)
+            .expect("Failed to set reuse address");
+        socket
+            .bind(&"127.0.0.1:0".parse::<SocketAddr>().unwrap().into())
+            .expect("Failed to bind");
+        socket.listen(16).expect("Failed to listen");
+
+        let listener = TcpListener::from(socket);
+        assert!(matches!(listener.0, TcpListenerInner::Tokio(_)));
+        let peer_address = listener.local_addr().expect("Failed to get local address");
+        let _stream = tokio::net::TcpStream::connect(peer_address)
+            .await
+            .expect("Failed to connect to listener");
+        assert!(listener.accept().await.is_ok());
+
+        let socket = listener.into_socket2();
+        assert!(socket.reuse_address().expect("Failed to get reuse address"));
+    }
+
+    #[test]
+    fn test_incoming_should_be_send() {
+        fn assert_send<T: Send>() {}
+
+        assert_send::<Incoming<'_>>();
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_should_iterate_incoming_in_sync_context() {
+        let listener = block_on(TcpListener::bind("127.0.0.1:0")).expect("Failed to bind");
+        let peer_address = listener.local_addr().expect("Failed to get local address");
+
+        let _first = std::net::TcpStream::connect(peer_address).expect("Failed to connect");
+        let _second = std::net::TcpStream::connect(peer_address).expect("Failed to connect");
+        let mut accepted = 0;
+        for stream in listener.incoming() {
+            assert!(
+                stream
+                    .expect("Failed to accept connection")
+                    .peer_addr()
+                    .is_ok()
+            );
+            accepted += 1;
+            if accepted == 2 {
+                break;
+            }
+        }
+        assert_eq!(accepted, 2);
+    }
+
+    #[cfg(tokio_net)]
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_should_accept_incoming_from_tokio() {
+        let addr = "127.0.0.1:0"
+            .parse::<SocketAddr>()
+            .expect("Failed to parse address");
+
+        let listener = TcpListener::bind(addr)
+            .await
+            .expect("Failed to bind listener");
+        let peer_address = listener.local_addr().expect("Failed to get local address");
+
+        let _stream = tokio::net::TcpStream::connect(peer_address)
+            .await
+            .expect("Failed to connect to listener");
+        let mut incoming = listener.incoming();
+        let accepted = incoming
+            .next()
+            .await
+            .expect("incoming returned none")
+            .expect("Failed to accept connection");
+        assert!(accepted.peer_addr().is_ok());
+    }
+
+    #[cfg(all(stream, tokio_net))]
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_should_stream_incoming_from_tokio() {
+        use futures_util::StreamExt as _;
+
+        let addr = "127.0.0.1:0"
+            .parse::<SocketAddr>()
+            .expect("Failed to parse address");
+
+        let listener = TcpListener::bind(addr)
+            .await
+            .expect("Failed to bind listener");
+        let peer_address = listener.local_addr().expect("Failed to get local address");
+
+        let _first = tokio::net::TcpStream::connect(peer_address)
+            .await
+            .expect("Failed to connect to listener");
+        let _second = tokio::net::TcpStream::connect(peer_address)
+            .await
+            .expect("Failed to connect to listener");
+        let accepted: Vec<_> = listener.incoming().take(2).collect().await;
+        assert_eq!(accepted.len(), 2);
+        assert!(accepted.iter().all(|stream| stream.is_ok()));
+    }
+
+    #[cfg(tokio_net)]
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_should_bind_with_explicit_backend() {
+        let addr = "127.0.0.1:0"
+            .parse::<std::net::SocketAddr>()
+            .expect("Failed to parse address");
+
+        let socket = TcpListener::bind_std(addr).expect("Failed to bind");
+        assert!(matches!(socket.0, TcpListenerInner::Std(_)));
+
+        let socket = TcpListener::bind_tokio(addr).await.expect("Failed to bind");
+        assert!(matches!(socket.0, TcpListenerInner::Tokio(_)));
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_should_bind_and_connect_to_host_names() {
+        let listener = block_on(TcpListener::bind(("127.0.0.1", 0))).expect("Failed to bind");
+        let port = listener
+            .local_addr()
+            .expect("Failed to get local address")
+            .port();
+
+        // `localhost` may resolve to `::1` as well, which is refused before falling back to `127.0.0.1`
+        let stream = block_on(crate::net::TcpStream::connect(format!("localhost:{port}")))
+            .expect("Failed to connect to listener");
+        assert_eq!(
+            stream.peer_addr().expect("Failed to get peer address"),
+            listener.local_addr().expect("Failed to get local address")
+        );
+    }
+
+    #[cfg(tokio_net)]
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_should_bind_and_connect_to_host_names_tokio() {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("Failed to bind");
+        let port = listener
+            .local_addr()
+            .expect("Failed to get local address")
+            .port();
+
+        let stream = crate::net::TcpStream::connect(("localhost", port))
+            .await
+            .expect("Failed to connect to listener");
+        assert_eq!(
+            stream
+                .peer_addr()
+                .expect("Failed to get peer address")
+                .port(),
+            port
+        );
+    }
 }
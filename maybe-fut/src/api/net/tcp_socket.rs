@@ -0,0 +1,347 @@
+use std::net::SocketAddr;
+
+/// A TCP socket that has not yet been converted to a [`super::TcpStream`] or [`super::TcpListener`].
+///
+/// It's used to configure the socket (e.g. `SO_REUSEADDR` and the buffer sizes) and bind it before connecting with
+/// [`TcpSocket::connect`] or listening with [`TcpSocket::listen`].
+///
+/// A [`TcpSocket`] is created by calling [`TcpSocket::new_v4`] or [`TcpSocket::new_v6`].
+#[derive(Debug, Unwrap)]
+#[unwrap_types(
+    std(socket2::Socket),
+    tokio(tokio::net::TcpSocket),
+    tokio_gated("tokio-net")
+)]
+pub struct TcpSocket(TcpSocketInner);
+
+#[derive(Debug)]
+enum TcpSocketInner {
+    Std(socket2::Socket),
+    #[cfg(tokio_net)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio-net")))]
+    Tokio(tokio::net::TcpSocket),
+}
+
+#[cfg(socket2)]
+#[cfg_attr(docsrs, doc(cfg(feature = "socket2")))]
+impl From<socket2::Socket> for TcpSocket {
+    fn from(socket: socket2::Socket) -> Self {
+        Self(TcpSocketInner::Std(socket))
+    }
+}
+
+#[cfg(tokio_net)]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio-net")))]
+impl From<tokio::net::TcpSocket> for TcpSocket {
+    fn from(socket: tokio::net::TcpSocket) -> Self {
+        Self(TcpSocketInner::Tokio(socket))
+    }
+}
+
+impl TcpSocket {
+    /// Creates a new socket configured for IPv4.
+    pub fn new_v4() -> std::io::Result<Self> {
+        #[cfg(tokio_net)]
+        if crate::is_tokio_context() {
+            return tokio::net::TcpSocket::new_v4().map(Self::from);
+        }
+
+        Self::new_std(socket2::Domain::IPV4)
+    }
+
+    /// Creates a new socket configured for IPv6.
+    pub fn new_v6() -> std::io::Result<Self> {
+        #[cfg(tokio_net)]
+        if crate::is_tokio_context() {
+            return tokio::net::TcpSocket::new_v6().map(Self::from);
+        }
+
+        Self::new_std(socket2::Domain::IPV6)
+    }
+
+    fn new_std(domain: socket2::Domain) -> std::io::Result<Self> {
+        socket2::Socket::new(domain, socket2::Type::STREAM, Some(socket2::Protocol::TCP))
+            .map(|socket| Self(TcpSocketInner::Std(socket)))
+    }
+
+    /// Allows the socket to bind to an in-use address, setting the `SO_REUSEADDR` option.
+    pub fn set_reuseaddr(&self, reuseaddr: bool) -> std::io::Result<()> {
+        match &self.0 {
+            TcpSocketInner::Std(socket) => socket.set_reuse_address(reuseaddr),
+            #[cfg(tokio_net)]
+            TcpSocketInner::Tokio(socket) => socket.set_reuseaddr(reuseaddr),
+        }
+    }
+
+    /// Gets the value of the `SO_REUSEADDR` option on the socket.
+    pub fn reuseaddr(&self) -> std::io::Result<bool> {
+        match &self.0 {
+            TcpSocketInner::Std(socket) => socket.reuse_address(),
+            #[cfg(tokio_net)]
+            TcpSocketInner::Tokio(socket) => socket.reuseaddr(),
+        }
+    }
+
+    /// Allows the socket to bind to an in-use port, setting the `SO_REUSEPORT` option.
+    #[cfg(all(unix, not(target_os = "solaris"), not(target_os = "illumos")))]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn set_reuseport(&self, reuseport: bool) -> std::io::Result<()> {
+        match &self.0 {
+            TcpSocketInner::Std(socket) => socket.set_reuse_port(reuseport),
+            #[cfg(tokio_net)]
+            TcpSocketInner::Tokio(socket) => socket.set_reuseport(reuseport),
+        }
+    }
+
+    /// Gets the value of the `SO_REUSEPORT` option on the socket.
+    #[cfg(all(unix, not(target_os = "solaris"), not(target_os = "illumos")))]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn reuseport(&self) -> std::io::Result<bool> {
+        match &self.0 {
+            TcpSocketInner::Std(socket) => socket.reuse_port(),
+            #[cfg(tokio_net)]
+            TcpSocketInner::Tokio(socket) => socket.reuseport(),
+        }
+    }
+
+    /// Sets the size of the send buffer of the socket, setting the `SO_SNDBUF` option.
+    pub fn set_send_buffer_size(&self, size: u32) -> std::io::Result<()> {
+        match &self.0 {
+            TcpSocketInner::Std(socket) => socket.set_send_buffer_size(size as usize),
+            #[cfg(tokio_net)]
+            TcpSocketInner::Tokio(socket) => socket.set_send_buffer_size(size),
+        }
+    }
+
+    /// Gets the value of the `SO_SNDBUF` option on the socket.
+    pub fn send_buffer_size(&self) -> std::io::Result<u32> {
+        match &self.0 {
+            TcpSocketInner::Std(socket) => socket
+                .send_buffer_size()
+                .map(|size| u32::try_from(size).unwrap_or(u32::MAX)),
+            #[cfg(tokio_net)]
+            TcpSocketInner::Tokio(socket) => socket.send_buffer_size(),
+        }
+    }
+
+    /// Sets the size of the receive buffer of the socket, setting the `SO_RCVBUF` option.
+    pub fn set_recv_buffer_size(&self, size: u32) -> std::io::Result<()> {
+        match &self.0 {
+            TcpSocketInner::Std(socket) => socket.set_recv_buffer_size(size as usize),
+            #[cfg(tokio_net)]
+            TcpSocketInner::Tokio(socket) => socket.set_recv_buffer_size(size),
+        }
+    }
+
+    /// Gets the value of the `SO_RCVBUF` option on the socket.
+    pub fn recv_buffer_size(&self) -> std::io::Result<u32> {
+        match &self.0 {
+            TcpSocketInner::Std(socket) => socket
+                .recv_buffer_size()
+                .map(|size| u32::try_from(size).unwrap_or(u32::MAX)),
+            #[cfg(tokio_net)]
+            TcpSocketInner::Tokio(socket) => socket.recv_buffer_size(),
+        }
+    }
+
+    /// Sets the value of the `TCP_NODELAY` option on the socket.
+    pub fn set_nodelay(&self, nodelay: bool) -> std::io::Result<()> {
+        match &self.0 {
+            TcpSocketInner::Std(socket) => socket.set_tcp_nodelay(nodelay),
+            #[cfg(tokio_net)]
+            TcpSocketInner::Tokio(socket) => socket.set_nodelay(nodelay),
+        }
+    }
+
+    /// Gets the value of the `TCP_NODELAY` option on the socket.
+    pub fn nodelay(&self) -> std::io::Result<bool> {
+        match &self.0 {
+            TcpSocketInner::Std(socket) => socket.tcp_nodelay(),
+            #[cfg(tokio_net)]
+            TcpSocketInner::Tokio(socket) => socket.nodelay(),
+        }
+    }
+
+    /// Binds the socket to the given address.
+    pub fn bind(&self, addr: SocketAddr) -> std::io::Result<()> {
+        match &self.0 {
+            TcpSocketInner::Std(socket) => socket.bind(&addr.into()),
+            #[cfg(tokio_net)]
+            TcpSocketInner::Tokio(socket) => socket.bind(addr),
+        }
+    }
+
+    /// Returns the local address that this socket is bound to.
+    pub fn local_addr(&self) -> std::io::Result<SocketAddr> {
+        match &self.0 {
+            TcpSocketInner::Std(socket) => socket.local_addr()?.as_socket().ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    "the socket is not bound to an IP address",
+                )
+            }),
+            #[cfg(tokio_net)]
+            TcpSocketInner::Tokio(socket) => socket.local_addr(),
+        }
+    }
+
+    /// Establishes a TCP connection with a peer at the specified address, consuming the socket.
+    pub async fn connect(self, addr: SocketAddr) -> std::io::Result<super::TcpStream> {
+        match self.0 {
+            TcpSocketInner::Std(socket) => {
+                socket.connect(&addr.into())?;
+                Ok(std::net::TcpStream::from(socket).into())
+            }
+            #[cfg(tokio_net)]
+            TcpSocketInner::Tokio(socket) => socket.connect(addr).await.map(super::TcpStream::from),
+        }
+    }
+
+    /// Converts the socket into a [`super::TcpListener`], listening for connections with the given backlog.
+    pub fn listen(self, backlog: u32) -> std::io::Result<super::TcpListener> {
+        match self.0 {
+            TcpSocketInner::Std(socket) => {
+                socket.listen(i32::try_from(backlog).unwrap_or(i32::MAX))?;
+                Ok(std::net::TcpListener::from(socket).into())
+            }
+            #[cfg(tokio_net)]
+            TcpSocketInner::Tokio(socket) => socket.listen(backlog).map(super::TcpListener::from),
+        }
+    }
+}
+
+#[cfg(unix)]
+impl std::os::fd::AsFd for TcpSocket {
+    fn as_fd(&self) -> std::os::fd::BorrowedFd<'_> {
+        match &self.0 {
+            TcpSocketInner::Std(socket) => socket.as_fd(),
+            #[cfg(tokio_net)]
+            TcpSocketInner::Tokio(socket) => socket.as_fd(),
+        }
+    }
+}
+
+#[cfg(unix)]
+impl std::os::fd::AsRawFd for TcpSocket {
+    fn as_raw_fd(&self) -> std::os::fd::RawFd {
+        match &self.0 {
+            TcpSocketInner::Std(socket) => socket.as_raw_fd(),
+            #[cfg(tokio_net)]
+            TcpSocketInner::Tokio(socket) => socket.as_raw_fd(),
+        }
+    }
+}
+
+#[cfg(windows)]
+impl std::os::windows::io::AsSocket for TcpSocket {
+    fn as_socket(&self) -> std::os::windows::io::BorrowedSocket<'_> {
+        match &self.0 {
+            TcpSocketInner::Std(socket) => socket.as_socket(),
+            #[cfg(tokio_net)]
+            TcpSocketInner::Tokio(socket) => socket.as_socket(),
+        }
+    }
+}
+
+#[cfg(windows)]
+impl std::os::windows::io::AsRawSocket for TcpSocket {
+    fn as_raw_socket(&self) -> std::os::windows::io::RawSocket {
+        match &self.0 {
+            TcpSocketInner::Std(socket) => socket.as_raw_socket(),
+            #[cfg(tokio_net)]
+            TcpSocketInner::Tokio(socket) => socket.as_raw_socket(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::SyncRuntime;
+    use crate::io::{Read, Write};
+
+    #[test]
+    #[serial_test::serial]
+    fn test_should_configure_and_listen_std() {
+        let socket = TcpSocket::new_v4().expect("failed to create socket");
+        assert!(matches!(socket.0, TcpSocketInner::Std(_)));
+        socket.set_reuseaddr(true).expect("failed to set reuseaddr");
+        assert!(socket.reuseaddr().expect("failed to get reuseaddr"));
+        socket.set_nodelay(true).expect("failed to set nodelay");
+        assert!(socket.nodelay().expect("failed to get nodelay"));
+        socket
+            .set_send_buffer_size(64 * 1024)
+            .expect("failed to set send buffer size");
+        assert!(
+            socket
+                .send_buffer_size()
+                .expect("failed to get send buffer size")
+                > 0
+        );
+        socket
+            .set_recv_buffer_size(64 * 1024)
+            .expect("failed to set recv buffer size");
+        assert!(
+            socket
+                .recv_buffer_size()
+                .expect("failed to get recv buffer size")
+                > 0
+        );
+
+        socket
+            .bind("127.0.0.1:0".parse().unwrap())
+            .expect("failed to bind");
+        let addr = socket.local_addr().expect("failed to get local addr");
+        let listener = socket.listen(16).expect("failed to listen");
+        assert_eq!(
+            listener.local_addr().expect("failed to get local addr"),
+            addr
+        );
+
+        let client = std::thread::spawn(move || {
+            let socket = TcpSocket::new_v4().expect("failed to create socket");
+            let mut stream =
+                SyncRuntime::block_on(socket.connect(addr)).expect("failed to connect");
+            SyncRuntime::block_on(stream.write_all(b"Ping")).expect("failed to write");
+        });
+
+        let (mut stream, _) = SyncRuntime::block_on(listener.accept()).expect("failed to accept");
+        let mut buf = [0; 4];
+        SyncRuntime::block_on(stream.read_exact(&mut buf)).expect("failed to read");
+        assert_eq!(&buf, b"Ping");
+
+        client.join().expect("failed to join");
+    }
+
+    #[cfg(tokio_net)]
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_should_configure_and_listen_tokio() {
+        let socket = TcpSocket::new_v4().expect("failed to create socket");
+        assert!(matches!(socket.0, TcpSocketInner::Tokio(_)));
+        socket.set_reuseaddr(true).expect("failed to set reuseaddr");
+        assert!(socket.reuseaddr().expect("failed to get reuseaddr"));
+
+        socket
+            .bind("127.0.0.1:0".parse().unwrap())
+            .expect("failed to bind");
+        let addr = socket.local_addr().expect("failed to get local addr");
+        let listener = socket.listen(16).expect("failed to listen");
+
+        let client = async {
+            let socket = TcpSocket::new_v4().expect("failed to create socket");
+            let mut stream = socket.connect(addr).await.expect("failed to connect");
+            stream.write_all(b"Ping").await.expect("failed to write");
+        };
+        let server = async {
+            let (mut stream, _) = listener.accept().await.expect("failed to accept");
+            let mut buf = [0; 4];
+            stream.read_exact(&mut buf).await.expect("failed to read");
+            assert_eq!(&buf, b"Ping");
+        };
+        tokio::join!(client, server);
+    }
+}
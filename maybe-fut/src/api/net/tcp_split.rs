@@ -0,0 +1,199 @@
+//! The read and write halves of a [`TcpStream`], returned by [`TcpStream::split`] and [`TcpStream::into_split`].
+
+use super::TcpStream;
+use super::tcp_stream::TcpStreamInner;
+
+/// The borrowed read half of a [`TcpStream`], created by [`TcpStream::split`].
+///
+/// Reading from a [`ReadHalf`] is usually done by using the [`crate::io::Read`] trait.
+#[derive(Debug, Read)]
+#[io(feature("tokio-net"), async_std("async-std"))]
+pub struct ReadHalf<'a>(ReadHalfInner<'a>);
+
+#[derive(Debug)]
+enum ReadHalfInner<'a> {
+    Std(&'a std::net::TcpStream),
+    #[cfg(tokio_net)]
+    Tokio(tokio::net::tcp::ReadHalf<'a>),
+    #[cfg(async_std)]
+    AsyncStd(&'a async_std::net::TcpStream),
+}
+
+/// The borrowed write half of a [`TcpStream`], created by [`TcpStream::split`].
+///
+/// Writing to a [`WriteHalf`] is usually done by using the [`crate::io::Write`] trait.
+#[derive(Debug, Write)]
+#[io(feature("tokio-net"), async_std("async-std"))]
+pub struct WriteHalf<'a>(WriteHalfInner<'a>);
+
+#[derive(Debug)]
+enum WriteHalfInner<'a> {
+    Std(&'a std::net::TcpStream),
+    #[cfg(tokio_net)]
+    Tokio(tokio::net::tcp::WriteHalf<'a>),
+    #[cfg(async_std)]
+    AsyncStd(&'a async_std::net::TcpStream),
+}
+
+/// The owned read half of a [`TcpStream`], created by [`TcpStream::into_split`].
+///
+/// Reading from an [`OwnedReadHalf`] is usually done by using the [`crate::io::Read`] trait.
+#[derive(Debug, Read)]
+#[io(feature("tokio-net"), async_std("async-std"))]
+pub struct OwnedReadHalf(OwnedReadHalfInner);
+
+#[derive(Debug)]
+enum OwnedReadHalfInner {
+    Std(std::net::TcpStream),
+    #[cfg(tokio_net)]
+    Tokio(tokio::net::tcp::OwnedReadHalf),
+    #[cfg(async_std)]
+    AsyncStd(async_std::net::TcpStream),
+}
+
+/// The owned write half of a [`TcpStream`], created by [`TcpStream::into_split`].
+///
+/// Writing to an [`OwnedWriteHalf`] is usually done by using the [`crate::io::Write`] trait.
+/// With tokio, dropping the write half shuts down the write side of the connection.
+#[derive(Debug, Write)]
+#[io(feature("tokio-net"), async_std("async-std"))]
+pub struct OwnedWriteHalf(OwnedWriteHalfInner);
+
+#[derive(Debug)]
+enum OwnedWriteHalfInner {
+    Std(std::net::TcpStream),
+    #[cfg(tokio_net)]
+    Tokio(tokio::net::tcp::OwnedWriteHalf),
+    #[cfg(async_std)]
+    AsyncStd(async_std::net::TcpStream),
+}
+
+impl TcpStream {
+    /// Splits the stream into a read half and a write half, borrowing the stream, so that they can be used
+    /// concurrently.
+    pub fn split(&mut self) -> (ReadHalf<'_>, WriteHalf<'_>) {
+        match &mut self.0 {
+            TcpStreamInner::Std(stream) => (
+                ReadHalf(ReadHalfInner::Std(&*stream)),
+                WriteHalf(WriteHalfInner::Std(&*stream)),
+            ),
+            #[cfg(tokio_net)]
+            TcpStreamInner::Tokio(stream) => {
+                let (read, write) = stream.split();
+                (
+                    ReadHalf(ReadHalfInner::Tokio(read)),
+                    WriteHalf(WriteHalfInner::Tokio(write)),
+                )
+            }
+            #[cfg(async_std)]
+            TcpStreamInner::AsyncStd(stream) => (
+                ReadHalf(ReadHalfInner::AsyncStd(&*stream)),
+                WriteHalf(WriteHalfInner::AsyncStd(&*stream)),
+            ),
+        }
+    }
+
+    /// Splits the stream into an owned read half and an owned write half, which can be moved to different tasks or
+    /// threads.
+    ///
+    /// The std stream is cloned with `try_clone`, which may fail.
+    pub fn into_split(self) -> std::io::Result<(OwnedReadHalf, OwnedWriteHalf)> {
+        match self.0 {
+            TcpStreamInner::Std(stream) => Ok((
+                OwnedReadHalf(OwnedReadHalfInner::Std(stream.try_clone()?)),
+                OwnedWriteHalf(OwnedWriteHalfInner::Std(stream)),
+            )),
+            #[cfg(tokio_net)]
+            TcpStreamInner::Tokio(stream) => {
+                let (read, write) = stream.into_split();
+                Ok((
+                    OwnedReadHalf(OwnedReadHalfInner::Tokio(read)),
+                    OwnedWriteHalf(OwnedWriteHalfInner::Tokio(write)),
+                ))
+            }
+            #[cfg(async_std)]
+            TcpStreamInner::AsyncStd(stream) => Ok((
+                OwnedReadHalf(OwnedReadHalfInner::AsyncStd(stream.clone())),
+                OwnedWriteHalf(OwnedWriteHalfInner::AsyncStd(stream)),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::SyncRuntime;
+    use crate::io::{Read, Write};
+
+    /// Binds a listener echoing back the bytes of a single connection.
+    fn echo_server() -> (std::net::SocketAddr, std::thread::JoinHandle<()>) {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("failed to bind");
+        let addr = listener.local_addr().expect("failed to get local addr");
+        let join = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().expect("failed to accept");
+            let mut reader = stream.try_clone().expect("failed to clone");
+            std::io::copy(&mut reader, &mut stream).expect("failed to echo");
+        });
+
+        (addr, join)
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_should_split_std() {
+        let (addr, join) = echo_server();
+        let mut stream =
+            SyncRuntime::block_on(TcpStream::connect(addr)).expect("failed to connect");
+        {
+            let (mut read, mut write) = stream.split();
+            SyncRuntime::block_on(write.write_all(b"Ping")).expect("failed to write");
+            let mut buf = [0; 4];
+            SyncRuntime::block_on(read.read_exact(&mut buf)).expect("failed to read");
+            assert_eq!(&buf, b"Ping");
+        }
+
+        let (mut read, mut write) = stream.into_split().expect("failed to split");
+        SyncRuntime::block_on(write.write_all(b"Pong")).expect("failed to write");
+        let mut buf = [0; 4];
+        SyncRuntime::block_on(read.read_exact(&mut buf)).expect("failed to read");
+        assert_eq!(&buf, b"Pong");
+
+        // close the connection to stop the echo server
+        drop(read);
+        drop(write);
+        join.join().expect("failed to join");
+    }
+
+    #[cfg(tokio_net)]
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_should_split_tokio() {
+        let listener = crate::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("failed to bind");
+        let addr = listener.local_addr().expect("failed to get local addr");
+
+        let client = async {
+            let mut stream = TcpStream::connect(addr).await.expect("failed to connect");
+            let (mut read, mut write) = stream.split();
+            let mut buf = [0; 4];
+            let (written, read) = tokio::join!(write.write_all(b"Ping"), read.read_exact(&mut buf));
+            written.expect("failed to write");
+            read.expect("failed to read");
+            assert_eq!(&buf, b"Pong");
+        };
+        let server = async {
+            let (stream, _) = listener.accept().await.expect("failed to accept");
+            let (mut read, mut write) = stream.into_split().expect("failed to split");
+            let mut buf = [0; 4];
+            read.read_exact(&mut buf).await.expect("failed to read");
+            assert_eq!(&buf, b"Ping");
+            write.write_all(b"Pong").await.expect("failed to write");
+        };
+        tokio::join!(client, server);
+    }
+}
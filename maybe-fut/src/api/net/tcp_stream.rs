@@ -9,20 +9,23 @@ use crate::{maybe_fut_constructor_result, maybe_fut_method, maybe_fut_method_syn
 ///
 /// Reading and writing to a [`TcpStream`] is usually done by using the [`crate::io::Read`] and [`crate::io::Write`] traits.
 #[derive(Debug, Unwrap, Read, Write)]
-#[io(feature("tokio-net"))]
+#[io(feature("tokio-net"), async_std("async-std"))]
 #[unwrap_types(
     std(std::net::TcpStream),
     tokio(tokio::net::TcpStream),
     tokio_gated("tokio-net")
 )]
-pub struct TcpStream(TcpStreamInner);
+pub struct TcpStream(pub(super) TcpStreamInner);
 
 #[derive(Debug)]
-enum TcpStreamInner {
+pub(super) enum TcpStreamInner {
     Std(std::net::TcpStream),
     #[cfg(tokio_net)]
     #[cfg_attr(docsrs, doc(cfg(feature = "tokio-net")))]
     Tokio(tokio::net::TcpStream),
+    #[cfg(async_std)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "async-std")))]
+    AsyncStd(async_std::net::TcpStream),
 }
 
 impl From<std::net::TcpStream> for TcpStream {
@@ -39,6 +42,14 @@ impl From<tokio::net::TcpStream> for TcpStream {
     }
 }
 
+#[cfg(async_std)]
+#[cfg_attr(docsrs, doc(cfg(feature = "async-std")))]
+impl From<async_std::net::TcpStream> for TcpStream {
+    fn from(stream: async_std::net::TcpStream) -> Self {
+        Self(TcpStreamInner::AsyncStd(stream))
+    }
+}
+
 #[cfg(unix)]
 impl std::os::fd::AsFd for TcpStream {
     fn as_fd(&self) -> std::os::fd::BorrowedFd<'_> {
@@ -46,6 +57,8 @@ impl std::os::fd::AsFd for TcpStream {
             TcpStreamInner::Std(file) => file.as_fd(),
             #[cfg(tokio_net)]
             TcpStreamInner::Tokio(file) => file.as_fd(),
+            #[cfg(async_std)]
+            TcpStreamInner::AsyncStd(file) => file.as_fd(),
         }
     }
 }
@@ -57,6 +70,8 @@ impl std::os::fd::AsRawFd for TcpStream {
             TcpStreamInner::Std(file) => file.as_raw_fd(),
             #[cfg(tokio_net)]
             TcpStreamInner::Tokio(file) => file.as_raw_fd(),
+            #[cfg(async_std)]
+            TcpStreamInner::AsyncStd(file) => file.as_raw_fd(),
         }
     }
 }
@@ -68,6 +83,8 @@ impl std::os::windows::io::AsSocket for TcpStream {
             TcpStreamInner::Std(file) => file.as_socket(),
             #[cfg(tokio_net)]
             TcpStreamInner::Tokio(file) => file.as_socket(),
+            #[cfg(async_std)]
+            TcpStreamInner::AsyncStd(file) => file.as_socket(),
         }
     }
 }
@@ -79,41 +96,275 @@ impl std::os::windows::io::AsRawSocket for TcpStream {
             TcpStreamInner::Std(file) => file.as_raw_socket(),
             #[cfg(tokio_net)]
             TcpStreamInner::Tokio(file) => file.as_raw_socket(),
+            #[cfg(async_std)]
+            TcpStreamInner::AsyncStd(file) => file.as_raw_socket(),
         }
     }
 }
 
+/// Wraps a std stream into the variant of the current context.
+///
+/// # Panics
+///
+/// Panics if the stream can't be registered with the tokio reactor.
+fn from_std_in_context(stream: std::net::TcpStream) -> TcpStream {
+    #[cfg(async_std)]
+    if crate::context::is_async_std_context() {
+        return async_std::net::TcpStream::from(stream).into();
+    }
+    #[cfg(tokio_net)]
+    if crate::context::is_tokio_context() {
+        return stream
+            .set_nonblocking(true)
+            .and_then(|()| tokio::net::TcpStream::from_std(stream))
+            .map(TcpStream::from)
+            .expect("failed to register the stream with the tokio reactor");
+    }
+
+    stream.into()
+}
+
+/// Switches a stream released by an async runtime back to blocking mode.
+#[cfg(any(tokio_net, async_std))]
+fn into_blocking(stream: std::net::TcpStream) -> std::io::Result<std::net::TcpStream> {
+    stream.set_nonblocking(false)?;
+
+    Ok(stream)
+}
+
+#[cfg(unix)]
+impl From<std::os::fd::OwnedFd> for TcpStream {
+    /// Takes ownership of the file descriptor, wrapping it into the variant of the current context.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the stream can't be registered with the tokio reactor.
+    fn from(fd: std::os::fd::OwnedFd) -> Self {
+        from_std_in_context(std::net::TcpStream::from(fd))
+    }
+}
+
+#[cfg(windows)]
+impl From<std::os::windows::io::OwnedSocket> for TcpStream {
+    /// Takes ownership of the socket, wrapping it into the variant of the current context.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the stream can't be registered with the tokio reactor.
+    fn from(socket: std::os::windows::io::OwnedSocket) -> Self {
+        from_std_in_context(std::net::TcpStream::from(socket))
+    }
+}
+
+#[cfg(unix)]
+impl From<TcpStream> for std::os::fd::OwnedFd {
+    /// Releases the file descriptor of the stream.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the stream can't be deregistered from the tokio reactor.
+    fn from(stream: TcpStream) -> Self {
+        match stream.0 {
+            TcpStreamInner::Std(stream) => stream.into(),
+            #[cfg(tokio_net)]
+            TcpStreamInner::Tokio(stream) => stream
+                .into_std()
+                .expect("failed to deregister the stream from the tokio reactor")
+                .into(),
+            #[cfg(async_std)]
+            TcpStreamInner::AsyncStd(stream) => stream.into(),
+        }
+    }
+}
+
+#[cfg(windows)]
+impl From<TcpStream> for std::os::windows::io::OwnedSocket {
+    /// Releases the socket.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the stream can't be deregistered from the tokio reactor.
+    fn from(stream: TcpStream) -> Self {
+        match stream.0 {
+            TcpStreamInner::Std(stream) => stream.into(),
+            #[cfg(tokio_net)]
+            TcpStreamInner::Tokio(stream) => stream
+                .into_std()
+                .expect("failed to deregister the stream from the tokio reactor")
+                .into(),
+            #[cfg(async_std)]
+            TcpStreamInner::AsyncStd(stream) => stream.into(),
+        }
+    }
+}
+
+#[cfg(unix)]
+impl std::os::fd::IntoRawFd for TcpStream {
+    fn into_raw_fd(self) -> std::os::fd::RawFd {
+        std::os::fd::OwnedFd::from(self).into_raw_fd()
+    }
+}
+
+#[cfg(windows)]
+impl std::os::windows::io::IntoRawSocket for TcpStream {
+    fn into_raw_socket(self) -> std::os::windows::io::RawSocket {
+        std::os::windows::io::OwnedSocket::from(self).into_raw_socket()
+    }
+}
+
+#[cfg(socket2)]
+#[cfg_attr(docsrs, doc(cfg(feature = "socket2")))]
+impl From<socket2::Socket> for TcpStream {
+    /// Takes ownership of a socket configured with socket2, wrapping it into the variant of the current context.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the stream can't be registered with the tokio reactor.
+    fn from(socket: socket2::Socket) -> Self {
+        from_std_in_context(std::net::TcpStream::from(socket))
+    }
+}
+
+#[cfg(socket2)]
+#[cfg_attr(docsrs, doc(cfg(feature = "socket2")))]
+impl From<TcpStream> for socket2::Socket {
+    /// Releases the socket, to configure it with socket2.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases as the conversion into the owned descriptor of the stream.
+    fn from(stream: TcpStream) -> Self {
+        #[cfg(unix)]
+        let socket = std::os::fd::OwnedFd::from(stream);
+        #[cfg(windows)]
+        let socket = std::os::windows::io::OwnedSocket::from(stream);
+
+        socket2::Socket::from(socket)
+    }
+}
+
 impl TcpStream {
-    maybe_fut_constructor_result!(
-        /// Opens a TCP connection to a remote host at the specified address.
-        connect(addr: SocketAddr) -> std::io::Result<TcpStream>,
-        std::net::TcpStream::connect,
-        tokio::net::TcpStream::connect,
-        tokio_net
-    );
+    /// Opens a TCP connection to a remote host at the specified address.
+    ///
+    /// If `addr` resolves to several addresses, a connection is attempted with each of them in order, until one
+    /// succeeds; otherwise, the error of the last attempt is returned.
+    pub async fn connect(addr: impl super::ToSocketAddrs) -> std::io::Result<TcpStream> {
+        super::lookup::each_addr(addr, Self::connect_addr).await
+    }
+
+    /// Opens a TCP connection to a single address.
+    async fn connect_addr(addr: SocketAddr) -> std::io::Result<TcpStream> {
+        maybe_fut_constructor_result!(
+            @body
+            (addr),
+            std::net::TcpStream::connect,
+            tokio::net::TcpStream::connect,
+            tokio_net,
+            async_std::net::TcpStream::connect,
+            async_std
+        )
+    }
+
+    /// Opens a TCP connection to a remote host with the std implementation, regardless of the context.
+    ///
+    /// Unlike [`Self::connect`], it never checks the context, which is useful to create a stream in an async context to use it on another thread.
+    pub fn connect_std(addr: SocketAddr) -> std::io::Result<Self> {
+        std::net::TcpStream::connect(addr).map(Self::from)
+    }
+
+    /// Opens a TCP connection to a remote host with the tokio implementation, regardless of the context.
+    ///
+    /// Unlike [`Self::connect`], it never checks the context. It must be called from within a tokio runtime.
+    #[cfg(tokio_net)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio-net")))]
+    pub async fn connect_tokio(addr: SocketAddr) -> std::io::Result<Self> {
+        tokio::net::TcpStream::connect(addr).await.map(Self::from)
+    }
+
+    /// Converts the [`TcpStream`] inner instance to a [`std::net::TcpStream`], to hand it to a library requiring
+    /// a std stream.
+    ///
+    /// The streams of the async runtimes are switched back to blocking mode; a std stream is returned as it is.
+    pub fn to_std(self) -> std::io::Result<std::net::TcpStream> {
+        match self.0 {
+            TcpStreamInner::Std(stream) => Ok(stream),
+            #[cfg(tokio_net)]
+            TcpStreamInner::Tokio(stream) => stream.into_std().and_then(into_blocking),
+            #[cfg(all(async_std, unix))]
+            TcpStreamInner::AsyncStd(stream) => into_blocking(std::net::TcpStream::from(
+                std::os::fd::OwnedFd::from(stream),
+            )),
+            #[cfg(all(async_std, windows))]
+            TcpStreamInner::AsyncStd(stream) => into_blocking(std::net::TcpStream::from(
+                std::os::windows::io::OwnedSocket::from(stream),
+            )),
+        }
+    }
+
+    /// Converts the [`TcpStream`] inner instance to a [`tokio::net::TcpStream`], to hand it to a library requiring
+    /// a tokio stream.
+    ///
+    /// The stream is switched to non-blocking mode and registered with the reactor of the current tokio runtime,
+    /// so it must be called from within a tokio runtime.
+    #[cfg(tokio_net)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio-net")))]
+    pub fn to_tokio(self) -> std::io::Result<tokio::net::TcpStream> {
+        match self.0 {
+            TcpStreamInner::Tokio(stream) => Ok(stream),
+            inner => {
+                let stream = Self(inner).to_std()?;
+                stream.set_nonblocking(true)?;
+                tokio::net::TcpStream::from_std(stream)
+            }
+        }
+    }
+
+    /// Converts the stream into a [`socket2::Socket`], to set the options not exposed by maybe-fut.
+    ///
+    /// The [`socket2::Socket`] can be converted back into a stream of the current context with [`From`].
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases as the conversion into the owned descriptor of the stream.
+    #[cfg(socket2)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "socket2")))]
+    pub fn into_socket2(self) -> socket2::Socket {
+        self.into()
+    }
 
     maybe_fut_method_sync!(
         /// Returns the local address that this stream is bound to.
         local_addr() -> std::io::Result<SocketAddr>,
         TcpStreamInner::Std,
         TcpStreamInner::Tokio,
-        tokio_net
+        tokio_net,
+        TcpStreamInner::AsyncStd,
+        async_std
     );
 
-    maybe_fut_method_sync!(
-        /// Returns the value of the `SO_ERROR` option.
-        take_error() -> std::io::Result<Option<std::io::Error>>,
-        TcpStreamInner::Std,
-        TcpStreamInner::Tokio,
-        tokio_net
-    );
+    /// Returns the value of the `SO_ERROR` option.
+    ///
+    /// It doesn't work with async-std's `TcpStream` because it doesn't support getting the `SO_ERROR` option.
+    pub fn take_error(&self) -> std::io::Result<Option<std::io::Error>> {
+        match &self.0 {
+            TcpStreamInner::Std(stream) => stream.take_error(),
+            #[cfg(tokio_net)]
+            TcpStreamInner::Tokio(stream) => stream.take_error(),
+            #[cfg(async_std)]
+            TcpStreamInner::AsyncStd(_) => Err(std::io::Error::other(
+                "async-std TcpStream does not support take_error",
+            )),
+        }
+    }
 
     maybe_fut_method_sync!(
         /// Returns the remote address that this stream is connected to.
         peer_addr() -> std::io::Result<SocketAddr>,
         TcpStreamInner::Std,
         TcpStreamInner::Tokio,
-        tokio_net
+        tokio_net,
+        TcpStreamInner::AsyncStd,
+        async_std
     );
 
     maybe_fut_method_sync!(
@@ -121,7 +372,9 @@ impl TcpStream {
         nodelay() -> std::io::Result<bool>,
         TcpStreamInner::Std,
         TcpStreamInner::Tokio,
-        tokio_net
+        tokio_net,
+        TcpStreamInner::AsyncStd,
+        async_std
     );
 
     maybe_fut_method_sync!(
@@ -129,16 +382,145 @@ impl TcpStream {
         set_nodelay(nodelay: bool) -> std::io::Result<()>,
         TcpStreamInner::Std,
         TcpStreamInner::Tokio,
-        tokio_net
+        tokio_net,
+        TcpStreamInner::AsyncStd,
+        async_std
     );
 
+    /// Waits until the stream is readable, i.e. a read may not block, to drive manual non-blocking IO loops.
+    ///
+    /// With std, the current thread is blocked with `poll`, which is only supported on Unix.
+    /// The readiness may be spurious, so the following read can still fail with [`std::io::ErrorKind::WouldBlock`].
+    /// It doesn't work with async-std's `TcpStream`, because it doesn't expose its readiness.
+    pub async fn readable(&self) -> std::io::Result<()> {
+        match &self.0 {
+            TcpStreamInner::Std(stream) => super::ready::readable(stream),
+            #[cfg(tokio_net)]
+            TcpStreamInner::Tokio(stream) => stream.readable().await,
+            #[cfg(async_std)]
+            TcpStreamInner::AsyncStd(_) => Err(std::io::Error::other(
+                "async-std TcpStream does not support readable",
+            )),
+        }
+    }
+
+    /// Waits until the stream is writable, i.e. a write may not block, to drive manual non-blocking IO loops.
+    ///
+    /// With std, the current thread is blocked with `poll`, which is only supported on Unix.
+    /// The readiness may be spurious, so the following write can still fail with [`std::io::ErrorKind::WouldBlock`].
+    /// It doesn't work with async-std's `TcpStream`, because it doesn't expose its readiness.
+    pub async fn writable(&self) -> std::io::Result<()> {
+        match &self.0 {
+            TcpStreamInner::Std(stream) => super::ready::writable(stream),
+            #[cfg(tokio_net)]
+            TcpStreamInner::Tokio(stream) => stream.writable().await,
+            #[cfg(async_std)]
+            TcpStreamInner::AsyncStd(_) => Err(std::io::Error::other(
+                "async-std TcpStream does not support writable",
+            )),
+        }
+    }
+
+    /// Tries to read data from the stream into `buf`, without waiting.
+    ///
+    /// If no data is available, it fails with [`std::io::ErrorKind::WouldBlock`]; it's usually called after
+    /// [`Self::readable`]. On success, returns the number of bytes read.
+    /// On the platforms other than Unix, the std stream must be in non-blocking mode, otherwise the call blocks.
+    pub fn try_read(&self, buf: &mut [u8]) -> std::io::Result<usize> {
+        #[cfg(tokio_net)]
+        if let TcpStreamInner::Tokio(stream) = &self.0 {
+            return stream.try_read(buf);
+        }
+
+        super::ready::try_recv(socket2::SockRef::from(self), buf)
+    }
+
+    /// Tries to write `buf` to the stream, without waiting.
+    ///
+    /// If the stream isn't writable, it fails with [`std::io::ErrorKind::WouldBlock`]; it's usually called after
+    /// [`Self::writable`]. On success, returns the number of bytes written.
+    /// On the platforms other than Unix, the std stream must be in non-blocking mode, otherwise the call blocks.
+    pub fn try_write(&self, buf: &[u8]) -> std::io::Result<usize> {
+        #[cfg(tokio_net)]
+        if let TcpStreamInner::Tokio(stream) = &self.0 {
+            return stream.try_write(buf);
+        }
+
+        super::ready::try_send(socket2::SockRef::from(self), buf)
+    }
+
+    /// Returns the statistics of the connection, read from the `TCP_INFO` option of the socket.
+    ///
+    /// It's only supported on Linux; on the other platforms, it fails with [`std::io::ErrorKind::Unsupported`].
+    pub fn info(&self) -> std::io::Result<super::TcpInfo> {
+        super::tcp_info::tcp_info(self)
+    }
+
+    /// Sets the value of the `IP_TOS` option on this socket, the type-of-service field of the IPv4 packets sent from it.
+    ///
+    /// The DSCP class of the packets is in the upper six bits, e.g. `0xb8` for Expedited Forwarding.
+    pub fn set_tos(&self, tos: u8) -> std::io::Result<()> {
+        super::tos::set_tos(socket2::SockRef::from(self), tos)
+    }
+
+    /// Gets the value of the `IP_TOS` option on this socket.
+    pub fn tos(&self) -> std::io::Result<u8> {
+        super::tos::tos(socket2::SockRef::from(self))
+    }
+
+    /// Sets the value of the `IPV6_TCLASS` option on this socket, the traffic class of the IPv6 packets sent from it.
+    ///
+    /// It's only supported on Linux, Android, macOS and the BSDs; on the other platforms, it fails with
+    /// [`std::io::ErrorKind::Unsupported`].
+    pub fn set_tclass_v6(&self, tclass: u8) -> std::io::Result<()> {
+        super::tos::set_tclass_v6(socket2::SockRef::from(self), tclass)
+    }
+
+    /// Gets the value of the `IPV6_TCLASS` option on this socket.
+    pub fn tclass_v6(&self) -> std::io::Result<u8> {
+        super::tos::tclass_v6(socket2::SockRef::from(self))
+    }
+
+    /// Sets the value of the `SO_LINGER` option on this socket.
+    ///
+    /// With `Some`, closing the socket blocks until the pending data is sent or the timeout elapses;
+    /// with a zero timeout, the connection is reset on close. `None` disables the option.
+    pub fn set_linger(&self, linger: Option<std::time::Duration>) -> std::io::Result<()> {
+        socket2::SockRef::from(self).set_linger(linger)
+    }
+
+    /// Gets the value of the `SO_LINGER` option on this socket.
+    pub fn linger(&self) -> std::io::Result<Option<std::time::Duration>> {
+        socket2::SockRef::from(self).linger()
+    }
+
+    /// Sets the value of the `SO_KEEPALIVE` option on this socket, with the keepalive parameters of the system.
+    pub fn set_keepalive(&self, keepalive: bool) -> std::io::Result<()> {
+        socket2::SockRef::from(self).set_keepalive(keepalive)
+    }
+
+    /// Gets the value of the `SO_KEEPALIVE` option on this socket.
+    pub fn keepalive(&self) -> std::io::Result<bool> {
+        socket2::SockRef::from(self).keepalive()
+    }
+
+    /// Enables `SO_KEEPALIVE` on this socket, configuring the idle time, the interval and the number of retries
+    /// of the probes set in `keepalive`.
+    #[cfg(socket2)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "socket2")))]
+    pub fn set_tcp_keepalive(&self, keepalive: &super::TcpKeepalive) -> std::io::Result<()> {
+        socket2::SockRef::from(self).set_tcp_keepalive(keepalive)
+    }
+
     maybe_fut_method!(
         /// Receives data on the socket from the remote address to which it is connected, without removing that data from the queue.
         /// On success, returns the number of bytes read.
         peek(buf: &mut [u8]) -> std::io::Result<usize>,
         TcpStreamInner::Std,
         TcpStreamInner::Tokio,
-        tokio_net
+        tokio_net,
+        TcpStreamInner::AsyncStd,
+        async_std
     );
 
     maybe_fut_method_sync!(
@@ -146,7 +528,9 @@ impl TcpStream {
         ttl() -> std::io::Result<u32>,
         TcpStreamInner::Std,
         TcpStreamInner::Tokio,
-        tokio_net
+        tokio_net,
+        TcpStreamInner::AsyncStd,
+        async_std
     );
 
     maybe_fut_method_sync!(
@@ -154,22 +538,92 @@ impl TcpStream {
         set_ttl(ttl: u32) -> std::io::Result<()>,
         TcpStreamInner::Std,
         TcpStreamInner::Tokio,
-        tokio_net
+        tokio_net,
+        TcpStreamInner::AsyncStd,
+        async_std
     );
 }
 
+/// Reads from a shared [`TcpStream`], as [`std::net::TcpStream`] allows.
+///
+/// This allows to share a connection between a reader and a writer, e.g. through an [`std::sync::Arc`],
+/// without cloning it.
+impl crate::io::Read for &TcpStream {
+    async fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match &self.0 {
+            TcpStreamInner::Std(stream) => std::io::Read::read(&mut &*stream, buf),
+            #[cfg(tokio_net)]
+            TcpStreamInner::Tokio(stream) => loop {
+                stream.readable().await?;
+                match stream.try_read(buf) {
+                    Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => continue,
+                    result => break result,
+                }
+            },
+            #[cfg(async_std)]
+            TcpStreamInner::AsyncStd(stream) => {
+                use async_std::io::ReadExt as _;
+                (&mut &*stream).read(buf).await
+            }
+        }
+    }
+}
+
+/// Writes to a shared [`TcpStream`], as [`std::net::TcpStream`] allows.
+///
+/// This allows to share a connection between a reader and a writer, e.g. through an [`std::sync::Arc`],
+/// without cloning it.
+impl crate::io::Write for &TcpStream {
+    async fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match &self.0 {
+            TcpStreamInner::Std(stream) => std::io::Write::write(&mut &*stream, buf),
+            #[cfg(tokio_net)]
+            TcpStreamInner::Tokio(stream) => loop {
+                stream.writable().await?;
+                match stream.try_write(buf) {
+                    Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => continue,
+                    result => break result,
+                }
+            },
+            #[cfg(async_std)]
+            TcpStreamInner::AsyncStd(stream) => {
+                use async_std::io::WriteExt as _;
+                (&mut &*stream).write(buf).await
+            }
+        }
+    }
+
+    async fn flush(&mut self) -> std::io::Result<()> {
+        match &self.0 {
+            TcpStreamInner::Std(stream) => std::io::Write::flush(&mut &*stream),
+            // tokio streams are not buffered
+            #[cfg(tokio_net)]
+            TcpStreamInner::Tokio(_) => Ok(()),
+            #[cfg(async_std)]
+            TcpStreamInner::AsyncStd(stream) => {
+                use async_std::io::WriteExt as _;
+                (&mut &*stream).flush().await
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
 
     use std::io::{Read as _, Write as _};
     use std::net::TcpListener;
+    use std::rc::Rc;
     use std::sync::Arc;
     use std::sync::atomic::AtomicBool;
     use std::thread::JoinHandle;
+    use std::time::Duration;
 
     use super::*;
-    use crate::block_on;
     use crate::io::{Read as _, Write};
+    #[cfg(socket2)]
+    use crate::net::TcpKeepalive;
+    use crate::{Unwrap as _, block_on};
 
     #[test]
     #[serial_test::serial]
@@ -314,6 +768,67 @@ mod test {
         // join.join().expect("Failed to join server thread");
     }
 
+    #[cfg(async_std)]
+    #[async_std::test]
+    #[serial_test::serial]
+    async fn test_should_read_and_write_from_tcp_stream_async_std() {
+        let (_join, peer_addr, exit) = ping_server();
+
+        let mut stream = TcpStream::connect(peer_addr).await.unwrap();
+        assert!(matches!(stream.0, TcpStreamInner::AsyncStd(_)));
+        assert_eq!(stream.peer_addr().unwrap(), peer_addr);
+        stream
+            .write_all(b"Ping")
+            .await
+            .expect("Failed to write to stream");
+        let mut buf = [0; 1024];
+        let size = stream
+            .read(&mut buf)
+            .await
+            .expect("Failed to read from stream");
+        assert_eq!(size, 4);
+        assert_eq!(&buf[..size], b"Pong");
+        exit.store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_should_read_and_write_through_shared_tcp_stream_std() {
+        let (_join, peer_addr, exit) = ping_server();
+
+        let stream = Rc::new(block_on(TcpStream::connect(peer_addr)).unwrap());
+        let mut writer = &*stream;
+        let mut reader = &*stream;
+        block_on(writer.write_all(b"Ping")).expect("Failed to write to stream");
+        let mut buf = [0; 1024];
+        let size = block_on(reader.read(&mut buf)).expect("Failed to read from stream");
+        assert_eq!(&buf[..size], b"Pong");
+        exit.store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    #[cfg(tokio_net)]
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_should_read_and_write_through_shared_tcp_stream_tokio() {
+        let (_join, peer_addr, exit) = ping_server();
+
+        let stream = Rc::new(TcpStream::connect(peer_addr).await.unwrap());
+        let writer = async {
+            let mut writer = &*stream;
+            writer.write_all(b"Ping").await
+        };
+        let reader = async {
+            let mut reader = &*stream;
+            let mut buf = [0; 1024];
+            let size = reader.read(&mut buf).await?;
+            Ok::<_, std::io::Error>(buf[..size].to_vec())
+        };
+        let (written, read) = tokio::join!(writer, reader);
+        written.expect("Failed to write to stream");
+        assert_eq!(read.expect("Failed to read from stream"), b"Pong");
+        exit.store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+
     fn ping_server() -> (JoinHandle<()>, SocketAddr, Arc<AtomicBool>) {
         // sleep for a random amount of time
         std::thread::sleep(std::time::Duration::from_millis(
@@ -361,4 +876,245 @@ mod test {
 
         (join, addr, exit)
     }
+
+    #[cfg(all(unix, tokio_net))]
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_should_convert_tcp_stream_from_and_into_owned_fd() {
+        let (_join, peer_addr, exit) = ping_server();
+
+        let fd = std::os::fd::OwnedFd::from(std::net::TcpStream::connect(peer_addr).unwrap());
+        let mut stream = TcpStream::from(fd);
+        assert!(stream.get_tokio_ref().is_some());
+        stream
+            .write_all(b"Ping")
+            .await
+            .expect("Failed to write to stream");
+
+        let mut stream = std::net::TcpStream::from(std::os::fd::OwnedFd::from(stream));
+        stream.set_nonblocking(false).unwrap();
+        let mut buf = [0; 4];
+        std::io::Read::read_exact(&mut stream, &mut buf).expect("Failed to read from stream");
+        assert_eq!(&buf, b"Pong");
+        exit.store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    #[cfg(tokio_net)]
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_should_convert_tcp_stream_to_std_and_tokio() {
+        let (_join, peer_addr, exit) = ping_server();
+
+        let stream = TcpStream::connect(peer_addr).await.unwrap();
+        assert!(matches!(stream.0, TcpStreamInner::Tokio(_)));
+        let mut stream = stream.to_std().expect("failed to convert to std");
+        // the std stream is blocking, so reads wait for the data
+        stream.write_all(b"Ping").expect("failed to write");
+        let mut buf = [0; 4];
+        stream.read_exact(&mut buf).expect("failed to read");
+        assert_eq!(&buf, b"Pong");
+
+        let stream = TcpStream::connect_std(peer_addr).unwrap();
+        let mut stream = stream.to_tokio().expect("failed to convert to tokio");
+        tokio::io::AsyncWriteExt::write_all(&mut stream, b"Ping")
+            .await
+            .expect("failed to write");
+        tokio::io::AsyncReadExt::read_exact(&mut stream, &mut buf)
+            .await
+            .expect("failed to read");
+        assert_eq!(&buf, b"Pong");
+
+        exit.store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    #[cfg(tokio_net)]
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_should_connect_with_explicit_backend() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("Failed to bind");
+        let addr = listener.local_addr().expect("Failed to get local address");
+
+        let stream = TcpStream::connect_std(addr).expect("Failed to connect");
+        assert!(matches!(stream.0, TcpStreamInner::Std(_)));
+
+        let stream = TcpStream::connect_tokio(addr)
+            .await
+            .expect("Failed to connect");
+        assert!(matches!(stream.0, TcpStreamInner::Tokio(_)));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    #[serial_test::serial]
+    fn test_should_get_tcp_info() {
+        let (_join, peer_addr, exit) = ping_server();
+        let stream = block_on(TcpStream::connect(peer_addr)).unwrap();
+        let info = stream.info().expect("failed to get TCP_INFO");
+        assert!(info.congestion_window > 0);
+
+        exit.store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    #[cfg(all(target_os = "linux", tokio_net))]
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_should_get_tcp_info_tokio() {
+        let (_join, peer_addr, exit) = ping_server();
+        let stream = TcpStream::connect(peer_addr).await.unwrap();
+        let info = stream.info().expect("failed to get TCP_INFO");
+        assert!(info.congestion_window > 0);
+
+        exit.store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_should_set_tos() {
+        let (_join, peer_addr, exit) = ping_server();
+        let stream = block_on(TcpStream::connect(peer_addr)).unwrap();
+        stream.set_tos(0xb8).expect("failed to set IP_TOS");
+        assert_eq!(stream.tos().expect("failed to get IP_TOS"), 0xb8);
+
+        exit.store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[serial_test::serial]
+    fn test_should_wait_for_readiness_std() {
+        let (_join, peer_addr, exit) = ping_server();
+        let mut stream = block_on(TcpStream::connect(peer_addr)).unwrap();
+        stream.get_std_ref().unwrap().set_nonblocking(true).unwrap();
+
+        block_on(stream.writable()).expect("failed to wait for writable");
+        block_on(stream.write_all(b"Ping")).expect("failed to write");
+        block_on(stream.readable()).expect("failed to wait for readable");
+        let mut buf = [0; 4];
+        let size = block_on(stream.read(&mut buf)).expect("failed to read");
+        assert_eq!(&buf[..size], &b"Pong"[..size]);
+        assert!(size > 0);
+
+        exit.store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    #[cfg(tokio_net)]
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_should_wait_for_readiness_tokio() {
+        let (_join, peer_addr, exit) = ping_server();
+        let mut stream = TcpStream::connect(peer_addr).await.unwrap();
+
+        stream
+            .writable()
+            .await
+            .expect("failed to wait for writable");
+        stream.write_all(b"Ping").await.expect("failed to write");
+        stream
+            .readable()
+            .await
+            .expect("failed to wait for readable");
+        let mut buf = [0; 4];
+        stream.read_exact(&mut buf).await.expect("failed to read");
+        assert_eq!(&buf, b"Pong");
+
+        exit.store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[serial_test::serial]
+    fn test_should_try_read_and_write_std() {
+        let (_join, peer_addr, exit) = ping_server();
+        let stream = block_on(TcpStream::connect(peer_addr)).unwrap();
+
+        let mut buf = [0; 4];
+        let err = stream
+            .try_read(&mut buf)
+            .expect_err("nothing should be read");
+        assert_eq!(err.kind(), std::io::ErrorKind::WouldBlock);
+
+        assert_eq!(stream.try_write(b"Ping").expect("failed to write"), 4);
+        block_on(stream.readable()).expect("failed to wait for readable");
+        let size = stream.try_read(&mut buf).expect("failed to read");
+        assert_eq!(&buf[..size], &b"Pong"[..size]);
+        assert!(size > 0);
+
+        exit.store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    #[cfg(tokio_net)]
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_should_try_read_and_write_tokio() {
+        let (_join, peer_addr, exit) = ping_server();
+        let stream = TcpStream::connect(peer_addr).await.unwrap();
+
+        let mut buf = [0; 4];
+        let err = stream
+            .try_read(&mut buf)
+            .expect_err("nothing should be read");
+        assert_eq!(err.kind(), std::io::ErrorKind::WouldBlock);
+
+        stream
+            .writable()
+            .await
+            .expect("failed to wait for writable");
+        assert_eq!(stream.try_write(b"Ping").expect("failed to write"), 4);
+        let size = loop {
+            stream
+                .readable()
+                .await
+                .expect("failed to wait for readable");
+            match stream.try_read(&mut buf) {
+                Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => continue,
+                res => break res.expect("failed to read"),
+            }
+        };
+        assert_eq!(&buf[..size], &b"Pong"[..size]);
+        assert!(size > 0);
+
+        exit.store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_should_set_linger_and_keepalive() {
+        let (_join, peer_addr, exit) = ping_server();
+        let stream = block_on(TcpStream::connect(peer_addr)).unwrap();
+
+        stream
+            .set_linger(Some(Duration::from_secs(5)))
+            .expect("failed to set SO_LINGER");
+        assert_eq!(
+            stream.linger().expect("failed to get SO_LINGER"),
+            Some(Duration::from_secs(5))
+        );
+        stream.set_linger(None).expect("failed to set SO_LINGER");
+        assert_eq!(stream.linger().expect("failed to get SO_LINGER"), None);
+
+        assert!(!stream.keepalive().expect("failed to get SO_KEEPALIVE"));
+        stream
+            .set_keepalive(true)
+            .expect("failed to set SO_KEEPALIVE");
+        assert!(stream.keepalive().expect("failed to get SO_KEEPALIVE"));
+        #[cfg(socket2)]
+        {
+            stream
+                .set_tcp_keepalive(&TcpKeepalive::new().with_time(Duration::from_secs(60)))
+                .expect("failed to set keepalive");
+            #[cfg(target_os = "linux")]
+            assert_eq!(
+                socket2::SockRef::from(&stream)
+                    .tcp_keepalive_time()
+                    .expect("failed to get TCP_KEEPIDLE"),
+                Duration::from_secs(60)
+            );
+        }
+        stream
+            .set_keepalive(false)
+            .expect("failed to set SO_KEEPALIVE");
+        assert!(!stream.keepalive().expect("failed to get SO_KEEPALIVE"));
+
+        exit.store(true, std::sync::atomic::Ordering::Relaxed);
+    }
 }
@@ -0,0 +1,80 @@
+use socket2::SockRef;
+
+/// Sets the `IP_TOS` option of `socket`.
+pub fn set_tos(socket: SockRef<'_>, tos: u8) -> std::io::Result<()> {
+    socket.set_tos_v4(tos.into())
+}
+
+/// Reads the `IP_TOS` option of `socket`.
+pub fn tos(socket: SockRef<'_>) -> std::io::Result<u8> {
+    // the type-of-service field is a single byte
+    socket.tos_v4().map(|tos| tos as u8)
+}
+
+/// Sets the `IPV6_TCLASS` option of `socket`.
+#[cfg(any(
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+pub fn set_tclass_v6(socket: SockRef<'_>, tclass: u8) -> std::io::Result<()> {
+    socket.set_tclass_v6(tclass.into())
+}
+
+/// Reads the `IPV6_TCLASS` option of `socket`.
+#[cfg(any(
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+pub fn tclass_v6(socket: SockRef<'_>) -> std::io::Result<u8> {
+    // the traffic class field is a single byte
+    socket.tclass_v6().map(|tclass| tclass as u8)
+}
+
+/// `IPV6_TCLASS` is not supported on this platform; fails with [`std::io::ErrorKind::Unsupported`].
+#[cfg(not(any(
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "openbsd",
+)))]
+pub fn set_tclass_v6(_socket: SockRef<'_>, _tclass: u8) -> std::io::Result<()> {
+    Err(unsupported_tclass())
+}
+
+/// `IPV6_TCLASS` is not supported on this platform; fails with [`std::io::ErrorKind::Unsupported`].
+#[cfg(not(any(
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "openbsd",
+)))]
+pub fn tclass_v6(_socket: SockRef<'_>) -> std::io::Result<u8> {
+    Err(unsupported_tclass())
+}
+
+#[cfg(not(any(
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "openbsd",
+)))]
+fn unsupported_tclass() -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "IPV6_TCLASS is not supported on this platform",
+    )
+}
@@ -1,3 +1,4 @@
+use std::io::{IoSlice, IoSliceMut};
 use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
 
 use crate::{maybe_fut_constructor_result, maybe_fut_method, maybe_fut_method_sync};
@@ -81,14 +82,166 @@ impl std::os::windows::io::AsRawSocket for UdpSocket {
     }
 }
 
+/// Wraps a std socket into the variant of the current context.
+///
+/// # Panics
+///
+/// Panics if the socket can't be registered with the tokio reactor.
+fn from_std_in_context(socket: std::net::UdpSocket) -> UdpSocket {
+    #[cfg(tokio_net)]
+    if crate::context::is_tokio_context() {
+        return socket
+            .set_nonblocking(true)
+            .and_then(|()| tokio::net::UdpSocket::from_std(socket))
+            .map(UdpSocket::from)
+            .expect("failed to register the socket with the tokio reactor");
+    }
+
+    socket.into()
+}
+
+#[cfg(unix)]
+impl From<std::os::fd::OwnedFd> for UdpSocket {
+    /// Takes ownership of the file descriptor, wrapping it into the variant of the current context.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the socket can't be registered with the tokio reactor.
+    fn from(fd: std::os::fd::OwnedFd) -> Self {
+        from_std_in_context(std::net::UdpSocket::from(fd))
+    }
+}
+
+#[cfg(windows)]
+impl From<std::os::windows::io::OwnedSocket> for UdpSocket {
+    /// Takes ownership of the socket, wrapping it into the variant of the current context.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the socket can't be registered with the tokio reactor.
+    fn from(socket: std::os::windows::io::OwnedSocket) -> Self {
+        from_std_in_context(std::net::UdpSocket::from(socket))
+    }
+}
+
+#[cfg(unix)]
+impl From<UdpSocket> for std::os::fd::OwnedFd {
+    /// Releases the file descriptor of the socket.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the socket can't be deregistered from the tokio reactor.
+    fn from(socket: UdpSocket) -> Self {
+        match socket.0 {
+            UdpSocketInner::Std(socket) => socket.into(),
+            #[cfg(tokio_net)]
+            UdpSocketInner::Tokio(socket) => socket
+                .into_std()
+                .expect("failed to deregister the socket from the tokio reactor")
+                .into(),
+        }
+    }
+}
+
+#[cfg(windows)]
+impl From<UdpSocket> for std::os::windows::io::OwnedSocket {
+    /// Releases the socket.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the socket can't be deregistered from the tokio reactor.
+    fn from(socket: UdpSocket) -> Self {
+        match socket.0 {
+            UdpSocketInner::Std(socket) => socket.into(),
+            #[cfg(tokio_net)]
+            UdpSocketInner::Tokio(socket) => socket
+                .into_std()
+                .expect("failed to deregister the socket from the tokio reactor")
+                .into(),
+        }
+    }
+}
+
+#[cfg(unix)]
+impl std::os::fd::IntoRawFd for UdpSocket {
+    fn into_raw_fd(self) -> std::os::fd::RawFd {
+        std::os::fd::OwnedFd::from(self).into_raw_fd()
+    }
+}
+
+#[cfg(windows)]
+impl std::os::windows::io::IntoRawSocket for UdpSocket {
+    fn into_raw_socket(self) -> std::os::windows::io::RawSocket {
+        std::os::windows::io::OwnedSocket::from(self).into_raw_socket()
+    }
+}
+
+#[cfg(socket2)]
+#[cfg_attr(docsrs, doc(cfg(feature = "socket2")))]
+impl From<socket2::Socket> for UdpSocket {
+    /// Takes ownership of a socket configured with socket2, wrapping it into the variant of the current context.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the socket can't be registered with the tokio reactor.
+    fn from(socket: socket2::Socket) -> Self {
+        from_std_in_context(std::net::UdpSocket::from(socket))
+    }
+}
+
+#[cfg(socket2)]
+#[cfg_attr(docsrs, doc(cfg(feature = "socket2")))]
+impl From<UdpSocket> for socket2::Socket {
+    /// Releases the socket, to configure it with socket2.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases as the conversion into the owned descriptor of the socket.
+    fn from(socket: UdpSocket) -> Self {
+        #[cfg(unix)]
+        let socket = std::os::fd::OwnedFd::from(socket);
+        #[cfg(windows)]
+        let socket = std::os::windows::io::OwnedSocket::from(socket);
+
+        socket2::Socket::from(socket)
+    }
+}
+
 impl UdpSocket {
-    maybe_fut_constructor_result!(
-        /// Creates a new UDP socket from the given address.
-        bind(addr: std::net::SocketAddr) -> std::io::Result<UdpSocket>,
-        std::net::UdpSocket::bind,
-        tokio::net::UdpSocket::bind,
-        tokio_net
-    );
+    /// Creates a new UDP socket from the given address.
+    ///
+    /// If `addr` resolves to several addresses, each of them is tried in order until one succeeds; otherwise,
+    /// the error of the last attempt is returned.
+    pub async fn bind(addr: impl super::ToSocketAddrs) -> std::io::Result<UdpSocket> {
+        super::lookup::each_addr(addr, Self::bind_addr).await
+    }
+
+    /// Creates a new UDP socket bound to a single address.
+    async fn bind_addr(addr: std::net::SocketAddr) -> std::io::Result<UdpSocket> {
+        maybe_fut_constructor_result!(
+            @body
+            (addr),
+            std::net::UdpSocket::bind,
+            tokio::net::UdpSocket::bind,
+            tokio_net
+        )
+    }
+
+    /// Creates a UDP socket bound to the specified address with the std implementation, regardless of the context.
+    ///
+    /// Unlike [`Self::bind`], it never checks the context, which is useful to create a socket in an async context to use it on another thread.
+    pub fn bind_std(addr: std::net::SocketAddr) -> std::io::Result<Self> {
+        std::net::UdpSocket::bind(addr).map(Self::from)
+    }
+
+    /// Creates a UDP socket bound to the specified address with the tokio implementation, regardless of the context.
+    ///
+    /// Unlike [`Self::bind`], it never checks the context. It must be called from within a tokio runtime.
+    #[cfg(tokio_net)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio-net")))]
+    pub async fn bind_tokio(addr: std::net::SocketAddr) -> std::io::Result<Self> {
+        tokio::net::UdpSocket::bind(addr).await.map(Self::from)
+    }
 
     maybe_fut_method!(
         /// Receives a single datagram messages on the socket.
@@ -129,6 +282,19 @@ impl UdpSocket {
         tokio_net
     );
 
+    /// Converts the socket into a [`socket2::Socket`], to set the options not exposed by maybe-fut.
+    ///
+    /// The [`socket2::Socket`] can be converted back into a socket of the current context with [`From`].
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases as the conversion into the owned descriptor of the socket.
+    #[cfg(socket2)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "socket2")))]
+    pub fn into_socket2(self) -> socket2::Socket {
+        self.into()
+    }
+
     maybe_fut_method_sync!(
         /// Returns the socket address of the local endpoint this socket is bound to.
         local_addr() -> std::io::Result<std::net::SocketAddr>,
@@ -324,6 +490,57 @@ impl UdpSocket {
         }
     }
 
+    /// Sets the value of the `IP_MULTICAST_IF` option on the socket, selecting the interface, by its address, used to
+    /// send the IPv4 multicast packets.
+    ///
+    /// [`Ipv4Addr::UNSPECIFIED`] lets the system choose the interface.
+    pub fn set_multicast_if_v4(&self, interface: Ipv4Addr) -> std::io::Result<()> {
+        socket2::SockRef::from(self).set_multicast_if_v4(&interface)
+    }
+
+    /// Gets the value of the `IP_MULTICAST_IF` option on the socket.
+    pub fn multicast_if_v4(&self) -> std::io::Result<Ipv4Addr> {
+        socket2::SockRef::from(self).multicast_if_v4()
+    }
+
+    /// Sets the value of the `IPV6_MULTICAST_IF` option on the socket, selecting the interface, by its index, used to
+    /// send the IPv6 multicast packets.
+    ///
+    /// `0` lets the system choose the interface.
+    pub fn set_multicast_if_v6(&self, interface: u32) -> std::io::Result<()> {
+        socket2::SockRef::from(self).set_multicast_if_v6(interface)
+    }
+
+    /// Gets the value of the `IPV6_MULTICAST_IF` option on the socket.
+    pub fn multicast_if_v6(&self) -> std::io::Result<u32> {
+        socket2::SockRef::from(self).multicast_if_v6()
+    }
+
+    /// Sets the value of the `IP_TOS` option on this socket, the type-of-service field of the IPv4 packets sent from it.
+    ///
+    /// The DSCP class of the packets is in the upper six bits, e.g. `0xb8` for Expedited Forwarding.
+    pub fn set_tos(&self, tos: u8) -> std::io::Result<()> {
+        super::tos::set_tos(socket2::SockRef::from(self), tos)
+    }
+
+    /// Gets the value of the `IP_TOS` option on this socket.
+    pub fn tos(&self) -> std::io::Result<u8> {
+        super::tos::tos(socket2::SockRef::from(self))
+    }
+
+    /// Sets the value of the `IPV6_TCLASS` option on this socket, the traffic class of the IPv6 packets sent from it.
+    ///
+    /// It's only supported on Linux, Android, macOS and the BSDs; on the other platforms, it fails with
+    /// [`std::io::ErrorKind::Unsupported`].
+    pub fn set_tclass_v6(&self, tclass: u8) -> std::io::Result<()> {
+        super::tos::set_tclass_v6(socket2::SockRef::from(self), tclass)
+    }
+
+    /// Gets the value of the `IPV6_TCLASS` option on this socket.
+    pub fn tclass_v6(&self) -> std::io::Result<u8> {
+        super::tos::tclass_v6(socket2::SockRef::from(self))
+    }
+
     maybe_fut_method_sync!(
         /// Gets the value of the `SO_ERROR` option on the socket.
         take_error() -> std::io::Result<Option<std::io::Error>>,
@@ -332,16 +549,15 @@ impl UdpSocket {
         tokio_net
     );
 
-    /// Connects this UDP socket to a remote address,
-    /// allowing the send and recv syscalls to be used to send data and also applies filters to only
-    /// receive data from the specified address.
-    pub async fn connect(&self, addr: SocketAddr) -> std::io::Result<()> {
-        match &self.0 {
-            UdpSocketInner::Std(socket) => socket.connect(addr),
-            #[cfg(feature = "tokio-net")]
-            UdpSocketInner::Tokio(socket) => socket.connect(addr).await,
-        }
-    }
+    maybe_fut_method!(
+        /// Connects this UDP socket to a remote address,
+        /// allowing the send and recv syscalls to be used to send data and also applies filters to only
+        /// receive data from the specified address.
+        connect(addr: SocketAddr) -> std::io::Result<()>,
+        UdpSocketInner::Std,
+        UdpSocketInner::Tokio,
+        tokio_net
+    );
 
     maybe_fut_method!(
         /// Sendss data on the socket to the remote address this socket is connected to.
@@ -373,6 +589,150 @@ impl UdpSocket {
         tokio_net
     );
 
+    /// Sends the buffers as a single datagram to the given address.
+    ///
+    /// On Success, returns the number of bytes written.
+    /// The buffers are gathered by `sendmsg`.
+    pub async fn send_to_vectored(
+        &self,
+        bufs: &[IoSlice<'_>],
+        target: SocketAddr,
+    ) -> std::io::Result<usize> {
+        match &self.0 {
+            UdpSocketInner::Std(socket) => {
+                super::vectored::send_to_vectored(socket2::SockRef::from(socket), bufs, target)
+            }
+            #[cfg(feature = "tokio-net")]
+            UdpSocketInner::Tokio(socket) => {
+                socket
+                    .async_io(tokio::io::Interest::WRITABLE, || {
+                        super::vectored::send_to_vectored(
+                            socket2::SockRef::from(socket),
+                            bufs,
+                            target,
+                        )
+                    })
+                    .await
+            }
+        }
+    }
+
+    /// Receives a single datagram message on the socket, scattering it into the buffers.
+    ///
+    /// On success, returns the number of bytes read and the source address.
+    /// The buffers are filled by `recvmsg`.
+    pub async fn recv_from_vectored(
+        &self,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> std::io::Result<(usize, SocketAddr)> {
+        match &self.0 {
+            UdpSocketInner::Std(socket) => {
+                super::vectored::recv_from_vectored(socket2::SockRef::from(socket), bufs)
+            }
+            #[cfg(feature = "tokio-net")]
+            UdpSocketInner::Tokio(socket) => {
+                socket
+                    .async_io(tokio::io::Interest::READABLE, || {
+                        super::vectored::recv_from_vectored(socket2::SockRef::from(socket), bufs)
+                    })
+                    .await
+            }
+        }
+    }
+
+    /// Sends the buffers as a single datagram to the remote address this socket is connected to.
+    ///
+    /// On Success, returns the number of bytes written.
+    /// The buffers are gathered by `sendmsg`.
+    pub async fn send_vectored(&self, bufs: &[IoSlice<'_>]) -> std::io::Result<usize> {
+        match &self.0 {
+            UdpSocketInner::Std(socket) => {
+                super::vectored::send_vectored(socket2::SockRef::from(socket), bufs)
+            }
+            #[cfg(feature = "tokio-net")]
+            UdpSocketInner::Tokio(socket) => {
+                socket
+                    .async_io(tokio::io::Interest::WRITABLE, || {
+                        super::vectored::send_vectored(socket2::SockRef::from(socket), bufs)
+                    })
+                    .await
+            }
+        }
+    }
+
+    /// Receives a single datagram message on the connected socket, scattering it into the buffers.
+    ///
+    /// On success, returns the number of bytes read.
+    /// The buffers are filled by `recvmsg`.
+    pub async fn recv_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> std::io::Result<usize> {
+        match &self.0 {
+            UdpSocketInner::Std(socket) => {
+                super::vectored::recv_vectored(socket2::SockRef::from(socket), bufs)
+            }
+            #[cfg(feature = "tokio-net")]
+            UdpSocketInner::Tokio(socket) => {
+                socket
+                    .async_io(tokio::io::Interest::READABLE, || {
+                        super::vectored::recv_vectored(socket2::SockRef::from(socket), bufs)
+                    })
+                    .await
+            }
+        }
+    }
+
+    /// Waits until the socket is readable, i.e. a receive may not block, to drive manual non-blocking IO loops.
+    ///
+    /// With std, the current thread is blocked with `poll`, which is only supported on Unix.
+    /// The readiness may be spurious, so the following receive can still fail with
+    /// [`std::io::ErrorKind::WouldBlock`].
+    pub async fn readable(&self) -> std::io::Result<()> {
+        match &self.0 {
+            UdpSocketInner::Std(socket) => super::ready::readable(socket),
+            #[cfg(feature = "tokio-net")]
+            UdpSocketInner::Tokio(socket) => socket.readable().await,
+        }
+    }
+
+    /// Waits until the socket is writable, i.e. a send may not block, to drive manual non-blocking IO loops.
+    ///
+    /// With std, the current thread is blocked with `poll`, which is only supported on Unix.
+    /// The readiness may be spurious, so the following send can still fail with [`std::io::ErrorKind::WouldBlock`].
+    pub async fn writable(&self) -> std::io::Result<()> {
+        match &self.0 {
+            UdpSocketInner::Std(socket) => super::ready::writable(socket),
+            #[cfg(feature = "tokio-net")]
+            UdpSocketInner::Tokio(socket) => socket.writable().await,
+        }
+    }
+
+    /// Tries to receive a single datagram message on the connected socket, without waiting.
+    ///
+    /// If no datagram is available, it fails with [`std::io::ErrorKind::WouldBlock`]; it's usually called after
+    /// [`Self::readable`]. On success, returns the number of bytes read.
+    /// On the platforms other than Unix, the std socket must be in non-blocking mode, otherwise the call blocks.
+    pub fn try_recv(&self, buf: &mut [u8]) -> std::io::Result<usize> {
+        #[cfg(feature = "tokio-net")]
+        if let UdpSocketInner::Tokio(socket) = &self.0 {
+            return socket.try_recv(buf);
+        }
+
+        super::ready::try_recv(socket2::SockRef::from(self), buf)
+    }
+
+    /// Tries to send data on the socket to the remote address it is connected to, without waiting.
+    ///
+    /// If the socket isn't writable, it fails with [`std::io::ErrorKind::WouldBlock`]; it's usually called after
+    /// [`Self::writable`]. On success, returns the number of bytes written.
+    /// On the platforms other than Unix, the std socket must be in non-blocking mode, otherwise the call blocks.
+    pub fn try_send(&self, buf: &[u8]) -> std::io::Result<usize> {
+        #[cfg(feature = "tokio-net")]
+        if let UdpSocketInner::Tokio(socket) = &self.0 {
+            return socket.try_send(buf);
+        }
+
+        super::ready::try_send(socket2::SockRef::from(self), buf)
+    }
+
     /// Moves this UDP socket into or out of non-blocking mode.
     ///
     /// It doesn't work with Tokio's `UdpSocket` because it doesn't support non-blocking mode.
@@ -476,6 +836,167 @@ mod test {
         // server_handle.join().expect("server thread panicked");
     }
 
+    #[test]
+    #[serial_test::serial]
+    fn test_should_send_and_recv_vectored_udp_std() {
+        let (_server_handle, server_addr, exit) = echo_server();
+        let socket = bind_std();
+
+        let sent_bytes = block_on(socket.send_to_vectored(
+            &[IoSlice::new(b"Hello, "), IoSlice::new(b"UDP!")],
+            server_addr,
+        ))
+        .expect("failed to send");
+        assert_eq!(sent_bytes, 11);
+
+        let mut head = [0; 5];
+        let mut tail = [0; 16];
+        let (received_bytes, src) = block_on(
+            socket
+                .recv_from_vectored(&mut [IoSliceMut::new(&mut head), IoSliceMut::new(&mut tail)]),
+        )
+        .expect("failed to receive");
+        assert_eq!(received_bytes, 11);
+        assert_eq!(src, server_addr);
+        assert_eq!(&head, b"Hello");
+        assert_eq!(&tail[..6], b", UDP!");
+
+        block_on(socket.connect(server_addr)).expect("failed to connect");
+        let sent_bytes =
+            block_on(socket.send_vectored(&[IoSlice::new(b"Ping"), IoSlice::new(b"!")]))
+                .expect("failed to send");
+        assert_eq!(sent_bytes, 5);
+        let mut head = [0; 2];
+        let mut tail = [0; 8];
+        let received_bytes = block_on(
+            socket.recv_vectored(&mut [IoSliceMut::new(&mut head), IoSliceMut::new(&mut tail)]),
+        )
+        .expect("failed to receive");
+        assert_eq!(received_bytes, 5);
+        assert_eq!(&head, b"Pi");
+        assert_eq!(&tail[..3], b"ng!");
+
+        exit.store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    #[cfg(feature = "tokio-net")]
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_should_send_and_recv_vectored_udp_tokio() {
+        let (_server_handle, server_addr, exit) = echo_server();
+        let socket = bind_tokio().await;
+
+        let sent_bytes = socket
+            .send_to_vectored(
+                &[IoSlice::new(b"Hello, "), IoSlice::new(b"UDP!")],
+                server_addr,
+            )
+            .await
+            .expect("failed to send");
+        assert_eq!(sent_bytes, 11);
+
+        let mut head = [0; 5];
+        let mut tail = [0; 16];
+        let (received_bytes, src) = socket
+            .recv_from_vectored(&mut [IoSliceMut::new(&mut head), IoSliceMut::new(&mut tail)])
+            .await
+            .expect("failed to receive");
+        assert_eq!(received_bytes, 11);
+        assert_eq!(src, server_addr);
+        assert_eq!(&head, b"Hello");
+        assert_eq!(&tail[..6], b", UDP!");
+
+        socket
+            .connect(server_addr)
+            .await
+            .expect("failed to connect");
+        socket
+            .send_vectored(&[IoSlice::new(b"Ping"), IoSlice::new(b"!")])
+            .await
+            .expect("failed to send");
+        let mut head = [0; 2];
+        let mut tail = [0; 8];
+        let received_bytes = socket
+            .recv_vectored(&mut [IoSliceMut::new(&mut head), IoSliceMut::new(&mut tail)])
+            .await
+            .expect("failed to receive");
+        assert_eq!(received_bytes, 5);
+        assert_eq!(&head, b"Pi");
+        assert_eq!(&tail[..3], b"ng!");
+
+        exit.store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[serial_test::serial]
+    fn test_should_wait_for_readiness_udp_std() {
+        let socket = bind_std();
+        socket
+            .set_nonblocking(true)
+            .expect("failed to set nonblocking");
+        let addr = socket.local_addr().expect("failed to get local addr");
+
+        block_on(socket.writable()).expect("failed to wait for writable");
+        block_on(socket.send_to(b"Ping", addr)).expect("failed to send");
+        block_on(socket.readable()).expect("failed to wait for readable");
+        let mut buf = [0; 4];
+        let (size, _) = block_on(socket.recv_from(&mut buf)).expect("failed to receive");
+        assert_eq!(&buf[..size], b"Ping");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[serial_test::serial]
+    fn test_should_try_send_and_recv_udp_std() {
+        let socket = bind_std();
+        let addr = socket.local_addr().expect("failed to get local addr");
+        block_on(socket.connect(addr)).expect("failed to connect");
+
+        let mut buf = [0; 4];
+        let err = socket
+            .try_recv(&mut buf)
+            .expect_err("nothing should be received");
+        assert_eq!(err.kind(), std::io::ErrorKind::WouldBlock);
+
+        assert_eq!(socket.try_send(b"Ping").expect("failed to send"), 4);
+        block_on(socket.readable()).expect("failed to wait for readable");
+        assert_eq!(socket.try_recv(&mut buf).expect("failed to receive"), 4);
+        assert_eq!(&buf, b"Ping");
+    }
+
+    #[cfg(feature = "tokio-net")]
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_should_try_send_and_recv_udp_tokio() {
+        let socket = bind_tokio().await;
+        let addr = socket.local_addr().expect("failed to get local addr");
+        socket.connect(addr).await.expect("failed to connect");
+
+        let mut buf = [0; 4];
+        let err = socket
+            .try_recv(&mut buf)
+            .expect_err("nothing should be received");
+        assert_eq!(err.kind(), std::io::ErrorKind::WouldBlock);
+
+        socket
+            .writable()
+            .await
+            .expect("failed to wait for writable");
+        assert_eq!(socket.try_send(b"Ping").expect("failed to send"), 4);
+        let size = loop {
+            socket
+                .readable()
+                .await
+                .expect("failed to wait for readable");
+            match socket.try_recv(&mut buf) {
+                Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => continue,
+                res => break res.expect("failed to receive"),
+            }
+        };
+        assert_eq!(&buf[..size], b"Ping");
+    }
+
     #[test]
     fn test_should_get_options_std() {
         let socket = bind_std();
@@ -720,4 +1241,135 @@ mod test {
         });
         (handle, addr, exit)
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_should_convert_udp_socket_from_and_into_owned_fd() {
+        let std_socket = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+        let addr = std_socket.local_addr().unwrap();
+
+        let socket = UdpSocket::from(std::os::fd::OwnedFd::from(std_socket));
+        assert!(socket.get_std_ref().is_some());
+        assert_eq!(socket.local_addr().unwrap(), addr);
+
+        let std_socket = std::net::UdpSocket::from(std::os::fd::OwnedFd::from(socket));
+        assert_eq!(std_socket.local_addr().unwrap(), addr);
+    }
+
+    #[cfg(socket2)]
+    #[test]
+    #[serial_test::serial]
+    fn test_should_convert_udp_socket_from_and_into_socket2() {
+        let socket = socket2::Socket::new(socket2::Domain::IPV4, socket2::Type::DGRAM, None)
+            .expect("failed to create socket");
+        socket
+            .set_reuse_address(true)
+            .expect("failed to set reuse address");
+        socket
+            .bind(&"127.0.0.1:0".parse::<SocketAddr>().unwrap().into())
+            .expect("failed to bind");
+
+        let socket = UdpSocket::from(socket);
+        assert!(matches!(socket.0, UdpSocketInner::Std(_)));
+        let addr = socket.local_addr().expect("failed to get local addr");
+
+        let socket = socket.into_socket2();
+        assert!(socket.reuse_address().expect("failed to get reuse address"));
+        assert_eq!(
+            socket.local_addr().unwrap().as_socket(),
+            Some(addr),
+            "the socket should still be bound to the same address"
+        );
+    }
+
+    #[cfg(tokio_net)]
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_should_bind_with_explicit_backend() {
+        let addr = "127.0.0.1:0"
+            .parse::<std::net::SocketAddr>()
+            .expect("Failed to parse address");
+
+        let socket = UdpSocket::bind_std(addr).expect("Failed to bind");
+        assert!(matches!(socket.0, UdpSocketInner::Std(_)));
+
+        let socket = UdpSocket::bind_tokio(addr).await.expect("Failed to bind");
+        assert!(matches!(socket.0, UdpSocketInner::Tokio(_)));
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_should_set_multicast_if_v4_std() {
+        let addr = "127.0.0.1:0"
+            .parse::<std::net::SocketAddr>()
+            .expect("Failed to parse address");
+        let socket = block_on(UdpSocket::bind(addr)).expect("Failed to bind");
+        socket
+            .set_multicast_if_v4(Ipv4Addr::LOCALHOST)
+            .expect("Failed to set multicast interface");
+        assert_eq!(
+            socket
+                .multicast_if_v4()
+                .expect("Failed to get multicast interface"),
+            Ipv4Addr::LOCALHOST
+        );
+    }
+
+    #[cfg(tokio_net)]
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_should_set_multicast_if_v4_tokio() {
+        let addr = "127.0.0.1:0"
+            .parse::<std::net::SocketAddr>()
+            .expect("Failed to parse address");
+        let socket = UdpSocket::bind(addr).await.expect("Failed to bind");
+        assert!(matches!(socket.0, UdpSocketInner::Tokio(_)));
+        socket
+            .set_multicast_if_v4(Ipv4Addr::LOCALHOST)
+            .expect("Failed to set multicast interface");
+        assert_eq!(
+            socket
+                .multicast_if_v4()
+                .expect("Failed to get multicast interface"),
+            Ipv4Addr::LOCALHOST
+        );
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_should_set_tos_std() {
+        let addr = "127.0.0.1:0"
+            .parse::<std::net::SocketAddr>()
+            .expect("Failed to parse address");
+        let socket = block_on(UdpSocket::bind(addr)).expect("Failed to bind");
+        socket.set_tos(0x28).expect("Failed to set IP_TOS");
+        assert_eq!(socket.tos().expect("Failed to get IP_TOS"), 0x28);
+    }
+
+    #[cfg(tokio_net)]
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_should_set_tos_tokio() {
+        let addr = "127.0.0.1:0"
+            .parse::<std::net::SocketAddr>()
+            .expect("Failed to parse address");
+        let socket = UdpSocket::bind(addr).await.expect("Failed to bind");
+        assert!(matches!(socket.0, UdpSocketInner::Tokio(_)));
+        socket.set_tos(0x28).expect("Failed to set IP_TOS");
+        assert_eq!(socket.tos().expect("Failed to get IP_TOS"), 0x28);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    #[serial_test::serial]
+    fn test_should_set_tclass_v6() {
+        let addr = "[::1]:0"
+            .parse::<std::net::SocketAddr>()
+            .expect("Failed to parse address");
+        let socket = block_on(UdpSocket::bind(addr)).expect("Failed to bind");
+        socket
+            .set_tclass_v6(0xb8)
+            .expect("Failed to set IPV6_TCLASS");
+        assert_eq!(socket.tclass_v6().expect("Failed to get IPV6_TCLASS"), 0xb8);
+    }
 }
@@ -0,0 +1,195 @@
+use std::os::unix::net::SocketAddr;
+use std::path::Path;
+
+use crate::maybe_fut_method_sync;
+
+/// A Unix socket server, listening for connections.
+///
+/// You can accept a new connection by using the [`UnixListener::accept`] method.
+///
+/// A [`UnixListener`] is created by calling [`UnixListener::bind`].
+#[derive(Unwrap, Debug)]
+#[unwrap_types(
+    std(std::os::unix::net::UnixListener),
+    tokio(tokio::net::UnixListener),
+    tokio_gated("tokio-net")
+)]
+pub struct UnixListener(UnixListenerInner);
+
+#[derive(Debug)]
+enum UnixListenerInner {
+    Std(std::os::unix::net::UnixListener),
+    #[cfg(tokio_net)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio-net")))]
+    Tokio(tokio::net::UnixListener),
+}
+
+impl From<std::os::unix::net::UnixListener> for UnixListener {
+    fn from(listener: std::os::unix::net::UnixListener) -> Self {
+        Self(UnixListenerInner::Std(listener))
+    }
+}
+
+#[cfg(tokio_net)]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio-net")))]
+impl From<tokio::net::UnixListener> for UnixListener {
+    fn from(listener: tokio::net::UnixListener) -> Self {
+        Self(UnixListenerInner::Tokio(listener))
+    }
+}
+
+impl UnixListener {
+    /// Creates a new [`UnixListener`] bound to the specified path.
+    ///
+    /// The returned listener is ready for accepting connections.
+    /// Binding is not async, neither with std nor with tokio, so this is a sync constructor.
+    pub fn bind(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        #[cfg(tokio_net)]
+        if crate::is_tokio_context() {
+            return tokio::net::UnixListener::bind(path).map(Self::from);
+        }
+
+        std::os::unix::net::UnixListener::bind(path).map(Self::from)
+    }
+
+    /// Creates a new Unix listener bound to the specified path with the std implementation, regardless of the context.
+    ///
+    /// Unlike [`Self::bind`], it never checks the context, which is useful to create a listener in an async context to use it on another thread.
+    pub fn bind_std(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        std::os::unix::net::UnixListener::bind(path).map(Self::from)
+    }
+
+    /// Creates a new Unix listener bound to the specified path with the tokio implementation, regardless of the context.
+    ///
+    /// Unlike [`Self::bind`], it never checks the context. It must be called from within a tokio runtime.
+    #[cfg(tokio_net)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio-net")))]
+    pub fn bind_tokio(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        tokio::net::UnixListener::bind(path).map(Self::from)
+    }
+
+    /// Accepts a new incoming connection.
+    ///
+    /// This method will block until a new connection is established.
+    pub async fn accept(&self) -> std::io::Result<(crate::net::UnixStream, SocketAddr)> {
+        match &self.0 {
+            UnixListenerInner::Std(listener) => {
+                let (stream, addr) = listener.accept()?;
+                Ok((crate::net::UnixStream::from(stream), addr))
+            }
+            #[cfg(tokio_net)]
+            UnixListenerInner::Tokio(listener) => {
+                let (stream, addr) = listener.accept().await?;
+                Ok((crate::net::UnixStream::from(stream), addr.into()))
+            }
+        }
+    }
+
+    maybe_fut_method_sync!(
+        /// Returns the local socket address of this listener.
+        local_addr() -> std::io::Result<SocketAddr> => SocketAddr::from,
+        UnixListenerInner::Std,
+        UnixListenerInner::Tokio,
+        tokio_net
+    );
+
+    maybe_fut_method_sync!(
+        /// Returns the value of the `SO_ERROR` option.
+        take_error() -> std::io::Result<Option<std::io::Error>>,
+        UnixListenerInner::Std,
+        UnixListenerInner::Tokio,
+        tokio_net
+    );
+}
+
+impl std::os::fd::AsFd for UnixListener {
+    fn as_fd(&self) -> std::os::fd::BorrowedFd<'_> {
+        match &self.0 {
+            UnixListenerInner::Std(listener) => listener.as_fd(),
+            #[cfg(tokio_net)]
+            UnixListenerInner::Tokio(listener) => listener.as_fd(),
+        }
+    }
+}
+
+impl std::os::fd::AsRawFd for UnixListener {
+    fn as_raw_fd(&self) -> std::os::fd::RawFd {
+        match &self.0 {
+            UnixListenerInner::Std(listener) => listener.as_raw_fd(),
+            #[cfg(tokio_net)]
+            UnixListenerInner::Tokio(listener) => listener.as_raw_fd(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::io::{Read, Write};
+    use crate::net::UnixStream;
+    use crate::{SyncRuntime, Unwrap};
+
+    #[test]
+    fn test_should_accept_std() {
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let path = dir.path().join("listener.sock");
+        let listener = UnixListener::bind(&path).expect("failed to bind");
+        assert!(matches!(listener.0, UnixListenerInner::Std(_)));
+        assert_eq!(
+            listener
+                .local_addr()
+                .expect("failed to get local addr")
+                .as_pathname(),
+            Some(path.as_path())
+        );
+
+        let client_path = path.clone();
+        let client = std::thread::spawn(move || {
+            let mut stream = UnixStream::connect_std(&client_path).expect("failed to connect");
+            SyncRuntime::block_on(stream.write_all(b"Ping")).expect("failed to write");
+        });
+
+        let (mut stream, addr) =
+            SyncRuntime::block_on(listener.accept()).expect("failed to accept");
+        assert!(addr.is_unnamed());
+        let mut buf = [0; 4];
+        SyncRuntime::block_on(stream.read_exact(&mut buf)).expect("failed to read");
+        assert_eq!(&buf, b"Ping");
+        assert!(
+            listener
+                .take_error()
+                .expect("failed to take error")
+                .is_none()
+        );
+        assert!(listener.unwrap_std().local_addr().is_ok());
+
+        client.join().expect("failed to join");
+    }
+
+    #[cfg(tokio_net)]
+    #[tokio::test]
+    async fn test_should_accept_tokio() {
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let path = dir.path().join("listener.sock");
+        let listener = UnixListener::bind(&path).expect("failed to bind");
+        assert!(matches!(listener.0, UnixListenerInner::Tokio(_)));
+
+        let client = tokio::spawn({
+            let path = path.clone();
+            async move {
+                let mut stream = UnixStream::connect(&path).await.expect("failed to connect");
+                stream.write_all(b"Ping").await.expect("failed to write");
+            }
+        });
+
+        let (mut stream, _addr) = listener.accept().await.expect("failed to accept");
+        let mut buf = [0; 4];
+        stream.read_exact(&mut buf).await.expect("failed to read");
+        assert_eq!(&buf, b"Ping");
+
+        client.await.expect("failed to join");
+    }
+}
@@ -0,0 +1,212 @@
+use std::os::unix::net::SocketAddr;
+use std::path::Path;
+
+use crate::{maybe_fut_constructor_result, maybe_fut_method_sync};
+
+/// A Unix stream socket, connected to a local peer.
+///
+/// A Unix stream is created by connecting to a socket bound to a path, via the [`UnixStream::connect`] method.
+///
+/// Reading and writing to a [`UnixStream`] is usually done by using the [`crate::io::Read`] and [`crate::io::Write`] traits.
+#[derive(Debug, Unwrap, Read, Write)]
+#[io(feature("tokio-net"))]
+#[unwrap_types(
+    std(std::os::unix::net::UnixStream),
+    tokio(tokio::net::UnixStream),
+    tokio_gated("tokio-net")
+)]
+pub struct UnixStream(UnixStreamInner);
+
+#[derive(Debug)]
+enum UnixStreamInner {
+    Std(std::os::unix::net::UnixStream),
+    #[cfg(tokio_net)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio-net")))]
+    Tokio(tokio::net::UnixStream),
+}
+
+impl From<std::os::unix::net::UnixStream> for UnixStream {
+    fn from(stream: std::os::unix::net::UnixStream) -> Self {
+        Self(UnixStreamInner::Std(stream))
+    }
+}
+
+#[cfg(tokio_net)]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio-net")))]
+impl From<tokio::net::UnixStream> for UnixStream {
+    fn from(stream: tokio::net::UnixStream) -> Self {
+        Self(UnixStreamInner::Tokio(stream))
+    }
+}
+
+impl std::os::fd::AsFd for UnixStream {
+    fn as_fd(&self) -> std::os::fd::BorrowedFd<'_> {
+        match &self.0 {
+            UnixStreamInner::Std(stream) => stream.as_fd(),
+            #[cfg(tokio_net)]
+            UnixStreamInner::Tokio(stream) => stream.as_fd(),
+        }
+    }
+}
+
+impl std::os::fd::AsRawFd for UnixStream {
+    fn as_raw_fd(&self) -> std::os::fd::RawFd {
+        match &self.0 {
+            UnixStreamInner::Std(stream) => stream.as_raw_fd(),
+            #[cfg(tokio_net)]
+            UnixStreamInner::Tokio(stream) => stream.as_raw_fd(),
+        }
+    }
+}
+
+impl UnixStream {
+    maybe_fut_constructor_result!(
+        /// Connects to the socket bound to the specified path.
+        connect(path: impl AsRef<Path>) -> std::io::Result<UnixStream>,
+        std::os::unix::net::UnixStream::connect,
+        tokio::net::UnixStream::connect,
+        tokio_net
+    );
+
+    /// Connects to the socket bound to the specified path with the std implementation, regardless of the context.
+    ///
+    /// Unlike [`Self::connect`], it never checks the context, which is useful to create a stream in an async context to use it on another thread.
+    pub fn connect_std(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        std::os::unix::net::UnixStream::connect(path).map(Self::from)
+    }
+
+    /// Connects to the socket bound to the specified path with the tokio implementation, regardless of the context.
+    ///
+    /// Unlike [`Self::connect`], it never checks the context. It must be called from within a tokio runtime.
+    #[cfg(tokio_net)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio-net")))]
+    pub async fn connect_tokio(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        tokio::net::UnixStream::connect(path).await.map(Self::from)
+    }
+
+    maybe_fut_method_sync!(
+        /// Returns the socket address of the local half of this connection.
+        local_addr() -> std::io::Result<SocketAddr> => SocketAddr::from,
+        UnixStreamInner::Std,
+        UnixStreamInner::Tokio,
+        tokio_net
+    );
+
+    maybe_fut_method_sync!(
+        /// Returns the socket address of the remote half of this connection.
+        peer_addr() -> std::io::Result<SocketAddr> => SocketAddr::from,
+        UnixStreamInner::Std,
+        UnixStreamInner::Tokio,
+        tokio_net
+    );
+
+    maybe_fut_method_sync!(
+        /// Returns the value of the `SO_ERROR` option.
+        take_error() -> std::io::Result<Option<std::io::Error>>,
+        UnixStreamInner::Std,
+        UnixStreamInner::Tokio,
+        tokio_net
+    );
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::io::{Read as _, Write as _};
+    use std::os::unix::net::UnixListener;
+    use std::path::PathBuf;
+    use std::thread::JoinHandle;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::io::{Read, Write};
+    use crate::{SyncRuntime, Unwrap};
+
+    /// Binds a listener answering `Pong` to the first message of a single connection.
+    fn ping_server() -> (tempfile::TempDir, PathBuf, JoinHandle<()>) {
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let path = dir.path().join("ping.sock");
+        let listener = UnixListener::bind(&path).expect("failed to bind");
+
+        let join = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().expect("failed to accept");
+            let mut buf = [0; 4];
+            stream.read_exact(&mut buf).expect("failed to read");
+            assert_eq!(&buf, b"Ping");
+            stream.write_all(b"Pong").expect("failed to write");
+        });
+
+        (dir, path, join)
+    }
+
+    #[test]
+    fn test_should_connect_std() {
+        let (_dir, path, join) = ping_server();
+        let mut stream =
+            SyncRuntime::block_on(UnixStream::connect(&path)).expect("failed to connect");
+        assert!(matches!(stream.0, UnixStreamInner::Std(_)));
+        assert_eq!(
+            stream
+                .peer_addr()
+                .expect("failed to get peer addr")
+                .as_pathname(),
+            Some(path.as_path())
+        );
+        assert!(
+            stream
+                .local_addr()
+                .expect("failed to get local addr")
+                .is_unnamed()
+        );
+
+        SyncRuntime::block_on(stream.write_all(b"Ping")).expect("failed to write");
+        let mut buf = [0; 4];
+        SyncRuntime::block_on(stream.read_exact(&mut buf)).expect("failed to read");
+        assert_eq!(&buf, b"Pong");
+        assert!(stream.take_error().expect("failed to take error").is_none());
+        assert!(stream.unwrap_std().peer_addr().is_ok());
+
+        join.join().expect("failed to join");
+    }
+
+    #[cfg(tokio_net)]
+    #[tokio::test]
+    async fn test_should_connect_tokio() {
+        let (_dir, path, join) = ping_server();
+        let mut stream = UnixStream::connect(&path).await.expect("failed to connect");
+        assert!(matches!(stream.0, UnixStreamInner::Tokio(_)));
+        assert_eq!(
+            stream
+                .peer_addr()
+                .expect("failed to get peer addr")
+                .as_pathname(),
+            Some(path.as_path())
+        );
+
+        stream.write_all(b"Ping").await.expect("failed to write");
+        let mut buf = [0; 4];
+        stream.read_exact(&mut buf).await.expect("failed to read");
+        assert_eq!(&buf, b"Pong");
+
+        tokio::task::spawn_blocking(move || join.join())
+            .await
+            .expect("failed to join")
+            .expect("failed to join");
+    }
+
+    #[cfg(tokio_net)]
+    #[tokio::test]
+    async fn test_should_connect_std_in_tokio_context() {
+        let (_dir, path, join) = ping_server();
+        let mut stream = UnixStream::connect_std(&path).expect("failed to connect");
+        assert!(matches!(stream.0, UnixStreamInner::Std(_)));
+
+        stream.write_all(b"Ping").await.expect("failed to write");
+        let mut buf = [0; 4];
+        stream.read_exact(&mut buf).await.expect("failed to read");
+        assert_eq!(&buf, b"Pong");
+
+        join.join().expect("failed to join");
+    }
+}
@@ -0,0 +1,529 @@
+//! io_uring implementation of the net api, based on tokio-uring.
+//!
+//! tokio-uring sockets are bound to the thread of the runtime which created them, so they're not [`Send`].
+//! For this reason, they're provided by the separate [`TcpStream`], [`TcpListener`] and [`UdpSocket`] types rather
+//! than by the types of [`crate::net`], which can still be moved across tasks and threads when the `uring-net`
+//! feature is enabled.
+//!
+//! tokio-uring sockets only provide the I/O operations, which take owned buffers, so the data is copied from and
+//! into the borrowed buffers of the std api. The other methods (addresses, socket options) are performed on a std
+//! view of the same socket.
+//!
+//! The types of this module must be used inside of a tokio-uring runtime, such as the one started by
+//! [`crate::start_uring`].
+
+use std::io;
+use std::mem::ManuallyDrop;
+use std::net::{Ipv4Addr, Ipv6Addr, Shutdown, SocketAddr};
+use std::os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, OwnedFd, RawFd};
+
+use super::ToSocketAddrs;
+
+/// A tokio-uring socket, along with a std view of the same socket.
+struct Socket<T, S> {
+    socket: T,
+    /// Never dropped, since the descriptor is owned by `socket`.
+    std: ManuallyDrop<S>,
+}
+
+impl<T: AsRawFd, S: FromRawFd> From<T> for Socket<T, S> {
+    fn from(socket: T) -> Self {
+        // SAFETY: the descriptor is owned by the socket, which outlives the view
+        let std = ManuallyDrop::new(unsafe { S::from_raw_fd(socket.as_raw_fd()) });
+        Self { socket, std }
+    }
+}
+
+impl<T: AsRawFd, S> Socket<T, S> {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        // SAFETY: the descriptor is owned by the socket, which outlives the borrow
+        unsafe { BorrowedFd::borrow_raw(self.socket.as_raw_fd()) }
+    }
+}
+
+/// A TCP stream between a local and a remote socket, served by io_uring.
+///
+/// Reading and writing to a [`TcpStream`] is usually done by using the [`crate::io::Read`] and
+/// [`crate::io::Write`] traits.
+pub struct TcpStream(Socket<tokio_uring::net::TcpStream, std::net::TcpStream>);
+
+impl std::fmt::Debug for TcpStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("TcpStream").field(&*self.0.std).finish()
+    }
+}
+
+impl From<tokio_uring::net::TcpStream> for TcpStream {
+    fn from(stream: tokio_uring::net::TcpStream) -> Self {
+        Self(stream.into())
+    }
+}
+
+impl From<std::net::TcpStream> for TcpStream {
+    /// Takes ownership of the std stream, which must be in blocking mode.
+    fn from(stream: std::net::TcpStream) -> Self {
+        tokio_uring::net::TcpStream::from_std(stream).into()
+    }
+}
+
+impl TcpStream {
+    /// Opens a TCP connection to a remote host at the specified address.
+    ///
+    /// See [`crate::net::TcpStream::connect`].
+    pub async fn connect(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        super::lookup::each_addr(addr, |addr| async move {
+            tokio_uring::net::TcpStream::connect(addr)
+                .await
+                .map(Self::from)
+        })
+        .await
+    }
+
+    /// Creates a new stream sharing the descriptor of this one.
+    pub fn try_clone(&self) -> io::Result<Self> {
+        self.0.std.try_clone().map(Self::from)
+    }
+
+    /// Returns the local address that this stream is bound to.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.0.std.local_addr()
+    }
+
+    /// Returns the remote address that this stream is connected to.
+    pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+        self.0.std.peer_addr()
+    }
+
+    /// Shuts down the read, write, or both halves of this connection.
+    pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
+        self.0.socket.shutdown(how)
+    }
+
+    /// Sets the value of the `TCP_NODELAY` option on this socket.
+    pub fn set_nodelay(&self, nodelay: bool) -> io::Result<()> {
+        self.0.std.set_nodelay(nodelay)
+    }
+
+    /// Gets the value of the `TCP_NODELAY` option on this socket.
+    pub fn nodelay(&self) -> io::Result<bool> {
+        self.0.std.nodelay()
+    }
+
+    /// Sets the value for the `IP_TTL` option on this socket.
+    pub fn set_ttl(&self, ttl: u32) -> io::Result<()> {
+        self.0.std.set_ttl(ttl)
+    }
+
+    /// Gets the value of the `IP_TTL` option for this socket.
+    pub fn ttl(&self) -> io::Result<u32> {
+        self.0.std.ttl()
+    }
+
+    /// Returns the value of the `SO_ERROR` option.
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        self.0.std.take_error()
+    }
+
+    /// Closes the stream, returning a std stream sharing its descriptor, in blocking mode.
+    pub fn into_std(self) -> io::Result<std::net::TcpStream> {
+        self.0.std.try_clone()
+    }
+}
+
+impl crate::io::Read for TcpStream {
+    async fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let (res, data) = self.0.socket.read(Vec::with_capacity(buf.len())).await;
+        let size = res?;
+        buf[..size].copy_from_slice(&data[..size]);
+
+        Ok(size)
+    }
+}
+
+impl crate::io::Write for TcpStream {
+    async fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.socket.write(buf.to_vec()).await.0
+    }
+
+    /// Writes all the buffers with a single operation.
+    async fn write_vectored(&mut self, bufs: &[io::IoSlice<'_>]) -> io::Result<usize> {
+        let data: Vec<u8> = bufs.iter().flat_map(|buf| buf.iter().copied()).collect();
+        self.write(&data).await
+    }
+
+    /// Writes are not buffered, so there is nothing to flush.
+    async fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl AsFd for TcpStream {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.0.as_fd()
+    }
+}
+
+impl AsRawFd for TcpStream {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.socket.as_raw_fd()
+    }
+}
+
+impl TryFrom<TcpStream> for OwnedFd {
+    type Error = io::Error;
+
+    /// Returns a duplicate of the socket descriptor; the stream itself is closed when dropped.
+    fn try_from(stream: TcpStream) -> io::Result<Self> {
+        stream.as_fd().try_clone_to_owned()
+    }
+}
+
+/// A TCP socket server, listening for connections served by io_uring.
+///
+/// tokio-uring listeners don't expose their descriptor, so the connections are accepted through tokio,
+/// which runs alongside tokio-uring, and then handed over to tokio-uring.
+#[derive(Debug)]
+pub struct TcpListener(tokio::net::TcpListener);
+
+impl TcpListener {
+    /// Creates a new [`TcpListener`] bound to the specified address.
+    ///
+    /// See [`crate::net::TcpListener::bind`].
+    pub async fn bind(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        super::lookup::each_addr(addr, |addr| async move {
+            tokio::net::TcpListener::bind(addr).await.map(Self)
+        })
+        .await
+    }
+
+    /// Creates a new listener from a std listener.
+    pub fn from_std(listener: std::net::TcpListener) -> io::Result<Self> {
+        listener.set_nonblocking(true)?;
+        tokio::net::TcpListener::from_std(listener).map(Self)
+    }
+
+    /// Returns the std listener, in blocking mode.
+    pub fn into_std(self) -> io::Result<std::net::TcpListener> {
+        let listener = self.0.into_std()?;
+        listener.set_nonblocking(false)?;
+
+        Ok(listener)
+    }
+
+    /// Accepts a new incoming connection.
+    pub async fn accept(&self) -> io::Result<(TcpStream, SocketAddr)> {
+        let (stream, addr) = self.0.accept().await?;
+        // tokio sockets are non-blocking, while io_uring waits for the socket to be ready
+        let stream = stream.into_std()?;
+        stream.set_nonblocking(false)?;
+
+        Ok((stream.into(), addr))
+    }
+
+    /// Returns the local address that this listener is bound to.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.0.local_addr()
+    }
+
+    /// Sets the value for the `IP_TTL` option on this socket.
+    pub fn set_ttl(&self, ttl: u32) -> io::Result<()> {
+        self.0.set_ttl(ttl)
+    }
+
+    /// Gets the value of the `IP_TTL` option for this socket.
+    pub fn ttl(&self) -> io::Result<u32> {
+        self.0.ttl()
+    }
+}
+
+impl AsFd for TcpListener {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.0.as_fd()
+    }
+}
+
+impl AsRawFd for TcpListener {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.as_raw_fd()
+    }
+}
+
+/// A UDP socket, served by io_uring.
+pub struct UdpSocket(Socket<tokio_uring::net::UdpSocket, std::net::UdpSocket>);
+
+impl std::fmt::Debug for UdpSocket {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("UdpSocket").field(&*self.0.std).finish()
+    }
+}
+
+impl From<tokio_uring::net::UdpSocket> for UdpSocket {
+    fn from(socket: tokio_uring::net::UdpSocket) -> Self {
+        Self(socket.into())
+    }
+}
+
+impl From<std::net::UdpSocket> for UdpSocket {
+    /// Takes ownership of the std socket, which must be in blocking mode.
+    fn from(socket: std::net::UdpSocket) -> Self {
+        tokio_uring::net::UdpSocket::from_std(socket).into()
+    }
+}
+
+impl UdpSocket {
+    /// Creates a UDP socket bound to the given address.
+    ///
+    /// See [`crate::net::UdpSocket::bind`].
+    pub async fn bind(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        super::lookup::each_addr(addr, |addr| async move {
+            tokio_uring::net::UdpSocket::bind(addr)
+                .await
+                .map(Self::from)
+        })
+        .await
+    }
+
+    /// Creates a new socket sharing the descriptor of this one.
+    pub fn try_clone(&self) -> io::Result<Self> {
+        self.0.std.try_clone().map(Self::from)
+    }
+
+    /// Connects this UDP socket to a remote address, so that [`Self::send`] and [`Self::recv`] can be used.
+    pub async fn connect(&self, addr: SocketAddr) -> io::Result<()> {
+        self.0.socket.connect(addr).await
+    }
+
+    /// Receives a single datagram from the connected address.
+    pub async fn recv(&self, buf: &mut [u8]) -> io::Result<usize> {
+        let (res, data) = self.0.socket.read(Vec::with_capacity(buf.len())).await;
+        let size = res?;
+        buf[..size].copy_from_slice(&data[..size]);
+
+        Ok(size)
+    }
+
+    /// Receives a single datagram, returning the number of bytes read and the origin address.
+    pub async fn recv_from(&self, buf: &mut [u8]) -> io::Result<(usize, SocketAddr)> {
+        let (res, data) = self.0.socket.recv_from(Vec::with_capacity(buf.len())).await;
+        let (size, addr) = res?;
+        buf[..size].copy_from_slice(&data[..size]);
+
+        Ok((size, addr))
+    }
+
+    /// Sends data to the connected address.
+    pub async fn send(&self, buf: &[u8]) -> io::Result<usize> {
+        self.0.socket.write(buf.to_vec()).await.0
+    }
+
+    /// Sends data to the given address.
+    pub async fn send_to(&self, buf: &[u8], target: SocketAddr) -> io::Result<usize> {
+        self.0.socket.send_to(buf.to_vec(), target).await.0
+    }
+
+    /// Sends all the buffers as a single datagram, copied into one buffer.
+    pub async fn send_vectored(&self, bufs: &[io::IoSlice<'_>]) -> io::Result<usize> {
+        let data: Vec<u8> = bufs.iter().flat_map(|buf| buf.iter().copied()).collect();
+        self.send(&data).await
+    }
+
+    /// Sends all the buffers as a single datagram to `target`, copied into one buffer.
+    pub async fn send_to_vectored(
+        &self,
+        bufs: &[io::IoSlice<'_>],
+        target: SocketAddr,
+    ) -> io::Result<usize> {
+        let data: Vec<u8> = bufs.iter().flat_map(|buf| buf.iter().copied()).collect();
+        self.send_to(&data, target).await
+    }
+
+    /// Receives a datagram into one buffer, then copies it into the buffers.
+    pub async fn recv_vectored(&self, bufs: &mut [io::IoSliceMut<'_>]) -> io::Result<usize> {
+        let mut data = vec![0; bufs.iter().map(|buf| buf.len()).sum()];
+        let size = self.recv(&mut data).await?;
+
+        Ok(super::vectored::scatter(&data[..size], bufs))
+    }
+
+    /// Receives a datagram into one buffer, then copies it into the buffers.
+    pub async fn recv_from_vectored(
+        &self,
+        bufs: &mut [io::IoSliceMut<'_>],
+    ) -> io::Result<(usize, SocketAddr)> {
+        let mut data = vec![0; bufs.iter().map(|buf| buf.len()).sum()];
+        let (size, addr) = self.recv_from(&mut data).await?;
+
+        Ok((super::vectored::scatter(&data[..size], bufs), addr))
+    }
+
+    /// Returns the local address that this socket is bound to.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.0.std.local_addr()
+    }
+
+    /// Returns the remote address that this socket is connected to.
+    pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+        self.0.std.peer_addr()
+    }
+
+    /// Sets the value of the `SO_BROADCAST` option for this socket.
+    pub fn set_broadcast(&self, broadcast: bool) -> io::Result<()> {
+        self.0.std.set_broadcast(broadcast)
+    }
+
+    /// Gets the value of the `SO_BROADCAST` option for this socket.
+    pub fn broadcast(&self) -> io::Result<bool> {
+        self.0.std.broadcast()
+    }
+
+    /// Sets the value for the `IP_TTL` option on this socket.
+    pub fn set_ttl(&self, ttl: u32) -> io::Result<()> {
+        self.0.std.set_ttl(ttl)
+    }
+
+    /// Gets the value of the `IP_TTL` option for this socket.
+    pub fn ttl(&self) -> io::Result<u32> {
+        self.0.std.ttl()
+    }
+
+    /// Executes an operation of the `IP_ADD_MEMBERSHIP` type.
+    pub fn join_multicast_v4(&self, multiaddr: &Ipv4Addr, interface: &Ipv4Addr) -> io::Result<()> {
+        self.0.std.join_multicast_v4(multiaddr, interface)
+    }
+
+    /// Executes an operation of the `IPV6_ADD_MEMBERSHIP` type.
+    pub fn join_multicast_v6(&self, multiaddr: &Ipv6Addr, interface: u32) -> io::Result<()> {
+        self.0.std.join_multicast_v6(multiaddr, interface)
+    }
+
+    /// Executes an operation of the `IP_DROP_MEMBERSHIP` type.
+    pub fn leave_multicast_v4(&self, multiaddr: &Ipv4Addr, interface: &Ipv4Addr) -> io::Result<()> {
+        self.0.std.leave_multicast_v4(multiaddr, interface)
+    }
+
+    /// Executes an operation of the `IPV6_DROP_MEMBERSHIP` type.
+    pub fn leave_multicast_v6(&self, multiaddr: &Ipv6Addr, interface: u32) -> io::Result<()> {
+        self.0.std.leave_multicast_v6(multiaddr, interface)
+    }
+
+    /// Returns the value of the `SO_ERROR` option.
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        self.0.std.take_error()
+    }
+}
+
+impl AsFd for UdpSocket {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.0.as_fd()
+    }
+}
+
+impl AsRawFd for UdpSocket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.socket.as_raw_fd()
+    }
+}
+
+impl TryFrom<UdpSocket> for OwnedFd {
+    type Error = io::Error;
+
+    /// Returns a duplicate of the socket descriptor; the socket itself is closed when dropped.
+    fn try_from(socket: UdpSocket) -> io::Result<Self> {
+        socket.as_fd().try_clone_to_owned()
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::io::{Read as _, Write as _};
+
+    #[test]
+    #[serial_test::serial]
+    fn test_should_accept_and_connect_uring() {
+        crate::start_uring(async {
+            let listener = TcpListener::bind("127.0.0.1:0")
+                .await
+                .expect("Failed to bind listener");
+            assert!(listener.set_ttl(64).is_ok());
+            assert_eq!(listener.ttl().unwrap(), 64);
+            let addr = listener.local_addr().expect("Failed to get local address");
+
+            let mut stream = TcpStream::connect(addr).await.expect("Failed to connect");
+            assert_eq!(stream.peer_addr().unwrap(), addr);
+            assert!(stream.set_nodelay(true).is_ok());
+            assert!(stream.nodelay().unwrap());
+            let (mut accepted, _) = listener.accept().await.expect("Failed to accept");
+
+            stream.write_all(b"Ping").await.expect("Failed to write");
+            let mut buf = [0; 4];
+            accepted.read_exact(&mut buf).await.expect("Failed to read");
+            assert_eq!(&buf, b"Ping");
+
+            accepted.write_all(b"Pong").await.expect("Failed to write");
+            stream.read_exact(&mut buf).await.expect("Failed to read");
+            assert_eq!(&buf, b"Pong");
+        });
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_should_send_and_recv_from_udp_uring() {
+        crate::start_uring(async {
+            let socket = UdpSocket::bind("127.0.0.1:0")
+                .await
+                .expect("Failed to bind socket");
+            let peer = std::net::UdpSocket::bind("127.0.0.1:0").expect("Failed to bind peer");
+            let peer_addr = peer.local_addr().unwrap();
+
+            socket
+                .send_to(b"Ping", peer_addr)
+                .await
+                .expect("Failed to send");
+            let mut buf = [0; 4];
+            let (size, addr) = peer.recv_from(&mut buf).expect("Failed to receive");
+            assert_eq!(&buf[..size], b"Ping");
+            assert_eq!(addr, socket.local_addr().unwrap());
+
+            peer.send_to(b"Pong", addr).expect("Failed to send");
+            let (size, addr) = socket.recv_from(&mut buf).await.expect("Failed to receive");
+            assert_eq!(&buf[..size], b"Pong");
+            assert_eq!(addr, peer_addr);
+        });
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_should_send_and_recv_vectored_udp_uring() {
+        crate::start_uring(async {
+            let socket = UdpSocket::bind("127.0.0.1:0")
+                .await
+                .expect("Failed to bind socket");
+            let peer = std::net::UdpSocket::bind("127.0.0.1:0").expect("Failed to bind peer");
+            let peer_addr = peer.local_addr().unwrap();
+            socket.connect(peer_addr).await.expect("Failed to connect");
+            peer.connect(socket.local_addr().unwrap())
+                .expect("Failed to connect");
+
+            let bufs = [io::IoSlice::new(b"Hello, "), io::IoSlice::new(b"world")];
+            assert_eq!(socket.send_vectored(&bufs).await.unwrap(), 12);
+            let mut buf = [0; 12];
+            assert_eq!(peer.recv(&mut buf).unwrap(), 12);
+            assert_eq!(&buf, b"Hello, world");
+
+            peer.send(b"Hello, world").expect("Failed to send");
+            let mut first = [0; 7];
+            let mut second = [0; 5];
+            let mut bufs = [
+                io::IoSliceMut::new(&mut first),
+                io::IoSliceMut::new(&mut second),
+            ];
+            assert_eq!(socket.recv_vectored(&mut bufs).await.unwrap(), 12);
+            assert_eq!(&first, b"Hello, ");
+            assert_eq!(&second, b"world");
+        });
+    }
+}
@@ -0,0 +1,83 @@
+use std::io::{self, IoSlice, IoSliceMut};
+use std::mem::MaybeUninit;
+use std::net::SocketAddr;
+
+use socket2::{MaybeUninitSlice, SockAddr, SockRef};
+
+/// Sends the buffers as a single datagram on the connected `socket`, with `sendmsg`.
+pub fn send_vectored(socket: SockRef<'_>, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+    socket.send_vectored(bufs)
+}
+
+/// Sends the buffers as a single datagram to `target`, with `sendmsg`.
+pub fn send_to_vectored(
+    socket: SockRef<'_>,
+    bufs: &[IoSlice<'_>],
+    target: SocketAddr,
+) -> io::Result<usize> {
+    socket.send_to_vectored(bufs, &SockAddr::from(target))
+}
+
+/// Receives a single datagram on the connected `socket`, scattering it into the buffers with `recvmsg`.
+pub fn recv_vectored(socket: SockRef<'_>, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+    socket
+        .recv_vectored(&mut uninit_slices(bufs))
+        .map(|(size, _flags)| size)
+}
+
+/// Receives a single datagram on `socket`, scattering it into the buffers with `recvmsg`.
+///
+/// Returns the number of bytes read and the source address.
+pub fn recv_from_vectored(
+    socket: SockRef<'_>,
+    bufs: &mut [IoSliceMut<'_>],
+) -> io::Result<(usize, SocketAddr)> {
+    let (size, _flags, addr) = socket.recv_from_vectored(&mut uninit_slices(bufs))?;
+    let addr = addr
+        .as_socket()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "source is not an IP address"))?;
+
+    Ok((size, addr))
+}
+
+fn uninit_slices<'a>(bufs: &'a mut [IoSliceMut<'_>]) -> Vec<MaybeUninitSlice<'a>> {
+    bufs.iter_mut()
+        .map(|buf| {
+            let buf: &mut [u8] = buf;
+            // SAFETY: socket2 never writes uninitialised bytes into the buffers passed to `recvmsg`
+            let buf = unsafe { &mut *(buf as *mut [u8] as *mut [MaybeUninit<u8>]) };
+            MaybeUninitSlice::new(buf)
+        })
+        .collect()
+}
+
+/// Copies `data` into the buffers, in order, returning the number of bytes copied.
+#[cfg(uring_net)]
+pub fn scatter(data: &[u8], bufs: &mut [IoSliceMut<'_>]) -> usize {
+    let mut copied = 0;
+    for buf in bufs {
+        let size = buf.len().min(data.len() - copied);
+        buf[..size].copy_from_slice(&data[copied..copied + size]);
+        copied += size;
+    }
+
+    copied
+}
+
+#[cfg(all(test, uring_net))]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_should_scatter_data() {
+        let mut first = [0; 2];
+        let mut second = [0; 4];
+        let mut bufs = [IoSliceMut::new(&mut first), IoSliceMut::new(&mut second)];
+        assert_eq!(scatter(b"Hello", &mut bufs), 5);
+        assert_eq!(&first, b"He");
+        assert_eq!(&second, b"llo\0");
+    }
+}
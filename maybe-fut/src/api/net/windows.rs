@@ -0,0 +1,264 @@
+//! Windows named pipes, for local inter-process communication.
+//!
+//! A [`NamedPipeServer`] creates an instance of the pipe and waits for a client with [`NamedPipeServer::connect`];
+//! a [`NamedPipeClient`] opens the pipe created by a server. Pipe names are in the form `\\.\pipe\<name>`.
+//!
+//! References:
+//!
+//! - [Named Pipes](https://learn.microsoft.com/en-us/windows/win32/ipc/named-pipes)
+//! - [Tokio Named Pipes](https://docs.rs/tokio/latest/tokio/net/windows/named_pipe/index.html)
+
+use std::ffi::OsStr;
+use std::io;
+use std::os::windows::ffi::OsStrExt as _;
+use std::os::windows::io::{AsHandle, AsRawHandle as _, BorrowedHandle, FromRawHandle as _};
+
+use windows_sys::Win32::Foundation::{ERROR_PIPE_CONNECTED, INVALID_HANDLE_VALUE};
+use windows_sys::Win32::Storage::FileSystem::PIPE_ACCESS_DUPLEX;
+use windows_sys::Win32::System::Pipes::{
+    ConnectNamedPipe, CreateNamedPipeW, DisconnectNamedPipe, PIPE_READMODE_BYTE, PIPE_TYPE_BYTE,
+    PIPE_UNLIMITED_INSTANCES, PIPE_WAIT,
+};
+
+/// The size of the input and output buffers of the pipes created with std, the same as tokio's default.
+const BUFFER_SIZE: u32 = 65536;
+
+/// The server end of a named pipe.
+///
+/// Each [`NamedPipeServer`] is a single instance of the pipe, serving one client at a time:
+/// create a new instance with [`NamedPipeServer::create`] to serve more clients concurrently.
+///
+/// Reading and writing to a [`NamedPipeServer`] is usually done by using the [`crate::io::Read`] and
+/// [`crate::io::Write`] traits.
+#[derive(Debug, Read, Write)]
+#[io(feature("tokio-net"))]
+pub struct NamedPipeServer(NamedPipeServerInner);
+
+#[derive(Debug)]
+enum NamedPipeServerInner {
+    /// The handle of the pipe, wrapped in a [`std::fs::File`] to read and write it.
+    Std(std::fs::File),
+    #[cfg(tokio_net)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio-net")))]
+    Tokio(tokio::net::windows::named_pipe::NamedPipeServer),
+}
+
+#[cfg(tokio_net)]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio-net")))]
+impl From<tokio::net::windows::named_pipe::NamedPipeServer> for NamedPipeServer {
+    fn from(server: tokio::net::windows::named_pipe::NamedPipeServer) -> Self {
+        Self(NamedPipeServerInner::Tokio(server))
+    }
+}
+
+impl AsHandle for NamedPipeServer {
+    fn as_handle(&self) -> BorrowedHandle<'_> {
+        match &self.0 {
+            NamedPipeServerInner::Std(pipe) => pipe.as_handle(),
+            #[cfg(tokio_net)]
+            NamedPipeServerInner::Tokio(pipe) => pipe.as_handle(),
+        }
+    }
+}
+
+impl NamedPipeServer {
+    /// Creates a new instance of the named pipe `name`, in byte mode and duplex.
+    ///
+    /// Creating the pipe is not async, neither with std nor with tokio, so this is a sync constructor.
+    pub fn create(name: impl AsRef<OsStr>) -> io::Result<Self> {
+        #[cfg(tokio_net)]
+        if crate::is_tokio_context() {
+            return tokio::net::windows::named_pipe::ServerOptions::new()
+                .create(name)
+                .map(Self::from);
+        }
+
+        Self::create_std(name)
+    }
+
+    /// Creates a new instance of the named pipe `name` with the std implementation, regardless of the context.
+    ///
+    /// Unlike [`Self::create`], it never checks the context, which is useful to create a pipe in an async context
+    /// to use it on another thread.
+    pub fn create_std(name: impl AsRef<OsStr>) -> io::Result<Self> {
+        let name: Vec<u16> = name.as_ref().encode_wide().chain(Some(0)).collect();
+        // SAFETY: `name` is a valid nul-terminated wide string, and the default security attributes are used
+        let handle = unsafe {
+            CreateNamedPipeW(
+                name.as_ptr(),
+                PIPE_ACCESS_DUPLEX,
+                PIPE_TYPE_BYTE | PIPE_READMODE_BYTE | PIPE_WAIT,
+                PIPE_UNLIMITED_INSTANCES,
+                BUFFER_SIZE,
+                BUFFER_SIZE,
+                0,
+                std::ptr::null(),
+            )
+        };
+        if handle == INVALID_HANDLE_VALUE {
+            return Err(io::Error::last_os_error());
+        }
+
+        // SAFETY: `handle` is a valid handle of a pipe, owned by nothing else
+        let pipe = unsafe { std::fs::File::from_raw_handle(handle) };
+        Ok(Self(NamedPipeServerInner::Std(pipe)))
+    }
+
+    /// Waits for a client to connect to this instance of the pipe.
+    ///
+    /// If a client connected between the creation of the pipe and this call, it returns immediately.
+    pub async fn connect(&self) -> io::Result<()> {
+        match &self.0 {
+            NamedPipeServerInner::Std(pipe) => {
+                // SAFETY: the handle is valid, and it is not overlapped, so the call blocks until a client connects
+                if unsafe { ConnectNamedPipe(pipe.as_raw_handle(), std::ptr::null_mut()) } != 0 {
+                    return Ok(());
+                }
+                match io::Error::last_os_error() {
+                    err if err.raw_os_error() == Some(ERROR_PIPE_CONNECTED as i32) => Ok(()),
+                    err => Err(err),
+                }
+            }
+            #[cfg(tokio_net)]
+            NamedPipeServerInner::Tokio(pipe) => pipe.connect().await,
+        }
+    }
+
+    /// Disconnects the client of this instance of the pipe, so that it can wait for another client with
+    /// [`Self::connect`].
+    pub fn disconnect(&self) -> io::Result<()> {
+        match &self.0 {
+            NamedPipeServerInner::Std(pipe) => {
+                // SAFETY: the handle is valid for the whole call
+                if unsafe { DisconnectNamedPipe(pipe.as_raw_handle()) } == 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                Ok(())
+            }
+            #[cfg(tokio_net)]
+            NamedPipeServerInner::Tokio(pipe) => pipe.disconnect(),
+        }
+    }
+}
+
+/// The client end of a named pipe.
+///
+/// Reading and writing to a [`NamedPipeClient`] is usually done by using the [`crate::io::Read`] and
+/// [`crate::io::Write`] traits.
+#[derive(Debug, Read, Write)]
+#[io(feature("tokio-net"))]
+pub struct NamedPipeClient(NamedPipeClientInner);
+
+#[derive(Debug)]
+enum NamedPipeClientInner {
+    /// The handle of the pipe, opened as a [`std::fs::File`].
+    Std(std::fs::File),
+    #[cfg(tokio_net)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio-net")))]
+    Tokio(tokio::net::windows::named_pipe::NamedPipeClient),
+}
+
+#[cfg(tokio_net)]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio-net")))]
+impl From<tokio::net::windows::named_pipe::NamedPipeClient> for NamedPipeClient {
+    fn from(client: tokio::net::windows::named_pipe::NamedPipeClient) -> Self {
+        Self(NamedPipeClientInner::Tokio(client))
+    }
+}
+
+impl AsHandle for NamedPipeClient {
+    fn as_handle(&self) -> BorrowedHandle<'_> {
+        match &self.0 {
+            NamedPipeClientInner::Std(pipe) => pipe.as_handle(),
+            #[cfg(tokio_net)]
+            NamedPipeClientInner::Tokio(pipe) => pipe.as_handle(),
+        }
+    }
+}
+
+impl NamedPipeClient {
+    /// Opens the named pipe `name`, created by a [`NamedPipeServer`], for reading and writing.
+    ///
+    /// It fails with the `ERROR_PIPE_BUSY` OS error if all the instances of the pipe are serving a client.
+    /// Opening the pipe is not async, neither with std nor with tokio, so this is a sync constructor.
+    pub fn open(name: impl AsRef<OsStr>) -> io::Result<Self> {
+        #[cfg(tokio_net)]
+        if crate::is_tokio_context() {
+            return tokio::net::windows::named_pipe::ClientOptions::new()
+                .open(name)
+                .map(Self::from);
+        }
+
+        Self::open_std(name)
+    }
+
+    /// Opens the named pipe `name` with the std implementation, regardless of the context.
+    ///
+    /// Unlike [`Self::open`], it never checks the context, which is useful to open a pipe in an async context
+    /// to use it on another thread.
+    pub fn open_std(name: impl AsRef<OsStr>) -> io::Result<Self> {
+        std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(name.as_ref())
+            .map(|pipe| Self(NamedPipeClientInner::Std(pipe)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::SyncRuntime;
+    use crate::io::{Read, Write};
+
+    fn pipe_name(test: &str) -> String {
+        format!(r"\\.\pipe\maybe-fut-{test}-{}", std::process::id())
+    }
+
+    #[test]
+    fn test_should_talk_over_named_pipe_std() {
+        let name = pipe_name("std");
+        let server = NamedPipeServer::create(&name).expect("failed to create pipe");
+        assert!(matches!(server.0, NamedPipeServerInner::Std(_)));
+
+        let client = std::thread::spawn(move || {
+            let mut client = NamedPipeClient::open(&name).expect("failed to open pipe");
+            SyncRuntime::block_on(client.write_all(b"Ping")).expect("failed to write");
+            let mut buf = [0; 4];
+            SyncRuntime::block_on(client.read_exact(&mut buf)).expect("failed to read");
+            buf
+        });
+
+        let mut server = server;
+        SyncRuntime::block_on(server.connect()).expect("failed to connect");
+        let mut buf = [0; 4];
+        SyncRuntime::block_on(server.read_exact(&mut buf)).expect("failed to read");
+        assert_eq!(&buf, b"Ping");
+        SyncRuntime::block_on(server.write_all(b"Pong")).expect("failed to write");
+
+        assert_eq!(&client.join().expect("failed to join"), b"Pong");
+        server.disconnect().expect("failed to disconnect");
+    }
+
+    #[cfg(tokio_net)]
+    #[tokio::test]
+    async fn test_should_talk_over_named_pipe_tokio() {
+        let name = pipe_name("tokio");
+        let mut server = NamedPipeServer::create(&name).expect("failed to create pipe");
+        assert!(matches!(server.0, NamedPipeServerInner::Tokio(_)));
+        let mut client = NamedPipeClient::open(&name).expect("failed to open pipe");
+        assert!(matches!(client.0, NamedPipeClientInner::Tokio(_)));
+        server.connect().await.expect("failed to connect");
+
+        client.write_all(b"Ping").await.expect("failed to write");
+        let mut buf = [0; 4];
+        server.read_exact(&mut buf).await.expect("failed to read");
+        assert_eq!(&buf, b"Ping");
+        server.write_all(b"Pong").await.expect("failed to write");
+        client.read_exact(&mut buf).await.expect("failed to read");
+        assert_eq!(&buf, b"Pong");
+    }
+}
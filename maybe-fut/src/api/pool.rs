@@ -0,0 +1,314 @@
+//! A generic pool of reusable connections, or of any other value which is expensive to create.
+//!
+//! The [`Pool`] keeps the connections checked in, up to a maximum size, and hands them out again on checkout,
+//! discarding the ones which have been idle for too long or which fail the health check.
+//! The pool doesn't create connections: on an empty checkout, the caller opens a new one and checks it in when done.
+//!
+//! ```rust
+//! use maybe_fut::net::TcpStream;
+//! use maybe_fut::pool::Pool;
+//! use std::time::Duration;
+//!
+//! async fn connect(pool: &Pool<TcpStream>, addr: std::net::SocketAddr) -> std::io::Result<TcpStream> {
+//!     match pool.checkout().await {
+//!         Some(stream) => Ok(stream),
+//!         None => TcpStream::connect(addr).await,
+//!     }
+//! }
+//!
+//! let pool: Pool<TcpStream> = Pool::builder()
+//!     .max_size(8)
+//!     .idle_timeout(Duration::from_secs(30))
+//!     .health_check(|stream: &mut TcpStream| stream.take_error().is_ok_and(|err| err.is_none()))
+//!     .build();
+//! # let _ = connect;
+//! # let _ = pool;
+//! ```
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::sync::{Mutex, MutexGuard};
+use crate::time::{Clock, Instant, SystemClock};
+
+/// The default maximum number of idle connections of a [`Pool`].
+pub const DEFAULT_MAX_SIZE: usize = 16;
+
+/// The health check of the connections of a [`Pool`].
+type HealthCheck<T> = Box<dyn Fn(&mut T) -> bool + Send + Sync>;
+
+/// A pool of idle connections, shared by its clones.
+///
+/// All the methods are async, and never suspend the caller, so they can be used from sync callers through
+/// [`crate::block_on`] as well.
+pub struct Pool<T, C = SystemClock> {
+    shared: Arc<Shared<T, C>>,
+}
+
+struct Shared<T, C> {
+    max_size: usize,
+    idle_timeout: Option<Duration>,
+    health_check: Option<HealthCheck<T>>,
+    clock: C,
+    /// The idle connections, with the time they've been checked in; the most recent is at the back.
+    idle: Mutex<VecDeque<(T, Instant)>>,
+}
+
+impl<T, C> Clone for Pool<T, C> {
+    fn clone(&self) -> Self {
+        Self {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl<T, C> std::fmt::Debug for Pool<T, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Pool")
+            .field("max_size", &self.shared.max_size)
+            .field("idle_timeout", &self.shared.idle_timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Default for Pool<T> {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl<T> Pool<T> {
+    /// Creates a new [`Pool`] keeping up to `max_size` idle connections, without idle timeout nor health check.
+    pub fn new(max_size: usize) -> Self {
+        Self::builder().max_size(max_size).build()
+    }
+
+    /// Returns a new [`PoolBuilder`] to configure the pool.
+    pub fn builder() -> PoolBuilder<T> {
+        PoolBuilder {
+            max_size: DEFAULT_MAX_SIZE,
+            idle_timeout: None,
+            health_check: None,
+            clock: SystemClock,
+        }
+    }
+}
+
+impl<T, C> Pool<T, C>
+where
+    C: Clock,
+{
+    /// Checks out an idle connection, if any.
+    ///
+    /// The most recently checked in connection is returned first.
+    /// The connections idle for longer than the idle timeout and the ones failing the health check are dropped.
+    pub async fn checkout(&self) -> Option<T> {
+        let now = self.shared.clock.now();
+        let mut idle = self.idle().await;
+        while let Some((mut conn, since)) = idle.pop_back() {
+            if self.is_expired(since, now) {
+                // the connections before it have been idle for even longer
+                idle.clear();
+                return None;
+            }
+            if self
+                .shared
+                .health_check
+                .as_ref()
+                .is_none_or(|check| check(&mut conn))
+            {
+                return Some(conn);
+            }
+        }
+
+        None
+    }
+
+    /// Checks a connection in, to be reused by a later checkout.
+    ///
+    /// If the pool is full, the connection idle for the longest time is dropped to make room for it.
+    pub async fn checkin(&self, conn: T) {
+        if self.shared.max_size == 0 {
+            return;
+        }
+
+        let now = self.shared.clock.now();
+        let mut idle = self.idle().await;
+        if idle.len() >= self.shared.max_size {
+            idle.pop_front();
+        }
+        idle.push_back((conn, now));
+    }
+
+    /// Returns the number of idle connections in the pool, including the expired ones not yet dropped.
+    pub async fn idle_count(&self) -> usize {
+        self.idle().await.len()
+    }
+
+    /// Drops the connections idle for longer than the idle timeout.
+    ///
+    /// Expired connections are dropped on checkout as well; this is useful to release them when the pool is unused.
+    pub async fn purge_expired(&self) {
+        let now = self.shared.clock.now();
+        self.idle()
+            .await
+            .retain(|(_, since)| !self.is_expired(*since, now));
+    }
+
+    /// Drops all the idle connections.
+    pub async fn clear(&self) {
+        self.idle().await.clear();
+    }
+
+    /// Returns whether a connection idle since `since` has expired at `now`.
+    fn is_expired(&self, since: Instant, now: Instant) -> bool {
+        self.shared
+            .idle_timeout
+            .is_some_and(|timeout| now.saturating_duration_since(since) > timeout)
+    }
+
+    async fn idle(&self) -> MutexGuard<'_, VecDeque<(T, Instant)>> {
+        // the lock is never held across await points, so a poisoned queue is still consistent
+        self.shared
+            .idle
+            .lock()
+            .await
+            .unwrap_or_else(|poisoned| MutexGuard::from(poisoned.into_inner()))
+    }
+}
+
+/// Configures and builds a [`Pool`].
+pub struct PoolBuilder<T, C = SystemClock> {
+    max_size: usize,
+    idle_timeout: Option<Duration>,
+    health_check: Option<HealthCheck<T>>,
+    clock: C,
+}
+
+impl<T, C> PoolBuilder<T, C> {
+    /// Sets the maximum number of idle connections kept by the pool; [`DEFAULT_MAX_SIZE`] by default.
+    pub fn max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Sets the time after which an idle connection is dropped instead of being checked out.
+    pub fn idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = Some(idle_timeout);
+        self
+    }
+
+    /// Sets the check run on the idle connections on checkout; the connections failing it are dropped.
+    pub fn health_check<F>(mut self, health_check: F) -> Self
+    where
+        F: Fn(&mut T) -> bool + Send + Sync + 'static,
+    {
+        self.health_check = Some(Box::new(health_check));
+        self
+    }
+
+    /// Sets the clock measuring the idle time of the connections, e.g. a [`crate::time::ManualClock`] in tests.
+    pub fn clock<C2>(self, clock: C2) -> PoolBuilder<T, C2>
+    where
+        C2: Clock,
+    {
+        PoolBuilder {
+            max_size: self.max_size,
+            idle_timeout: self.idle_timeout,
+            health_check: self.health_check,
+            clock,
+        }
+    }
+
+    /// Builds the [`Pool`].
+    pub fn build(self) -> Pool<T, C> {
+        Pool {
+            shared: Arc::new(Shared {
+                max_size: self.max_size,
+                idle_timeout: self.idle_timeout,
+                health_check: self.health_check,
+                clock: self.clock,
+                // a std mutex, since it must never suspend sync callers, whatever the context of creation
+                idle: Mutex::const_new_std(VecDeque::new()),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::SyncRuntime;
+    use crate::time::ManualClock;
+
+    #[test]
+    fn test_should_checkout_most_recent_connection() {
+        let pool = Pool::new(2);
+        assert_eq!(SyncRuntime::block_on(pool.checkout()), None);
+
+        SyncRuntime::block_on(pool.checkin(1));
+        SyncRuntime::block_on(pool.checkin(2));
+        SyncRuntime::block_on(pool.checkin(3));
+        assert_eq!(SyncRuntime::block_on(pool.idle_count()), 2);
+
+        assert_eq!(SyncRuntime::block_on(pool.checkout()), Some(3));
+        assert_eq!(SyncRuntime::block_on(pool.checkout()), Some(2));
+        assert_eq!(SyncRuntime::block_on(pool.checkout()), None);
+    }
+
+    #[test]
+    fn test_should_drop_expired_connections() {
+        let clock = ManualClock::new();
+        let pool = Pool::builder()
+            .idle_timeout(Duration::from_secs(30))
+            .clock(clock.clone())
+            .build();
+
+        SyncRuntime::block_on(pool.checkin("old"));
+        clock.advance(Duration::from_secs(20));
+        SyncRuntime::block_on(pool.checkin("new"));
+        clock.advance(Duration::from_secs(20));
+
+        assert_eq!(SyncRuntime::block_on(pool.checkout()), Some("new"));
+        assert_eq!(SyncRuntime::block_on(pool.checkout()), None);
+        assert_eq!(SyncRuntime::block_on(pool.idle_count()), 0);
+
+        SyncRuntime::block_on(pool.checkin("new"));
+        clock.advance(Duration::from_secs(31));
+        SyncRuntime::block_on(pool.purge_expired());
+        assert_eq!(SyncRuntime::block_on(pool.idle_count()), 0);
+    }
+
+    #[test]
+    fn test_should_drop_unhealthy_connections() {
+        let pool = Pool::builder()
+            .health_check(|conn: &mut i32| *conn % 2 == 0)
+            .build();
+
+        for conn in 1..=4 {
+            SyncRuntime::block_on(pool.checkin(conn));
+        }
+
+        assert_eq!(SyncRuntime::block_on(pool.checkout()), Some(4));
+        assert_eq!(SyncRuntime::block_on(pool.checkout()), Some(2));
+        assert_eq!(SyncRuntime::block_on(pool.checkout()), None);
+    }
+
+    #[tokio::test]
+    async fn test_should_share_connections_between_clones() {
+        let pool = Pool::default();
+        let clone = pool.clone();
+
+        let handle = tokio::spawn(async move { clone.checkin(String::from("conn")).await });
+        handle.await.expect("failed to join");
+
+        assert_eq!(pool.checkout().await.as_deref(), Some("conn"));
+        pool.checkin(String::from("conn")).await;
+        pool.clear().await;
+        assert_eq!(pool.idle_count().await, 0);
+    }
+}
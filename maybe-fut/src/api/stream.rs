@@ -0,0 +1,120 @@
+//! Asynchronous sequences of values.
+//!
+//! The [`Stream`] trait is the async counterpart of [`Iterator`]: it yields its items through the async [`Stream::next`] method.
+//! The streaming types of maybe-fut, such as [`crate::fs::ReadDir`], [`crate::io::Lines`], [`crate::io::Split`]
+//! and [`crate::net::Incoming`], implement it, so they can be composed with its adapters.
+//!
+//! In sync contexts, any stream can be used as a blocking [`Iterator`] through [`Stream::into_blocking_iter`];
+//! likewise, any [`Iterator`] can be used as a stream through [`iter`].
+//!
+//! Reference:
+//!
+//! - std: <https://doc.rust-lang.org/std/iter/trait.Iterator.html>
+//! - futures: <https://docs.rs/futures/latest/futures/stream/trait.Stream.html>
+
+mod blocking_iter;
+mod buffered;
+mod iter;
+mod map;
+mod take;
+mod throttle;
+mod timeout;
+
+pub use self::blocking_iter::BlockingIter;
+pub use self::buffered::Buffered;
+pub use self::iter::{Iter, iter};
+pub use self::map::Map;
+pub use self::take::Take;
+pub use self::throttle::Throttle;
+pub use self::timeout::{Elapsed, Timeout};
+
+/// The [`Stream`] trait provides an asynchronous interface to yield a sequence of values.
+pub trait Stream {
+    /// The type of the values yielded by the stream.
+    type Item;
+
+    /// Returns the next value of the stream, or `None` if the stream is exhausted.
+    fn next(&mut self) -> impl Future<Output = Option<Self::Item>>;
+
+    /// Returns a stream which calls `f` on each value of this stream.
+    fn map<T, F>(self, f: F) -> Map<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(Self::Item) -> T,
+    {
+        Map::new(self, f)
+    }
+
+    /// Returns a stream which yields at most `n` values of this stream.
+    fn take(self, n: usize) -> Take<Self>
+    where
+        Self: Sized,
+    {
+        Take::new(self, n)
+    }
+
+    /// Returns a stream which fails with [`Elapsed`] when this stream takes longer than `duration` to yield a value.
+    ///
+    /// See [`Timeout`] for more details.
+    fn timeout(self, duration: std::time::Duration) -> Timeout<Self>
+    where
+        Self: Sized,
+    {
+        Timeout::new(self, duration)
+    }
+
+    /// Returns a stream which yields the values of this stream at most once per `period`.
+    ///
+    /// See [`Throttle`] for more details.
+    fn throttle(self, period: std::time::Duration) -> Throttle<Self>
+    where
+        Self: Sized,
+    {
+        Throttle::new(self, period)
+    }
+
+    /// Returns a stream which runs up to `n` of the futures yielded by this stream concurrently, yielding their
+    /// outputs in order.
+    ///
+    /// See [`Buffered`] for more details.
+    fn buffered(self, n: usize) -> Buffered<Self>
+    where
+        Self: Sized,
+        Self::Item: Future,
+    {
+        Buffered::new(self, n)
+    }
+
+    /// Collects all the values of the stream into a collection.
+    fn collect<C>(mut self) -> impl Future<Output = C>
+    where
+        Self: Sized,
+        C: Default + Extend<Self::Item>,
+    {
+        async move {
+            let mut collection = C::default();
+            while let Some(item) = self.next().await {
+                collection.extend(std::iter::once(item));
+            }
+            collection
+        }
+    }
+
+    /// Returns a blocking [`Iterator`] over the values of this stream, to be used in sync contexts.
+    ///
+    /// See [`BlockingIter`] for more details.
+    fn into_blocking_iter(self) -> BlockingIter<Self>
+    where
+        Self: Sized,
+    {
+        BlockingIter::new(self)
+    }
+}
+
+impl<S: Stream + ?Sized> Stream for &mut S {
+    type Item = S::Item;
+
+    fn next(&mut self) -> impl Future<Output = Option<Self::Item>> {
+        (**self).next()
+    }
+}
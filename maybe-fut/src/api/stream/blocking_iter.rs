@@ -0,0 +1,70 @@
+use super::Stream;
+use crate::SyncRuntime;
+
+/// A blocking [`Iterator`] over the values of a [`Stream`].
+///
+/// This struct is created by [`Stream::into_blocking_iter`].
+///
+/// The values are awaited with [`SyncRuntime::block_on`], so the iterator must be used only in sync contexts,
+/// where the maybe-fut types use their std implementation and never yield.
+///
+/// # Panics
+///
+/// Iterating panics if the stream yields, e.g. because it is a tokio type.
+#[derive(Debug)]
+pub struct BlockingIter<S> {
+    stream: S,
+}
+
+impl<S> BlockingIter<S> {
+    pub(crate) fn new(stream: S) -> Self {
+        Self { stream }
+    }
+
+    /// Returns the underlying stream.
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<S: Stream> Iterator for BlockingIter<S> {
+    type Item = S::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        SyncRuntime::block_on(self.stream.next())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::stream::iter;
+
+    #[test]
+    fn test_should_iterate_stream_in_sync_context() {
+        let values: Vec<_> = iter([1, 2, 3])
+            .map(|x| x * 2)
+            .into_blocking_iter()
+            .collect();
+        assert_eq!(values, vec![2, 4, 6]);
+    }
+
+    #[test]
+    fn test_should_iterate_read_dir_in_sync_context() {
+        let tempdir = tempfile::tempdir().unwrap();
+        std::fs::write(tempdir.path().join("a.txt"), "a").unwrap();
+        std::fs::write(tempdir.path().join("b.txt"), "b").unwrap();
+
+        let entries =
+            SyncRuntime::block_on(crate::fs::read_dir(tempdir.path())).expect("read_dir failed");
+        let mut names: Vec<_> = entries
+            .into_blocking_iter()
+            .map(|entry| entry.expect("entry failed").file_name())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["a.txt", "b.txt"]);
+    }
+}
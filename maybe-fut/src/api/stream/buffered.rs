@@ -0,0 +1,133 @@
+use std::collections::VecDeque;
+use std::future::poll_fn;
+use std::pin::Pin;
+use std::task::Poll;
+
+use super::Stream;
+
+/// A [`Stream`] which runs up to `n` of the futures yielded by another stream concurrently, yielding their outputs
+/// in the same order as the futures.
+///
+/// This struct is created by [`Stream::buffered`].
+///
+/// In async contexts, the buffered futures are polled concurrently by the calling task.
+/// In sync contexts, each future completes as soon as it is polled, so they run one after the other.
+pub struct Buffered<S>
+where
+    S: Stream,
+    S::Item: Future,
+{
+    stream: S,
+    max: usize,
+    /// The buffered futures, or their outputs once completed, in the order of the stream.
+    queue: VecDeque<Slot<S::Item>>,
+    exhausted: bool,
+}
+
+/// A buffered future, or its output once completed.
+enum Slot<F: Future> {
+    Pending(Pin<Box<F>>),
+    Done(F::Output),
+}
+
+impl<S> Buffered<S>
+where
+    S: Stream,
+    S::Item: Future,
+{
+    pub(crate) fn new(stream: S, max: usize) -> Self {
+        Self {
+            stream,
+            // at least one future must run to make progress
+            max: max.max(1),
+            queue: VecDeque::new(),
+            exhausted: false,
+        }
+    }
+}
+
+impl<S> std::fmt::Debug for Buffered<S>
+where
+    S: Stream + std::fmt::Debug,
+    S::Item: Future,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Buffered")
+            .field("stream", &self.stream)
+            .field("max", &self.max)
+            .field("buffered", &self.queue.len())
+            .finish()
+    }
+}
+
+impl<S> Stream for Buffered<S>
+where
+    S: Stream,
+    S::Item: Future,
+{
+    type Item = <S::Item as Future>::Output;
+
+    async fn next(&mut self) -> Option<Self::Item> {
+        while !self.exhausted && self.queue.len() < self.max {
+            match self.stream.next().await {
+                Some(future) => self.queue.push_back(Slot::Pending(Box::pin(future))),
+                None => self.exhausted = true,
+            }
+        }
+
+        poll_fn(|cx| {
+            for slot in self.queue.iter_mut() {
+                if let Slot::Pending(future) = slot {
+                    if let Poll::Ready(output) = future.as_mut().poll(cx) {
+                        *slot = Slot::Done(output);
+                    }
+                }
+            }
+
+            match self.queue.front() {
+                None => Poll::Ready(None),
+                Some(Slot::Pending(_)) => Poll::Pending,
+                Some(Slot::Done(_)) => match self.queue.pop_front() {
+                    Some(Slot::Done(output)) => Poll::Ready(Some(output)),
+                    _ => unreachable!("the front slot is done"),
+                },
+            }
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::time::Duration;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::stream::{Stream, iter};
+
+    #[test]
+    fn test_should_buffer_futures_sync() {
+        let values: Vec<_> = iter(1..=5)
+            .map(|x| async move { x * 2 })
+            .buffered(2)
+            .into_blocking_iter()
+            .collect();
+        assert_eq!(values, vec![2, 4, 6, 8, 10]);
+    }
+
+    #[tokio::test]
+    async fn test_should_run_futures_concurrently_in_order() {
+        let start = std::time::Instant::now();
+        let values: Vec<_> = iter([100, 50, 10, 100])
+            .map(|delay| async move {
+                tokio::time::sleep(Duration::from_millis(delay)).await;
+                delay
+            })
+            .buffered(4)
+            .collect()
+            .await;
+        assert_eq!(values, vec![100, 50, 10, 100]);
+        assert!(start.elapsed() < Duration::from_millis(250));
+    }
+}
@@ -0,0 +1,40 @@
+use super::Stream;
+
+/// A [`Stream`] over the values of an [`Iterator`].
+///
+/// This struct is created by the [`iter`] function.
+#[derive(Debug, Clone)]
+pub struct Iter<I> {
+    iter: I,
+}
+
+impl<I: Iterator> Stream for Iter<I> {
+    type Item = I::Item;
+
+    async fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+}
+
+/// Creates a [`Stream`] yielding the values of `iter`.
+pub fn iter<I: IntoIterator>(iter: I) -> Iter<I::IntoIter> {
+    Iter {
+        iter: iter.into_iter(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_should_stream_iterator() {
+        let mut stream = iter(["a", "b"]);
+        assert_eq!(stream.next().await, Some("a"));
+        assert_eq!(stream.next().await, Some("b"));
+        assert_eq!(stream.next().await, None);
+    }
+}
@@ -0,0 +1,42 @@
+use super::Stream;
+
+/// A [`Stream`] which maps the values of another stream.
+///
+/// This struct is created by [`Stream::map`].
+#[derive(Debug, Clone)]
+pub struct Map<S, F> {
+    stream: S,
+    f: F,
+}
+
+impl<S, F> Map<S, F> {
+    pub(crate) fn new(stream: S, f: F) -> Self {
+        Self { stream, f }
+    }
+}
+
+impl<T, S, F> Stream for Map<S, F>
+where
+    S: Stream,
+    F: FnMut(S::Item) -> T,
+{
+    type Item = T;
+
+    async fn next(&mut self) -> Option<Self::Item> {
+        self.stream.next().await.map(&mut self.f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use crate::stream::{Stream, iter};
+
+    #[tokio::test]
+    async fn test_should_map_stream() {
+        let values: Vec<_> = iter(1..=3).map(|x| x.to_string()).collect().await;
+        assert_eq!(values, vec!["1", "2", "3"]);
+    }
+}
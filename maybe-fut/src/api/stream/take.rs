@@ -0,0 +1,45 @@
+use super::Stream;
+
+/// A [`Stream`] which yields at most `n` values of another stream.
+///
+/// This struct is created by [`Stream::take`].
+#[derive(Debug, Clone)]
+pub struct Take<S> {
+    stream: S,
+    remaining: usize,
+}
+
+impl<S> Take<S> {
+    pub(crate) fn new(stream: S, remaining: usize) -> Self {
+        Self { stream, remaining }
+    }
+}
+
+impl<S: Stream> Stream for Take<S> {
+    type Item = S::Item;
+
+    async fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let item = self.stream.next().await;
+        if item.is_some() {
+            self.remaining -= 1;
+        }
+        item
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use crate::stream::{Stream, iter};
+
+    #[tokio::test]
+    async fn test_should_take_values() {
+        let values: Vec<_> = iter(1..).take(3).collect().await;
+        assert_eq!(values, vec![1, 2, 3]);
+    }
+}
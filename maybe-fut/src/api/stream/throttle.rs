@@ -0,0 +1,87 @@
+use std::time::Duration;
+
+use super::Stream;
+use crate::time::{Clock, Instant, SystemClock};
+
+/// A [`Stream`] which yields the values of another stream at most once per period.
+///
+/// This struct is created by [`Stream::throttle`].
+///
+/// The values are not dropped: the stream waits until the period since the previous value has elapsed before pulling
+/// the next one, blocking the thread in sync contexts.
+#[derive(Debug, Clone)]
+pub struct Throttle<S, C = SystemClock> {
+    stream: S,
+    period: Duration,
+    clock: C,
+    last: Option<Instant>,
+}
+
+impl<S> Throttle<S> {
+    pub(crate) fn new(stream: S, period: Duration) -> Self {
+        Self::with_clock(stream, period, SystemClock)
+    }
+}
+
+impl<S, C: Clock> Throttle<S, C> {
+    /// Creates a [`Throttle`] measuring the period with `clock`, e.g. a [`crate::time::ManualClock`] in tests.
+    pub fn with_clock(stream: S, period: Duration, clock: C) -> Self {
+        Self {
+            stream,
+            period,
+            clock,
+            last: None,
+        }
+    }
+}
+
+impl<S: Stream, C: Clock> Stream for Throttle<S, C> {
+    type Item = S::Item;
+
+    async fn next(&mut self) -> Option<Self::Item> {
+        if let Some(last) = self.last {
+            let elapsed = self.clock.now().saturating_duration_since(last);
+            if let Some(remaining) = self.period.checked_sub(elapsed).filter(|d| !d.is_zero()) {
+                self.clock.sleep(remaining).await;
+            }
+        }
+
+        let item = self.stream.next().await;
+        if item.is_some() {
+            self.last = Some(self.clock.now());
+        }
+        item
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::stream::iter;
+    use crate::time::ManualClock;
+
+    #[test]
+    fn test_should_throttle_values_sync() {
+        let clock = ManualClock::new();
+        let values: Vec<_> = Throttle::with_clock(iter(1..), Duration::from_millis(30), &clock)
+            .take(3)
+            .into_blocking_iter()
+            .collect();
+        assert_eq!(values, vec![1, 2, 3]);
+        assert_eq!(clock.elapsed(), Duration::from_millis(60));
+    }
+
+    #[tokio::test]
+    async fn test_should_throttle_values_tokio() {
+        let start = std::time::Instant::now();
+        let values: Vec<_> = iter(1..=3)
+            .throttle(Duration::from_millis(30))
+            .collect()
+            .await;
+        assert_eq!(values, vec![1, 2, 3]);
+        assert!(start.elapsed() >= Duration::from_millis(60));
+    }
+}
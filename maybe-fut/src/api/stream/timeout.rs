@@ -0,0 +1,107 @@
+use std::future::poll_fn;
+use std::pin::pin;
+use std::task::Poll;
+use std::time::Duration;
+
+use super::Stream;
+use crate::time::{Clock as _, SystemClock};
+
+/// A [`Stream`] which fails with [`Elapsed`] when another stream takes too long to yield a value.
+///
+/// This struct is created by [`Stream::timeout`].
+///
+/// In async contexts, the wait for the next value is dropped when the timeout elapses, and the stream can be polled
+/// again afterwards. In sync contexts, where the next value blocks until it is available, the timeout can't
+/// interrupt it, so the values are always yielded.
+#[derive(Debug, Clone)]
+pub struct Timeout<S> {
+    stream: S,
+    duration: Duration,
+}
+
+impl<S> Timeout<S> {
+    pub(crate) fn new(stream: S, duration: Duration) -> Self {
+        Self { stream, duration }
+    }
+}
+
+impl<S: Stream> Stream for Timeout<S> {
+    type Item = Result<S::Item, Elapsed>;
+
+    async fn next(&mut self) -> Option<Self::Item> {
+        if !crate::context::is_async_runtime() {
+            return self.stream.next().await.map(Ok);
+        }
+
+        let mut next = pin!(self.stream.next());
+        let mut sleep = pin!(SystemClock.sleep(self.duration));
+        poll_fn(|cx| {
+            if let Poll::Ready(item) = next.as_mut().poll(cx) {
+                return Poll::Ready(item.map(Ok));
+            }
+            sleep.as_mut().poll(cx).map(|()| Some(Err(Elapsed(()))))
+        })
+        .await
+    }
+}
+
+/// The error yielded by [`Timeout`] when the next value is not yielded in time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elapsed(());
+
+impl std::fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "deadline has elapsed")
+    }
+}
+
+impl std::error::Error for Elapsed {}
+
+impl From<Elapsed> for std::io::Error {
+    fn from(err: Elapsed) -> Self {
+        std::io::Error::new(std::io::ErrorKind::TimedOut, err)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::stream::iter;
+
+    /// A stream which waits for the given delays before yielding them.
+    struct Delayed(std::vec::IntoIter<u64>);
+
+    impl Stream for Delayed {
+        type Item = u64;
+
+        async fn next(&mut self) -> Option<Self::Item> {
+            let delay = self.0.next()?;
+            SystemClock.sleep(Duration::from_millis(delay)).await;
+            Some(delay)
+        }
+    }
+
+    #[test]
+    fn test_should_never_time_out_sync() {
+        let values: Vec<_> = iter(1..=3)
+            .timeout(Duration::ZERO)
+            .into_blocking_iter()
+            .collect();
+        assert_eq!(values, vec![Ok(1), Ok(2), Ok(3)]);
+    }
+
+    #[tokio::test]
+    async fn test_should_time_out_slow_values() {
+        let mut stream = Delayed(vec![0, 500, 0].into_iter()).timeout(Duration::from_millis(100));
+        assert_eq!(stream.next().await, Some(Ok(0)));
+        assert_eq!(stream.next().await, Some(Err(Elapsed(()))));
+        assert_eq!(stream.next().await, Some(Ok(0)));
+        assert_eq!(stream.next().await, None);
+
+        let err = std::io::Error::from(Elapsed(()));
+        assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+    }
+}
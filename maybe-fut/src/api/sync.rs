@@ -4,9 +4,22 @@
 //! Tokio references: <https://docs.rs/tokio/latest/tokio/sync/index.html>
 
 mod barrier;
+mod cancellation_token;
+mod held_guard;
+mod lazy_lock;
 mod mutex;
+mod once_cell;
 mod rwlock;
+mod semaphore;
+mod wait_group;
 
-pub use self::barrier::{Barrier, BarrierWaitResult};
-pub use self::mutex::{Mutex, MutexGuard};
-pub use self::rwlock::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+pub use self::barrier::{Barrier, BarrierWaitResult, TimedBarrier};
+pub use self::cancellation_token::CancellationToken;
+pub use self::lazy_lock::LazyLock;
+pub use self::mutex::{Mutex, MutexGuard, OwnedMutexGuard};
+pub use self::once_cell::OnceCell;
+pub use self::rwlock::{
+    OwnedRwLockReadGuard, OwnedRwLockWriteGuard, RwLock, RwLockReadGuard, RwLockWriteGuard,
+};
+pub use self::semaphore::{AcquireError, Semaphore, SemaphorePermit, TryAcquireError};
+pub use self::wait_group::{WaitGroup, WaitGroupGuard};
@@ -1,6 +1,10 @@
-use crate::maybe_fut_constructor_sync;
+use std::sync::{Condvar, Mutex};
+use std::time::{Duration, Instant};
 
 /// A barrier enables multiple threads to synchronize the beginning of some computation.
+///
+/// In sync contexts, it's backed by a [`std::sync::Barrier`], which can't time out; use a [`TimedBarrier`] to wait
+/// with a timeout.
 #[derive(Debug, Unwrap)]
 #[unwrap_types(
     std(std::sync::Barrier),
@@ -35,15 +39,17 @@ impl From<tokio::sync::Barrier> for Barrier {
 }
 
 impl Barrier {
-    maybe_fut_constructor_sync!(
-        /// Creates a new barrier that can block a given number of threads.
-        ///
-        /// A barrier will block n-1 threads which call [`Self::wait`] and then wake up all threads at once when the `n`th thread calls [`Self::wait`].
-        new(n: usize) -> Self,
-        std::sync::Barrier::new,
-        tokio::sync::Barrier::new,
-        tokio_sync
-    );
+    /// Creates a new barrier that can block a given number of threads.
+    ///
+    /// A barrier will block n-1 threads which call [`Self::wait`] and then wake up all threads at once when the `n`th thread calls [`Self::wait`].
+    pub fn new(n: usize) -> Self {
+        #[cfg(tokio_sync)]
+        if crate::is_tokio_context() {
+            return tokio::sync::Barrier::new(n).into();
+        }
+
+        std::sync::Barrier::new(n).into()
+    }
 
     /// Blocks the current thread until all threads have rendezvoused here.
     ///
@@ -57,6 +63,145 @@ impl Barrier {
     }
 }
 
+/// A barrier whose waits can time out, with [`TimedBarrier::wait_timeout`], so that a peer which died doesn't block
+/// the others forever.
+///
+/// In sync contexts, it's built on a [`Mutex`] and a [`Condvar`], since a [`std::sync::Barrier`] can't time out;
+/// in tokio contexts, it's backed by a [`tokio::sync::Barrier`].
+#[derive(Debug)]
+pub struct TimedBarrier(TimedBarrierInner);
+
+/// Inner wrapper for [`TimedBarrier`].
+#[derive(Debug)]
+enum TimedBarrierInner {
+    /// Barrier built on a [`Condvar`].
+    Std(CondvarBarrier),
+    /// Tokio barrier.
+    #[cfg(tokio_sync)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio-sync")))]
+    Tokio(tokio::sync::Barrier),
+}
+
+#[cfg(tokio_sync)]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio-sync")))]
+impl From<tokio::sync::Barrier> for TimedBarrier {
+    fn from(barrier: tokio::sync::Barrier) -> Self {
+        Self(TimedBarrierInner::Tokio(barrier))
+    }
+}
+
+impl TimedBarrier {
+    /// Creates a new barrier that can block a given number of threads.
+    ///
+    /// A barrier will block n-1 threads which call [`Self::wait`] or [`Self::wait_timeout`] and then wake up all
+    /// threads at once when the `n`th thread calls one of them.
+    pub fn new(n: usize) -> Self {
+        #[cfg(tokio_sync)]
+        if crate::is_tokio_context() {
+            return tokio::sync::Barrier::new(n).into();
+        }
+
+        Self(TimedBarrierInner::Std(CondvarBarrier::new(n)))
+    }
+
+    /// Blocks the current thread until all threads have rendezvoused here.
+    ///
+    /// Barriers are re-usable after all threads have rendezvoused once, and can be used continuously.
+    pub async fn wait(&self) -> BarrierWaitResult {
+        match &self.0 {
+            TimedBarrierInner::Std(barrier) => barrier
+                .wait(None)
+                .expect("a barrier without timeout can't time out"),
+            #[cfg(tokio_sync)]
+            TimedBarrierInner::Tokio(barrier) => barrier.wait().await.into(),
+        }
+    }
+
+    /// Blocks the current thread until all threads have rendezvoused here, or until `timeout` has elapsed.
+    ///
+    /// Returns `None` if the barrier didn't trip before the timeout. The thread which timed out is withdrawn from
+    /// the barrier, except with tokio, where it's still counted as arrived, since [`tokio::sync::Barrier::wait`]
+    /// isn't cancel safe.
+    pub async fn wait_timeout(&self, timeout: Duration) -> Option<BarrierWaitResult> {
+        match &self.0 {
+            TimedBarrierInner::Std(barrier) => barrier.wait(Some(timeout)),
+            #[cfg(tokio_sync)]
+            TimedBarrierInner::Tokio(barrier) => tokio::time::timeout(timeout, barrier.wait())
+                .await
+                .ok()
+                .map(BarrierWaitResult::from),
+        }
+    }
+}
+
+/// A barrier built on a [`Mutex`] and a [`Condvar`], like [`std::sync::Barrier`], supporting timeouts.
+#[derive(Debug)]
+struct CondvarBarrier {
+    n: usize,
+    state: Mutex<CondvarBarrierState>,
+    condvar: Condvar,
+}
+
+#[derive(Debug)]
+struct CondvarBarrierState {
+    arrived: usize,
+    /// Incremented each time the barrier trips, to tell the waiting threads apart from the next rendezvous.
+    generation: usize,
+}
+
+impl CondvarBarrier {
+    fn new(n: usize) -> Self {
+        Self {
+            n,
+            state: Mutex::new(CondvarBarrierState {
+                arrived: 0,
+                generation: 0,
+            }),
+            condvar: Condvar::new(),
+        }
+    }
+
+    /// Waits for the other threads, returning `None` if `timeout` elapses first.
+    fn wait(&self, timeout: Option<Duration>) -> Option<BarrierWaitResult> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        // the state is updated with single operations, so a poisoned state is still consistent
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let generation = state.generation;
+        state.arrived += 1;
+        if state.arrived >= self.n {
+            state.arrived = 0;
+            state.generation = state.generation.wrapping_add(1);
+            self.condvar.notify_all();
+            return Some(BarrierWaitResult(InnerBarrierWaitResult::Condvar(true)));
+        }
+
+        while state.generation == generation {
+            state = match deadline {
+                Some(deadline) => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    if remaining.is_zero() {
+                        state.arrived -= 1;
+                        return None;
+                    }
+                    self.condvar
+                        .wait_timeout(state, remaining)
+                        .unwrap_or_else(|poisoned| poisoned.into_inner())
+                        .0
+                }
+                None => self
+                    .condvar
+                    .wait(state)
+                    .unwrap_or_else(|poisoned| poisoned.into_inner()),
+            };
+        }
+
+        Some(BarrierWaitResult(InnerBarrierWaitResult::Condvar(false)))
+    }
+}
+
 /// Result of a [`Barrier`] [`Barrier::wait`] operation.
 #[derive(Debug)]
 pub struct BarrierWaitResult(InnerBarrierWaitResult);
@@ -66,6 +211,8 @@ pub struct BarrierWaitResult(InnerBarrierWaitResult);
 enum InnerBarrierWaitResult {
     /// Std barrier wait result.
     Std(std::sync::BarrierWaitResult),
+    /// Condvar barrier wait result, telling whether this thread is the leader.
+    Condvar(bool),
     /// Tokio barrier wait result.
     #[cfg(tokio_sync)]
     Tokio(tokio::sync::BarrierWaitResult),
@@ -91,6 +238,7 @@ impl BarrierWaitResult {
     pub fn is_leader(&self) -> bool {
         match &self.0 {
             InnerBarrierWaitResult::Std(result) => result.is_leader(),
+            InnerBarrierWaitResult::Condvar(is_leader) => *is_leader,
             #[cfg(tokio_sync)]
             InnerBarrierWaitResult::Tokio(result) => result.is_leader(),
         }
@@ -100,6 +248,8 @@ impl BarrierWaitResult {
 #[cfg(test)]
 mod test {
 
+    use pretty_assertions::assert_eq;
+
     use super::*;
 
     #[test]
@@ -108,6 +258,19 @@ mod test {
         assert!(matches!(barrier.0, BarrierInner::Std(_)));
     }
 
+    #[test]
+    fn test_should_create_timed_barrier_sync() {
+        let barrier = TimedBarrier::new(1);
+        assert!(matches!(barrier.0, TimedBarrierInner::Std(_)));
+    }
+
+    #[cfg(tokio_sync)]
+    #[tokio::test]
+    async fn test_should_create_timed_barrier_async() {
+        let barrier = TimedBarrier::new(1);
+        assert!(matches!(barrier.0, TimedBarrierInner::Tokio(_)));
+    }
+
     #[cfg(tokio_sync)]
     #[tokio::test]
     async fn test_should_create_barrier_async() {
@@ -129,4 +292,55 @@ mod test {
         let result = barrier.wait().await;
         assert!(matches!(result.0, InnerBarrierWaitResult::Tokio(_)));
     }
+
+    #[test]
+    fn test_should_wait_for_threads_sync() {
+        let barrier = std::sync::Arc::new(TimedBarrier::new(3));
+        let handles: Vec<_> = (0..2)
+            .map(|_| {
+                let barrier = std::sync::Arc::clone(&barrier);
+                std::thread::spawn(move || crate::SyncRuntime::block_on(barrier.wait()).is_leader())
+            })
+            .collect();
+
+        let mut leaders = usize::from(crate::SyncRuntime::block_on(barrier.wait()).is_leader());
+        for handle in handles {
+            leaders += usize::from(handle.join().expect("failed to join"));
+        }
+        assert_eq!(leaders, 1);
+    }
+
+    #[test]
+    fn test_should_time_out_waiting_sync() {
+        let barrier = TimedBarrier::new(2);
+        let result = crate::SyncRuntime::block_on(barrier.wait_timeout(Duration::from_millis(20)));
+        assert!(result.is_none());
+
+        // the thread which timed out is withdrawn, so the barrier still needs two threads
+        let result = crate::SyncRuntime::block_on(barrier.wait_timeout(Duration::from_millis(20)));
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_should_trip_before_timeout_sync() {
+        let barrier = std::sync::Arc::new(TimedBarrier::new(2));
+        let clone = std::sync::Arc::clone(&barrier);
+        let handle = std::thread::spawn(move || crate::SyncRuntime::block_on(clone.wait()));
+
+        let result = crate::SyncRuntime::block_on(barrier.wait_timeout(Duration::from_secs(5)));
+        assert!(result.is_some());
+        handle.join().expect("failed to join");
+    }
+
+    #[cfg(tokio_sync)]
+    #[tokio::test]
+    async fn test_should_time_out_waiting_tokio() {
+        let barrier = TimedBarrier::new(2);
+        assert!(
+            barrier
+                .wait_timeout(Duration::from_millis(20))
+                .await
+                .is_none()
+        );
+    }
 }
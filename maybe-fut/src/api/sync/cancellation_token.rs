@@ -0,0 +1,193 @@
+use std::future::poll_fn;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, Weak};
+use std::task::{Poll, Waker};
+
+/// A token to signal cancellation to the tasks and threads holding a clone of it.
+///
+/// Once [`CancellationToken::cancel`] is called, all the clones and the child tokens are cancelled, and the tasks
+/// waiting on [`CancellationToken::cancelled`] are woken up.
+///
+/// The same implementation serves both sync and async callers, so a graceful shutdown can be expressed once.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    cancelled: AtomicBool,
+    /// The wakers of the tasks waiting for the cancellation.
+    wakers: Mutex<Vec<Waker>>,
+    /// Notifies the threads waiting for the cancellation in sync contexts.
+    condvar: Condvar,
+    /// The child tokens, cancelled with this one.
+    children: Mutex<Vec<Weak<Inner>>>,
+}
+
+impl CancellationToken {
+    /// Creates a new [`CancellationToken`], not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a child token, which is cancelled when this token is cancelled.
+    ///
+    /// Cancelling the child token doesn't cancel its parent. If this token is already cancelled, the child token is
+    /// created cancelled.
+    pub fn child_token(&self) -> Self {
+        let child = Self::new();
+        let mut children = lock(&self.inner.children);
+        if self.is_cancelled() {
+            child.cancel();
+        } else {
+            children.retain(|child| child.strong_count() > 0);
+            children.push(Arc::downgrade(&child.inner));
+        }
+
+        child
+    }
+
+    /// Cancels the token, all of its clones and its child tokens, waking up the tasks and threads waiting for them.
+    ///
+    /// Cancelling a token more than once has no effect.
+    pub fn cancel(&self) {
+        self.inner.cancel();
+    }
+
+    /// Returns whether the token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::Acquire)
+    }
+
+    /// Waits until the token is cancelled.
+    ///
+    /// In sync contexts, the current thread is blocked until the token is cancelled.
+    pub async fn cancelled(&self) {
+        if !crate::context::is_async_runtime() {
+            let mut wakers = self.wakers();
+            while !self.is_cancelled() {
+                wakers = self
+                    .inner
+                    .condvar
+                    .wait(wakers)
+                    .unwrap_or_else(|poisoned| poisoned.into_inner());
+            }
+            return;
+        }
+
+        poll_fn(|cx| {
+            if self.is_cancelled() {
+                return Poll::Ready(());
+            }
+            let mut wakers = self.wakers();
+            // the token may have been cancelled while acquiring the lock
+            if self.is_cancelled() {
+                return Poll::Ready(());
+            }
+            if !wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+                wakers.push(cx.waker().clone());
+            }
+            Poll::Pending
+        })
+        .await
+    }
+
+    fn wakers(&self) -> std::sync::MutexGuard<'_, Vec<Waker>> {
+        lock(&self.inner.wakers)
+    }
+}
+
+impl Inner {
+    fn cancel(&self) {
+        let mut wakers = lock(&self.wakers);
+        self.cancelled.store(true, Ordering::Release);
+        for waker in wakers.drain(..) {
+            waker.wake();
+        }
+        self.condvar.notify_all();
+        drop(wakers);
+
+        // taken after setting the flag, so a child created meanwhile is either in the list or created cancelled
+        let children = std::mem::take(&mut *lock(&self.children));
+        for child in children.iter().filter_map(Weak::upgrade) {
+            child.cancel();
+        }
+    }
+}
+
+fn lock<T>(mutex: &Mutex<Vec<T>>) -> std::sync::MutexGuard<'_, Vec<T>> {
+    // the lists are only pushed, retained and drained, so a poisoned list is still consistent
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::time::Duration;
+
+    use super::*;
+    use crate::SyncRuntime;
+
+    #[test]
+    fn test_should_cancel_clones() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(!clone.is_cancelled());
+
+        token.cancel();
+        assert!(clone.is_cancelled());
+        SyncRuntime::block_on(clone.cancelled());
+    }
+
+    #[test]
+    fn test_should_wait_for_cancellation_sync() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        let handle = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(50));
+            clone.cancel();
+        });
+
+        SyncRuntime::block_on(token.cancelled());
+        assert!(token.is_cancelled());
+        handle.join().expect("failed to join");
+    }
+
+    #[test]
+    fn test_should_cancel_child_tokens() {
+        let token = CancellationToken::new();
+        let child = token.child_token();
+        let grandchild = child.child_token();
+        let sibling = token.child_token();
+
+        sibling.cancel();
+        assert!(!token.is_cancelled());
+        assert!(!child.is_cancelled());
+
+        token.cancel();
+        assert!(child.is_cancelled());
+        assert!(grandchild.is_cancelled());
+        SyncRuntime::block_on(grandchild.cancelled());
+
+        // children of a cancelled token are created cancelled
+        assert!(token.child_token().is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn test_should_wait_for_cancellation_tokio() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        let handle = tokio::spawn(async move { clone.cancelled().await });
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!handle.is_finished());
+        token.cancel();
+        tokio::time::timeout(Duration::from_secs(1), handle)
+            .await
+            .expect("timed out")
+            .expect("failed to join");
+    }
+}
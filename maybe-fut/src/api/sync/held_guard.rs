@@ -0,0 +1,95 @@
+use std::ptr::NonNull;
+use std::sync::mpsc;
+
+/// A std lock guard held by a dedicated thread until the [`HeldGuard`] is dropped.
+///
+/// The std guards can't be sent to another thread, since on some platforms a std lock must be released by the thread
+/// which acquired it. Holding the guard on its own thread lets the owned guards move across threads, while the lock
+/// is still released by the thread which acquired it.
+pub(super) struct HeldGuard<T: ?Sized> {
+    data: NonNull<T>,
+    /// Dropping the sender wakes the holding thread up, which releases the guard.
+    _release: mpsc::SyncSender<()>,
+}
+
+/// Handle passed to the thread holding a [`HeldGuard`].
+pub(super) struct GuardHolder<T: ?Sized> {
+    acquired: mpsc::SyncSender<(AcquiredData<T>, bool)>,
+    release: mpsc::Receiver<()>,
+}
+
+/// Pointer to the data of an acquired guard, sent from the holding thread.
+struct AcquiredData<T: ?Sized>(NonNull<T>);
+
+// SAFETY: the pointer is only dereferenced by the [`HeldGuard`], whose owners bound `T` like the guards they replace
+unsafe impl<T: ?Sized> Send for AcquiredData<T> {}
+
+impl<T: ?Sized> HeldGuard<T> {
+    /// Spawns a thread running `hold`, which must acquire the guard and pass it to [`GuardHolder::hold`].
+    ///
+    /// Returns the held guard along with whether the lock was poisoned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hold` panics before acquiring the guard.
+    pub(super) fn spawn<F>(hold: F) -> (Self, bool)
+    where
+        F: FnOnce(GuardHolder<T>) + Send + 'static,
+        T: 'static,
+    {
+        let (acquired, acquired_rx) = mpsc::sync_channel(1);
+        let (release_tx, release) = mpsc::sync_channel(0);
+        std::thread::spawn(move || hold(GuardHolder { acquired, release }));
+
+        let (AcquiredData(data), poisoned) = acquired_rx
+            .recv()
+            .expect("the thread holding the guard panicked");
+
+        (
+            Self {
+                data,
+                _release: release_tx,
+            },
+            poisoned,
+        )
+    }
+
+    /// Returns a shared reference to the guarded data.
+    pub(super) fn get(&self) -> &T {
+        // SAFETY: the data is guarded until the holding thread releases the guard, which happens after `self` is
+        // dropped
+        unsafe { self.data.as_ref() }
+    }
+
+    /// Returns an exclusive reference to the guarded data.
+    ///
+    /// # Safety
+    ///
+    /// The held guard must grant exclusive access to the data.
+    pub(super) unsafe fn get_mut(&mut self) -> &mut T {
+        // SAFETY: the data is guarded until the holding thread releases the guard, which happens after `self` is
+        // dropped, and the caller ensures the access is exclusive
+        unsafe { self.data.as_mut() }
+    }
+}
+
+impl<T> std::fmt::Debug for HeldGuard<T>
+where
+    T: ?Sized + std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self.get(), f)
+    }
+}
+
+impl<T: ?Sized> GuardHolder<T> {
+    /// Passes the data of the acquired guard to the [`HeldGuard`], then blocks until it's dropped.
+    ///
+    /// The guard owning `data` must be kept alive until this returns.
+    pub(super) fn hold(self, data: NonNull<T>, poisoned: bool) {
+        if self.acquired.send((AcquiredData(data), poisoned)).is_ok() {
+            // the sender is never used, so this returns once the held guard is dropped
+            let _ = self.release.recv();
+        }
+    }
+}
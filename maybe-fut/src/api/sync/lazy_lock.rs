@@ -0,0 +1,159 @@
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Mutex;
+
+use super::OnceCell;
+
+/// A value which is lazily initialized on first access by an async closure.
+///
+/// The value is initialized by [`LazyLock::force`]; in sync contexts, it's also initialized on the first
+/// dereference, while in async contexts it must be forced before being dereferenced, since [`Deref`] can't await
+/// the initializer. It is backed by a [`OnceCell`], so concurrent accesses wait for a single initializer.
+///
+/// A [`LazyLock`] can be declared as a `static` with [`LazyLock::const_new_std`] or [`LazyLock::const_new_tokio`].
+pub struct LazyLock<T, F> {
+    cell: OnceCell<T>,
+    /// The initializer, taken by the caller initializing the value.
+    init: Mutex<Option<F>>,
+}
+
+impl<T, F> LazyLock<T, F> {
+    /// Creates a new lazy value with the given initializer, backed by a [`OnceCell`] for the current context.
+    pub fn new(f: F) -> Self {
+        Self {
+            cell: OnceCell::new(),
+            init: Mutex::new(Some(f)),
+        }
+    }
+
+    /// Creates a new lazy value backed by a std cell, in a const context.
+    ///
+    /// See [`OnceCell::const_new_std`].
+    pub const fn const_new_std(f: F) -> Self {
+        Self {
+            cell: OnceCell::const_new_std(),
+            init: Mutex::new(Some(f)),
+        }
+    }
+
+    /// Creates a new lazy value backed by a tokio cell, in a const context.
+    ///
+    /// See [`OnceCell::const_new_tokio`].
+    #[cfg(tokio_sync)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio-sync")))]
+    pub const fn const_new_tokio(f: F) -> Self {
+        Self {
+            cell: OnceCell::const_new_tokio(),
+            init: Mutex::new(Some(f)),
+        }
+    }
+
+    /// Returns a reference to the value, or `None` if it's not initialized yet.
+    pub fn get(this: &Self) -> Option<&T> {
+        this.cell.get()
+    }
+}
+
+impl<T, F, Fut> LazyLock<T, F>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = T>,
+{
+    /// Initializes the value, if it's not initialized yet, and returns a reference to it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a previous initialization panicked or was cancelled, since the initializer has been consumed.
+    pub async fn force(this: &Self) -> &T {
+        this.cell
+            .get_or_init(|| async {
+                let init = this
+                    .init
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .take()
+                    .expect("LazyLock instance has previously been poisoned");
+                init().await
+            })
+            .await
+    }
+}
+
+impl<T, F, Fut> Deref for LazyLock<T, F>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = T>,
+{
+    type Target = T;
+
+    /// Dereferences the value, initializing it in sync contexts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is not initialized in an async context: call [`LazyLock::force`] first.
+    fn deref(&self) -> &T {
+        if let Some(value) = self.cell.get() {
+            return value;
+        }
+        if crate::context::is_async_runtime() {
+            panic!(
+                "LazyLock must be initialized with LazyLock::force before being dereferenced in async contexts"
+            );
+        }
+
+        crate::SyncRuntime::block_on(Self::force(self))
+    }
+}
+
+impl<T, F> fmt::Debug for LazyLock<T, F>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut d = f.debug_tuple("LazyLock");
+        match self.cell.get() {
+            Some(value) => d.field(value),
+            None => d.field(&format_args!("<uninit>")),
+        };
+        d.finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_should_init_on_deref_sync() {
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        static VALUE: LazyLock<String, fn() -> std::future::Ready<String>> =
+            LazyLock::const_new_std(|| {
+                CALLS.fetch_add(1, Ordering::SeqCst);
+                std::future::ready(String::from("Hello"))
+            });
+
+        assert_eq!(LazyLock::get(&VALUE), None);
+        assert_eq!(VALUE.as_str(), "Hello");
+        assert_eq!(VALUE.len(), 5);
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+        assert_eq!(format!("{VALUE:?}"), "LazyLock(\"Hello\")");
+    }
+
+    #[cfg(tokio_sync)]
+    #[tokio::test]
+    async fn test_should_force_tokio() {
+        let value = LazyLock::new(|| async {
+            tokio::task::yield_now().await;
+            42
+        });
+        assert_eq!(format!("{value:?}"), "LazyLock(<uninit>)");
+
+        assert_eq!(LazyLock::force(&value).await, &42);
+        assert_eq!(*value, 42);
+    }
+}
@@ -1,8 +1,10 @@
 mod guard;
+mod owned_guard;
 
-use std::sync::{PoisonError, TryLockError};
+use std::sync::{Arc, PoisonError, TryLockError};
 
 pub use self::guard::MutexGuard;
+pub use self::owned_guard::OwnedMutexGuard;
 use crate::maybe_fut_constructor_sync;
 
 /// A mutual exclusion primitive useful for protecting shared data
@@ -15,8 +17,8 @@ use crate::maybe_fut_constructor_sync;
 /// which guarantees that the data is only ever accessed when the mutex is locked.
 #[derive(Debug, Unwrap)]
 #[unwrap_types(
-    std(std::sync::Mutex),
-    tokio(tokio::sync::Mutex),
+    std(std::sync::Mutex<T>),
+    tokio(tokio::sync::Mutex<T>),
     tokio_gated("tokio-sync")
 )]
 pub struct Mutex<T>(MutexInner<T>);
@@ -58,6 +60,24 @@ where
         tokio_sync
     );
 
+    /// Creates a new std mutex in an unlocked state, in a const context.
+    ///
+    /// Unlike [`Mutex::new`], the runtime can't be checked in a const context, so this can be used to declare a
+    /// `static` which is always backed by a [`std::sync::Mutex`].
+    pub const fn const_new_std(t: T) -> Self {
+        Mutex(MutexInner::Std(std::sync::Mutex::new(t)))
+    }
+
+    /// Creates a new tokio mutex in an unlocked state, in a const context.
+    ///
+    /// Unlike [`Mutex::new`], the runtime can't be checked in a const context, so this can be used to declare a
+    /// `static` which is always backed by a [`tokio::sync::Mutex`].
+    #[cfg(tokio_sync)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio-sync")))]
+    pub const fn const_new_tokio(t: T) -> Self {
+        Mutex(MutexInner::Tokio(tokio::sync::Mutex::const_new(t)))
+    }
+
     /// Clear the poisoned state from a mutex.
     ///
     /// If the mutex is poisoned, it will remain poisoned until this function is called.
@@ -104,6 +124,43 @@ where
         }
     }
 
+    /// Acquires a mutex from sync code, blocking the current thread until it is able to do so.
+    ///
+    /// It's the same as [`Mutex::lock`], without the boilerplate of blocking on a future.
+    ///
+    /// # Panics
+    ///
+    /// With a tokio mutex, panics if called within an async context, like [`tokio::sync::Mutex::blocking_lock`].
+    pub fn blocking_lock(
+        &self,
+    ) -> Result<MutexGuard<'_, T>, PoisonError<std::sync::MutexGuard<'_, T>>> {
+        match &self.0 {
+            MutexInner::Std(mutex) => Ok(MutexGuard::from(mutex.lock()?)),
+            #[cfg(tokio_sync)]
+            MutexInner::Tokio(mutex) => Ok(MutexGuard::from(mutex.blocking_lock())),
+        }
+    }
+
+    /// Acquires the mutex behind an [`Arc`], returning an owned guard which keeps the [`Arc`] alive.
+    ///
+    /// Unlike the guard returned by [`Mutex::lock`], the [`OwnedMutexGuard`] has no lifetime, so it can be stored in
+    /// structs, and it can be sent to another thread, like a [`tokio::sync::OwnedMutexGuard`].
+    ///
+    /// Since a std mutex must be unlocked by the thread which locked it, with a std mutex the guard is held by a
+    /// dedicated thread, which unlocks the mutex once the [`OwnedMutexGuard`] is dropped.
+    pub async fn lock_owned(
+        self: Arc<Self>,
+    ) -> Result<OwnedMutexGuard<T>, PoisonError<OwnedMutexGuard<T>>>
+    where
+        T: Send + 'static,
+    {
+        match &self.0 {
+            MutexInner::Std(_) => OwnedMutexGuard::lock_std(self),
+            #[cfg(tokio_sync)]
+            MutexInner::Tokio(_) => Ok(OwnedMutexGuard::lock_tokio(self).await),
+        }
+    }
+
     /// Attempts to acquire this lock.
     ///
     /// If the lock could not be acquired at this time, then [`TryLockError`] is returned.
@@ -261,4 +318,115 @@ mod test {
         mutex.clear_poison();
         assert!(!mutex.is_poisoned());
     }
+
+    #[test]
+    fn test_should_blocking_lock_sync_mutex() {
+        let mutex = Mutex::new(42);
+        *mutex.blocking_lock().unwrap() += 1;
+        assert_eq!(*mutex.blocking_lock().unwrap(), 43);
+    }
+
+    #[cfg(tokio_sync)]
+    #[test]
+    fn test_should_blocking_lock_tokio_mutex() {
+        let mutex: Mutex<i32> = Mutex::from(tokio::sync::Mutex::new(42));
+        *mutex.blocking_lock().unwrap() += 1;
+        assert_eq!(*mutex.blocking_lock().unwrap(), 43);
+    }
+
+    #[test]
+    fn test_should_lock_owned_sync_mutex() {
+        let mutex = Arc::new(Mutex::new(42));
+        let mut guard = SyncRuntime::block_on(Arc::clone(&mutex).lock_owned()).unwrap();
+        assert!(Arc::ptr_eq(OwnedMutexGuard::mutex(&guard), &mutex));
+        assert!(SyncRuntime::block_on(mutex.try_lock()).is_err());
+
+        // the guard keeps the mutex alive
+        drop(mutex);
+        *guard = 43;
+        let mutex = Arc::clone(OwnedMutexGuard::mutex(&guard));
+        drop(guard);
+        assert_eq!(*SyncRuntime::block_on(mutex.lock()).unwrap(), 43);
+    }
+
+    #[test]
+    fn test_should_lock_owned_poisoned_sync_mutex() {
+        let mutex = Arc::new(Mutex::new(42));
+        let clone = Arc::clone(&mutex);
+        let _ = std::thread::spawn(move || {
+            let _guard = SyncRuntime::block_on(clone.lock()).unwrap();
+            panic!("poison the mutex");
+        })
+        .join();
+
+        let guard = SyncRuntime::block_on(mutex.lock_owned())
+            .unwrap_err()
+            .into_inner();
+        assert_eq!(*guard, 42);
+    }
+
+    #[cfg(tokio_sync)]
+    #[tokio::test]
+    async fn test_should_lock_owned_tokio_mutex() {
+        let mutex = Arc::new(Mutex::new(42));
+        let mut guard = Arc::clone(&mutex).lock_owned().await.unwrap();
+        assert!(mutex.try_lock().await.is_err());
+        drop(mutex);
+        *guard = 43;
+        let mutex = Arc::clone(OwnedMutexGuard::mutex(&guard));
+        drop(guard);
+        assert_eq!(*mutex.lock().await.unwrap(), 43);
+    }
+
+    #[test]
+    fn test_owned_mutex_guard_should_be_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<OwnedMutexGuard<i32>>();
+    }
+
+    #[test]
+    fn test_should_send_owned_guard_sync_mutex() {
+        let mutex = Arc::new(Mutex::new(42));
+        let guard = SyncRuntime::block_on(Arc::clone(&mutex).lock_owned()).unwrap();
+        std::thread::spawn(move || {
+            let mut guard = guard;
+            *guard = 43;
+        })
+        .join()
+        .unwrap();
+
+        assert_eq!(*SyncRuntime::block_on(mutex.lock()).unwrap(), 43);
+    }
+
+    #[cfg(tokio_sync)]
+    #[tokio::test]
+    async fn test_should_send_owned_guard_tokio_mutex() {
+        let mutex = Arc::new(Mutex::new(42));
+        let mut guard = Arc::clone(&mutex).lock_owned().await.unwrap();
+        tokio::spawn(async move {
+            *guard = 43;
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(*mutex.lock().await.unwrap(), 43);
+    }
+
+    static STD_MUTEX: Mutex<i32> = Mutex::const_new_std(0);
+
+    #[test]
+    fn test_mutex_const_new_std() {
+        assert!(matches!(STD_MUTEX.0, MutexInner::Std(_)));
+        *SyncRuntime::block_on(STD_MUTEX.lock()).unwrap() += 1;
+    }
+
+    #[cfg(tokio_sync)]
+    static TOKIO_MUTEX: Mutex<i32> = Mutex::const_new_tokio(0);
+
+    #[cfg(tokio_sync)]
+    #[tokio::test]
+    async fn test_mutex_const_new_tokio() {
+        assert!(matches!(TOKIO_MUTEX.0, MutexInner::Tokio(_)));
+        *TOKIO_MUTEX.lock().await.unwrap() += 1;
+    }
 }
@@ -0,0 +1,120 @@
+use std::ops::{Deref, DerefMut};
+use std::ptr::NonNull;
+use std::sync::{Arc, PoisonError};
+
+use super::super::held_guard::HeldGuard;
+use super::{Mutex, MutexInner};
+
+/// An owned RAII guard of a [`Mutex`], which keeps the [`Arc`] of the mutex alive, so it has no lifetime.
+///
+/// The mutex is unlocked when the guard is dropped.
+///
+/// This structure is created by the [`Mutex::lock_owned`] method.
+#[derive(Debug)]
+pub struct OwnedMutexGuard<T: 'static>(OwnedMutexGuardInner<T>);
+
+#[derive(Debug)]
+enum OwnedMutexGuardInner<T: 'static> {
+    /// Std mutex guard, held by a dedicated thread
+    Std {
+        guard: HeldGuard<T>,
+        mutex: Arc<Mutex<T>>,
+    },
+    /// Tokio mutex guard; it is declared before the mutex it borrows, so it's dropped first.
+    #[cfg(tokio_sync)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio-sync")))]
+    Tokio {
+        guard: tokio::sync::MutexGuard<'static, T>,
+        mutex: Arc<Mutex<T>>,
+    },
+}
+
+// SAFETY: the std guard is held and released by its own thread, so the guard only gives access to the data from
+// other threads, like a tokio guard, which is `Send` when `T` is.
+unsafe impl<T: Send> Send for OwnedMutexGuard<T> {}
+
+// SAFETY: a shared reference to the guard only gives shared access to the data.
+unsafe impl<T: Send + Sync> Sync for OwnedMutexGuard<T> {}
+
+impl<T> OwnedMutexGuard<T> {
+    /// Locks the std mutex behind `mutex`, holding its guard on a dedicated thread.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mutex` is not backed by std.
+    pub(super) fn lock_std(mutex: Arc<Mutex<T>>) -> Result<Self, PoisonError<Self>>
+    where
+        T: Send,
+    {
+        let holder_mutex = Arc::clone(&mutex);
+        let (guard, poisoned) = HeldGuard::spawn(move |holder| {
+            #[allow(irrefutable_let_patterns)]
+            let MutexInner::Std(std_mutex) = &holder_mutex.0 else {
+                panic!("the mutex is not backed by std");
+            };
+            let (mut guard, poisoned) = match std_mutex.lock() {
+                Ok(guard) => (guard, false),
+                Err(poisoned) => (poisoned.into_inner(), true),
+            };
+            holder.hold(NonNull::from(&mut *guard), poisoned);
+        });
+
+        let guard = Self(OwnedMutexGuardInner::Std { guard, mutex });
+        if poisoned {
+            Err(PoisonError::new(guard))
+        } else {
+            Ok(guard)
+        }
+    }
+
+    /// Locks the tokio mutex behind `mutex`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mutex` is not backed by tokio.
+    #[cfg(tokio_sync)]
+    pub(super) async fn lock_tokio(mutex: Arc<Mutex<T>>) -> Self {
+        let MutexInner::Tokio(tokio_mutex) = &mutex.0 else {
+            panic!("the mutex is not backed by tokio");
+        };
+        // SAFETY: the guard borrows the mutex for as long as the `Arc`, which is stored along with it and dropped
+        // after it
+        let tokio_mutex: &'static tokio::sync::Mutex<T> =
+            unsafe { &*std::ptr::from_ref(tokio_mutex) };
+        let guard = tokio_mutex.lock().await;
+
+        Self(OwnedMutexGuardInner::Tokio { guard, mutex })
+    }
+
+    /// Returns the [`Arc`] of the mutex locked by this guard.
+    pub fn mutex(this: &Self) -> &Arc<Mutex<T>> {
+        match &this.0 {
+            OwnedMutexGuardInner::Std { mutex, .. } => mutex,
+            #[cfg(tokio_sync)]
+            OwnedMutexGuardInner::Tokio { mutex, .. } => mutex,
+        }
+    }
+}
+
+impl<T> Deref for OwnedMutexGuard<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        match &self.0 {
+            OwnedMutexGuardInner::Std { guard, .. } => guard.get(),
+            #[cfg(tokio_sync)]
+            OwnedMutexGuardInner::Tokio { guard, .. } => guard.deref(),
+        }
+    }
+}
+
+impl<T> DerefMut for OwnedMutexGuard<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match &mut self.0 {
+            // SAFETY: a mutex guard grants exclusive access
+            OwnedMutexGuardInner::Std { guard, .. } => unsafe { guard.get_mut() },
+            #[cfg(tokio_sync)]
+            OwnedMutexGuardInner::Tokio { guard, .. } => guard.deref_mut(),
+        }
+    }
+}
@@ -0,0 +1,262 @@
+use std::convert::Infallible;
+use std::sync::{Mutex, OnceLock};
+
+use crate::maybe_fut_constructor_sync;
+
+/// A thread-safe cell which can be written to only once, initialized with an async closure.
+///
+/// It is useful for the lazy initialization of globals, such as a client which needs to hit the network to be
+/// built. A [`OnceCell`] can be declared as a `static` with [`OnceCell::const_new_std`] or
+/// [`OnceCell::const_new_tokio`].
+///
+/// In sync contexts, the initializer of [`OnceCell::get_or_init`] and [`OnceCell::get_or_try_init`] is run to
+/// completion on the current thread, blocking the other threads initializing the cell.
+#[derive(Debug)]
+pub struct OnceCell<T>(OnceCellInner<T>);
+
+/// Inner wrapper for [`OnceCell`].
+#[derive(Debug)]
+enum OnceCellInner<T> {
+    /// Std once cell.
+    Std(StdOnceCell<T>),
+    /// Tokio once cell.
+    #[cfg(tokio_sync)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio-sync")))]
+    Tokio(tokio::sync::OnceCell<T>),
+}
+
+impl<T> From<OnceLock<T>> for OnceCell<T> {
+    fn from(lock: OnceLock<T>) -> Self {
+        OnceCell(OnceCellInner::Std(StdOnceCell {
+            lock,
+            init: Mutex::new(()),
+        }))
+    }
+}
+
+#[cfg(tokio_sync)]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio-sync")))]
+impl<T> From<tokio::sync::OnceCell<T>> for OnceCell<T> {
+    fn from(cell: tokio::sync::OnceCell<T>) -> Self {
+        OnceCell(OnceCellInner::Tokio(cell))
+    }
+}
+
+impl<T> OnceCell<T> {
+    maybe_fut_constructor_sync!(
+        /// Creates a new empty cell.
+        new() -> Self,
+        OnceLock::new,
+        tokio::sync::OnceCell::new,
+        tokio_sync
+    );
+
+    /// Creates a new empty std cell, in a const context.
+    ///
+    /// Unlike [`OnceCell::new`], the runtime can't be checked in a const context, so this can be used to declare a
+    /// `static` which is always backed by a [`std::sync::OnceLock`].
+    /// Its initializers are always run to completion with the sync runtime, so they must not await tokio resources.
+    pub const fn const_new_std() -> Self {
+        OnceCell(OnceCellInner::Std(StdOnceCell {
+            lock: OnceLock::new(),
+            init: Mutex::new(()),
+        }))
+    }
+
+    /// Creates a new empty tokio cell, in a const context.
+    ///
+    /// Unlike [`OnceCell::new`], the runtime can't be checked in a const context, so this can be used to declare a
+    /// `static` which is always backed by a [`tokio::sync::OnceCell`].
+    #[cfg(tokio_sync)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio-sync")))]
+    pub const fn const_new_tokio() -> Self {
+        OnceCell(OnceCellInner::Tokio(tokio::sync::OnceCell::const_new()))
+    }
+
+    /// Returns a reference to the value of the cell, or `None` if the cell is not initialized yet.
+    pub fn get(&self) -> Option<&T> {
+        match &self.0 {
+            OnceCellInner::Std(cell) => cell.lock.get(),
+            #[cfg(tokio_sync)]
+            OnceCellInner::Tokio(cell) => cell.get(),
+        }
+    }
+
+    /// Returns `true` if the cell has been initialized.
+    pub fn initialized(&self) -> bool {
+        self.get().is_some()
+    }
+
+    /// Sets the value of the cell.
+    ///
+    /// If the cell is already initialized, or, with tokio, being initialized, the value is given back as the error.
+    pub fn set(&self, value: T) -> Result<(), T> {
+        match &self.0 {
+            OnceCellInner::Std(cell) => cell.lock.set(value),
+            #[cfg(tokio_sync)]
+            OnceCellInner::Tokio(cell) => cell.set(value).map_err(|err| match err {
+                tokio::sync::SetError::AlreadyInitializedError(value)
+                | tokio::sync::SetError::InitializingError(value) => value,
+            }),
+        }
+    }
+
+    /// Returns the value of the cell, initializing it with `f` if the cell is empty.
+    ///
+    /// Only one caller runs its initializer at a time; the others wait for it and get the value it returned.
+    pub async fn get_or_init<F, Fut>(&self, f: F) -> &T
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        match &self.0 {
+            OnceCellInner::Std(cell) => {
+                let Ok(value) =
+                    cell.get_or_try_init(|| async move { Ok::<_, Infallible>(f().await) });
+                value
+            }
+            #[cfg(tokio_sync)]
+            OnceCellInner::Tokio(cell) => cell.get_or_init(f).await,
+        }
+    }
+
+    /// Returns the value of the cell, initializing it with `f` if the cell is empty.
+    ///
+    /// If the initializer fails, the error is returned and the cell is left empty, so the next caller runs its own
+    /// initializer.
+    pub async fn get_or_try_init<E, F, Fut>(&self, f: F) -> Result<&T, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        match &self.0 {
+            OnceCellInner::Std(cell) => cell.get_or_try_init(f),
+            #[cfg(tokio_sync)]
+            OnceCellInner::Tokio(cell) => cell.get_or_try_init(f).await,
+        }
+    }
+
+    /// Consumes the cell, returning its value, or `None` if the cell is not initialized.
+    pub fn into_inner(self) -> Option<T> {
+        match self.0 {
+            OnceCellInner::Std(cell) => cell.lock.into_inner(),
+            #[cfg(tokio_sync)]
+            OnceCellInner::Tokio(cell) => cell.into_inner(),
+        }
+    }
+}
+
+impl<T> Default for OnceCell<T> {
+    fn default() -> Self {
+        OnceCell::new()
+    }
+}
+
+/// The std implementation of the cell.
+///
+/// [`OnceLock::get_or_try_init`] is unstable, so the initializers are serialized with a mutex.
+#[derive(Debug)]
+struct StdOnceCell<T> {
+    lock: OnceLock<T>,
+    init: Mutex<()>,
+}
+
+impl<T> StdOnceCell<T> {
+    /// Initializes the cell blocking on `f`, unless it's already initialized.
+    fn get_or_try_init<E, F, Fut>(&self, f: F) -> Result<&T, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        if let Some(value) = self.lock.get() {
+            return Ok(value);
+        }
+
+        // the mutex guards no data, so it can't be left inconsistent by a panicking initializer
+        let _init = self
+            .init
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(value) = self.lock.get() {
+            return Ok(value);
+        }
+        let value = crate::SyncRuntime::block_on(f())?;
+
+        Ok(self.lock.get_or_init(|| value))
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::SyncRuntime;
+
+    #[test]
+    fn test_should_init_once_sync() {
+        let cell = OnceCell::new();
+        assert!(matches!(cell.0, OnceCellInner::Std(_)));
+        assert_eq!(cell.get(), None);
+
+        assert_eq!(
+            SyncRuntime::block_on(cell.get_or_init(|| async { 42 })),
+            &42
+        );
+        assert_eq!(SyncRuntime::block_on(cell.get_or_init(|| async { 0 })), &42);
+        assert!(cell.initialized());
+        assert_eq!(cell.set(0), Err(0));
+        assert_eq!(cell.into_inner(), Some(42));
+    }
+
+    #[test]
+    fn test_should_retry_failed_init_sync() {
+        let cell = OnceCell::new();
+        let result = SyncRuntime::block_on(cell.get_or_try_init(|| async { Err("failed") }));
+        assert_eq!(result, Err("failed"));
+        assert!(!cell.initialized());
+
+        let result = SyncRuntime::block_on(cell.get_or_try_init(|| async { Ok::<_, &str>(42) }));
+        assert_eq!(result, Ok(&42));
+    }
+
+    #[test]
+    fn test_should_run_initializer_once_across_threads() {
+        static CELL: OnceCell<usize> = OnceCell::const_new_std();
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let calls = Arc::clone(&calls);
+                std::thread::spawn(move || {
+                    *SyncRuntime::block_on(CELL.get_or_init(|| async {
+                        std::thread::sleep(std::time::Duration::from_millis(20));
+                        calls.fetch_add(1, Ordering::SeqCst)
+                    }))
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            assert_eq!(handle.join().expect("failed to join"), 0);
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[cfg(tokio_sync)]
+    #[tokio::test]
+    async fn test_should_init_once_tokio() {
+        let cell = OnceCell::new();
+        assert!(matches!(cell.0, OnceCellInner::Tokio(_)));
+
+        let result = cell.get_or_try_init(|| async { Err("failed") }).await;
+        assert_eq!(result, Err("failed"));
+        assert_eq!(cell.get_or_init(|| async { 42 }).await, &42);
+        assert_eq!(cell.get_or_init(|| async { 0 }).await, &42);
+        assert_eq!(cell.set(0), Err(0));
+    }
+}
@@ -1,6 +1,12 @@
+mod owned_read_guard;
+mod owned_write_guard;
 mod read_guard;
 mod write_guard;
 
+use std::sync::{Arc, PoisonError};
+
+pub use self::owned_read_guard::OwnedRwLockReadGuard;
+pub use self::owned_write_guard::OwnedRwLockWriteGuard;
 pub use self::read_guard::RwLockReadGuard;
 pub use self::write_guard::RwLockWriteGuard;
 use crate::maybe_fut_constructor_sync;
@@ -12,8 +18,8 @@ use crate::maybe_fut_constructor_sync;
 /// and the read portion of this lock typically allows for read-only access (shared access).
 #[derive(Debug, Unwrap)]
 #[unwrap_types(
-    std(std::sync::RwLock),
-    tokio(tokio::sync::RwLock),
+    std(std::sync::RwLock<T>),
+    tokio(tokio::sync::RwLock<T>),
     tokio_gated("tokio-sync")
 )]
 pub struct RwLock<T>(RwLockInner<T>)
@@ -57,6 +63,24 @@ where
         tokio_sync
     );
 
+    /// Creates a new std lock in an unlocked state, in a const context.
+    ///
+    /// Unlike [`RwLock::new`], the runtime can't be checked in a const context, so this can be used to declare a
+    /// `static` which is always backed by a [`std::sync::RwLock`].
+    pub const fn const_new_std(t: T) -> Self {
+        RwLock(RwLockInner::Std(std::sync::RwLock::new(t)))
+    }
+
+    /// Creates a new tokio lock in an unlocked state, in a const context.
+    ///
+    /// Unlike [`RwLock::new`], the runtime can't be checked in a const context, so this can be used to declare a
+    /// `static` which is always backed by a [`tokio::sync::RwLock`].
+    #[cfg(tokio_sync)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio-sync")))]
+    pub const fn const_new_tokio(t: T) -> Self {
+        RwLock(RwLockInner::Tokio(tokio::sync::RwLock::const_new(t)))
+    }
+
     /// Clear the poisoned state from a read-write lock.
     ///
     /// If the lock is poisoned, it will remain poisoned until this function is called.
@@ -92,6 +116,45 @@ where
         }
     }
 
+    /// Locks this RwLock with shared read access from sync code, blocking the current thread until it can be
+    /// acquired.
+    ///
+    /// It's the same as [`RwLock::read`], without the boilerplate of blocking on a future.
+    ///
+    /// # Panics
+    ///
+    /// With a tokio lock, panics if called within an async context, like [`tokio::sync::RwLock::blocking_read`].
+    pub fn blocking_read(
+        &self,
+    ) -> Result<RwLockReadGuard<'_, T>, PoisonError<std::sync::RwLockReadGuard<'_, T>>> {
+        match &self.0 {
+            RwLockInner::Std(lock) => Ok(RwLockReadGuard::from(lock.read()?)),
+            #[cfg(tokio_sync)]
+            RwLockInner::Tokio(lock) => Ok(RwLockReadGuard::from(lock.blocking_read())),
+        }
+    }
+
+    /// Locks the RwLock behind an [`Arc`] with shared read access, returning an owned guard which keeps the [`Arc`]
+    /// alive.
+    ///
+    /// Unlike the guard returned by [`RwLock::read`], the [`OwnedRwLockReadGuard`] has no lifetime, so it can be
+    /// stored in structs, and it can be sent to another thread, like a [`tokio::sync::OwnedRwLockReadGuard`].
+    ///
+    /// Since a std lock must be unlocked by the thread which locked it, with a std lock the guard is held by a
+    /// dedicated thread, which unlocks the lock once the [`OwnedRwLockReadGuard`] is dropped.
+    pub async fn read_owned(
+        self: Arc<Self>,
+    ) -> Result<OwnedRwLockReadGuard<T>, PoisonError<OwnedRwLockReadGuard<T>>>
+    where
+        T: Send + Sync + 'static,
+    {
+        match &self.0 {
+            RwLockInner::Std(_) => OwnedRwLockReadGuard::read_std(self),
+            #[cfg(tokio_sync)]
+            RwLockInner::Tokio(_) => Ok(OwnedRwLockReadGuard::read_tokio(self).await),
+        }
+    }
+
     /// Attempts to lock this RwLock with shared read access, returning immediately if it cannot be acquired.
     pub async fn try_read(
         &self,
@@ -119,6 +182,45 @@ where
         }
     }
 
+    /// Locks this RwLock with exclusive write access from sync code, blocking the current thread until it can be
+    /// acquired.
+    ///
+    /// It's the same as [`RwLock::write`], without the boilerplate of blocking on a future.
+    ///
+    /// # Panics
+    ///
+    /// With a tokio lock, panics if called within an async context, like [`tokio::sync::RwLock::blocking_write`].
+    pub fn blocking_write(
+        &self,
+    ) -> Result<RwLockWriteGuard<'_, T>, PoisonError<std::sync::RwLockWriteGuard<'_, T>>> {
+        match &self.0 {
+            RwLockInner::Std(lock) => Ok(RwLockWriteGuard::from(lock.write()?)),
+            #[cfg(tokio_sync)]
+            RwLockInner::Tokio(lock) => Ok(RwLockWriteGuard::from(lock.blocking_write())),
+        }
+    }
+
+    /// Locks the RwLock behind an [`Arc`] with exclusive write access, returning an owned guard which keeps the
+    /// [`Arc`] alive.
+    ///
+    /// Unlike the guard returned by [`RwLock::write`], the [`OwnedRwLockWriteGuard`] has no lifetime, so it can be
+    /// stored in structs, and it can be sent to another thread, like a [`tokio::sync::OwnedRwLockWriteGuard`].
+    ///
+    /// Since a std lock must be unlocked by the thread which locked it, with a std lock the guard is held by a
+    /// dedicated thread, which unlocks the lock once the [`OwnedRwLockWriteGuard`] is dropped.
+    pub async fn write_owned(
+        self: Arc<Self>,
+    ) -> Result<OwnedRwLockWriteGuard<T>, PoisonError<OwnedRwLockWriteGuard<T>>>
+    where
+        T: Send + Sync + 'static,
+    {
+        match &self.0 {
+            RwLockInner::Std(_) => OwnedRwLockWriteGuard::write_std(self),
+            #[cfg(tokio_sync)]
+            RwLockInner::Tokio(_) => Ok(OwnedRwLockWriteGuard::write_tokio(self).await),
+        }
+    }
+
     /// Attempts to lock this RwLock with exclusive write access, returning immediately if it cannot be acquired.
     pub async fn try_write(
         &self,
@@ -288,4 +390,101 @@ mod test {
         let read_guard = rwlock.read().await.unwrap();
         assert_eq!(*read_guard, 43);
     }
+
+    #[test]
+    fn test_rwlock_blocking_read_write_sync() {
+        let rwlock = RwLock::new(42);
+        *rwlock.blocking_write().unwrap() += 1;
+        assert_eq!(*rwlock.blocking_read().unwrap(), 43);
+    }
+
+    #[cfg(tokio_sync)]
+    #[test]
+    fn test_rwlock_blocking_read_write_tokio() {
+        let rwlock: RwLock<i32> = RwLock::from(tokio::sync::RwLock::new(42));
+        *rwlock.blocking_write().unwrap() += 1;
+        assert_eq!(*rwlock.blocking_read().unwrap(), 43);
+    }
+
+    #[test]
+    fn test_rwlock_owned_guards_sync() {
+        let rwlock = Arc::new(RwLock::new(42));
+        let mut write_guard = SyncRuntime::block_on(Arc::clone(&rwlock).write_owned()).unwrap();
+        assert!(Arc::ptr_eq(
+            OwnedRwLockWriteGuard::rwlock(&write_guard),
+            &rwlock
+        ));
+        assert!(SyncRuntime::block_on(rwlock.try_read()).is_err());
+        *write_guard = 43;
+        drop(write_guard);
+
+        // the guards keep the lock alive
+        let first = SyncRuntime::block_on(Arc::clone(&rwlock).read_owned()).unwrap();
+        let second = SyncRuntime::block_on(rwlock.read_owned()).unwrap();
+        drop(first);
+        assert_eq!(*second, 43);
+        assert!(SyncRuntime::block_on(OwnedRwLockReadGuard::rwlock(&second).try_write()).is_err());
+    }
+
+    #[cfg(tokio_sync)]
+    #[tokio::test]
+    async fn test_rwlock_owned_guards_tokio() {
+        let rwlock = Arc::new(RwLock::new(42));
+        let mut write_guard = Arc::clone(&rwlock).write_owned().await.unwrap();
+        assert!(rwlock.try_read().await.is_err());
+        *write_guard = 43;
+        drop(write_guard);
+
+        let first = Arc::clone(&rwlock).read_owned().await.unwrap();
+        let second = rwlock.read_owned().await.unwrap();
+        drop(first);
+        assert_eq!(*second, 43);
+        assert!(
+            OwnedRwLockReadGuard::rwlock(&second)
+                .try_write()
+                .await
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_rwlock_owned_guards_should_be_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<OwnedRwLockReadGuard<i32>>();
+        assert_send::<OwnedRwLockWriteGuard<i32>>();
+    }
+
+    #[test]
+    fn test_rwlock_should_send_owned_guards_sync() {
+        let rwlock = Arc::new(RwLock::new(42));
+        let write_guard = SyncRuntime::block_on(Arc::clone(&rwlock).write_owned()).unwrap();
+        std::thread::spawn(move || {
+            let mut write_guard = write_guard;
+            *write_guard = 43;
+        })
+        .join()
+        .unwrap();
+
+        let read_guard = SyncRuntime::block_on(Arc::clone(&rwlock).read_owned()).unwrap();
+        assert_eq!(std::thread::spawn(move || *read_guard).join().unwrap(), 43);
+        assert_eq!(*SyncRuntime::block_on(rwlock.write()).unwrap(), 43);
+    }
+
+    static STD_RWLOCK: RwLock<i32> = RwLock::const_new_std(0);
+
+    #[test]
+    fn test_rwlock_const_new_std() {
+        assert!(matches!(STD_RWLOCK.0, RwLockInner::Std(_)));
+        *SyncRuntime::block_on(STD_RWLOCK.write()).unwrap() += 1;
+    }
+
+    #[cfg(tokio_sync)]
+    static TOKIO_RWLOCK: RwLock<i32> = RwLock::const_new_tokio(0);
+
+    #[cfg(tokio_sync)]
+    #[tokio::test]
+    async fn test_rwlock_const_new_tokio() {
+        assert!(matches!(TOKIO_RWLOCK.0, RwLockInner::Tokio(_)));
+        *TOKIO_RWLOCK.write().await.unwrap() += 1;
+    }
 }
@@ -0,0 +1,119 @@
+use std::ops::{Deref, DerefMut};
+use std::ptr::NonNull;
+use std::sync::{Arc, PoisonError};
+
+use super::super::held_guard::HeldGuard;
+use super::{RwLock, RwLockInner};
+
+/// An owned RAII structure releasing the exclusive write access of a lock when dropped, which keeps the [`Arc`] of
+/// the lock alive, so it has no lifetime.
+///
+/// This structure is created by the [`RwLock::write_owned`] method.
+#[derive(Debug)]
+pub struct OwnedRwLockWriteGuard<T: 'static>(OwnedRwLockWriteGuardInner<T>);
+
+#[derive(Debug)]
+enum OwnedRwLockWriteGuardInner<T: 'static> {
+    /// Std lock guard, held by a dedicated thread
+    Std {
+        guard: HeldGuard<T>,
+        lock: Arc<RwLock<T>>,
+    },
+    /// Tokio lock guard; it is declared before the lock it borrows, so it's dropped first.
+    #[cfg(tokio_sync)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio-sync")))]
+    Tokio {
+        guard: tokio::sync::RwLockWriteGuard<'static, T>,
+        lock: Arc<RwLock<T>>,
+    },
+}
+
+// SAFETY: the std guard is held and released by its own thread, so the guard only gives access to the data from
+// other threads, like a tokio guard, which is `Send` when `T` is `Send` and `Sync`.
+unsafe impl<T: Send + Sync> Send for OwnedRwLockWriteGuard<T> {}
+
+// SAFETY: a shared reference to the guard only gives shared access to the data.
+unsafe impl<T: Send + Sync> Sync for OwnedRwLockWriteGuard<T> {}
+
+impl<T> OwnedRwLockWriteGuard<T> {
+    /// Locks the std lock behind `lock` with exclusive write access, holding its guard on a dedicated thread.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lock` is not backed by std.
+    pub(super) fn write_std(lock: Arc<RwLock<T>>) -> Result<Self, PoisonError<Self>>
+    where
+        T: Send + Sync,
+    {
+        let holder_lock = Arc::clone(&lock);
+        let (guard, poisoned) = HeldGuard::spawn(move |holder| {
+            #[allow(irrefutable_let_patterns)]
+            let RwLockInner::Std(std_lock) = &holder_lock.0 else {
+                panic!("the lock is not backed by std");
+            };
+            let (mut guard, poisoned) = match std_lock.write() {
+                Ok(guard) => (guard, false),
+                Err(poisoned) => (poisoned.into_inner(), true),
+            };
+            holder.hold(NonNull::from(&mut *guard), poisoned);
+        });
+
+        let guard = Self(OwnedRwLockWriteGuardInner::Std { guard, lock });
+        if poisoned {
+            Err(PoisonError::new(guard))
+        } else {
+            Ok(guard)
+        }
+    }
+
+    /// Locks the tokio lock behind `lock` with exclusive write access.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lock` is not backed by tokio.
+    #[cfg(tokio_sync)]
+    pub(super) async fn write_tokio(lock: Arc<RwLock<T>>) -> Self {
+        let RwLockInner::Tokio(tokio_lock) = &lock.0 else {
+            panic!("the lock is not backed by tokio");
+        };
+        // SAFETY: the guard borrows the lock for as long as the `Arc`, which is stored along with it and dropped
+        // after it
+        let tokio_lock: &'static tokio::sync::RwLock<T> =
+            unsafe { &*std::ptr::from_ref(tokio_lock) };
+        let guard = tokio_lock.write().await;
+
+        Self(OwnedRwLockWriteGuardInner::Tokio { guard, lock })
+    }
+
+    /// Returns the [`Arc`] of the lock held by this guard.
+    pub fn rwlock(this: &Self) -> &Arc<RwLock<T>> {
+        match &this.0 {
+            OwnedRwLockWriteGuardInner::Std { lock, .. } => lock,
+            #[cfg(tokio_sync)]
+            OwnedRwLockWriteGuardInner::Tokio { lock, .. } => lock,
+        }
+    }
+}
+
+impl<T> Deref for OwnedRwLockWriteGuard<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        match &self.0 {
+            OwnedRwLockWriteGuardInner::Std { guard, .. } => guard.get(),
+            #[cfg(tokio_sync)]
+            OwnedRwLockWriteGuardInner::Tokio { guard, .. } => guard.deref(),
+        }
+    }
+}
+
+impl<T> DerefMut for OwnedRwLockWriteGuard<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match &mut self.0 {
+            // SAFETY: a write guard grants exclusive access
+            OwnedRwLockWriteGuardInner::Std { guard, .. } => unsafe { guard.get_mut() },
+            #[cfg(tokio_sync)]
+            OwnedRwLockWriteGuardInner::Tokio { guard, .. } => guard.deref_mut(),
+        }
+    }
+}
@@ -0,0 +1,385 @@
+use std::fmt;
+use std::sync::{Condvar, Mutex};
+
+/// A counting semaphore, limiting the number of tasks or threads accessing a resource at the same time.
+///
+/// A permit is acquired with [`Semaphore::acquire`] or [`Semaphore::try_acquire`], and it is released back to the
+/// semaphore when the returned [`SemaphorePermit`] is dropped.
+///
+/// In sync contexts, [`Semaphore::acquire`] blocks the current thread until a permit is available.
+#[derive(Debug)]
+pub struct Semaphore(SemaphoreInner);
+
+/// Inner wrapper for [`Semaphore`].
+#[derive(Debug)]
+enum SemaphoreInner {
+    /// Std semaphore, built on a [`Mutex`] and a [`Condvar`].
+    Std(StdSemaphore),
+    /// Tokio semaphore.
+    #[cfg(tokio_sync)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio-sync")))]
+    Tokio(tokio::sync::Semaphore),
+}
+
+#[cfg(tokio_sync)]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio-sync")))]
+impl From<tokio::sync::Semaphore> for Semaphore {
+    fn from(semaphore: tokio::sync::Semaphore) -> Self {
+        Self(SemaphoreInner::Tokio(semaphore))
+    }
+}
+
+impl Semaphore {
+    /// The maximum number of permits of a semaphore, the same as [`tokio::sync::Semaphore::MAX_PERMITS`].
+    pub const MAX_PERMITS: usize = usize::MAX >> 3;
+
+    /// Creates a new semaphore with the given number of permits.
+    ///
+    /// # Panics
+    ///
+    /// In a tokio context, panics if `permits` exceeds [`Semaphore::MAX_PERMITS`].
+    pub fn new(permits: usize) -> Self {
+        #[cfg(tokio_sync)]
+        if crate::is_tokio_context() {
+            return tokio::sync::Semaphore::new(permits).into();
+        }
+
+        Self(SemaphoreInner::Std(StdSemaphore::new(permits)))
+    }
+
+    /// Creates a new std semaphore with the given number of permits, regardless of the context.
+    ///
+    /// Unlike [`Semaphore::new`], it never checks the context, which is useful to create a semaphore in an async
+    /// context to share it with sync threads.
+    pub fn new_std(permits: usize) -> Self {
+        Self(SemaphoreInner::Std(StdSemaphore::new(permits)))
+    }
+
+    /// Returns the number of permits currently available.
+    pub fn available_permits(&self) -> usize {
+        match &self.0 {
+            SemaphoreInner::Std(semaphore) => semaphore.state().permits,
+            #[cfg(tokio_sync)]
+            SemaphoreInner::Tokio(semaphore) => semaphore.available_permits(),
+        }
+    }
+
+    /// Adds `n` new permits to the semaphore, waking up the tasks and threads waiting for them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the permits would exceed [`Semaphore::MAX_PERMITS`], like [`tokio::sync::Semaphore::add_permits`].
+    pub fn add_permits(&self, n: usize) {
+        match &self.0 {
+            SemaphoreInner::Std(semaphore) => semaphore.release(n),
+            #[cfg(tokio_sync)]
+            SemaphoreInner::Tokio(semaphore) => semaphore.add_permits(n),
+        }
+    }
+
+    /// Acquires a permit, waiting until one is available.
+    ///
+    /// In sync contexts, the current thread is blocked until a permit is available.
+    /// Fails with [`AcquireError`] if the semaphore has been closed.
+    pub async fn acquire(&self) -> Result<SemaphorePermit<'_>, AcquireError> {
+        match &self.0 {
+            SemaphoreInner::Std(semaphore) => {
+                let mut state = semaphore.state();
+                loop {
+                    if state.closed {
+                        return Err(AcquireError);
+                    }
+                    if state.permits > 0 {
+                        state.permits -= 1;
+                        return Ok(SemaphorePermit(SemaphorePermitInner::Std(
+                            StdSemaphorePermit(semaphore),
+                        )));
+                    }
+                    state = semaphore
+                        .condvar
+                        .wait(state)
+                        .unwrap_or_else(|poisoned| poisoned.into_inner());
+                }
+            }
+            #[cfg(tokio_sync)]
+            SemaphoreInner::Tokio(semaphore) => semaphore
+                .acquire()
+                .await
+                .map(|permit| SemaphorePermit(SemaphorePermitInner::Tokio(permit)))
+                .map_err(|_| AcquireError),
+        }
+    }
+
+    /// Tries to acquire a permit, without waiting.
+    ///
+    /// Fails with [`TryAcquireError::NoPermits`] if no permit is available, or with [`TryAcquireError::Closed`] if
+    /// the semaphore has been closed.
+    pub fn try_acquire(&self) -> Result<SemaphorePermit<'_>, TryAcquireError> {
+        match &self.0 {
+            SemaphoreInner::Std(semaphore) => {
+                let mut state = semaphore.state();
+                if state.closed {
+                    return Err(TryAcquireError::Closed);
+                }
+                if state.permits == 0 {
+                    return Err(TryAcquireError::NoPermits);
+                }
+                state.permits -= 1;
+                Ok(SemaphorePermit(SemaphorePermitInner::Std(
+                    StdSemaphorePermit(semaphore),
+                )))
+            }
+            #[cfg(tokio_sync)]
+            SemaphoreInner::Tokio(semaphore) => semaphore
+                .try_acquire()
+                .map(|permit| SemaphorePermit(SemaphorePermitInner::Tokio(permit)))
+                .map_err(|err| match err {
+                    tokio::sync::TryAcquireError::Closed => TryAcquireError::Closed,
+                    tokio::sync::TryAcquireError::NoPermits => TryAcquireError::NoPermits,
+                }),
+        }
+    }
+
+    /// Closes the semaphore, so that acquiring a permit fails from now on.
+    ///
+    /// The tasks and threads waiting for a permit are woken up with an error; the permits already acquired are
+    /// not affected.
+    pub fn close(&self) {
+        match &self.0 {
+            SemaphoreInner::Std(semaphore) => {
+                semaphore.state().closed = true;
+                semaphore.condvar.notify_all();
+            }
+            #[cfg(tokio_sync)]
+            SemaphoreInner::Tokio(semaphore) => semaphore.close(),
+        }
+    }
+
+    /// Returns `true` if the semaphore has been closed.
+    pub fn is_closed(&self) -> bool {
+        match &self.0 {
+            SemaphoreInner::Std(semaphore) => semaphore.state().closed,
+            #[cfg(tokio_sync)]
+            SemaphoreInner::Tokio(semaphore) => semaphore.is_closed(),
+        }
+    }
+}
+
+/// A permit acquired from a [`Semaphore`], released back to it when dropped.
+///
+/// This structure is created by the [`Semaphore::acquire`] and [`Semaphore::try_acquire`] methods.
+#[derive(Debug)]
+#[must_use = "the permit is released as soon as it is dropped"]
+pub struct SemaphorePermit<'a>(SemaphorePermitInner<'a>);
+
+/// Inner wrapper for [`SemaphorePermit`].
+#[derive(Debug)]
+enum SemaphorePermitInner<'a> {
+    /// Std semaphore permit.
+    Std(StdSemaphorePermit<'a>),
+    /// Tokio semaphore permit.
+    #[cfg(tokio_sync)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio-sync")))]
+    Tokio(tokio::sync::SemaphorePermit<'a>),
+}
+
+impl SemaphorePermit<'_> {
+    /// Forgets the permit without releasing it back to the semaphore, reducing the number of its permits.
+    pub fn forget(self) {
+        match self.0 {
+            SemaphorePermitInner::Std(permit) => std::mem::forget(permit),
+            #[cfg(tokio_sync)]
+            SemaphorePermitInner::Tokio(permit) => permit.forget(),
+        }
+    }
+}
+
+/// The error returned by [`Semaphore::acquire`] when the semaphore has been closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AcquireError;
+
+impl fmt::Display for AcquireError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "semaphore closed")
+    }
+}
+
+impl std::error::Error for AcquireError {}
+
+/// The error returned by [`Semaphore::try_acquire`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryAcquireError {
+    /// The semaphore has been closed.
+    Closed,
+    /// No permit is available.
+    NoPermits,
+}
+
+impl fmt::Display for TryAcquireError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Closed => write!(f, "semaphore closed"),
+            Self::NoPermits => write!(f, "no permits available"),
+        }
+    }
+}
+
+impl std::error::Error for TryAcquireError {}
+
+/// The std implementation of the semaphore.
+#[derive(Debug)]
+struct StdSemaphore {
+    state: Mutex<StdSemaphoreState>,
+    /// Notifies the threads waiting for a permit.
+    condvar: Condvar,
+}
+
+#[derive(Debug)]
+struct StdSemaphoreState {
+    permits: usize,
+    closed: bool,
+}
+
+impl StdSemaphore {
+    fn new(permits: usize) -> Self {
+        Self {
+            state: Mutex::new(StdSemaphoreState {
+                permits,
+                closed: false,
+            }),
+            condvar: Condvar::new(),
+        }
+    }
+
+    /// Gives `n` permits back to the semaphore, waking up the waiting threads, which compete for them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the permits would exceed [`Semaphore::MAX_PERMITS`].
+    fn release(&self, n: usize) {
+        let mut state = self.state();
+        state.permits = state
+            .permits
+            .checked_add(n)
+            .filter(|permits| *permits <= Semaphore::MAX_PERMITS)
+            .unwrap_or_else(|| {
+                panic!(
+                    "number of added permits ({n}) would overflow MAX_PERMITS ({})",
+                    Semaphore::MAX_PERMITS
+                )
+            });
+        drop(state);
+        self.condvar.notify_all();
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, StdSemaphoreState> {
+        // the state is updated with single assignments, so a poisoned state is still consistent
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// A permit of a [`StdSemaphore`], released when dropped.
+#[derive(Debug)]
+struct StdSemaphorePermit<'a>(&'a StdSemaphore);
+
+impl Drop for StdSemaphorePermit<'_> {
+    fn drop(&mut self) {
+        self.0.release(1);
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::SyncRuntime;
+
+    #[test]
+    fn test_should_acquire_and_release_permits_sync() {
+        let semaphore = Semaphore::new(2);
+        assert!(matches!(semaphore.0, SemaphoreInner::Std(_)));
+
+        let first = SyncRuntime::block_on(semaphore.acquire()).expect("failed to acquire");
+        let second = semaphore.try_acquire().expect("failed to acquire");
+        assert_eq!(semaphore.available_permits(), 0);
+        assert_eq!(
+            semaphore.try_acquire().map(|_| ()),
+            Err(TryAcquireError::NoPermits)
+        );
+
+        drop(first);
+        assert_eq!(semaphore.available_permits(), 1);
+        second.forget();
+        assert_eq!(semaphore.available_permits(), 1);
+
+        semaphore.add_permits(2);
+        assert_eq!(semaphore.available_permits(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "would overflow MAX_PERMITS")]
+    fn test_should_not_overflow_permits_sync() {
+        let semaphore = Semaphore::new(1);
+        semaphore.add_permits(Semaphore::MAX_PERMITS);
+    }
+
+    #[test]
+    fn test_should_wait_for_permit_sync() {
+        let semaphore = Arc::new(Semaphore::new(1));
+        let permit = semaphore.try_acquire().expect("failed to acquire");
+
+        let clone = Arc::clone(&semaphore);
+        let handle = std::thread::spawn(move || {
+            SyncRuntime::block_on(clone.acquire())
+                .map(|_| ())
+                .expect("failed to acquire");
+        });
+
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(!handle.is_finished());
+        drop(permit);
+        handle.join().expect("failed to join");
+        assert_eq!(semaphore.available_permits(), 1);
+    }
+
+    #[test]
+    fn test_should_close_semaphore_sync() {
+        let semaphore = Arc::new(Semaphore::new(0));
+        let clone = Arc::clone(&semaphore);
+        let handle = std::thread::spawn(move || SyncRuntime::block_on(clone.acquire()).map(|_| ()));
+
+        std::thread::sleep(Duration::from_millis(50));
+        semaphore.close();
+        assert!(semaphore.is_closed());
+        assert_eq!(handle.join().expect("failed to join"), Err(AcquireError));
+        assert_eq!(
+            semaphore.try_acquire().map(|_| ()),
+            Err(TryAcquireError::Closed)
+        );
+    }
+
+    #[cfg(tokio_sync)]
+    #[tokio::test]
+    async fn test_should_acquire_and_release_permits_tokio() {
+        let semaphore = Semaphore::new(1);
+        assert!(matches!(semaphore.0, SemaphoreInner::Tokio(_)));
+
+        let permit = semaphore.acquire().await.expect("failed to acquire");
+        assert_eq!(
+            semaphore.try_acquire().map(|_| ()),
+            Err(TryAcquireError::NoPermits)
+        );
+        drop(permit);
+        assert_eq!(semaphore.available_permits(), 1);
+
+        semaphore.close();
+        assert_eq!(semaphore.acquire().await.map(|_| ()), Err(AcquireError));
+    }
+}
@@ -0,0 +1,181 @@
+use std::future::poll_fn;
+use std::sync::{Arc, Condvar, Mutex};
+use std::task::{Poll, Waker};
+
+/// A group of units of work, which can be waited for until all of them are done.
+///
+/// A unit of work is registered with [`WaitGroup::add`], and it is done when the returned [`WaitGroupGuard`] is
+/// dropped; [`WaitGroup::wait`] waits until no unit of work is pending. It is useful to wait for the tasks or
+/// threads serving the connections of a server before shutting it down.
+///
+/// In sync contexts, [`WaitGroup::wait`] blocks the current thread until the pending units of work are done.
+#[derive(Debug, Clone, Default)]
+pub struct WaitGroup {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    state: Mutex<State>,
+    /// Notifies the threads waiting for the group in sync contexts.
+    condvar: Condvar,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    pending: usize,
+    /// The wakers of the tasks waiting for the group.
+    wakers: Vec<Waker>,
+}
+
+impl WaitGroup {
+    /// Creates a new [`WaitGroup`], without pending units of work.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a unit of work, which is done when the returned guard is dropped.
+    ///
+    /// The guard can be moved into the task or thread doing the work.
+    pub fn add(&self) -> WaitGroupGuard {
+        self.inner.state().pending += 1;
+
+        WaitGroupGuard {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+
+    /// Returns the number of pending units of work.
+    pub fn len(&self) -> usize {
+        self.inner.state().pending
+    }
+
+    /// Returns `true` if there are no pending units of work.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Waits until all the pending units of work are done.
+    ///
+    /// The units of work registered while waiting are waited for as well.
+    pub async fn wait(&self) {
+        if !crate::context::is_async_runtime() {
+            let mut state = self.inner.state();
+            while state.pending > 0 {
+                state = self
+                    .inner
+                    .condvar
+                    .wait(state)
+                    .unwrap_or_else(|poisoned| poisoned.into_inner());
+            }
+            return;
+        }
+
+        poll_fn(|cx| {
+            let mut state = self.inner.state();
+            if state.pending == 0 {
+                return Poll::Ready(());
+            }
+            if !state.wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+                state.wakers.push(cx.waker().clone());
+            }
+            Poll::Pending
+        })
+        .await
+    }
+}
+
+impl Inner {
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        // the state is updated with single operations, so a poisoned state is still consistent
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// A unit of work registered in a [`WaitGroup`], which is done when the guard is dropped.
+///
+/// This structure is created by the [`WaitGroup::add`] method.
+#[derive(Debug)]
+#[must_use = "the unit of work is done as soon as the guard is dropped"]
+pub struct WaitGroupGuard {
+    inner: Arc<Inner>,
+}
+
+impl Drop for WaitGroupGuard {
+    fn drop(&mut self) {
+        let mut state = self.inner.state();
+        state.pending -= 1;
+        if state.pending == 0 {
+            for waker in state.wakers.drain(..) {
+                waker.wake();
+            }
+            self.inner.condvar.notify_all();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::time::Duration;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::SyncRuntime;
+
+    #[test]
+    fn test_should_count_pending_work() {
+        let group = WaitGroup::new();
+        assert!(group.is_empty());
+
+        let first = group.add();
+        let second = group.clone().add();
+        assert_eq!(group.len(), 2);
+
+        drop(first);
+        assert_eq!(group.len(), 1);
+        drop(second);
+        assert!(group.is_empty());
+        SyncRuntime::block_on(group.wait());
+    }
+
+    #[test]
+    fn test_should_wait_for_threads_sync() {
+        let group = WaitGroup::new();
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let guard = group.add();
+                std::thread::spawn(move || {
+                    std::thread::sleep(Duration::from_millis(20));
+                    drop(guard);
+                })
+            })
+            .collect();
+
+        SyncRuntime::block_on(group.wait());
+        assert!(group.is_empty());
+        for handle in handles {
+            handle.join().expect("failed to join");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_should_wait_for_tasks_tokio() {
+        let group = WaitGroup::new();
+        for _ in 0..4 {
+            let guard = group.add();
+            tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                drop(guard);
+            });
+        }
+
+        tokio::time::timeout(Duration::from_secs(1), group.wait())
+            .await
+            .expect("timed out");
+        assert!(group.is_empty());
+    }
+}
@@ -0,0 +1,378 @@
+//! Scheduling of tasks.
+//!
+//! In tokio contexts, local tasks are spawned on a tokio local set, and in async contexts the tasks of a [`scope`]
+//! are polled by the scope itself, so they can borrow from the enclosing stack frame.
+//! Elsewhere, there is no executor to hand them over to, so they're run on the current thread, or on a thread each
+//! within a [`scope`].
+//!
+//! Reference:
+//!
+//! - tokio: <https://docs.rs/tokio/latest/tokio/task/index.html>
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::future::poll_fn;
+use std::panic::AssertUnwindSafe;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use crate::SyncRuntime;
+
+/// Spawns a `!Send` future, returning a [`JoinHandle`] to await its output.
+///
+/// In tokio contexts, the future is spawned on a new [`tokio::task::LocalSet`], so it doesn't need to be called
+/// within one; the local set runs on the current thread while the [`JoinHandle`] is awaited, and the tasks spawned
+/// with [`tokio::task::spawn_local`] by the future run on it too.
+/// Elsewhere, the future is run to completion on the current thread before returning.
+pub fn spawn_local<F>(future: F) -> JoinHandle<F::Output>
+where
+    F: Future + 'static,
+    F::Output: 'static,
+{
+    #[cfg(tokio)]
+    if crate::is_tokio_context() {
+        let tasks = tokio::task::LocalSet::new();
+        let handle = tasks.spawn_local(future);
+        return JoinHandle(JoinHandleInner::Tokio { tasks, handle });
+    }
+
+    let output = std::panic::catch_unwind(AssertUnwindSafe(|| SyncRuntime::block_on(future)));
+    JoinHandle(JoinHandleInner::Std(Some(output)))
+}
+
+/// Creates a scope for spawning tasks, which waits for all the tasks spawned in it before returning.
+///
+/// `f` is called with a [`Scope`], whose [`Scope::spawn`] spawns the tasks; once `f` returns, the scope waits for
+/// all of them, so no task outlives the scope, and the tasks can borrow from the enclosing stack frame.
+///
+/// In async contexts, the tasks are polled concurrently by the future returned by [`scope`], on the current task;
+/// if that future is dropped before completing, the tasks which are still running are dropped with it.
+/// Elsewhere, each task runs on its own thread of a [`std::thread::scope`], which blocks the current thread until
+/// they complete.
+///
+/// # Panics
+///
+/// If any of the tasks panicked, the scope panics with the payload of the first one once all the tasks are done.
+pub async fn scope<'env, F, T>(f: F) -> T
+where
+    F: for<'scope> FnOnce(&Scope<'scope, 'env>) -> T,
+{
+    let first_panic = Arc::new(Mutex::new(None));
+
+    let output = if crate::context::is_async_runtime() {
+        let tasks = RefCell::new(Vec::new());
+        let output = f(&Scope(ScopeInner::Async(&tasks)));
+
+        let mut tasks = tasks.into_inner();
+        poll_fn(|cx| {
+            tasks.retain_mut(|task| {
+                match std::panic::catch_unwind(AssertUnwindSafe(|| task.as_mut().poll(cx))) {
+                    Ok(poll) => poll.is_pending(),
+                    Err(payload) => {
+                        set_first_panic(&first_panic, payload);
+                        false
+                    }
+                }
+            });
+            if tasks.is_empty() {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+        .await;
+        output
+    } else {
+        std::thread::scope(|scope| {
+            f(&Scope(ScopeInner::Std {
+                scope,
+                first_panic: Arc::clone(&first_panic),
+            }))
+        })
+    };
+
+    if let Some(payload) = lock_first_panic(&first_panic).take() {
+        std::panic::resume_unwind(payload);
+    }
+    output
+}
+
+/// The payload of the first task which panicked within a [`scope`].
+type FirstPanic = Mutex<Option<Box<dyn Any + Send>>>;
+
+/// Records `payload` unless another task panicked first.
+fn set_first_panic(first_panic: &FirstPanic, payload: Box<dyn Any + Send>) {
+    lock_first_panic(first_panic).get_or_insert(payload);
+}
+
+fn lock_first_panic(
+    first_panic: &FirstPanic,
+) -> std::sync::MutexGuard<'_, Option<Box<dyn Any + Send>>> {
+    // the payload is set with a single assignment, so a poisoned lock is still consistent
+    first_panic
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// A scope to spawn tasks, created by [`scope`].
+///
+/// The tasks can borrow anything which outlives the scope, with lifetime `'env`.
+pub struct Scope<'scope, 'env: 'scope>(ScopeInner<'scope, 'env>);
+
+enum ScopeInner<'scope, 'env: 'scope> {
+    /// Std thread scope, with the payload of the first thread which panicked.
+    Std {
+        scope: &'scope std::thread::Scope<'scope, 'env>,
+        first_panic: Arc<FirstPanic>,
+    },
+    /// The tasks polled by the future of the scope.
+    Async(&'scope RefCell<Vec<ScopedTask<'env>>>),
+}
+
+/// A task spawned within a [`scope`] in an async context.
+type ScopedTask<'env> = Pin<Box<dyn Future<Output = ()> + Send + 'env>>;
+
+impl std::fmt::Debug for Scope<'_, '_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Scope").finish_non_exhaustive()
+    }
+}
+
+impl<'env> Scope<'_, 'env> {
+    /// Spawns a task within the scope, which waits for it before returning.
+    ///
+    /// In async contexts, the future is polled along with the other tasks of the scope; elsewhere, it's run on a
+    /// new thread.
+    pub fn spawn<F>(&self, future: F)
+    where
+        F: Future<Output = ()> + Send + 'env,
+    {
+        match &self.0 {
+            ScopeInner::Std { scope, first_panic } => {
+                let first_panic = Arc::clone(first_panic);
+                scope.spawn(move || {
+                    if let Err(payload) =
+                        std::panic::catch_unwind(AssertUnwindSafe(|| SyncRuntime::block_on(future)))
+                    {
+                        set_first_panic(&first_panic, payload);
+                    }
+                });
+            }
+            ScopeInner::Async(tasks) => tasks.borrow_mut().push(Box::pin(future)),
+        }
+    }
+}
+
+/// A handle to await the output of a task spawned by [`spawn_local`].
+///
+/// It resolves to `Err` with the panic payload if the task panicked, or with a message if the task was cancelled.
+/// In tokio contexts, the task runs while the handle is awaited, and dropping the handle cancels it, along with the
+/// local tasks it spawned.
+#[derive(Debug)]
+pub struct JoinHandle<T>(JoinHandleInner<T>);
+
+#[derive(Debug)]
+enum JoinHandleInner<T> {
+    /// The output of a task run on the current thread, taken when the handle is polled.
+    Std(Option<std::thread::Result<T>>),
+    /// Tokio join handle of the task, with the local set it was spawned on.
+    #[cfg(tokio)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+    Tokio {
+        tasks: tokio::task::LocalSet,
+        handle: tokio::task::JoinHandle<T>,
+    },
+}
+
+// the output is never pinned
+impl<T> Unpin for JoinHandle<T> {}
+
+impl<T> Future for JoinHandle<T> {
+    type Output = std::thread::Result<T>;
+
+    fn poll(
+        self: Pin<&mut Self>,
+        #[cfg_attr(not(tokio), allow(unused_variables))] cx: &mut Context<'_>,
+    ) -> Poll<Self::Output> {
+        match &mut self.get_mut().0 {
+            JoinHandleInner::Std(output) => {
+                Poll::Ready(output.take().expect("JoinHandle polled after completion"))
+            }
+            #[cfg(tokio)]
+            JoinHandleInner::Tokio { tasks, handle } => {
+                // runs the local tasks until they're all pending, so the task makes progress while it's awaited
+                let _ = Pin::new(tasks).poll(cx);
+                Pin::new(handle)
+                    .poll(cx)
+                    .map(|result| result.map_err(panic_payload))
+            }
+        }
+    }
+}
+
+/// Returns the panic payload of a tokio task, or a message if it was cancelled.
+#[cfg(tokio)]
+fn panic_payload(err: tokio::task::JoinError) -> Box<dyn Any + Send> {
+    match err.try_into_panic() {
+        Ok(payload) => payload,
+        Err(_) => Box::new("task was cancelled"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_should_spawn_local_sync() {
+        let counter = Rc::new(Cell::new(0));
+        let clone = Rc::clone(&counter);
+        let handle = spawn_local(async move {
+            clone.set(clone.get() + 1);
+            clone.get()
+        });
+
+        // the future has already run on the current thread
+        assert_eq!(counter.get(), 1);
+        assert_eq!(SyncRuntime::block_on(handle).expect("task panicked"), 1);
+    }
+
+    #[test]
+    fn test_should_catch_spawn_local_panic_sync() {
+        let handle = spawn_local(async { panic!("task failed") });
+
+        let payload = SyncRuntime::block_on(handle).expect_err("task should have panicked");
+        assert_eq!(payload.downcast_ref::<&str>(), Some(&"task failed"));
+    }
+
+    #[test]
+    fn test_should_wait_for_scope_sync() {
+        let done = AtomicUsize::new(0);
+        let output = SyncRuntime::block_on(scope(|scope| {
+            assert!(matches!(scope.0, ScopeInner::Std { .. }));
+            for _ in 0..4 {
+                // the tasks borrow from the enclosing stack frame
+                let done = &done;
+                scope.spawn(async move {
+                    std::thread::sleep(Duration::from_millis(20));
+                    done.fetch_add(1, Ordering::SeqCst);
+                });
+            }
+            "spawned"
+        }));
+
+        assert_eq!(output, "spawned");
+        assert_eq!(done.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "task failed")]
+    fn test_should_propagate_scope_panic_sync() {
+        SyncRuntime::block_on(scope(|scope| scope.spawn(async { panic!("task failed") })));
+    }
+
+    #[cfg(tokio)]
+    #[tokio::test]
+    async fn test_should_wait_for_scope_tokio() {
+        let done = AtomicUsize::new(0);
+        scope(|scope| {
+            assert!(matches!(scope.0, ScopeInner::Async(_)));
+            for _ in 0..4 {
+                let done = &done;
+                scope.spawn(async move {
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    done.fetch_add(1, Ordering::SeqCst);
+                });
+            }
+        })
+        .await;
+
+        assert_eq!(done.load(Ordering::SeqCst), 4);
+    }
+
+    #[cfg(tokio)]
+    #[tokio::test]
+    async fn test_should_cancel_scope_tasks_on_drop_tokio() {
+        let dropped = Arc::new(AtomicUsize::new(0));
+
+        struct Guard(Arc<AtomicUsize>);
+
+        impl Drop for Guard {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let pending = scope(|scope| {
+            for _ in 0..2 {
+                let guard = Guard(Arc::clone(&dropped));
+                scope.spawn(async move {
+                    let _guard = guard;
+                    std::future::pending::<()>().await;
+                });
+            }
+        });
+        assert!(
+            tokio::time::timeout(Duration::from_millis(20), pending)
+                .await
+                .is_err()
+        );
+
+        // the tasks are dropped along with the scope, before the timeout returns
+        assert_eq!(dropped.load(Ordering::SeqCst), 2);
+    }
+
+    #[cfg(tokio)]
+    #[tokio::test]
+    #[should_panic(expected = "task failed")]
+    async fn test_should_propagate_scope_panic_tokio() {
+        scope(|scope| scope.spawn(async { panic!("task failed") })).await;
+    }
+
+    #[cfg(tokio)]
+    #[tokio::test]
+    async fn test_should_spawn_local_tokio() {
+        let counter = Rc::new(Cell::new(0));
+        let clone = Rc::clone(&counter);
+        let handle = spawn_local(async move {
+            // the local set of the task is the current one
+            let nested = tokio::task::spawn_local(async { 1 });
+            tokio::task::yield_now().await;
+            clone.set(clone.get() + nested.await.expect("nested task panicked"));
+            clone.get()
+        });
+        assert!(matches!(handle.0, JoinHandleInner::Tokio { .. }));
+
+        assert_eq!(handle.await.expect("task panicked"), 1);
+        assert_eq!(counter.get(), 1);
+    }
+
+    #[cfg(tokio)]
+    #[tokio::test]
+    async fn test_should_spawn_local_within_local_set_tokio() {
+        let local = tokio::task::LocalSet::new();
+        let output = local
+            .run_until(async { spawn_local(async { 42 }).await.expect("task panicked") })
+            .await;
+        assert_eq!(output, 42);
+    }
+
+    #[cfg(tokio)]
+    #[tokio::test]
+    async fn test_should_catch_spawn_local_panic_tokio() {
+        let payload = spawn_local(async { panic!("task failed") })
+            .await
+            .expect_err("task should have panicked");
+        assert_eq!(payload.downcast_ref::<&str>(), Some(&"task failed"));
+    }
+}
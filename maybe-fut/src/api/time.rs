@@ -3,6 +3,11 @@
 //! Std references: <https://doc.rust-lang.org/std/time/index.html>
 //! Tokio references: <https://docs.rs/tokio/latest/tokio/time/index.html>
 
+mod clock;
 mod instant;
+mod sleep;
 
 pub use instant::Instant;
+
+pub use self::clock::{Clock, ManualClock, SystemClock};
+pub use self::sleep::{sleep, sleep_until};
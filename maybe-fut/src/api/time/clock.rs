@@ -0,0 +1,220 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Poll, Waker};
+use std::time::Duration;
+
+use super::Instant;
+
+/// A source of time, which can be injected into the code measuring or waiting for time.
+///
+/// [`SystemClock`] tells the time of the system, while [`ManualClock`] is advanced by hand, so that code depending on
+/// time can be tested deterministically and without waiting.
+pub trait Clock {
+    /// Returns the current time of the clock.
+    fn now(&self) -> Instant;
+
+    /// Waits until `duration` has elapsed on the clock.
+    fn sleep(&self, duration: Duration) -> impl Future<Output = ()>;
+}
+
+impl<C> Clock for &C
+where
+    C: Clock + ?Sized,
+{
+    fn now(&self) -> Instant {
+        (**self).now()
+    }
+
+    fn sleep(&self, duration: Duration) -> impl Future<Output = ()> {
+        (**self).sleep(duration)
+    }
+}
+
+/// The clock of the system.
+///
+/// Sleeping blocks the thread in sync contexts, and suspends the task inside of tokio runtimes (with the `tokio-time`
+/// feature) and async-std tasks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        super::sleep(duration).await
+    }
+}
+
+/// A clock which only moves forward when advanced with [`ManualClock::advance`].
+///
+/// Clones of a [`ManualClock`] share the same time, so a test can keep a clone to advance the clock
+/// injected into the code under test.
+///
+/// In async contexts, sleeping waits until the clock is advanced past the deadline.
+/// In sync contexts, where the caller can't be suspended, sleeping advances the clock to the deadline instead.
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    /// The time of the clock when it was created.
+    start: std::time::Instant,
+    state: Arc<Mutex<ManualClockState>>,
+}
+
+#[derive(Debug, Default)]
+struct ManualClockState {
+    /// The time elapsed since the start of the clock.
+    elapsed: Duration,
+    /// The tasks sleeping on the clock, with their deadline.
+    sleepers: Vec<(Duration, Waker)>,
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ManualClock {
+    /// Creates a new [`ManualClock`], starting at the current time of the system.
+    pub fn new() -> Self {
+        Self {
+            start: std::time::Instant::now(),
+            state: Arc::default(),
+        }
+    }
+
+    /// Returns the time elapsed on the clock since it was created.
+    pub fn elapsed(&self) -> Duration {
+        self.state().elapsed
+    }
+
+    /// Advances the clock by `duration`, waking up the tasks whose sleep has elapsed.
+    pub fn advance(&self, duration: Duration) {
+        let mut state = self.state();
+        state.elapsed += duration;
+
+        let elapsed = state.elapsed;
+        state.sleepers.retain(|(deadline, waker)| {
+            let awake = *deadline <= elapsed;
+            if awake {
+                waker.wake_by_ref();
+            }
+            !awake
+        });
+    }
+
+    fn state(&self) -> MutexGuard<'_, ManualClockState> {
+        // the state is always consistent, even if a thread panicked while holding the lock
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        Instant::from(self.start + self.elapsed())
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        let deadline = self.elapsed() + duration;
+        if !crate::is_async_context() {
+            let elapsed = self.elapsed();
+            if deadline > elapsed {
+                self.advance(deadline - elapsed);
+            }
+            return;
+        }
+
+        std::future::poll_fn(|cx| {
+            let mut state = self.state();
+            if state.elapsed >= deadline {
+                return Poll::Ready(());
+            }
+
+            match state
+                .sleepers
+                .iter_mut()
+                .find(|(_, waker)| waker.will_wake(cx.waker()))
+            {
+                Some(sleeper) => sleeper.0 = deadline,
+                None => state.sleepers.push((deadline, cx.waker().clone())),
+            }
+            Poll::Pending
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::SyncRuntime;
+
+    #[test]
+    fn test_should_sleep_on_system_clock_sync() {
+        let clock = SystemClock;
+        let start = clock.now();
+        SyncRuntime::block_on(clock.sleep(Duration::from_millis(10)));
+        assert!(clock.now().duration_since(start) >= Duration::from_millis(10));
+    }
+
+    #[cfg(tokio_time)]
+    #[tokio::test]
+    async fn test_should_sleep_on_system_clock_tokio() {
+        let clock = SystemClock;
+        let start = clock.now();
+        clock.sleep(Duration::from_millis(10)).await;
+        assert!(clock.now().duration_since(start) >= Duration::from_millis(10));
+    }
+
+    #[test]
+    fn test_should_advance_manual_clock() {
+        let clock = ManualClock::new();
+        let start = clock.now();
+
+        clock.advance(Duration::from_secs(60));
+        assert_eq!(clock.elapsed(), Duration::from_secs(60));
+        assert_eq!(clock.now().duration_since(start), Duration::from_secs(60));
+
+        // clones share the same time
+        clock.clone().advance(Duration::from_secs(1));
+        assert_eq!(clock.elapsed(), Duration::from_secs(61));
+    }
+
+    #[test]
+    fn test_should_advance_manual_clock_on_sleep_sync() {
+        async fn wait_an_hour(clock: impl Clock) {
+            clock.sleep(Duration::from_secs(3600)).await;
+        }
+
+        let clock = ManualClock::new();
+        SyncRuntime::block_on(wait_an_hour(&clock));
+        assert_eq!(clock.elapsed(), Duration::from_secs(3600));
+    }
+
+    #[cfg(tokio_time)]
+    #[tokio::test]
+    async fn test_should_wake_sleepers_of_manual_clock() {
+        let clock = ManualClock::new();
+        let sleeper = {
+            let clock = clock.clone();
+            tokio::spawn(async move { clock.sleep(Duration::from_secs(10)).await })
+        };
+        tokio::task::yield_now().await;
+
+        clock.advance(Duration::from_secs(5));
+        tokio::task::yield_now().await;
+        assert!(!sleeper.is_finished());
+
+        clock.advance(Duration::from_secs(5));
+        tokio::time::timeout(Duration::from_secs(1), sleeper)
+            .await
+            .expect("the sleeper was not woken up")
+            .expect("the sleeper panicked");
+        assert_eq!(clock.elapsed(), Duration::from_secs(10));
+    }
+}
@@ -0,0 +1,56 @@
+use std::time::Duration;
+
+use super::Instant;
+
+/// Waits until `duration` has elapsed.
+///
+/// It suspends the task inside of tokio runtimes (with the `tokio-time` feature) and async-std tasks, and blocks the
+/// thread in sync contexts.
+pub async fn sleep(duration: Duration) {
+    #[cfg(tokio_time)]
+    if crate::is_tokio_context() {
+        return tokio::time::sleep(duration).await;
+    }
+    #[cfg(async_std)]
+    if crate::is_async_std_context() {
+        return async_std::task::sleep(duration).await;
+    }
+
+    std::thread::sleep(duration);
+}
+
+/// Waits until `deadline` is reached.
+///
+/// It returns immediately if the deadline is in the past; see [`sleep`].
+pub async fn sleep_until(deadline: Instant) {
+    sleep(deadline.saturating_duration_since(Instant::now())).await
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::SyncRuntime;
+
+    #[test]
+    fn test_should_sleep_sync() {
+        let started = std::time::Instant::now();
+        SyncRuntime::block_on(sleep(Duration::from_millis(50)));
+        assert!(started.elapsed() >= Duration::from_millis(50));
+
+        let started = std::time::Instant::now();
+        SyncRuntime::block_on(sleep_until(Instant::now() + Duration::from_millis(50)));
+        assert!(started.elapsed() >= Duration::from_millis(50));
+    }
+
+    #[cfg(tokio_time)]
+    #[tokio::test]
+    async fn test_should_sleep_tokio() {
+        let started = tokio::time::Instant::now();
+        sleep(Duration::from_millis(50)).await;
+        assert!(started.elapsed() >= Duration::from_millis(50));
+
+        sleep_until(Instant::now() + Duration::from_millis(50)).await;
+        assert!(started.elapsed() >= Duration::from_millis(100));
+    }
+}
@@ -0,0 +1,148 @@
+//! Introspection of the implementations compiled into maybe-fut and of the ones selected in the current context.
+
+/// The features of maybe-fut which have been compiled in.
+///
+/// The tokio-uring features are only available on Linux, so they're reported as disabled on the other platforms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Features {
+    pub tokio: bool,
+    pub tokio_fs: bool,
+    pub tokio_net: bool,
+    pub tokio_sync: bool,
+    pub tokio_time: bool,
+    pub async_std: bool,
+    pub uring_fs: bool,
+    pub uring_net: bool,
+    pub stream: bool,
+    pub crc32fast: bool,
+    pub sha2: bool,
+    pub socket2: bool,
+}
+
+impl Features {
+    /// Returns the features of maybe-fut which have been compiled in.
+    pub const fn compiled() -> Self {
+        Self {
+            tokio: cfg!(tokio),
+            tokio_fs: cfg!(tokio_fs),
+            tokio_net: cfg!(tokio_net),
+            tokio_sync: cfg!(tokio_sync),
+            tokio_time: cfg!(tokio_time),
+            async_std: cfg!(async_std),
+            uring_fs: cfg!(uring_fs),
+            uring_net: cfg!(uring_net),
+            stream: cfg!(stream),
+            crc32fast: cfg!(crc32fast),
+            sha2: cfg!(sha2),
+            socket2: cfg!(socket2),
+        }
+    }
+}
+
+/// The implementation backing the maybe-fut types of a module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Implementation {
+    Std,
+    Tokio,
+    AsyncStd,
+}
+
+/// The features compiled into maybe-fut and the implementations selected in the current context, as returned by
+/// [`capabilities`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Capabilities {
+    /// The features which have been compiled in.
+    pub features: Features,
+    /// The implementation of [`crate::fs::File`] and of the `fs` functions.
+    pub fs: Implementation,
+    /// The implementation of the `net` types.
+    pub net: Implementation,
+    /// The implementation of the `sync` types.
+    pub sync: Implementation,
+    /// The implementation of [`crate::time::Instant`].
+    pub time: Implementation,
+}
+
+/// Returns the features compiled into maybe-fut and the implementations that the maybe-fut types would use if
+/// created in the current context.
+///
+/// This is useful to assert the configuration of the build at startup, or in tests.
+pub fn capabilities() -> Capabilities {
+    let features = Features::compiled();
+    let tokio = crate::is_tokio_context();
+    let async_std = crate::is_async_std_context();
+
+    let select = |async_std_enabled: bool, tokio_enabled: bool| {
+        if async_std_enabled && async_std {
+            Implementation::AsyncStd
+        } else if tokio_enabled && tokio {
+            Implementation::Tokio
+        } else {
+            Implementation::Std
+        }
+    };
+
+    Capabilities {
+        features,
+        fs: select(features.async_std, features.tokio_fs),
+        net: select(features.async_std, features.tokio_net),
+        sync: select(false, features.tokio_sync),
+        time: select(false, features.tokio_time),
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_should_report_std_in_sync_context() {
+        let capabilities = capabilities();
+        assert_eq!(capabilities.features, Features::compiled());
+        assert_eq!(capabilities.fs, Implementation::Std);
+        assert_eq!(capabilities.net, Implementation::Std);
+        assert_eq!(capabilities.sync, Implementation::Std);
+        assert_eq!(capabilities.time, Implementation::Std);
+    }
+
+    #[tokio::test]
+    async fn test_should_report_tokio_in_tokio_context() {
+        let capabilities = capabilities();
+        assert_eq!(capabilities.features.tokio, cfg!(tokio));
+        let expected = |enabled: bool| {
+            if enabled {
+                Implementation::Tokio
+            } else {
+                Implementation::Std
+            }
+        };
+        assert_eq!(capabilities.fs, expected(cfg!(tokio_fs)));
+        assert_eq!(capabilities.net, expected(cfg!(tokio_net)));
+        assert_eq!(capabilities.sync, expected(cfg!(tokio_sync)));
+        assert_eq!(capabilities.time, expected(cfg!(tokio_time)));
+    }
+
+    #[cfg(async_std)]
+    #[async_std::test]
+    async fn test_should_report_async_std_in_async_std_context() {
+        let capabilities = capabilities();
+        assert_eq!(capabilities.fs, Implementation::AsyncStd);
+        assert_eq!(capabilities.net, Implementation::AsyncStd);
+        assert_eq!(capabilities.sync, Implementation::Std);
+    }
+
+    #[cfg(uring)]
+    #[test]
+    fn test_should_report_tokio_in_uring_context() {
+        crate::start_uring(async {
+            // the tokio-uring types are provided by `fs::uring` and `net::uring`, so the shared types are still
+            // backed by tokio
+            let capabilities = capabilities();
+            assert_eq!(capabilities.fs, Implementation::Tokio);
+            assert_eq!(capabilities.net, Implementation::Tokio);
+        });
+    }
+}
@@ -1,8 +1,117 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// The environment variable which forces the std implementation when set to `1` or `true`.
+pub const FORCE_SYNC_ENV: &str = "MAYBE_FUT_FORCE_SYNC";
+
+/// The backend used by the maybe-fut types, set with [`set_default_backend`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Backend {
+    /// Select the implementation based on the current context.
+    #[default]
+    Auto,
+    /// Always use the std implementation, even in async contexts.
+    ///
+    /// The async methods of the maybe-fut types then block the calling task until they complete.
+    Std,
+}
+
+/// The default backend; [`DEFAULT_BACKEND_UNSET`] until it is either set or read from the environment.
+static DEFAULT_BACKEND: AtomicU8 = AtomicU8::new(DEFAULT_BACKEND_UNSET);
+
+const DEFAULT_BACKEND_UNSET: u8 = 0;
+const DEFAULT_BACKEND_AUTO: u8 = 1;
+const DEFAULT_BACKEND_STD: u8 = 2;
+
+impl Backend {
+    /// Returns the backend selected by the value of [`FORCE_SYNC_ENV`].
+    fn from_env_value(value: Option<&str>) -> Self {
+        match value.map(str::trim) {
+            Some(value) if value == "1" || value.eq_ignore_ascii_case("true") => Self::Std,
+            _ => Self::Auto,
+        }
+    }
+
+    fn from_u8(value: u8) -> Self {
+        match value {
+            DEFAULT_BACKEND_STD => Self::Std,
+            _ => Self::Auto,
+        }
+    }
+
+    fn to_u8(self) -> u8 {
+        match self {
+            Self::Auto => DEFAULT_BACKEND_AUTO,
+            Self::Std => DEFAULT_BACKEND_STD,
+        }
+    }
+}
+
+/// Sets the backend used by the maybe-fut types in the whole process.
+///
+/// It takes precedence over the [`FORCE_SYNC_ENV`] environment variable.
+/// Setting it to [`Backend::Std`] is useful to debug the interactions with the async runtime, without recompiling.
+pub fn set_default_backend(backend: Backend) {
+    DEFAULT_BACKEND.store(backend.to_u8(), Ordering::Relaxed);
+}
+
+/// Returns the backend used by the maybe-fut types.
+///
+/// Unless set with [`set_default_backend`], it is [`Backend::Std`] if the [`FORCE_SYNC_ENV`] environment variable
+/// is set to `1` or `true` on first use, and [`Backend::Auto`] otherwise.
+pub fn default_backend() -> Backend {
+    match DEFAULT_BACKEND.load(Ordering::Relaxed) {
+        DEFAULT_BACKEND_UNSET => {
+            let backend = Backend::from_env_value(std::env::var(FORCE_SYNC_ENV).ok().as_deref());
+            // a backend set in the meantime wins over the environment
+            match DEFAULT_BACKEND.compare_exchange(
+                DEFAULT_BACKEND_UNSET,
+                backend.to_u8(),
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => backend,
+                Err(current) => Backend::from_u8(current),
+            }
+        }
+        current => Backend::from_u8(current),
+    }
+}
+
+/// Returns whether the std implementation is forced by the [`default_backend`].
+#[inline]
+fn is_sync_forced() -> bool {
+    default_backend() == Backend::Std
+}
+
 /// Returns whether the current code is being executed in an async context.
 ///
-/// If tokio is disabled, this function will always return false.
+/// The context is async if the code is running inside of a Tokio runtime or, with the `async-std` feature,
+/// inside of an async-std task.
+///
+/// If both tokio and async-std are disabled, or if the std implementation is forced with [`set_default_backend`],
+/// this function will always return false.
 #[inline]
 pub fn is_async_context() -> bool {
+    is_tokio_context() || is_async_std_context()
+}
+
+/// Returns whether the current code is being polled by an async runtime, regardless of the [`default_backend`].
+#[inline]
+pub(crate) fn is_async_runtime() -> bool {
+    is_tokio_runtime() || is_async_std_task()
+}
+
+/// Returns whether the current code is being executed inside of a Tokio runtime.
+///
+/// If tokio is disabled, or if the std implementation is forced with [`set_default_backend`],
+/// this function will always return false.
+#[inline]
+pub fn is_tokio_context() -> bool {
+    is_tokio_runtime() && !is_sync_forced()
+}
+
+#[inline]
+fn is_tokio_runtime() -> bool {
     #[cfg(tokio)]
     {
         tokio::runtime::Handle::try_current().is_ok()
@@ -13,6 +122,141 @@ pub fn is_async_context() -> bool {
     }
 }
 
+/// Returns whether the current code is being executed inside of an async-std task.
+///
+/// If async-std is disabled, or if the std implementation is forced with [`set_default_backend`],
+/// this function will always return false.
+#[inline]
+pub fn is_async_std_context() -> bool {
+    is_async_std_task() && !is_sync_forced()
+}
+
+#[inline]
+fn is_async_std_task() -> bool {
+    #[cfg(async_std)]
+    {
+        async_std::task::try_current().is_some()
+    }
+    #[cfg(not(async_std))]
+    {
+        false
+    }
+}
+
+#[cfg(uring)]
+thread_local! {
+    /// Whether the current thread is running a tokio-uring runtime started by [`start_uring`].
+    static URING_CONTEXT: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+/// Returns whether the current code is being executed inside of a tokio-uring runtime started with [`start_uring`].
+///
+/// A tokio-uring runtime is also a Tokio runtime, so [`is_tokio_context`] returns `true` as well.
+///
+/// If both the `uring-fs` and `uring-net` features are disabled, or if the std implementation is forced with
+/// [`set_default_backend`], this function will always return false.
+#[inline]
+pub fn is_uring_context() -> bool {
+    #[cfg(uring)]
+    {
+        URING_CONTEXT.with(|ctx| ctx.get()) && !is_sync_forced()
+    }
+    #[cfg(not(uring))]
+    {
+        false
+    }
+}
+
+/// Starts a tokio-uring runtime on the current thread and runs `future` to completion on it.
+///
+/// tokio-uring doesn't expose whether the current thread is running one of its runtimes, so the runtime
+/// must be started with this function for [`is_uring_context`] to detect it.
+#[cfg(uring)]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "uring-fs", feature = "uring-net"))))]
+pub fn start_uring<F: std::future::Future>(future: F) -> F::Output {
+    /// Restores the previous context when the runtime exits, even on panic.
+    struct ContextGuard(bool);
+
+    impl Drop for ContextGuard {
+        fn drop(&mut self) {
+            URING_CONTEXT.with(|ctx| ctx.set(self.0));
+        }
+    }
+
+    let _guard = ContextGuard(URING_CONTEXT.with(|ctx| ctx.replace(true)));
+    tokio_uring::start(future)
+}
+
+/// Selects the expression to evaluate based on whether the current code is being executed in an async context.
+///
+/// The `tokio` branch is evaluated only if [`is_tokio_context`] returns `true` and it is compiled only if
+/// the `tokio` feature of maybe-fut is enabled; otherwise the `sync` branch is evaluated.
+///
+/// Optionally a `tokio_feature` of the calling crate can be provided, which is required as well to compile the `tokio` branch.
+///
+/// This is useful to hand-write one-off dual code paths, without defining new wrapper types.
+///
+/// ## Examples
+///
+/// ```rust
+/// async fn read_config(path: &std::path::Path) -> std::io::Result<String> {
+///     maybe_fut::ctx!(
+///         sync => std::fs::read_to_string(path),
+///         tokio => tokio::fs::read_to_string(path).await,
+///     )
+/// }
+/// ```
+///
+/// With the feature of the calling crate:
+///
+/// ```rust,ignore
+/// maybe_fut::ctx!(
+///     sync => std::thread::sleep(duration),
+///     tokio => tokio::time::sleep(duration).await,
+///     tokio_feature = "tokio",
+/// )
+/// ```
+#[macro_export]
+macro_rules! ctx {
+    (sync => $sync:expr, tokio => $tokio:expr $(,)?) => {
+        $crate::__maybe_fut_ctx!($sync, $tokio)
+    };
+    (sync => $sync:expr, tokio => $tokio:expr, tokio_feature = $feature:literal $(,)?) => {{
+        #[cfg(feature = $feature)]
+        {
+            $crate::__maybe_fut_ctx!($sync, $tokio)
+        }
+        #[cfg(not(feature = $feature))]
+        {
+            $sync
+        }
+    }};
+}
+
+/// Dispatches between the sync and the tokio expression of [`ctx!`].
+#[cfg(tokio)]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __maybe_fut_ctx {
+    ($sync:expr, $tokio:expr) => {
+        if $crate::is_tokio_context() {
+            $tokio
+        } else {
+            $sync
+        }
+    };
+}
+
+/// Dispatches between the sync and the tokio expression of [`ctx!`].
+#[cfg(not(tokio))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __maybe_fut_ctx {
+    ($sync:expr, $tokio:expr) => {
+        $sync
+    };
+}
+
 #[cfg(test)]
 mod test {
 
@@ -26,5 +270,65 @@ mod test {
     #[tokio::test]
     async fn test_should_return_true_if_in_async_context() {
         assert!(is_async_context());
+        assert!(is_tokio_context());
+        assert!(!is_async_std_context());
+    }
+
+    #[cfg(async_std)]
+    #[async_std::test]
+    async fn test_should_return_true_if_in_async_std_context() {
+        assert!(is_async_context());
+        assert!(is_async_std_context());
+        assert!(!is_tokio_context());
+    }
+
+    #[cfg(uring)]
+    #[test]
+    fn test_should_return_true_if_in_uring_context() {
+        assert!(!is_uring_context());
+        start_uring(async {
+            assert!(is_uring_context());
+            assert!(is_tokio_context());
+        });
+        assert!(!is_uring_context());
+    }
+
+    #[test]
+    fn test_should_select_sync_expr_in_sync_context() {
+        let ctx = crate::ctx!(sync => "sync", tokio => "tokio");
+        assert_eq!(ctx, "sync");
+    }
+
+    #[tokio::test]
+    async fn test_should_select_tokio_expr_in_async_context() {
+        let ctx = crate::ctx!(sync => "sync", tokio => async { "tokio" }.await);
+        assert_eq!(ctx, "tokio");
+    }
+
+    #[tokio::test]
+    async fn test_should_select_tokio_expr_with_tokio_feature() {
+        let ctx = crate::ctx!(
+            sync => "sync",
+            tokio => "tokio",
+            tokio_feature = "tokio",
+        );
+        assert_eq!(ctx, "tokio");
+    }
+
+    #[test]
+    fn test_should_select_backend_from_env_value() {
+        assert_eq!(Backend::from_env_value(None), Backend::Auto);
+        assert_eq!(Backend::from_env_value(Some("")), Backend::Auto);
+        assert_eq!(Backend::from_env_value(Some("0")), Backend::Auto);
+        assert_eq!(Backend::from_env_value(Some("1")), Backend::Std);
+        assert_eq!(Backend::from_env_value(Some("TRUE")), Backend::Std);
+    }
+
+    #[test]
+    fn test_should_convert_backend_to_u8_and_back() {
+        for backend in [Backend::Auto, Backend::Std] {
+            assert_eq!(Backend::from_u8(backend.to_u8()), backend);
+        }
+        assert_eq!(Backend::from_u8(DEFAULT_BACKEND_UNSET), Backend::Auto);
     }
 }
@@ -16,6 +16,8 @@
 //! This allows you to write your code once and have it work in both synchronous and asynchronous contexts.
 //!
 //! In order to check whether the current context is synchronous or asynchronous, you can use the [`is_async_context`] function.
+//! The std implementation can be forced in the whole process with [`set_default_backend`], or by setting the
+//! `MAYBE_FUT_FORCE_SYNC` environment variable to `1`, e.g. to debug the interactions with the async runtime.
 //!
 //! This is a simple example of how it works:
 //!
@@ -54,11 +56,20 @@
 //!     }
 //!     ```
 //!
-//!     If you see there is an attribute macro there, called `maybe_fut`. This macro takes 3 arguments:
+//!     If you see there is an attribute macro there, called `maybe_fut`. This macro takes the following arguments:
 //!
-//!     - `sync`: The name of the sync struct that will be generated.
-//!     - `tokio`: The name of the async struct that will be generated.
-//!     - `tokio_feature`: The name of the feature that will be used to enable the async struct.
+//!     - `sync` (optional): The name of the sync struct that will be generated. If omitted, only the async struct is generated.
+//!     - `tokio` (optional): The name of the async struct that will be generated. If omitted, only the sync struct is generated. At least one of `sync` and `tokio` must be set.
+//!     - `tokio_feature`: The name of the feature that will be used to enable the async struct. Required if `tokio` is set. It can also be a cfg predicate, in which string literals are feature names, such as `any("tokio", "tokio-net")` or `all("tokio", not("async-std"))`.
+//!     - `unwrap` (optional): If `true`, the maybe-fut types (spelled with their full path, e.g. `maybe_fut::fs::File`) in the arguments and in the return type of the methods are translated into their `std` implementation in the sync struct and into their `tokio` implementation in the async struct, so that the users of the generated API never see maybe-fut types. `Result` and `Option` of maybe-fut types are translated as well. Since the maybe-fut types are backed by tokio within a tokio runtime, the sync struct must not be used within a tokio runtime, nor the async struct outside of it: the translated methods panic in that case.
+//!     - `boxed` (optional): If `true`, the async methods of the tokio struct return a `Pin<Box<dyn Future<Output = T> + Send>>` instead of being `async fn`, so that they can be used to implement object-safe traits (e.g. behind `dyn Trait`). Trait impls are not affected.
+//!     - `send` (optional): If `true`, the async methods of the tokio struct return an `impl Future<Output = T> + Send` instead of being `async fn`, so that a future which is not `Send` is reported at the method definition instead of where it is spawned. Trait impls are not affected.
+//!
+//!     The attributes of the methods (e.g. `#[inline]` or `#[tracing::instrument]`) are re-emitted in the same order on the generated methods, so attribute macros are applied to the generated sync and async methods, after the async transformation.
+//!
+//!     Methods returning `&Self` or `&mut Self`, such as builder methods, return a reference to the generated struct, so that their calls can be chained on the wrappers as well. They must return their receiver (`self`), since the wrapper can only return a reference to itself; any other method returning `&Self` is rejected.
+//!
+//!     `unsafe fn` methods stay `unsafe` on the generated structs, which forward the call in an `unsafe` block.
 //!
 //! 2. Users can now access the public API exported from the library:
 //!
@@ -152,8 +163,75 @@
 //!     test_struct.greet();
 //!     test_struct.greet_async().await;
 //! }
+//!
+//! // the async methods of the trait get a blocking companion on the sync wrapper
+//! let test_struct = SyncTestStruct::new(42);
+//! test_struct.greet();
+//! test_struct.greet_async();
+//! ```
+//!
+//! The trait is still implemented by the sync wrapper, so it can be used in generic async code, while the inherent blocking
+//! companions of the async methods shadow the trait methods when called directly on the sync wrapper.
+//!
+//! The implementing type can also be an `enum`, which is handy for clients modeled as state machines. The generated wrappers are still newtypes over the enum:
+//!
+//! ```rust
+//! enum Connection {
+//!     Idle,
+//!     Connected(u16),
+//! }
+//!
+//! #[maybe_fut::maybe_fut(
+//!     sync = SyncConnection,
+//!     tokio = TokioConnection,
+//!     tokio_feature = "tokio",
+//! )]
+//! impl Connection {
+//!     /// Creates a new idle [`Connection`].
+//!     pub fn new() -> Self {
+//!         Self::Idle
+//!     }
+//!
+//!     /// Connects to the given port.
+//!     pub async fn connect(&mut self, port: u16) {
+//!         *self = Self::Connected(port);
+//!     }
+//! }
+//! ```
+//!
+//! Types annotated with `maybe_fut` can be collected into a sync and an async facade with the `module` attribute macro,
+//! which generates two submodules of the annotated module, re-exporting respectively the sync and the tokio wrappers:
+//!
+//! ```rust,ignore
+//! #[maybe_fut::module(sync = blocking, tokio = aio, tokio_feature = "tokio")]
+//! pub mod client {
+//!     pub struct Client;
+//!
+//!     #[maybe_fut::maybe_fut(sync = SyncClient, tokio = TokioClient, tokio_feature = "tokio")]
+//!     impl Client {
+//!         // ...
+//!     }
+//! }
+//!
+//! // mylib::client::blocking::SyncClient and mylib::client::aio::TokioClient are now available
 //! ```
 //!
+//! With the `async-std` feature, `fs::File`, the `fs` functions, `net::TcpStream` and `net::TcpListener` also have an async-std implementation, which is used when running inside of an async-std task (see [`is_async_std_context`]).
+//! The other types fall back to their `std` implementation inside of async-std tasks.
+//!
+//! With the `uring-fs` feature (Linux only), the `fs::uring` module provides a `File` type and the `remove_file`, `rename` and `remove_dir` functions using io_uring through tokio-uring, to be used inside of a runtime started with `start_uring`.
+//! Since tokio-uring files are bound to the thread of their runtime, `fs::uring::File` is not `Send`; `fs::File` is not affected by this feature.
+//!
+//! Likewise, with the `uring-net` feature (Linux only), the `net::uring` module provides `TcpStream`, `TcpListener` and `UdpSocket` types using io_uring: the data is copied from and into the owned buffers required by io_uring.
+//! `net::uring::TcpStream` and `net::uring::UdpSocket` are not `Send`; the types of `net` are not affected by this feature.
+//!
+//! The streaming types, `fs::ReadDir`, `io::Lines`, `io::Split` and `net::Incoming` (returned by `TcpListener::incoming`), implement the [`stream::Stream`] trait, whose async `next` method yields their items; in sync contexts, any stream can be iterated with [`stream::Stream::into_blocking_iter`].
+//! With the `stream` feature, they implement `futures_core::Stream` as well, so they can be used with the `StreamExt` combinators.
+//!
+//! The `io::HashingReader` and `io::HashingWriter` adapters compute the digest of the bytes going through them with an `io::Digest`, which is implemented for the `sha2` hashers with the `sha2` feature and for `crc32fast::Hasher` with the `crc32fast` feature, so a checksum can be verified without a second pass over the data.
+//!
+//! [`capabilities`] reports the features compiled into maybe-fut, and the implementation the types of each module would use in the current context.
+//!
 
 #![doc(html_playground_url = "https://play.rust-lang.org")]
 #![doc(
@@ -163,6 +241,9 @@
     html_logo_url = "https://raw.githubusercontent.com/veeso/maybe-fut/main/assets/images/logo-500.png"
 )]
 
+// allows the derives to refer to `::maybe_fut` from within the crate
+extern crate self as maybe_fut;
+
 #[macro_use]
 extern crate maybe_fut_io_derive;
 #[macro_use]
@@ -170,16 +251,28 @@ extern crate maybe_fut_unwrap_derive;
 
 // private api
 mod api;
+mod capabilities;
 mod context;
 mod macros;
 mod rt;
 mod unwrap;
 
 // public api (api is exported at top-level)
-// export maybe fut derive macro
-pub use maybe_fut_derive::maybe_fut;
+// export maybe fut derive macros
+pub use maybe_fut_derive::{maybe_fut, module};
+/// Derives [`Unwrap`] for a wrapper around an enum with a `Std` and a `Tokio` variant.
+pub use maybe_fut_unwrap_derive::Unwrap;
 
 pub use self::api::*;
-pub use self::context::is_async_context;
+pub use self::capabilities::{Capabilities, Features, Implementation, capabilities};
+#[cfg(uring)]
+pub use self::context::start_uring;
+pub use self::context::{
+    Backend, FORCE_SYNC_ENV, default_backend, is_async_context, is_async_std_context,
+    is_tokio_context, is_uring_context, set_default_backend,
+};
 pub use self::rt::{SyncRuntime, block_on};
-pub use self::unwrap::Unwrap;
+#[cfg(tokio)]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+pub use self::unwrap::AsyncImpl;
+pub use self::unwrap::{Either, Unwrap};
@@ -27,8 +27,36 @@
 ///     );
 /// }
 /// ```
+///
+/// The constructors of the other async backends can be passed after the tokio feature, as in [`maybe_fut_function`].
 #[macro_export]
 macro_rules! maybe_fut_constructor_result {
+    (@body
+        $args:tt,
+        $std_module:path,
+        $tokio_module:path,
+        $feature:ident
+        $(, $backend_module:path, $backend_feature:ident )*
+    ) => {{
+        $(
+            #[cfg($backend_feature)]
+            if $crate::__maybe_fut_context!($backend_feature) {
+                return $backend_module $args .await.map(Self::from);
+            }
+        )*
+        #[cfg($feature)]
+        {
+            if $crate::is_tokio_context() {
+                $tokio_module $args .await.map(Self::from)
+            } else {
+                $std_module $args .map(Self::from)
+            }
+        }
+        #[cfg(not($feature))]
+        {
+            $std_module $args .map(Self::from)
+        }
+    }};
     ($(#[$meta:meta])*
         $name:ident
         (
@@ -39,21 +67,18 @@ macro_rules! maybe_fut_constructor_result {
         $std_module:path,
         $tokio_module:path,
         $feature:ident
+        $(, $backend_module:path, $backend_feature:ident )*
     ) => {
             $(#[$meta])*
             pub async fn $name( $( $arg_name : $arg_type ),* ) -> $ret {
-                #[cfg($feature)]
-                {
-                    if $crate::is_async_context() {
-                        $tokio_module( $( $arg_name ),* ).await.map(Self::from)
-                    } else {
-                        $std_module( $( $arg_name ),* ).map(Self::from)
-                    }
-                }
-                #[cfg(not($feature))]
-                {
-                    $std_module( $( $arg_name ),* ).map(Self::from)
-                }
+                $crate::maybe_fut_constructor_result!(
+                    @body
+                    ( $( $arg_name ),* ),
+                    $std_module,
+                    $tokio_module,
+                    $feature
+                    $(, $backend_module, $backend_feature )*
+                )
             }
         };
 }
@@ -76,7 +101,7 @@ macro_rules! maybe_fut_constructor {
             pub async fn $name( $( $arg_name : $arg_type ),* ) -> $ret {
                 #[cfg($feature)]
                 {
-                    if $crate::is_async_context() {
+                    if $crate::is_tokio_context() {
                         $tokio_module( $( $arg_name ),* ).await.into()
                     } else {
                         $std_module( $( $arg_name ),* ).into()
@@ -108,7 +133,7 @@ macro_rules! maybe_fut_constructor_sync {
             pub fn $name( $( $arg_name : $arg_type ),* ) -> $ret {
                 #[cfg($feature)]
                 {
-                    if $crate::is_async_context() {
+                    if $crate::is_tokio_context() {
                         $tokio_module( $( $arg_name ),* ).into()
                     } else {
                         $std_module( $( $arg_name ),* ).into()
@@ -123,81 +148,213 @@ macro_rules! maybe_fut_constructor_sync {
 }
 
 /// A macro to create a method that can be used in both async and sync contexts.
+///
+/// The method may declare generic parameters with a single trait bound each (e.g. `<T: AsRef<[u8]>>`);
+/// `impl Trait` arguments are supported as well.
+///
+/// If the return type is followed by `=> path`, the result of the inner method is mapped with the given function,
+/// which is useful for methods returning `std::io::Result<Self>`.
+///
+/// The variants of the inner type for the other async backends (such as async-std) can be passed after the tokio feature,
+/// each followed by the feature enabling it.
+///
+/// ## Examples
+///
+/// ```rust,ignore
+/// impl File {
+///     maybe_fut_method!(
+///         /// Creates a new File instance that shares the same underlying file handle as the existing File instance.
+///         try_clone() -> std::io::Result<Self> => Self::from,
+///         FileInner::Std,
+///         FileInner::Tokio,
+///         tokio_fs
+///     );
+/// }
+/// ```
 #[macro_export]
 macro_rules! maybe_fut_method {
     ($(#[$meta:meta])*
         $name:ident
+        $( < $( $generic:ident $( : $bound:path )? ),* $(,)? > )?
         (
             $( $arg_name:ident : $arg_type:ty ),* $(,)?
         )
-        -> $ret:ty,
+        -> $ret:ty
+        $( => $map:path )?,
         $sync_inner_type:path,
         $async_inner_type:path,
         $feature:ident
+        $(, $backend_inner_type:path, $backend_feature:ident )*
     ) => {
-            $(#[$meta])*
-            pub async fn $name( &self, $( $arg_name : $arg_type ),* ) -> $ret {
-                match &self.0 {
-                    $sync_inner_type(inner) => inner.$name( $( $arg_name ),* ),
-                    #[cfg($feature)]
-                    $async_inner_type(inner) => inner.$name( $( $arg_name ),* ).await,
-                }
-            }
+            $crate::__maybe_fut_method!(
+                [$(#[$meta])*] [async] [&self] [&self.0]
+                $name [$( < $( $generic $( : $bound )? ),* > )?]
+                [$( $arg_name : $arg_type ),*] ( $( $arg_name ),* )
+                [$( .map($map) )?] [.await] -> $ret,
+                $sync_inner_type,
+                $async_inner_type,
+                $feature
+                $(, $backend_inner_type, $backend_feature )*
+            );
         };
 }
 
 /// A macro to create a method that can be used in both async and sync contexts.
+///
+/// Generic parameters, the mapping of the result and the variants of the other backends are supported as in [`maybe_fut_method`].
 #[macro_export]
 macro_rules! maybe_fut_method_sync {
     ($(#[$meta:meta])*
         $name:ident
+        $( < $( $generic:ident $( : $bound:path )? ),* $(,)? > )?
         (
             $( $arg_name:ident : $arg_type:ty ),* $(,)?
         )
-        -> $ret:ty,
+        -> $ret:ty
+        $( => $map:path )?,
         $sync_inner_type:path,
         $async_inner_type:path,
         $feature:ident
+        $(, $backend_inner_type:path, $backend_feature:ident )*
     ) => {
-            $(#[$meta])*
-            pub fn $name( &self, $( $arg_name : $arg_type ),* ) -> $ret {
-                match &self.0 {
-                    $sync_inner_type(inner) => inner.$name( $( $arg_name ),* ),
-                    #[cfg($feature)]
-                    $async_inner_type(inner) => inner.$name( $( $arg_name ),* ),
-                }
-            }
+            $crate::__maybe_fut_method!(
+                [$(#[$meta])*] [] [&self] [&self.0]
+                $name [$( < $( $generic $( : $bound )? ),* > )?]
+                [$( $arg_name : $arg_type ),*] ( $( $arg_name ),* )
+                [$( .map($map) )?] [] -> $ret,
+                $sync_inner_type,
+                $async_inner_type,
+                $feature
+                $(, $backend_inner_type, $backend_feature )*
+            );
         };
 }
 
 /// A macro to create a mutable method that can be used in both async and sync contexts.
+///
+/// Generic parameters, the mapping of the result and the variants of the other backends are supported as in [`maybe_fut_method`].
 #[macro_export]
 macro_rules! maybe_fut_method_mut {
-    (
-        $(#[$meta:meta])*
+    ($(#[$meta:meta])*
         $name:ident
+        $( < $( $generic:ident $( : $bound:path )? ),* $(,)? > )?
         (
             $( $arg_name:ident : $arg_type:ty ),* $(,)?
         )
-        -> $ret:ty,
+        -> $ret:ty
+        $( => $map:path )?,
         $sync_inner_type:path,
         $async_inner_type:path,
         $feature:ident
+        $(, $backend_inner_type:path, $backend_feature:ident )*
     ) => {
-            $(#[$meta])*
-            pub async fn $name( &mut self, $( $arg_name : $arg_type ),* ) -> $ret {
-                match &mut self.0 {
-                    $sync_inner_type(inner) => inner.$name( $( $arg_name ),* ),
-                    #[cfg($feature)]
-                    $async_inner_type(inner) => inner.$name( $( $arg_name ),* ).await,
-                }
-            }
+            $crate::__maybe_fut_method!(
+                [$(#[$meta])*] [async] [&mut self] [&mut self.0]
+                $name [$( < $( $generic $( : $bound )? ),* > )?]
+                [$( $arg_name : $arg_type ),*] ( $( $arg_name ),* )
+                [$( .map($map) )?] [.await] -> $ret,
+                $sync_inner_type,
+                $async_inner_type,
+                $feature
+                $(, $backend_inner_type, $backend_feature )*
+            );
         };
 }
 
+/// A macro to create a method consuming `self` that can be used in both async and sync contexts.
+///
+/// Generic parameters, the mapping of the result and the variants of the other backends are supported as in [`maybe_fut_method`].
+#[macro_export]
+macro_rules! maybe_fut_method_owned {
+    ($(#[$meta:meta])*
+        $name:ident
+        $( < $( $generic:ident $( : $bound:path )? ),* $(,)? > )?
+        (
+            $( $arg_name:ident : $arg_type:ty ),* $(,)?
+        )
+        -> $ret:ty
+        $( => $map:path )?,
+        $sync_inner_type:path,
+        $async_inner_type:path,
+        $feature:ident
+        $(, $backend_inner_type:path, $backend_feature:ident )*
+    ) => {
+            $crate::__maybe_fut_method!(
+                [$(#[$meta])*] [async] [self] [self.0]
+                $name [$( < $( $generic $( : $bound )? ),* > )?]
+                [$( $arg_name : $arg_type ),*] ( $( $arg_name ),* )
+                [$( .map($map) )?] [.await] -> $ret,
+                $sync_inner_type,
+                $async_inner_type,
+                $feature
+                $(, $backend_inner_type, $backend_feature )*
+            );
+        };
+}
+
+/// Generates the methods of [`maybe_fut_method`] and its variants.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __maybe_fut_method {
+    (@call $inner:ident $name:ident $args:tt [$($await:tt)*] [$($map:tt)*]) => {
+        $inner.$name $args $($await)* $($map)*
+    };
+    (
+        [$($meta:tt)*] [$($asyncness:tt)*] [$($receiver:tt)*] [$($scrutinee:tt)*]
+        $name:ident [$($generics:tt)*]
+        [$($params:tt)*] $args:tt $map:tt $await:tt -> $ret:ty,
+        $sync_inner_type:path,
+        $async_inner_type:path,
+        $feature:ident
+        $(, $backend_inner_type:path, $backend_feature:ident )*
+    ) => {
+        $($meta)*
+        pub $($asyncness)* fn $name $($generics)* ( $($receiver)*, $($params)* ) -> $ret {
+            match $($scrutinee)* {
+                $sync_inner_type(inner) => $crate::__maybe_fut_method!(@call inner $name $args [] $map),
+                #[cfg($feature)]
+                $async_inner_type(inner) => $crate::__maybe_fut_method!(@call inner $name $args $await $map),
+                $(
+                    #[cfg($backend_feature)]
+                    $backend_inner_type(inner) => $crate::__maybe_fut_method!(@call inner $name $args $await $map),
+                )*
+            }
+        }
+    };
+}
+
 #[macro_export]
 /// A macro to create a function that can be used in both async and sync contexts.
+///
+/// The functions of the other async backends (such as async-std) can be passed after the tokio feature,
+/// each followed by the feature enabling it. They are selected before tokio.
 macro_rules! maybe_fut_function {
+    (@body
+        $args:tt,
+        $sync_function:path,
+        $async_function:path,
+        $feature:ident
+        $(, $backend_function:path, $backend_feature:ident )*
+    ) => {{
+        $(
+            #[cfg($backend_feature)]
+            if $crate::__maybe_fut_context!($backend_feature) {
+                return $backend_function $args .await;
+            }
+        )*
+        #[cfg($feature)]
+        {
+            if $crate::is_tokio_context() {
+                $async_function $args .await
+            } else {
+                $sync_function $args
+            }
+        }
+        #[cfg(not($feature))]
+        {
+            $sync_function $args
+        }
+    }};
     (
         $(#[$meta:meta])*
         $name:ident
@@ -208,21 +365,48 @@ macro_rules! maybe_fut_function {
         $sync_function:path,
         $async_function:path,
         $feature:ident
+        $(, $backend_function:path, $backend_feature:ident )*
     ) => {
         $(#[$meta])*
         pub async fn $name( $( $arg_name : $arg_type ),* ) -> $ret {
-            #[cfg($feature)]
-            {
-                if $crate::is_async_context() {
-                    $async_function( $( $arg_name ),* ).await
-                } else {
-                    $sync_function( $( $arg_name ),* )
-                }
-            }
-            #[cfg(not($feature))]
-            {
-                $sync_function( $( $arg_name ),* )
-            }
+            $crate::maybe_fut_function!(
+                @body
+                ( $( $arg_name ),* ),
+                $sync_function,
+                $async_function,
+                $feature
+                $(, $backend_function, $backend_feature )*
+            )
         }
     };
 }
+
+/// Returns whether the code is running in the context of the async backend enabled by the given cfg alias.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __maybe_fut_context {
+    (async_std) => {
+        $crate::is_async_std_context()
+    };
+}
+
+/// Expands to the given items only if maybe-fut is built with the `tokio` feature.
+///
+/// Used by the `Unwrap` derive to emit the tokio accessors: a `#[cfg]` in the derived code would be evaluated against
+/// the features of the deriving crate, which may not match the ones of maybe-fut.
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "tokio")]
+macro_rules! __maybe_fut_if_tokio {
+    ($($item:tt)*) => {
+        $($item)*
+    };
+}
+
+/// Expands to the given items only if maybe-fut is built with the `tokio` feature.
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "tokio"))]
+macro_rules! __maybe_fut_if_tokio {
+    ($($item:tt)*) => {};
+}
@@ -1,6 +1,8 @@
 //! Sync contains the runtime to execute async code when working in sync context.
 
+use std::num::NonZeroUsize;
 use std::pin::Pin;
+use std::sync::Mutex;
 use std::task::{Context, Poll, Waker};
 
 /// A runtime to execute sync code without async context.
@@ -12,6 +14,10 @@ use std::task::{Context, Poll, Waker};
 pub struct SyncRuntime;
 
 impl SyncRuntime {
+    /// Runs the future to completion on the current thread.
+    ///
+    /// The future doesn't need to be [`Send`], so the sync api can be generated for methods holding `!Send` values,
+    /// such as [`std::rc::Rc`], across await points.
     pub fn block_on<F>(mut f: F) -> F::Output
     where
         F: Future,
@@ -26,6 +32,63 @@ impl SyncRuntime {
 
         val
     }
+
+    /// Blocks on all the given futures, running them concurrently on a small pool of threads.
+    ///
+    /// The outputs are returned in the same order as the futures.
+    /// The pool has at most as many threads as the available parallelism, and it's shut down before returning.
+    pub fn block_on_all<I>(futures: I) -> Vec<<I::Item as Future>::Output>
+    where
+        I: IntoIterator,
+        I::Item: Future + Send,
+        <I::Item as Future>::Output: Send,
+    {
+        let futures: Vec<_> = futures.into_iter().collect();
+        let count = futures.len();
+        if count <= 1 {
+            return futures.into_iter().map(Self::block_on).collect();
+        }
+
+        let workers = std::thread::available_parallelism()
+            .map_or(1, NonZeroUsize::get)
+            .clamp(2, count);
+        let queue = Mutex::new(futures.into_iter().enumerate());
+
+        let mut outputs: Vec<_> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut outputs = Vec::new();
+                        // the lock is released before running the future
+                        while let Some((index, future)) = Self::next_job(&queue) {
+                            outputs.push((index, Self::block_on(future)));
+                        }
+                        outputs
+                    })
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+                })
+                .collect()
+        });
+
+        outputs.sort_unstable_by_key(|(index, _)| *index);
+        outputs.into_iter().map(|(_, output)| output).collect()
+    }
+
+    /// Takes the next future to run from the queue of [`SyncRuntime::block_on_all`].
+    fn next_job<T>(queue: &Mutex<impl Iterator<Item = T>>) -> Option<T> {
+        queue
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .next()
+    }
 }
 
 /// Blocks on a future in a sync context.
@@ -60,4 +123,37 @@ mod test {
     async fn async_fn() -> i32 {
         42
     }
+
+    #[test]
+    fn test_should_block_on_all_futures_in_order() {
+        let futures = (0..16).map(|i| async move { i * 2 });
+        let result = SyncRuntime::block_on_all(futures);
+        assert_eq!(result, (0..16).map(|i| i * 2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_should_block_on_all_futures_concurrently() {
+        let barrier = std::sync::Barrier::new(2);
+        let futures = (0..2).map(|_| async { barrier.wait() });
+        // would deadlock if the futures were run sequentially
+        let result = SyncRuntime::block_on_all(futures);
+        assert_eq!(result.iter().filter(|res| res.is_leader()).count(), 1);
+    }
+
+    #[test]
+    fn test_should_block_on_all_without_futures() {
+        let result = SyncRuntime::block_on_all(Vec::<std::future::Ready<()>>::new());
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn test_should_block_on_non_send_future() {
+        let value = std::rc::Rc::new(21);
+        let future = async move {
+            let value = std::rc::Rc::clone(&value);
+            std::future::ready(()).await;
+            *value * 2
+        };
+        assert_eq!(block_on(future), 42);
+    }
 }
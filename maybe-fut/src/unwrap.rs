@@ -5,6 +5,9 @@
 /// This trait provides methods to get the underlying implementations for the MaybeFut wrappers.
 ///
 /// Every type implemented by the **maybe_fut** library has a corresponding `Unwrap` implementation.
+///
+/// The `*_async` methods are backend-agnostic aliases of the `*_tokio` methods, which return the
+/// underlying implementation of the async backend.
 pub trait Unwrap {
     type StdImpl;
     #[cfg(feature = "tokio")]
@@ -51,4 +54,290 @@ pub trait Unwrap {
     #[cfg(feature = "tokio")]
     /// Safely unwraps the tokio underlying implementation of the MaybeFut type as a mutable reference.
     fn get_tokio_mut(&mut self) -> Option<&mut Self::TokioImpl>;
+
+    #[cfg(feature = "tokio")]
+    /// Unwraps the async underlying implementation of the MaybeFut type.
+    ///
+    /// Alias of [`Unwrap::unwrap_tokio`].
+    fn unwrap_async(self) -> Self::TokioImpl
+    where
+        Self: Sized,
+    {
+        self.unwrap_tokio()
+    }
+
+    #[cfg(feature = "tokio")]
+    /// Unwraps the async underlying implementation of the MaybeFut type as a reference.
+    ///
+    /// Alias of [`Unwrap::unwrap_tokio_ref`].
+    fn unwrap_async_ref(&self) -> &Self::TokioImpl {
+        self.unwrap_tokio_ref()
+    }
+
+    #[cfg(feature = "tokio")]
+    /// Unwraps the async underlying implementation of the MaybeFut type as a mutable reference.
+    ///
+    /// Alias of [`Unwrap::unwrap_tokio_mut`].
+    fn unwrap_async_mut(&mut self) -> &mut Self::TokioImpl {
+        self.unwrap_tokio_mut()
+    }
+
+    #[cfg(feature = "tokio")]
+    /// Safely unwraps the async underlying implementation of the MaybeFut type.
+    ///
+    /// Alias of [`Unwrap::get_tokio`].
+    fn get_async(self) -> Option<Self::TokioImpl>
+    where
+        Self: Sized,
+    {
+        self.get_tokio()
+    }
+
+    #[cfg(feature = "tokio")]
+    /// Safely unwraps the async underlying implementation of the MaybeFut type as a reference.
+    ///
+    /// Alias of [`Unwrap::get_tokio_ref`].
+    fn get_async_ref(&self) -> Option<&Self::TokioImpl> {
+        self.get_tokio_ref()
+    }
+
+    #[cfg(feature = "tokio")]
+    /// Safely unwraps the async underlying implementation of the MaybeFut type as a mutable reference.
+    ///
+    /// Alias of [`Unwrap::get_tokio_mut`].
+    fn get_async_mut(&mut self) -> Option<&mut Self::TokioImpl> {
+        self.get_tokio_mut()
+    }
+
+    /// Maps the std underlying implementation of the MaybeFut type with `f`.
+    ///
+    /// Returns `None` if the MaybeFut type is not backed by std.
+    fn map_std<U, F>(self, f: F) -> Option<U>
+    where
+        Self: Sized,
+        F: FnOnce(Self::StdImpl) -> U,
+    {
+        self.get_std().map(f)
+    }
+
+    #[cfg(feature = "tokio")]
+    /// Maps the tokio underlying implementation of the MaybeFut type with `f`.
+    ///
+    /// Returns `None` if the MaybeFut type is not backed by tokio.
+    fn map_tokio<U, F>(self, f: F) -> Option<U>
+    where
+        Self: Sized,
+        F: FnOnce(Self::TokioImpl) -> U,
+    {
+        self.get_tokio().map(f)
+    }
+
+    #[cfg(feature = "tokio")]
+    /// Converts the MaybeFut type into its std or tokio underlying implementation, whichever it is.
+    ///
+    /// Returns the MaybeFut type back as the error if it's backed by another implementation,
+    /// such as async-std.
+    fn into_either(self) -> Result<Either<Self::StdImpl, Self::TokioImpl>, Self>
+    where
+        Self: Sized,
+    {
+        if self.get_std_ref().is_some() {
+            Ok(Either::Std(self.unwrap_std()))
+        } else if self.get_tokio_ref().is_some() {
+            Ok(Either::Tokio(self.unwrap_tokio()))
+        } else {
+            Err(self)
+        }
+    }
+}
+
+/// Either the std or the tokio underlying implementation of a MaybeFut type.
+///
+/// Returned by [`Unwrap::into_either`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Either<S, T> {
+    /// The std underlying implementation.
+    Std(S),
+    /// The tokio underlying implementation.
+    Tokio(T),
+}
+
+impl<S, T> Either<S, T> {
+    /// Returns whether this is the std underlying implementation.
+    pub fn is_std(&self) -> bool {
+        matches!(self, Self::Std(_))
+    }
+
+    /// Returns whether this is the tokio underlying implementation.
+    pub fn is_tokio(&self) -> bool {
+        matches!(self, Self::Tokio(_))
+    }
+
+    /// Applies `std` or `tokio` to the underlying implementation, depending on which one it is.
+    pub fn either<U>(self, std: impl FnOnce(S) -> U, tokio: impl FnOnce(T) -> U) -> U {
+        match self {
+            Self::Std(inner) => std(inner),
+            Self::Tokio(inner) => tokio(inner),
+        }
+    }
+}
+
+/// The underlying implementation of the async backend of a MaybeFut type.
+#[cfg(feature = "tokio")]
+pub type AsyncImpl<T> = <T as Unwrap>::TokioImpl;
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[derive(Unwrap)]
+    #[unwrap_types(std(std::io::Cursor<Vec<u8>>), tokio(std::io::Cursor<Vec<u8>>), tokio_gated("tokio"))]
+    struct NamedWrapper {
+        name: &'static str,
+        #[unwrap_inner]
+        inner: NamedWrapperInner,
+    }
+
+    enum NamedWrapperInner {
+        Std(std::io::Cursor<Vec<u8>>),
+        #[cfg(tokio)]
+        #[allow(dead_code)]
+        Tokio(std::io::Cursor<Vec<u8>>),
+    }
+
+    #[derive(Unwrap)]
+    #[unwrap_types(std(std::io::Cursor<Vec<u8>>), tokio(std::io::Cursor<Vec<u8>>), tokio_gated("tokio"))]
+    enum EnumWrapper {
+        Std(std::io::Cursor<Vec<u8>>),
+        #[cfg(tokio)]
+        #[allow(dead_code)]
+        Tokio(std::io::Cursor<Vec<u8>>),
+        /// Another backend, such as async-std.
+        #[allow(dead_code)]
+        Other(std::io::Cursor<Vec<u8>>),
+    }
+
+    mod inner {
+
+        pub enum PathInner {
+            Std(std::io::Cursor<Vec<u8>>),
+            #[cfg(tokio)]
+            #[allow(dead_code)]
+            Tokio(std::io::Cursor<Vec<u8>>),
+        }
+    }
+
+    #[derive(Unwrap)]
+    #[unwrap_types(std(std::io::Cursor<Vec<u8>>), tokio(std::io::Cursor<Vec<u8>>), tokio_gated("tokio"))]
+    struct PathWrapper(self::inner::PathInner);
+
+    #[test]
+    fn test_should_unwrap_named_field_struct() {
+        let mut wrapper = NamedWrapper {
+            name: "cursor",
+            inner: NamedWrapperInner::Std(std::io::Cursor::new(vec![1, 2, 3])),
+        };
+        assert_eq!(wrapper.name, "cursor");
+
+        assert_eq!(wrapper.unwrap_std_ref().get_ref(), &vec![1, 2, 3]);
+        wrapper.unwrap_std_mut().get_mut().push(4);
+        assert!(wrapper.get_std_ref().is_some());
+        assert_eq!(wrapper.unwrap_std().into_inner(), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_should_unwrap_enum() {
+        let mut wrapper = EnumWrapper::Std(std::io::Cursor::new(vec![1, 2, 3]));
+
+        assert_eq!(wrapper.unwrap_std_ref().get_ref(), &vec![1, 2, 3]);
+        wrapper.unwrap_std_mut().get_mut().push(4);
+        assert!(wrapper.get_std_mut().is_some());
+        #[cfg(tokio)]
+        assert!(wrapper.get_tokio_ref().is_none());
+        assert_eq!(wrapper.unwrap_std().into_inner(), vec![1, 2, 3, 4]);
+    }
+
+    #[cfg(tokio_fs)]
+    #[tokio::test]
+    async fn test_should_unwrap_async_aliases() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let mut file = crate::fs::File::create(tempdir.path().join("test.txt"))
+            .await
+            .expect("Failed to create file");
+
+        assert!(file.get_async_ref().is_some());
+        assert!(file.get_async_mut().is_some());
+        assert!(file.get_std_ref().is_none());
+        let _: &AsyncImpl<crate::fs::File> = file.unwrap_async_ref();
+        let _: &mut tokio::fs::File = file.unwrap_async_mut();
+        let _: tokio::fs::File = file.unwrap_async();
+    }
+
+    #[test]
+    fn test_should_unwrap_enum_by_path() {
+        let wrapper = PathWrapper(inner::PathInner::Std(std::io::Cursor::new(vec![1, 2, 3])));
+
+        assert!(wrapper.get_std_ref().is_some());
+        assert_eq!(wrapper.unwrap_std().into_inner(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_should_map_std() {
+        let wrapper = EnumWrapper::Std(std::io::Cursor::new(vec![1, 2, 3]));
+
+        assert_eq!(wrapper.map_std(|cursor| cursor.into_inner().len()), Some(3));
+    }
+
+    #[cfg(tokio)]
+    #[test]
+    fn test_should_convert_into_either() {
+        let wrapper = EnumWrapper::Std(std::io::Cursor::new(vec![1, 2, 3]));
+
+        let either = wrapper.into_either().ok().expect("not std or tokio");
+        assert!(either.is_std());
+        assert!(!either.is_tokio());
+        assert_eq!(
+            either.either(|std| std.into_inner(), |_| vec![]),
+            vec![1, 2, 3]
+        );
+    }
+
+    #[cfg(tokio)]
+    #[test]
+    fn test_should_not_convert_other_backends_into_either() {
+        let wrapper = EnumWrapper::Other(std::io::Cursor::new(vec![1, 2, 3]));
+
+        let Err(EnumWrapper::Other(cursor)) = wrapper.into_either() else {
+            panic!("expected the wrapper back");
+        };
+        assert_eq!(cursor.into_inner(), vec![1, 2, 3]);
+    }
+
+    #[cfg(all(tokio, async_std))]
+    #[async_std::test]
+    async fn test_should_not_convert_async_std_into_either() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let file = crate::fs::File::create(tempdir.path().join("test.txt"))
+            .await
+            .expect("Failed to create file");
+
+        assert!(file.into_either().is_err());
+    }
+
+    #[cfg(tokio_fs)]
+    #[tokio::test]
+    async fn test_should_map_tokio() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let file = crate::fs::File::create(tempdir.path().join("test.txt"))
+            .await
+            .expect("Failed to create file");
+
+        assert!(file.map_tokio(|file| file).is_some());
+
+        let file = crate::fs::File::create(tempdir.path().join("test.txt"))
+            .await
+            .expect("Failed to create file");
+        assert!(file.into_either().is_ok_and(|either| either.is_tokio()));
+    }
 }
@@ -0,0 +1,29 @@
+//! This module contains the test for the crate-level defaults of the `config` module.
+//!
+//! The defaults are global, so they're tested in their own test binary.
+
+use maybe_fut::config;
+use maybe_fut::io::test::{MockReader, MockWriter};
+use maybe_fut::io::{BufReader, BufWriter};
+
+#[test]
+fn test_should_set_buffer_defaults() {
+    assert_eq!(config::buf_capacity(), config::DEFAULT_BUF_SIZE);
+    assert_eq!(config::copy_buf_size(), config::DEFAULT_BUF_SIZE);
+
+    config::set_buf_capacity(1024);
+    assert_eq!(BufReader::new(maybe_fut::io::empty()).capacity(), 1024);
+    assert_eq!(BufWriter::new(maybe_fut::io::sink()).capacity(), 1024);
+
+    // the writer checks the size of each chunk
+    config::set_copy_buf_size(4);
+    let mut reader = MockReader::builder().read(b"hello, world").build_reader();
+    let mut writer = MockWriter::builder()
+        .write(b"hell")
+        .write(b"o, w")
+        .write(b"orld")
+        .build_writer();
+    let copied =
+        maybe_fut::block_on(maybe_fut::io::copy(&mut reader, &mut writer)).expect("Failed to copy");
+    assert_eq!(copied, 12);
+}
@@ -0,0 +1,26 @@
+//! This module contains the test for forcing the std implementation with `set_default_backend`.
+//!
+//! The default backend is global, so it's tested in its own test binary.
+#![cfg(feature = "tokio-fs")]
+
+use maybe_fut::{Backend, Unwrap as _, default_backend, set_default_backend};
+
+#[tokio::test]
+async fn test_should_force_std_backend_in_async_context() {
+    assert_eq!(default_backend(), Backend::Auto);
+    assert!(maybe_fut::is_async_context());
+
+    set_default_backend(Backend::Std);
+    assert_eq!(default_backend(), Backend::Std);
+    assert!(!maybe_fut::is_async_context());
+    assert!(!maybe_fut::is_tokio_context());
+
+    let temp = tempfile::NamedTempFile::new().expect("Failed to create temp file");
+    let file = maybe_fut::fs::File::open(temp.path())
+        .await
+        .expect("Failed to open file");
+    assert!(file.get_std_ref().is_some());
+
+    set_default_backend(Backend::Auto);
+    assert!(maybe_fut::is_tokio_context());
+}
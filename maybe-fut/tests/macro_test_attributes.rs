@@ -0,0 +1,58 @@
+//! This module contains the test for the `maybe_fut` macro preserving the attributes of the methods.
+
+use maybe_fut_derive::maybe_fut;
+
+#[derive(Debug, Clone, Copy)]
+struct TestStruct {
+    value: u64,
+}
+
+#[crate::maybe_fut(
+    sync = SyncTestStruct,
+    tokio = TokioTestStruct,
+    tokio_feature = "tokio",
+)]
+impl TestStruct {
+    /// Creates a new [`TestStruct`] instance.
+    #[inline]
+    #[must_use]
+    pub fn new(value: u64) -> Self {
+        Self { value }
+    }
+
+    /// Returns the doubled value.
+    #[tracing::instrument(skip(self), ret)]
+    pub async fn double(&self) -> u64 {
+        self.value * 2
+    }
+
+    /// Adds `other` to the value.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub fn add(&self, other: u64) -> u64 {
+        #![allow(clippy::let_and_return)]
+        let sum = self.value + other;
+        sum
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_should_preserve_attributes_async() {
+        let test_struct = TokioTestStruct::new(21);
+
+        assert_eq!(test_struct.double().await, 42);
+        assert_eq!(test_struct.add(21), 42);
+    }
+
+    #[test]
+    fn test_should_preserve_attributes_sync() {
+        let test_struct = SyncTestStruct::new(21);
+
+        assert_eq!(test_struct.double(), 42);
+        assert_eq!(test_struct.add(21), 42);
+    }
+}
@@ -0,0 +1,99 @@
+//! This module contains the test for the `maybe_fut` macro generating boxed futures.
+
+use std::fmt::Display;
+use std::future::Future;
+use std::pin::Pin;
+
+use maybe_fut_derive::maybe_fut;
+
+#[derive(Debug, Clone)]
+struct Greeter {
+    name: String,
+}
+
+#[crate::maybe_fut(
+    sync = SyncGreeter,
+    tokio = TokioGreeter,
+    tokio_feature = "tokio",
+    boxed = true,
+)]
+impl Greeter {
+    /// Creates a new [`Greeter`] instance.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+        }
+    }
+
+    /// Greets `other`.
+    pub async fn greet(&self, other: &str) -> String {
+        format!("Hello {}, I'm {}", other, self.name)
+    }
+
+    /// Greets `other` with the given punctuation.
+    pub async fn greet_with<P: Display + Send + Sync>(
+        &self,
+        other: &str,
+        punctuation: P,
+    ) -> String {
+        format!("Hello {}{}", other, punctuation)
+    }
+
+    /// Renames the greeter.
+    pub async fn rename(&mut self, name: impl Into<String> + Send) {
+        self.name = name.into();
+    }
+
+    /// Returns the name of the greeter.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// An object-safe trait to greet.
+trait DynGreet {
+    fn greet<'a>(&'a self, other: &'a str) -> Pin<Box<dyn Future<Output = String> + Send + 'a>>;
+}
+
+#[cfg(feature = "tokio")]
+impl DynGreet for TokioGreeter {
+    fn greet<'a>(&'a self, other: &'a str) -> Pin<Box<dyn Future<Output = String> + Send + 'a>> {
+        TokioGreeter::greet(self, other)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_should_proc_derive_boxed_async() {
+        let mut greeter = TokioGreeter::new("Omar");
+        assert_eq!(greeter.greet("Chris").await, "Hello Chris, I'm Omar");
+        assert_eq!(greeter.greet_with("Chris", '!').await, "Hello Chris!");
+
+        greeter.rename("Peter").await;
+        assert_eq!(greeter.name(), "Peter");
+
+        let greeter: Box<dyn DynGreet> = Box::new(greeter);
+        assert_eq!(greeter.greet("Chris").await, "Hello Chris, I'm Peter");
+    }
+
+    #[tokio::test]
+    async fn test_should_spawn_boxed_future() {
+        let greeter = TokioGreeter::new("Omar");
+        let handle = tokio::spawn(async move { greeter.greet("Chris").await });
+
+        assert_eq!(handle.await.unwrap(), "Hello Chris, I'm Omar");
+    }
+
+    #[test]
+    fn test_should_proc_derive_sync() {
+        let mut greeter = SyncGreeter::new("Omar");
+        assert_eq!(greeter.greet("Chris"), "Hello Chris, I'm Omar");
+
+        greeter.rename("Peter");
+        assert_eq!(greeter.name(), "Peter");
+    }
+}
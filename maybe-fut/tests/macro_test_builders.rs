@@ -0,0 +1,86 @@
+//! This module contains the test for the `maybe_fut` macro on builder methods returning `&mut Self` or `&Self`.
+
+use std::time::Duration;
+
+use maybe_fut_derive::maybe_fut;
+
+#[derive(Debug, Default)]
+struct Request {
+    timeout: Option<Duration>,
+    retries: u8,
+    sent: bool,
+}
+
+#[crate::maybe_fut(
+    sync = SyncRequest,
+    tokio = TokioRequest,
+    tokio_feature = "tokio",
+)]
+impl Request {
+    /// Creates a new [`Request`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the timeout of the request.
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the number of retries of the request.
+    pub async fn retries(&mut self, retries: u8) -> &mut Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Sends the request.
+    pub async fn send(&mut self) -> &mut Request {
+        self.sent = true;
+        self
+    }
+
+    /// Returns a reference to the request.
+    pub fn validate(&self) -> &Self {
+        assert!(self.timeout.is_some());
+        self
+    }
+
+    /// Returns whether the request has been sent.
+    pub fn is_sent(&self) -> bool {
+        self.sent
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_should_chain_builder_methods_sync() {
+        let mut request = SyncRequest::new();
+        let sent = request
+            .timeout(Duration::from_secs(5))
+            .retries(3)
+            .send()
+            .validate()
+            .is_sent();
+        assert!(sent);
+        assert_eq!(request.0.retries, 3);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_should_chain_builder_methods_async() {
+        let mut request = TokioRequest::new();
+        request
+            .timeout(Duration::from_secs(5))
+            .retries(3)
+            .await
+            .send()
+            .await;
+        assert!(request.validate().is_sent());
+        assert_eq!(request.0.timeout, Some(Duration::from_secs(5)));
+    }
+}
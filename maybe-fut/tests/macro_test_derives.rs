@@ -0,0 +1,234 @@
+//! This module contains the test for the `Unwrap` and io derives used outside of maybe-fut.
+
+use std::io::Cursor;
+
+use maybe_fut::Unwrap;
+use maybe_fut::io::{Read, Seek, Write};
+
+#[derive(Unwrap, Read, Write)]
+#[unwrap_types(std(Cursor<Vec<u8>>), tokio(tokio::io::DuplexStream), tokio_gated("tokio"))]
+#[io(feature("tokio"))]
+struct Buffer(BufferInner);
+
+enum BufferInner {
+    Std(Cursor<Vec<u8>>),
+    #[cfg(feature = "tokio")]
+    Tokio(tokio::io::DuplexStream),
+}
+
+#[derive(Unwrap)]
+#[unwrap_types(
+    std(Cursor<Vec<u8>>),
+    tokio(tokio::io::DuplexStream),
+    tokio_gated("tokio"),
+    crate_path(::maybe_fut)
+)]
+struct Renamed(BufferInner);
+
+#[derive(Unwrap)]
+#[unwrap_types(std(Cursor<Vec<u8>>))]
+struct StdOnly(StdOnlyInner);
+
+enum StdOnlyInner {
+    Std(Cursor<Vec<u8>>),
+}
+
+#[derive(Read, Write, Seek)]
+#[io(feature("tokio"))]
+enum EnumBuffer {
+    Std(Cursor<Vec<u8>>),
+    #[cfg(feature = "tokio")]
+    #[allow(dead_code)]
+    Tokio(tokio::fs::File),
+}
+
+#[derive(Read)]
+#[io(feature("tokio"))]
+struct Reader<S, T>(ReaderInner<S, T>)
+where
+    S: std::io::Read,
+    T: tokio::io::AsyncRead + Unpin;
+
+enum ReaderInner<S, T> {
+    Std(S),
+    #[cfg(feature = "tokio")]
+    Tokio(T),
+}
+
+#[derive(Unwrap)]
+#[unwrap_types(std(Cursor<B>), tokio(tokio::io::DuplexStream), tokio_gated("tokio"))]
+struct GenericBuffer<B = Vec<u8>>(GenericBufferInner<B>)
+where
+    B: AsRef<[u8]>;
+
+enum GenericBufferInner<B> {
+    Std(Cursor<B>),
+    #[cfg(feature = "tokio")]
+    #[allow(dead_code)]
+    Tokio(tokio::io::DuplexStream),
+}
+
+#[derive(Unwrap)]
+#[unwrap_types(std(&'a mut R))]
+struct BorrowedReader<'a, R: std::io::Read + ?Sized>(BorrowedReaderInner<'a, R>);
+
+enum BorrowedReaderInner<'a, R: ?Sized> {
+    Std(&'a mut R),
+}
+
+#[derive(Read, Write)]
+#[io(feature("tokio"), field = "1")]
+struct CountedBuffer(usize, BufferInner);
+
+#[derive(Read, Write)]
+#[io(feature("tokio"), field = "inner")]
+struct Stream {
+    written: usize,
+    inner: BufferInner,
+}
+
+#[derive(Read)]
+#[io(feature("tokio"))]
+struct NamedReader {
+    inner: BufferInner,
+}
+
+#[derive(Read, Write)]
+#[io(feature("tokio"), std_variant = "Sync", tokio_variant = "Async")]
+struct Variants(VariantsInner);
+
+enum VariantsInner {
+    Sync(Cursor<Vec<u8>>),
+    #[cfg(feature = "tokio")]
+    #[allow(dead_code)]
+    Async(tokio::io::DuplexStream),
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_should_derive_outside_of_maybe_fut() {
+        let mut buffer = Buffer(BufferInner::Std(Cursor::new(Vec::new())));
+
+        maybe_fut::block_on(buffer.write_all(b"hello")).expect("Failed to write");
+        buffer.unwrap_std_mut().set_position(0);
+
+        let data = maybe_fut::block_on(buffer.read_to_string_new()).expect("Failed to read");
+        assert_eq!(data, "hello");
+        assert_eq!(buffer.unwrap_std().into_inner(), b"hello");
+    }
+
+    #[tokio::test]
+    async fn test_should_derive_tokio_outside_of_maybe_fut() {
+        let (client, server) = tokio::io::duplex(64);
+        let mut client = Buffer(BufferInner::Tokio(client));
+        let mut server = Buffer(BufferInner::Tokio(server));
+
+        client.write_all(b"hello").await.expect("Failed to write");
+        drop(client);
+
+        let data = server.read_to_string_new().await.expect("Failed to read");
+        assert_eq!(data, "hello");
+    }
+
+    #[test]
+    fn test_should_derive_with_crate_path() {
+        let renamed = Renamed(BufferInner::Std(Cursor::new(vec![1])));
+
+        assert_eq!(renamed.unwrap_std().into_inner(), vec![1]);
+    }
+
+    #[test]
+    fn test_should_derive_for_generic_struct() {
+        let mut reader: Reader<_, tokio::io::Empty> =
+            Reader(ReaderInner::Std(Cursor::new(b"hello".to_vec())));
+
+        let data = maybe_fut::block_on(reader.read_to_string_new()).expect("Failed to read");
+        assert_eq!(data, "hello");
+    }
+
+    #[tokio::test]
+    async fn test_should_derive_tokio_for_generic_struct() {
+        let mut reader: Reader<Cursor<Vec<u8>>, _> = Reader(ReaderInner::Tokio(&b"hello"[..]));
+
+        let data = reader.read_to_string_new().await.expect("Failed to read");
+        assert_eq!(data, "hello");
+    }
+
+    #[test]
+    fn test_should_derive_for_selected_field() {
+        let mut buffer = CountedBuffer(0, BufferInner::Std(Cursor::new(Vec::new())));
+
+        buffer.0 += maybe_fut::block_on(buffer.write(b"hello")).expect("Failed to write");
+        assert_eq!(buffer.0, 5);
+    }
+
+    #[test]
+    fn test_should_derive_for_named_fields() {
+        let mut stream = Stream {
+            written: 0,
+            inner: BufferInner::Std(Cursor::new(Vec::new())),
+        };
+
+        stream.written += maybe_fut::block_on(stream.write(b"hello")).expect("Failed to write");
+        assert_eq!(stream.written, 5);
+
+        let mut reader = NamedReader {
+            inner: BufferInner::Std(Cursor::new(b"hello".to_vec())),
+        };
+        let data = maybe_fut::block_on(reader.read_to_string_new()).expect("Failed to read");
+        assert_eq!(data, "hello");
+    }
+
+    #[test]
+    fn test_should_derive_with_custom_variant_names() {
+        let mut variants = Variants(VariantsInner::Sync(Cursor::new(Vec::new())));
+
+        assert_eq!(maybe_fut::block_on(variants.write(b"hello")).unwrap(), 5);
+    }
+
+    #[test]
+    fn test_should_derive_without_tokio_type() {
+        let mut std_only = StdOnly(StdOnlyInner::Std(Cursor::new(vec![1])));
+
+        std_only.unwrap_std_mut().get_mut().push(2);
+        #[cfg(feature = "tokio")]
+        assert!(std_only.get_tokio_ref().is_some());
+        assert_eq!(std_only.unwrap_std().into_inner(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_should_derive_for_enum() {
+        let mut buffer = EnumBuffer::Std(Cursor::new(Vec::new()));
+
+        maybe_fut::block_on(buffer.write_all(b"hello")).expect("Failed to write");
+        maybe_fut::block_on(buffer.seek(std::io::SeekFrom::Start(1))).expect("Failed to seek");
+        let data = maybe_fut::block_on(buffer.read_to_string_new()).expect("Failed to read");
+        assert_eq!(data, "ello");
+    }
+
+    #[test]
+    fn test_should_derive_for_generic_struct_with_where_clause() {
+        let buffer: GenericBuffer = GenericBuffer(GenericBufferInner::Std(Cursor::new(vec![1, 2])));
+        assert_eq!(buffer.unwrap_std().into_inner(), vec![1, 2]);
+
+        let buffer = GenericBuffer(GenericBufferInner::Std(Cursor::new("hello")));
+        assert_eq!(
+            buffer.get_std_ref().map(|cursor| *cursor.get_ref()),
+            Some("hello")
+        );
+    }
+
+    #[test]
+    fn test_should_derive_for_generic_struct_with_bounds() {
+        let mut source = Cursor::new(b"hello".to_vec());
+        let mut reader = BorrowedReader(BorrowedReaderInner::Std(&mut source));
+
+        let mut data = String::new();
+        std::io::Read::read_to_string(reader.unwrap_std_mut(), &mut data).expect("Failed to read");
+        assert_eq!(data, "hello");
+    }
+}
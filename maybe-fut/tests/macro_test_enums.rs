@@ -0,0 +1,106 @@
+//! This module contains the test for the `maybe_fut` macro for enums.
+
+use maybe_fut_derive::maybe_fut;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Connection {
+    Idle,
+    Connecting { attempt: u8 },
+    Connected(u16),
+}
+
+#[crate::maybe_fut(
+    sync = SyncConnection,
+    tokio = TokioConnection,
+    tokio_feature = "tokio",
+)]
+impl Connection {
+    /// Creates a new idle [`Connection`].
+    pub fn new() -> Self {
+        Self::Idle
+    }
+
+    /// Creates a new [`Connection`] connected to `port`.
+    pub fn connected(port: u16) -> Result<Self, String> {
+        if port == 0 {
+            return Err("invalid port".to_string());
+        }
+
+        Ok(Connection::Connected(port))
+    }
+
+    /// Advances the state machine.
+    pub async fn advance(&mut self) {
+        *self = match *self {
+            Self::Idle => Self::Connecting { attempt: 1 },
+            Self::Connecting { attempt } if attempt < 2 => Self::Connecting {
+                attempt: attempt + 1,
+            },
+            Self::Connecting { .. } => Self::Connected(8080),
+            Self::Connected(port) => Self::Connected(port),
+        };
+    }
+
+    /// Returns the connected port, if any.
+    pub fn port(&self) -> Option<u16> {
+        match self {
+            Self::Connected(port) => Some(*port),
+            _ => None,
+        }
+    }
+}
+
+/// A trait to describe the state of a connection.
+pub trait Describe {
+    /// Describes the state.
+    fn describe(&self) -> &'static str;
+}
+
+#[crate::maybe_fut(
+    sync = SyncConnection,
+    tokio = TokioConnection,
+    tokio_feature = "tokio",
+)]
+impl Describe for Connection {
+    fn describe(&self) -> &'static str {
+        match self {
+            Self::Idle => "idle",
+            Self::Connecting { .. } => "connecting",
+            Self::Connected(_) => "connected",
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_should_proc_derive_async() {
+        let mut conn = TokioConnection::new();
+        assert_eq!(conn.describe(), "idle");
+        conn.advance().await;
+        conn.advance().await;
+        conn.advance().await;
+        assert_eq!(conn.port(), Some(8080));
+        assert_eq!(conn.describe(), "connected");
+
+        assert_eq!(TokioConnection::connected(22).unwrap().port(), Some(22));
+        assert!(TokioConnection::connected(0).is_err());
+    }
+
+    #[test]
+    fn test_should_proc_derive_sync() {
+        let mut conn = SyncConnection::new();
+        assert_eq!(conn.describe(), "idle");
+        conn.advance();
+        assert_eq!(conn.describe(), "connecting");
+        conn.advance();
+        conn.advance();
+        assert_eq!(conn.port(), Some(8080));
+
+        assert_eq!(SyncConnection::connected(22).unwrap().port(), Some(22));
+        assert!(SyncConnection::connected(0).is_err());
+    }
+}
@@ -37,6 +37,24 @@ where
         self.value
     }
 
+    /// Formats the value with the given suffix.
+    pub fn with_suffix<S>(&self, suffix: S) -> String
+    where
+        S: Display,
+    {
+        format!("{}{}", self.value, suffix)
+    }
+
+    /// Formats the value with the given suffix asynchronously.
+    pub async fn with_suffix_async<S: Display>(&self, suffix: S) -> String {
+        format!("{}{}", self.value, suffix)
+    }
+
+    /// Returns the size of `U`; `U` can't be inferred from the arguments.
+    pub fn size_of<U: Sized>() -> usize {
+        std::mem::size_of::<U>()
+    }
+
     #[inline]
     const fn life_meaning() -> u64 {
         42
@@ -87,6 +105,10 @@ mod test {
 
         test_struct.greet();
         test_struct.greet_async().await;
+
+        assert_eq!(test_struct.with_suffix("kg"), "96kg");
+        assert_eq!(test_struct.with_suffix_async('g').await, "96g");
+        assert_eq!(TokioTestStruct::<u64>::size_of::<u32>(), 4);
     }
 
     #[test]
@@ -100,5 +122,10 @@ mod test {
         assert_eq!(SyncTestStruct::<u64>::life_meaning(), 42);
 
         test_struct.greet();
+        assert_eq!(test_struct.greet_async(), "Hello, I'm 96");
+
+        assert_eq!(test_struct.with_suffix("kg"), "96kg");
+        assert_eq!(test_struct.with_suffix_async('g'), "96g");
+        assert_eq!(SyncTestStruct::<u64>::size_of::<u32>(), 4);
     }
 }
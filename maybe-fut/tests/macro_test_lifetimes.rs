@@ -0,0 +1,105 @@
+//! This module contains the test for the `maybe_fut` macro for lifetimes and const generics.
+
+use maybe_fut_derive::maybe_fut;
+
+#[derive(Debug, Clone, Copy)]
+struct Buf<'a, const N: usize> {
+    data: &'a [u8],
+}
+
+#[crate::maybe_fut(
+    sync = SyncBuf,
+    tokio = TokioBuf,
+    tokio_feature = "tokio",
+)]
+impl<'a, const N: usize> Buf<'a, N> {
+    /// Creates a new [`Buf`] instance.
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    /// Creates a new [`Buf`] instance, if `data` is not longer than `N`.
+    pub fn try_new(data: &'a [u8]) -> Option<Buf<'a, N>> {
+        (data.len() <= N).then_some(Self { data })
+    }
+
+    /// Returns the first byte of the buffer.
+    pub fn first(&self) -> Option<&u8> {
+        self.data.first()
+    }
+
+    /// Returns the capacity of the buffer.
+    pub async fn capacity(&self) -> usize {
+        N
+    }
+}
+
+/// A trait to describe a buffer.
+pub trait Describe {
+    /// Describes the buffer.
+    fn describe(&self) -> String;
+}
+
+#[crate::maybe_fut(
+    sync = SyncBuf,
+    tokio = TokioBuf,
+    tokio_feature = "tokio",
+)]
+impl<const N: usize> Describe for Buf<'_, N> {
+    fn describe(&self) -> String {
+        format!("{}/{}", self.data.len(), N)
+    }
+}
+
+#[derive(Debug)]
+struct Cursor<'a, T: Copy> {
+    items: &'a [T],
+}
+
+#[crate::maybe_fut(
+    sync = SyncCursor,
+    tokio = TokioCursor,
+    tokio_feature = "tokio",
+)]
+impl<T: Copy> Cursor<'_, T> {
+    /// Returns the last item.
+    pub fn last(&self) -> Option<T> {
+        self.items.last().copied()
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_should_proc_derive_async() {
+        let data = [1, 2, 3];
+        let buf: TokioBuf<'_, 4> = TokioBuf::new(&data);
+        assert_eq!(buf.first(), Some(&1));
+        assert_eq!(buf.capacity().await, 4);
+        assert_eq!(buf.describe(), "3/4");
+
+        assert!(TokioBuf::<'_, 2>::try_new(&data).is_none());
+        assert!(TokioBuf::<'_, 3>::try_new(&data).is_some());
+
+        let cursor = TokioCursor(Cursor { items: &data });
+        assert_eq!(cursor.last(), Some(3));
+    }
+
+    #[test]
+    fn test_should_proc_derive_sync() {
+        let data = [1, 2, 3];
+        let buf: SyncBuf<'_, 4> = SyncBuf::new(&data);
+        assert_eq!(buf.first(), Some(&1));
+        assert_eq!(buf.capacity(), 4);
+        assert_eq!(buf.describe(), "3/4");
+
+        assert!(SyncBuf::<'_, 2>::try_new(&data).is_none());
+        assert!(SyncBuf::<'_, 3>::try_new(&data).is_some());
+
+        let cursor = SyncCursor(Cursor { items: &data });
+        assert_eq!(cursor.last(), Some(3));
+    }
+}
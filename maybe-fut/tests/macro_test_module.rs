@@ -0,0 +1,109 @@
+//! This module contains the test for the `module` macro.
+
+#[maybe_fut::module(sync = blocking, tokio = aio, tokio_feature = "tokio")]
+mod client {
+
+    #[derive(Debug, Clone, Copy)]
+    pub struct Client {
+        id: u64,
+    }
+
+    #[maybe_fut::maybe_fut(
+        sync = SyncClient,
+        tokio = TokioClient,
+        tokio_feature = "tokio",
+    )]
+    impl Client {
+        /// Creates a new [`Client`] instance.
+        pub fn new(id: u64) -> Self {
+            Self { id }
+        }
+
+        /// Returns the id of the client.
+        pub async fn id(&self) -> u64 {
+            self.id
+        }
+    }
+
+    /// A trait to greet the user.
+    pub trait Greet {
+        /// Greets the user with a message.
+        fn greet(&self) -> String;
+    }
+
+    #[maybe_fut::maybe_fut(
+        sync = SyncClient,
+        tokio = TokioClient,
+        tokio_feature = "tokio",
+    )]
+    impl Greet for Client {
+        fn greet(&self) -> String {
+            format!("Hello, I'm {}", self.id)
+        }
+    }
+
+    #[derive(Debug, Default)]
+    pub struct Server;
+
+    #[maybe_fut::maybe_fut(
+        sync = SyncServer,
+        tokio = TokioServer,
+        tokio_feature = "tokio",
+    )]
+    impl Server {
+        /// Creates a new [`Server`] instance.
+        pub fn new() -> Self {
+            Self
+        }
+
+        /// Returns the port of the server.
+        pub async fn port(&self) -> u16 {
+            8080
+        }
+    }
+
+    #[derive(Debug, Default)]
+    pub struct Logger;
+
+    #[maybe_fut::maybe_fut(sync = SyncLogger)]
+    impl Logger {
+        /// Creates a new [`Logger`] instance.
+        pub fn new() -> Self {
+            Self
+        }
+
+        /// Returns the level of the logger.
+        pub async fn level(&self) -> &'static str {
+            "debug"
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::client::{Greet as _, aio, blocking};
+
+    #[tokio::test]
+    async fn test_should_export_async_facade() {
+        let client = aio::TokioClient::new(96);
+        assert_eq!(client.id().await, 96);
+        assert_eq!(client.greet(), "Hello, I'm 96");
+
+        let server = aio::TokioServer::new();
+        assert_eq!(server.port().await, 8080);
+    }
+
+    #[test]
+    fn test_should_export_sync_facade() {
+        let client = blocking::SyncClient::new(96);
+        assert_eq!(client.id(), 96);
+        assert_eq!(client.greet(), "Hello, I'm 96");
+
+        let server = blocking::SyncServer::new();
+        assert_eq!(server.port(), 8080);
+
+        let logger = blocking::SyncLogger::new();
+        assert_eq!(logger.level(), "debug");
+    }
+}
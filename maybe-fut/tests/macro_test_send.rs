@@ -0,0 +1,64 @@
+//! This module contains the test for the `maybe_fut` macro checking the generated futures are `Send`.
+
+use std::fmt::Display;
+
+use maybe_fut_derive::maybe_fut;
+
+#[derive(Debug, Clone)]
+struct Counter {
+    count: usize,
+}
+
+#[crate::maybe_fut(
+    sync = SyncCounter,
+    tokio = TokioCounter,
+    tokio_feature = "tokio",
+    send = true,
+)]
+impl Counter {
+    /// Creates a new [`Counter`] instance.
+    pub fn new() -> Self {
+        Self { count: 0 }
+    }
+
+    /// Increments the counter by `step`.
+    pub async fn increment(&mut self, step: usize) -> usize {
+        self.count += step;
+        self.count
+    }
+
+    /// Describes the counter with the given label.
+    pub async fn describe<L: Display + Send>(&self, label: L, suffix: &str) -> String {
+        format!("{label}: {}{suffix}", self.count)
+    }
+
+    /// Returns the current count.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_should_spawn_send_future() {
+        let handle = tokio::spawn(async move {
+            let mut counter = TokioCounter::new();
+            counter.increment(2).await;
+            counter.increment(3).await;
+            counter.describe("count", "!").await
+        });
+
+        assert_eq!(handle.await.unwrap(), "count: 5!");
+    }
+
+    #[test]
+    fn test_should_proc_derive_sync() {
+        let mut counter = SyncCounter::new();
+        assert_eq!(counter.increment(2), 2);
+        assert_eq!(counter.count(), 2);
+    }
+}
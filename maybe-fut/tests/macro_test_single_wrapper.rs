@@ -0,0 +1,74 @@
+//! This module contains the test for the `maybe_fut` macro generating only one of the wrappers.
+
+use maybe_fut_derive::maybe_fut;
+
+#[derive(Debug, Clone, Copy)]
+struct Counter {
+    value: u64,
+}
+
+#[crate::maybe_fut(sync = SyncCounter)]
+impl Counter {
+    /// Creates a new [`Counter`] instance.
+    pub fn new(value: u64) -> Self {
+        Self { value }
+    }
+
+    /// Increments the counter.
+    pub async fn increment(&mut self) -> u64 {
+        self.value += 1;
+        self.value
+    }
+}
+
+/// A trait to describe a value.
+pub trait Describe {
+    /// Describes the value asynchronously.
+    fn describe(&self) -> impl Future<Output = String>;
+}
+
+#[crate::maybe_fut(sync = SyncCounter)]
+impl Describe for Counter {
+    async fn describe(&self) -> String {
+        format!("counter at {}", self.value)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Timer {
+    ticks: u64,
+}
+
+#[crate::maybe_fut(tokio = TokioTimer, tokio_feature = "tokio")]
+impl Timer {
+    /// Creates a new [`Timer`] instance.
+    pub fn new() -> Self {
+        Self { ticks: 0 }
+    }
+
+    /// Ticks the timer.
+    pub async fn tick(&mut self) -> u64 {
+        self.ticks += 1;
+        self.ticks
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_should_generate_only_sync_wrapper() {
+        let mut counter = SyncCounter::new(41);
+        assert_eq!(counter.increment(), 42);
+        assert_eq!(counter.describe(), "counter at 42");
+    }
+
+    #[tokio::test]
+    async fn test_should_generate_only_tokio_wrapper() {
+        let mut timer = TokioTimer::new();
+        assert_eq!(timer.tick().await, 1);
+        assert_eq!(timer.tick().await, 2);
+    }
+}
@@ -0,0 +1,91 @@
+//! This module contains the test for the `maybe_fut` macro with a cfg predicate as `tokio_feature`.
+
+use maybe_fut_derive::maybe_fut;
+
+#[derive(Debug, Default)]
+struct Counter {
+    count: u64,
+}
+
+#[crate::maybe_fut(
+    sync = SyncCounter,
+    tokio = TokioCounter,
+    tokio_feature = any("tokio", "tokio-net"),
+)]
+impl Counter {
+    /// Creates a new [`Counter`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Increments the counter.
+    pub async fn increment(&mut self) -> u64 {
+        self.count += 1;
+        self.count
+    }
+}
+
+#[derive(Debug, Default)]
+struct Gauge {
+    value: i64,
+}
+
+#[crate::maybe_fut(
+    sync = SyncGauge,
+    tokio = TokioGauge,
+    tokio_feature = all(feature = "tokio", not(miri)),
+)]
+impl Gauge {
+    /// Creates a new [`Gauge`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the value of the gauge.
+    pub async fn set(&mut self, value: i64) -> i64 {
+        self.value = value;
+        self.value
+    }
+}
+
+#[derive(Debug, Default)]
+struct Hidden;
+
+#[crate::maybe_fut(
+    sync = SyncHidden,
+    tokio = TokioHidden,
+    tokio_feature = not(any("tokio", not("tokio"))),
+)]
+impl Hidden {
+    /// Creates a new [`Hidden`].
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_should_generate_sync_wrappers() {
+        let mut counter = SyncCounter::new();
+        assert_eq!(counter.increment(), 1);
+
+        let mut gauge = SyncGauge::new();
+        assert_eq!(gauge.set(-4), -4);
+
+        let _ = SyncHidden::new();
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_should_generate_gated_tokio_wrappers() {
+        let mut counter = TokioCounter::new();
+        assert_eq!(counter.increment().await, 1);
+
+        let mut gauge = TokioGauge::new();
+        assert_eq!(gauge.set(8).await, 8);
+    }
+}
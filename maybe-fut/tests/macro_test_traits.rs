@@ -60,5 +60,17 @@ mod test {
         let result = SyncTestStruct::new(96);
 
         println!("{}", result.greet());
+        assert_eq!(result.greet_async(), "Hello, I'm 96");
+    }
+
+    #[tokio::test]
+    async fn test_should_keep_async_trait_impl_for_sync_wrapper() {
+        async fn greet_async<G: Greet>(greeter: &G) -> String {
+            greeter.greet_async().await
+        }
+
+        let result = SyncTestStruct::new(96);
+
+        assert_eq!(greet_async(&result).await, "Hello, I'm 96");
     }
 }
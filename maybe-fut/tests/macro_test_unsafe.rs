@@ -0,0 +1,76 @@
+//! This module contains the test for the `maybe_fut` macro on `unsafe fn` methods.
+
+use maybe_fut_derive::maybe_fut;
+
+#[derive(Debug)]
+struct RawBuffer {
+    data: Vec<u8>,
+}
+
+#[crate::maybe_fut(
+    sync = SyncRawBuffer,
+    tokio = TokioRawBuffer,
+    tokio_feature = "tokio",
+)]
+impl RawBuffer {
+    /// Creates a new [`RawBuffer`] from a pointer and a length.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be valid for reads of `len` bytes.
+    pub unsafe fn from_raw_parts(ptr: *const u8, len: usize) -> Self {
+        Self {
+            data: unsafe { std::slice::from_raw_parts(ptr, len) }.to_vec(),
+        }
+    }
+
+    /// Returns the byte at `index`, without bounds checking.
+    ///
+    /// # Safety
+    ///
+    /// `index` must be less than the length of the buffer.
+    pub unsafe fn get_unchecked(&self, index: usize) -> u8 {
+        unsafe { *self.data.get_unchecked(index) }
+    }
+
+    /// Sets the length of the buffer.
+    ///
+    /// # Safety
+    ///
+    /// `len` must not exceed the length of the buffer.
+    pub async unsafe fn set_len(&mut self, len: usize) {
+        unsafe { self.data.set_len(len) }
+    }
+
+    /// Returns the data of the buffer.
+    pub fn data(&self) -> Vec<u8> {
+        self.data.clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    const DATA: &[u8] = b"hello";
+
+    #[test]
+    fn test_should_call_unsafe_methods_sync() {
+        let mut buffer = unsafe { SyncRawBuffer::from_raw_parts(DATA.as_ptr(), DATA.len()) };
+        assert_eq!(unsafe { buffer.get_unchecked(1) }, b'e');
+
+        unsafe { buffer.set_len(2) };
+        assert_eq!(buffer.data(), b"he");
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_should_call_unsafe_methods_tokio() {
+        let mut buffer = unsafe { TokioRawBuffer::from_raw_parts(DATA.as_ptr(), DATA.len()) };
+        assert_eq!(unsafe { buffer.get_unchecked(4) }, b'o');
+
+        unsafe { buffer.set_len(3) }.await;
+        assert_eq!(buffer.data(), b"hel");
+    }
+}
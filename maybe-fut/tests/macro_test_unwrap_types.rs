@@ -0,0 +1,60 @@
+//! This module contains the test for the `maybe_fut` macro translating maybe-fut types in signatures.
+
+use std::path::{Path, PathBuf};
+
+use maybe_fut_derive::maybe_fut;
+
+#[derive(Debug, Clone)]
+struct FsClient {
+    path: PathBuf,
+}
+
+#[crate::maybe_fut(
+    sync = SyncFsClient,
+    tokio = TokioFsClient,
+    tokio_feature = "tokio-fs",
+    unwrap = true,
+)]
+impl FsClient {
+    /// Creates a new [`FsClient`] instance.
+    pub fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+        }
+    }
+
+    /// Creates the file at the client path.
+    pub async fn create(&self) -> std::io::Result<maybe_fut::fs::File> {
+        maybe_fut::fs::File::create(&self.path).await
+    }
+
+    /// Returns the length of the given file.
+    pub async fn len(&self, file: maybe_fut::fs::File) -> std::io::Result<u64> {
+        file.metadata().await.map(|metadata| metadata.len())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[cfg(feature = "tokio-fs")]
+    #[tokio::test]
+    async fn test_should_translate_tokio_types() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let client = TokioFsClient::new(&tempdir.path().join("test.txt"));
+
+        let file: tokio::fs::File = client.create().await.expect("Failed to create file");
+        assert_eq!(client.len(file).await.unwrap(), 0);
+    }
+
+    #[test]
+    fn test_should_translate_std_types() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let client = SyncFsClient::new(&tempdir.path().join("test.txt"));
+
+        let file: std::fs::File = client.create().expect("Failed to create file");
+        assert_eq!(client.len(file).unwrap(), 0);
+    }
+}